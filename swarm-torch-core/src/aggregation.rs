@@ -3,6 +3,10 @@
 //! This module provides various aggregation strategies that can tolerate
 //! malicious or faulty participants in the swarm.
 
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
 #[cfg(feature = "alloc")]
 use alloc::string::ToString;
 #[cfg(feature = "alloc")]
@@ -10,34 +14,78 @@ use alloc::vec::Vec;
 
 #[cfg(feature = "alloc")]
 use crate::compression::{CompressedGradient, CompressionError, CompressionMethod};
+use crate::crypto::{sqrt_f32, GradientValidationError};
 #[cfg(feature = "alloc")]
 use crate::dataops::TransformAuditV0;
-use crate::traits::GradientUpdate;
+#[cfg(feature = "alloc")]
+use crate::reputation::ReputationRegistry;
+#[cfg(feature = "alloc")]
+use crate::traits::PeerId;
 #[cfg(feature = "alloc")]
 use crate::traits::UpdateTransform;
+use crate::traits::{GradientUpdate, UpdateEncoding};
 use crate::Result;
 
 const MAX_GRADIENT_DIM: usize = 10_000_000;
 
 fn validate_gradient_shapes(updates: &[GradientUpdate]) -> Result<usize> {
     if updates.is_empty() {
-        return Err(crate::Error::InsufficientUpdates);
+        return Err(crate::Error::InsufficientUpdates { got: 0, need: 1 });
+    }
+    // Delta elements are XORed bit patterns, not values.
+    if let Some(index) = updates
+        .iter()
+        .position(|update| update.encoding != UpdateEncoding::Full)
+    {
+        return Err(crate::Error::InvalidGradient {
+            index,
+            reason: GradientValidationError::NotFullEncoding,
+        });
     }
 
     let dim = updates[0].gradients.len();
     if dim == 0 || dim > MAX_GRADIENT_DIM {
-        return Err(crate::Error::InvalidGradient);
+        return Err(crate::Error::InvalidGradient {
+            index: 0,
+            reason: GradientValidationError::InvalidDimension {
+                dim,
+                max: MAX_GRADIENT_DIM,
+            },
+        });
     }
 
-    for update in updates.iter().skip(1) {
+    for (index, update) in updates.iter().enumerate().skip(1) {
         if update.gradients.len() != dim {
-            return Err(crate::Error::InvalidGradient);
+            return Err(crate::Error::InvalidGradient {
+                index,
+                reason: GradientValidationError::DimensionMismatch {
+                    expected: dim,
+                    got: update.gradients.len(),
+                },
+            });
         }
     }
 
     Ok(dim)
 }
 
+/// Coordinate `i` of every update as `(value, update index)`, sorted by value with
+/// the update index as tie-break.
+///
+/// Values are ordered with [`f32::total_cmp`] (`-NaN < -inf < … < -0.0 < +0.0 < … <
+/// +inf < +NaN`), so the order — and every statistic taken from it — is fully
+/// specified and bit-reproducible regardless of the sort implementation.
+#[cfg(feature = "alloc")]
+fn sorted_coordinate(updates: &[GradientUpdate], i: usize) -> Vec<(f32, usize)> {
+    let mut values: Vec<(f32, usize)> = updates
+        .iter()
+        .enumerate()
+        .map(|(index, update)| (update.gradients[i], index))
+        .collect();
+    values.sort_unstable_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    values
+}
+
 /// Trait for robust aggregation algorithms
 pub trait RobustAggregator: Send + Sync {
     /// Aggregate multiple gradient updates into one
@@ -186,6 +234,169 @@ pub fn apply_update_transforms<T: UpdateTransform>(
     (transformed, audit)
 }
 
+/// Per-update gradient pre-processing step applied before aggregation.
+///
+/// Pre-processors operate on the raw gradient slice only; provenance fields are
+/// never visible to them. Non-finite gradients are left untouched and should be
+/// rejected upstream by [`GradientValidator`](crate::crypto::GradientValidator).
+pub trait Preprocessor: Send + Sync {
+    /// Transform one update's gradient in place.
+    fn process(&self, grad: &mut [f32]);
+}
+
+/// Rescale each update so its L2 norm is at most the given bound.
+///
+/// Updates already within the bound are unchanged. A non-positive or NaN bound
+/// fails closed and zeroes the update.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClipByNorm(pub f32);
+
+impl Preprocessor for ClipByNorm {
+    fn process(&self, grad: &mut [f32]) {
+        let max_norm = if self.0 > 0.0 { self.0 } else { 0.0 };
+        let norm = sqrt_f32(grad.iter().map(|g| g * g).sum());
+        if norm > max_norm {
+            let factor = max_norm / norm;
+            for g in grad.iter_mut() {
+                *g *= factor;
+            }
+        }
+    }
+}
+
+/// Shift and scale each update to zero mean and unit (population) variance.
+///
+/// Constant updates (zero variance) are only mean-centered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Standardize;
+
+impl Preprocessor for Standardize {
+    fn process(&self, grad: &mut [f32]) {
+        if grad.is_empty() {
+            return;
+        }
+        let n = grad.len() as f32;
+        let mean = grad.iter().sum::<f32>() / n;
+        let variance = grad.iter().map(|g| (g - mean) * (g - mean)).sum::<f32>() / n;
+        let std_dev = sqrt_f32(variance);
+        let scale = if std_dev > 0.0 { 1.0 / std_dev } else { 1.0 };
+        for g in grad.iter_mut() {
+            *g = (*g - mean) * scale;
+        }
+    }
+}
+
+/// Apply `preprocessors` in order to a copy of each update, then aggregate.
+///
+/// Input updates are not modified. Shape validation is left to the aggregator.
+#[cfg(feature = "alloc")]
+pub fn aggregate_with_preprocessors<A: RobustAggregator + ?Sized>(
+    aggregator: &A,
+    updates: &[GradientUpdate],
+    preprocessors: &[Box<dyn Preprocessor>],
+) -> Result<Vec<f32>> {
+    let processed: Vec<GradientUpdate> = updates
+        .iter()
+        .map(|update| {
+            let mut update = update.clone();
+            for preprocessor in preprocessors {
+                preprocessor.process(&mut update.gradients);
+            }
+            update
+        })
+        .collect();
+    aggregator.aggregate(&processed)
+}
+
+/// Outlier pre-pass that drops updates whose coordinates sit far from the
+/// batch's per-coordinate median.
+///
+/// For every coordinate it computes the median and the median absolute
+/// deviation (MAD) across the batch. A coordinate value is an outlier when its
+/// distance from the median exceeds `k * MAD` (for a zero-spread coordinate,
+/// any deviation counts; NaN always counts). An update is flagged when more
+/// than `max_outlier_fraction` of its coordinates are outliers.
+///
+/// Runs in `O(n log n · dim)`, much cheaper than Krum's pairwise distances, and
+/// assumes honest updates are a majority of the batch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistributionFilter {
+    /// Outlier threshold in MADs
+    pub k: f32,
+    /// Fraction of outlying coordinates above which an update is flagged
+    pub max_outlier_fraction: f32,
+}
+
+impl Default for DistributionFilter {
+    fn default() -> Self {
+        Self {
+            k: 6.0,
+            max_outlier_fraction: 0.25,
+        }
+    }
+}
+
+/// Output of [`DistributionFilter::filter`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct DistributionFilterResult {
+    /// Unflagged updates, in input order
+    pub retained: Vec<GradientUpdate>,
+    /// Input indices of flagged updates, ascending
+    pub flagged: Vec<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl DistributionFilter {
+    /// Split `updates` into retained and flagged updates.
+    pub fn filter(&self, updates: &[GradientUpdate]) -> Result<DistributionFilterResult> {
+        let dim = validate_gradient_shapes(updates)?;
+        let n = updates.len();
+        let mut outliers = alloc::vec![0usize; n];
+        let mut deviations = Vec::with_capacity(n);
+
+        for i in 0..dim {
+            let values = sorted_coordinate(updates, i);
+            let median = sorted_median(values.iter().map(|(v, _)| *v), n);
+            deviations.clear();
+            deviations.extend(values.iter().map(|(v, _)| (v - median).abs()));
+            deviations.sort_unstable_by(f32::total_cmp);
+            let threshold = self.k * sorted_median(deviations.iter().copied(), n);
+
+            for (value, index) in values {
+                let deviation = (value - median).abs();
+                if deviation.is_nan() || deviation > threshold {
+                    outliers[index] += 1;
+                }
+            }
+        }
+
+        let limit = self.max_outlier_fraction * dim as f32;
+        let mut retained = Vec::with_capacity(n);
+        let mut flagged = Vec::new();
+        for (index, (update, count)) in updates.iter().zip(outliers).enumerate() {
+            if count as f32 > limit {
+                flagged.push(index);
+            } else {
+                retained.push(update.clone());
+            }
+        }
+        Ok(DistributionFilterResult { retained, flagged })
+    }
+}
+
+/// Median of `n` ascending values.
+#[cfg(feature = "alloc")]
+fn sorted_median(mut values: impl Iterator<Item = f32>, n: usize) -> f32 {
+    if n % 2 == 0 {
+        let lo = values.nth(n / 2 - 1).unwrap_or(0.0);
+        let hi = values.next().unwrap_or(0.0);
+        (lo + hi) / 2.0
+    } else {
+        values.nth(n / 2).unwrap_or(0.0)
+    }
+}
+
 /// Simple averaging aggregator (no Byzantine protection)
 #[derive(Debug, Clone, Default)]
 pub struct FedAvg;
@@ -235,9 +446,15 @@ impl RobustAggregator for FedAvg {
 ///   which truncates toward zero. For small peer counts this means fewer
 ///   values are actually trimmed than the ratio might suggest.
 /// - If the trim count leaves no values (`n <= 2 * trim_count`), the guard
-///   returns [`Error::InsufficientUpdates`](crate::Error::InsufficientUpdates).
+///   returns [`Error::InsufficientUpdates`](crate::Error::InsufficientUpdates)
+///   with the smallest larger update count the ratio can aggregate, or
+///   [`Error::AggregationFailed`](crate::Error::AggregationFailed) if no larger
+///   count works (ratios above 0.5 trim everything once `n` is large enough).
 /// - The guard is only reachable via direct struct construction (bypassing
-///   `new()`'s clamp) when using a `trim_ratio` above 0.49.
+///   `new()`'s clamp) when using a `trim_ratio` of 0.5 or more.
+///
+/// Each coordinate is sorted by value (`f32::total_cmp`) with the update index
+/// as tie-break, so output is bit-reproducible across platforms.
 #[derive(Debug, Clone)]
 pub struct TrimmedMean {
     /// Fraction of values to trim from each end (e.g., 0.2 for 20%).
@@ -259,6 +476,28 @@ impl TrimmedMean {
     }
 }
 
+impl TrimmedMean {
+    fn trim_count(&self, n: usize) -> usize {
+        ((n as f32) * self.trim_ratio) as usize
+    }
+
+    /// Whether `n` updates leave at least one value per coordinate after trimming.
+    fn leaves_values(&self, n: usize) -> bool {
+        n > 2 * self.trim_count(n)
+    }
+
+    /// Smallest update count above `got` that this ratio can aggregate, if any.
+    ///
+    /// Below 0.5 every count works; at 0.5 the next odd count does. Above 0.5 a
+    /// count `m` only works while `m * (2 * trim_ratio - 1) < 1`.
+    fn min_updates_above(&self, got: usize) -> Option<usize> {
+        let excess = 2.0 * self.trim_ratio - 1.0;
+        (got + 1..)
+            .take_while(|&m| m <= got + 2 || (m as f32) * excess < 1.0)
+            .find(|&m| self.leaves_values(m))
+    }
+}
+
 impl Default for TrimmedMean {
     fn default() -> Self {
         Self::new(0.2)
@@ -271,22 +510,26 @@ impl RobustAggregator for TrimmedMean {
         {
             let dim = validate_gradient_shapes(updates)?;
             let n = updates.len();
-            let trim_count = ((n as f32) * self.trim_ratio) as usize;
-
-            if n <= 2 * trim_count {
-                return Err(crate::Error::InsufficientUpdates);
+            let trim_count = self.trim_count(n);
+
+            if !self.leaves_values(n) {
+                return Err(match self.min_updates_above(n) {
+                    Some(need) => crate::Error::InsufficientUpdates { got: n, need },
+                    None => crate::Error::AggregationFailed {
+                        reason: "trim_ratio trims every update at this and any larger count",
+                    },
+                });
             }
 
             let mut result = alloc::vec![0.0f32; dim];
 
             // For each coordinate, sort values and compute trimmed mean
             for (i, slot) in result.iter_mut().enumerate().take(dim) {
-                let mut values: Vec<f32> = updates.iter().map(|u| u.gradients[i]).collect();
-                values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+                let values = sorted_coordinate(updates, i);
 
                 // Trim and average
                 let trimmed = &values[trim_count..n - trim_count];
-                let sum: f32 = trimmed.iter().sum();
+                let sum: f32 = trimmed.iter().map(|(value, _)| value).sum();
                 *slot = sum / (trimmed.len() as f32);
             }
 
@@ -307,6 +550,8 @@ impl RobustAggregator for TrimmedMean {
 }
 
 /// Coordinate-wise median aggregator
+///
+/// Uses the same deterministic per-coordinate ordering as [`TrimmedMean`].
 #[derive(Debug, Clone, Default)]
 pub struct CoordinateMedian;
 
@@ -319,14 +564,13 @@ impl RobustAggregator for CoordinateMedian {
             let mut result = alloc::vec![0.0f32; dim];
 
             for (i, slot) in result.iter_mut().enumerate().take(dim) {
-                let mut values: Vec<f32> = updates.iter().map(|u| u.gradients[i]).collect();
-                values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+                let values = sorted_coordinate(updates, i);
 
                 // Compute median
                 *slot = if n % 2 == 0 {
-                    (values[n / 2 - 1] + values[n / 2]) / 2.0
+                    (values[n / 2 - 1].0 + values[n / 2].0) / 2.0
                 } else {
-                    values[n / 2]
+                    values[n / 2].0
                 };
             }
 
@@ -346,6 +590,147 @@ impl RobustAggregator for CoordinateMedian {
     }
 }
 
+/// Reputation-gated mean: excludes updates from peers scoring below `min_score`
+/// instead of trimming a fixed ratio.
+///
+/// Scores are a snapshot of a [`ReputationRegistry`] taken at construction; the
+/// update's peer is `PeerId(update.sender)` and unknown peers score `0.0`. With
+/// `weighted`, retained updates are averaged with weight `1 + max(score, 0)`.
+///
+/// Trade-off: robustness follows observed behavior, not the values themselves. A
+/// low-reputation peer is dropped even when its update looks benign, and a
+/// high-reputation peer's outlier is kept — so this offers no static Byzantine
+/// bound ([`RobustAggregator::byzantine_tolerance`] is `0.0`) and is best chained
+/// after value-based filtering when a peer may be compromised.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct ReputationTrimmedMean {
+    scores: BTreeMap<PeerId, f32>,
+    /// Updates from peers scoring below this are excluded.
+    pub min_score: f32,
+    /// Weight retained updates by reputation instead of a plain mean.
+    pub weighted: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl ReputationTrimmedMean {
+    /// Snapshot `registry` scores; exclude peers below `min_score`.
+    pub fn from_registry(registry: &ReputationRegistry, min_score: f32) -> Self {
+        Self {
+            scores: registry
+                .iter()
+                .map(|(peer, reputation)| (*peer, reputation.score()))
+                .collect(),
+            min_score,
+            weighted: false,
+        }
+    }
+
+    /// Weight retained updates by `1 + max(score, 0)`.
+    pub fn weighted(mut self) -> Self {
+        self.weighted = true;
+        self
+    }
+
+    /// Snapshot score for `peer` (`0.0` if unknown).
+    pub fn score(&self, peer: &PeerId) -> f32 {
+        self.scores.get(peer).copied().unwrap_or(0.0)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl RobustAggregator for ReputationTrimmedMean {
+    fn aggregate(&self, updates: &[GradientUpdate]) -> Result<Vec<f32>> {
+        let dim = validate_gradient_shapes(updates)?;
+        let mut result = alloc::vec![0.0f32; dim];
+        let mut total_weight = 0.0f32;
+        for update in updates {
+            let score = self.score(&PeerId::new(update.sender));
+            if score < self.min_score {
+                continue;
+            }
+            let weight = if self.weighted {
+                1.0 + score.max(0.0)
+            } else {
+                1.0
+            };
+            for (slot, &gradient) in result.iter_mut().zip(update.gradients.iter()) {
+                *slot += weight * gradient;
+            }
+            total_weight += weight;
+        }
+        if total_weight == 0.0 {
+            return Err(crate::Error::InsufficientUpdates { got: 0, need: 1 });
+        }
+        for slot in result.iter_mut() {
+            *slot /= total_weight;
+        }
+        Ok(result)
+    }
+
+    fn byzantine_tolerance(&self) -> f32 {
+        0.0 // Depends on reputation, not on a static fraction
+    }
+
+    fn complexity(&self) -> AggregatorComplexity {
+        AggregatorComplexity::Linear
+    }
+}
+
+/// Majority-vote sign aggregator (SignSGD with majority vote).
+///
+/// Each coordinate takes the majority sign across updates (zero and ties count
+/// as `+`), scaled by the median of the per-update mean magnitudes. Pairs with
+/// [`SignCompressor`](crate::compression::SignCompressor): a strict minority of
+/// flipped-sign updates cannot change any output sign, and the median keeps a
+/// minority from inflating the step size.
+#[derive(Debug, Clone, Default)]
+pub struct SignVoteAggregator;
+
+impl RobustAggregator for SignVoteAggregator {
+    fn aggregate(&self, updates: &[GradientUpdate]) -> Result<Vec<f32>> {
+        #[cfg(feature = "alloc")]
+        {
+            let dim = validate_gradient_shapes(updates)?;
+            let n = updates.len();
+
+            let mut magnitudes: Vec<f32> = updates
+                .iter()
+                .map(|u| u.gradients.iter().map(|g| g.abs()).sum::<f32>() / dim as f32)
+                .collect();
+            magnitudes.sort_by(|a, b| a.total_cmp(b));
+            let scale = if n % 2 == 0 {
+                (magnitudes[n / 2 - 1] + magnitudes[n / 2]) / 2.0
+            } else {
+                magnitudes[n / 2]
+            };
+
+            let mut votes = alloc::vec![0i64; dim];
+            for update in updates {
+                for (vote, &gradient) in votes.iter_mut().zip(update.gradients.iter()) {
+                    *vote += if gradient < 0.0 { -1 } else { 1 };
+                }
+            }
+
+            Ok(votes
+                .into_iter()
+                .map(|vote| if vote < 0 { -scale } else { scale })
+                .collect())
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        Err(crate::Error::ResourceExhausted)
+    }
+
+    fn byzantine_tolerance(&self) -> f32 {
+        0.5 // Any strict minority of flipped signs is outvoted
+    }
+
+    fn complexity(&self) -> AggregatorComplexity {
+        AggregatorComplexity::Linear
+    }
+}
+
 /// Krum aggregator - selects the update closest to others
 #[cfg(feature = "krum")]
 #[derive(Debug, Clone)]
@@ -379,7 +764,10 @@ impl RobustAggregator for Krum {
             let f = self.num_byzantine;
 
             if n < 2 * f + 3 {
-                return Err(crate::Error::InsufficientUpdates);
+                return Err(crate::Error::InsufficientUpdates {
+                    got: n,
+                    need: 2 * f + 3,
+                });
             }
             if n > MAX_KRUM_PEERS {
                 return Err(crate::Error::ResourceExhausted);
@@ -455,7 +843,7 @@ impl Default for RobustAggregation {
 mod tests {
     use super::*;
     use crate::compression::CompressionMethod;
-    use crate::traits::UpdateTransform;
+    use crate::traits::{UpdateEncoding, UpdateTransform};
 
     fn update(gradients: Vec<f32>) -> GradientUpdate {
         GradientUpdate {
@@ -463,6 +851,7 @@ mod tests {
             sequence: 0,
             gradients,
             round_id: 0,
+            encoding: UpdateEncoding::Full,
         }
     }
 
@@ -477,9 +866,51 @@ mod tests {
             sequence,
             gradients,
             round_id,
+            encoding: UpdateEncoding::Full,
         }
     }
 
+    #[test]
+    fn distribution_filter_flags_inflated_update() {
+        let mut updates: Vec<GradientUpdate> = (0..5)
+            .map(|i| update((0..8).map(|d| d as f32 + 0.1 * i as f32).collect()))
+            .collect();
+        // Inflated on 6 of 8 coordinates.
+        updates.push(update(
+            (0..8)
+                .map(|d| if d < 6 { 100.0 } else { d as f32 })
+                .collect(),
+        ));
+
+        let result = DistributionFilter::default().filter(&updates).unwrap();
+        assert_eq!(result.flagged, vec![5]);
+        assert_eq!(result.retained.len(), 5);
+        assert_eq!(result.retained[4].gradients, updates[4].gradients);
+    }
+
+    #[test]
+    fn distribution_filter_handles_zero_spread_coordinate() {
+        // Coordinate 0 is identical across the batch (MAD = 0).
+        let mut updates: Vec<GradientUpdate> = (0..4)
+            .map(|i| update(vec![1.0, i as f32, -(i as f32), 0.5 * i as f32]))
+            .collect();
+        let filter = DistributionFilter {
+            k: 3.0,
+            max_outlier_fraction: 0.5,
+        };
+        assert!(filter.filter(&updates).unwrap().flagged.is_empty());
+
+        // Any deviation on the zero-spread coordinate is an outlier, but one
+        // coordinate alone stays under the fraction.
+        updates.push(update(vec![1.5, 2.0, -2.0, 1.0]));
+        let result = filter.filter(&updates).unwrap();
+        assert!(result.flagged.is_empty());
+
+        updates.push(update(vec![f32::NAN, 2.0, f32::NAN, 9.0]));
+        let result = filter.filter(&updates).unwrap();
+        assert_eq!(result.flagged, vec![5]);
+    }
+
     struct AddOneTransform;
 
     impl UpdateTransform for AddOneTransform {
@@ -518,21 +949,61 @@ mod tests {
     fn fedavg_rejects_mismatched_gradient_dimensions() {
         let updates = vec![update(vec![1.0, 2.0]), update(vec![3.0])];
         let result = FedAvg.aggregate(&updates);
-        assert!(matches!(result, Err(crate::Error::InvalidGradient)));
+        assert!(matches!(
+            result,
+            Err(crate::Error::InvalidGradient {
+                index: 1,
+                reason: GradientValidationError::DimensionMismatch {
+                    expected: 2,
+                    got: 1
+                },
+            })
+        ));
+    }
+
+    #[test]
+    fn aggregators_reject_delta_encoded_updates() {
+        let full = update(vec![1.0, 2.0]);
+        let delta = update(vec![3.0, 4.0]).delta_from(&full);
+        assert!(matches!(delta.encoding, UpdateEncoding::Delta { .. }));
+        let updates = vec![full, delta];
+
+        let is_not_full = |result: Result<Vec<f32>>| {
+            matches!(
+                result,
+                Err(crate::Error::InvalidGradient {
+                    index: 1,
+                    reason: GradientValidationError::NotFullEncoding,
+                })
+            )
+        };
+        assert!(is_not_full(FedAvg.aggregate(&updates)));
+        assert!(is_not_full(TrimmedMean::new(0.0).aggregate(&updates)));
+        assert!(is_not_full(CoordinateMedian.aggregate(&updates)));
+        assert!(DistributionFilter::default().filter(&updates).is_err());
     }
 
     #[test]
     fn trimmed_mean_rejects_mismatched_gradient_dimensions() {
         let updates = vec![update(vec![1.0, 2.0]), update(vec![3.0])];
         let result = TrimmedMean::new(0.2).aggregate(&updates);
-        assert!(matches!(result, Err(crate::Error::InvalidGradient)));
+        assert!(matches!(
+            result,
+            Err(crate::Error::InvalidGradient { index: 1, .. })
+        ));
     }
 
     #[test]
     fn coordinate_median_rejects_empty_gradient_vectors() {
         let updates = vec![update(vec![]), update(vec![])];
         let result = CoordinateMedian.aggregate(&updates);
-        assert!(matches!(result, Err(crate::Error::InvalidGradient)));
+        assert!(matches!(
+            result,
+            Err(crate::Error::InvalidGradient {
+                index: 0,
+                reason: GradientValidationError::InvalidDimension { dim: 0, .. },
+            })
+        ));
     }
 
     #[test]
@@ -540,7 +1011,16 @@ mod tests {
         let oversized = [0.0; 10_000_001].to_vec(); // Just barely over MAX_GRADIENT_DIM
         let updates = vec![update(oversized.clone())];
         let result = FedAvg.aggregate(&updates);
-        assert!(matches!(result, Err(crate::Error::InvalidGradient)));
+        assert!(matches!(
+            result,
+            Err(crate::Error::InvalidGradient {
+                reason: GradientValidationError::InvalidDimension {
+                    dim: 10_000_001,
+                    max: MAX_GRADIENT_DIM
+                },
+                ..
+            })
+        ));
     }
 
     #[test]
@@ -608,11 +1088,116 @@ mod tests {
         let updates = vec![update(vec![1.0, 2.0]), update(vec![3.0, 4.0])];
         let result = tm.aggregate(&updates);
         assert!(
-            matches!(result, Err(crate::Error::InsufficientUpdates)),
+            matches!(
+                result,
+                Err(crate::Error::InsufficientUpdates { got: 2, need: 3 })
+            ),
             "guard should return InsufficientUpdates, got {result:?}"
         );
     }
 
+    #[test]
+    fn coordinate_sort_ties_are_reproducible_and_match_golden() {
+        let columns = [
+            [1.0, 1.0, 1.0, 3.0, -2.0],
+            [0.5, 0.5, 0.5, 0.5, 0.5],
+            [2.0, 2.0, 7.0, 2.0, -9.0],
+            [0.0, -0.0, 0.0, -0.0, 0.0],
+        ];
+        let updates: Vec<GradientUpdate> = (0..5)
+            .map(|u| update(columns.iter().map(|c| c[u]).collect()))
+            .collect();
+
+        assert_eq!(
+            sorted_coordinate(&updates, 0),
+            vec![(-2.0, 4), (1.0, 0), (1.0, 1), (1.0, 2), (3.0, 3)]
+        );
+        // Signed zeros are ordered, with the update index breaking ties.
+        let zeros = sorted_coordinate(&updates, 3);
+        assert_eq!(
+            zeros.iter().map(|(_, i)| *i).collect::<Vec<_>>(),
+            vec![1, 3, 0, 2, 4]
+        );
+        assert!(zeros[0].0.is_sign_negative() && zeros[2].0.is_sign_positive());
+
+        let bits = |v: Vec<f32>| v.into_iter().map(f32::to_bits).collect::<Vec<_>>();
+        let trimmed = TrimmedMean::new(0.2).aggregate(&updates).unwrap();
+        let median = CoordinateMedian.aggregate(&updates).unwrap();
+        assert_eq!(bits(trimmed.clone()), bits(vec![1.0, 0.5, 2.0, 0.0]));
+        assert_eq!(bits(median.clone()), bits(vec![1.0, 0.5, 2.0, 0.0]));
+
+        for _ in 0..10 {
+            assert_eq!(
+                bits(TrimmedMean::new(0.2).aggregate(&updates).unwrap()),
+                bits(trimmed.clone())
+            );
+            assert_eq!(
+                bits(CoordinateMedian.aggregate(&updates).unwrap()),
+                bits(median.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn reputation_trimmed_mean_gates_on_reputation_not_values() {
+        use crate::reputation::{PeerOutcome, ReputationConfig};
+
+        let mut registry = ReputationRegistry::new(ReputationConfig::default());
+        registry.record(PeerId::new([2u8; 32]), PeerOutcome::RejectedSignature, 0);
+        for _ in 0..5 {
+            registry.record(PeerId::new([3u8; 32]), PeerOutcome::Accepted, 0);
+        }
+
+        // Peer 2 is low-reputation but its update is unremarkable; peer 3 is a
+        // trusted outlier.
+        let updates = vec![
+            update_with_meta(1, 0, 0, vec![1.0]),
+            update_with_meta(2, 0, 0, vec![1.2]),
+            update_with_meta(3, 0, 0, vec![10.0]),
+        ];
+        let aggregator = ReputationTrimmedMean::from_registry(&registry, -1.0);
+        assert_eq!(aggregator.aggregate(&updates).unwrap(), vec![5.5]);
+
+        // Weighted: peer 1 (score 0) weighs 1, peer 3 (score 5) weighs 6.
+        let weighted = aggregator.clone().weighted().aggregate(&updates).unwrap();
+        assert!((weighted[0] - (1.0 + 6.0 * 10.0) / 7.0).abs() < 1e-6);
+
+        // Nobody qualifies.
+        let strict = ReputationTrimmedMean::from_registry(&registry, 100.0);
+        assert!(matches!(
+            strict.aggregate(&updates),
+            Err(crate::Error::InsufficientUpdates { got: 0, need: 1 })
+        ));
+    }
+
+    #[test]
+    fn trimmed_mean_insufficient_updates_reports_counts() {
+        // 4 peers, 0.5 ratio: trim_count = 2 leaves nothing; 5 peers trim 2 and keep 1.
+        let tm = TrimmedMean { trim_ratio: 0.5 };
+        let updates = vec![update(vec![1.0]); 4];
+        let err = tm.aggregate(&updates).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::InsufficientUpdates { got: 4, need: 5 }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "insufficient updates for aggregation: got 4, need 5"
+        );
+    }
+
+    #[test]
+    fn trimmed_mean_ratio_above_half_fails_when_no_larger_count_fits() {
+        // 0.6 trims everything for every count from 4 up (5 -> 3, 6 -> 3, 7 -> 4, ...).
+        let tm = TrimmedMean { trim_ratio: 0.6 };
+        let updates = vec![update(vec![1.0]); 4];
+        assert!(matches!(
+            tm.aggregate(&updates),
+            Err(crate::Error::AggregationFailed { .. })
+        ));
+        assert_eq!(tm.aggregate(&updates[..3]).unwrap(), vec![1.0]);
+    }
+
     #[test]
     fn update_transform_preserves_sender_sequence_round_id() {
         let updates = vec![
@@ -685,7 +1270,10 @@ mod tests {
         let err = AggregationPipeline::new(FedAvg).run(&updates).unwrap_err();
         assert!(matches!(
             err,
-            AggregationPipelineError::Aggregation(crate::Error::InsufficientUpdates)
+            AggregationPipelineError::Aggregation(crate::Error::InsufficientUpdates {
+                got: 0,
+                need: 1
+            })
         ));
     }
 
@@ -723,4 +1311,91 @@ mod tests {
         assert_eq!(trace.estimated_flops, 0);
         assert_eq!(trace.estimated_compressed_bytes, Some(0));
     }
+
+    #[test]
+    fn clip_by_norm_caps_outlier_influence_on_fedavg() {
+        let updates = vec![
+            update(vec![0.3, 0.4]),
+            update(vec![0.6, 0.8]),
+            update(vec![300.0, 400.0]), // outlier: norm 500
+        ];
+        let unclipped = FedAvg.aggregate(&updates).unwrap();
+        assert!(unclipped[0] > 100.0);
+
+        let preprocessors: Vec<Box<dyn Preprocessor>> = vec![Box::new(ClipByNorm(1.0))];
+        let clipped = aggregate_with_preprocessors(&FedAvg, &updates, &preprocessors).unwrap();
+
+        // Each update contributes at most norm 1.0, so the mean's norm is <= 1.0.
+        let norm = (clipped[0] * clipped[0] + clipped[1] * clipped[1]).sqrt();
+        assert!(norm <= 1.0 + 1e-6, "norm={norm}");
+        assert!((clipped[0] - (0.3 + 0.6 + 0.6) / 3.0).abs() < 1e-6);
+        assert!((clipped[1] - (0.4 + 0.8 + 0.8) / 3.0).abs() < 1e-6);
+
+        // Inputs are untouched.
+        assert_eq!(updates[2].gradients, vec![300.0, 400.0]);
+    }
+
+    #[test]
+    fn standardize_yields_zero_mean_unit_variance() {
+        let mut grad = vec![1.0f32, 2.0, 3.0, 4.0];
+        Standardize.process(&mut grad);
+        let mean = grad.iter().sum::<f32>() / 4.0;
+        let variance = grad.iter().map(|g| (g - mean) * (g - mean)).sum::<f32>() / 4.0;
+        assert!(mean.abs() < 1e-6);
+        assert!((variance - 1.0).abs() < 1e-5);
+
+        let mut constant = vec![5.0f32; 3];
+        Standardize.process(&mut constant);
+        assert_eq!(constant, vec![0.0; 3]);
+    }
+
+    #[test]
+    fn preprocessors_apply_in_order() {
+        let updates = vec![update(vec![10.0, 0.0])];
+        let clip_then_std: Vec<Box<dyn Preprocessor>> =
+            vec![Box::new(ClipByNorm(1.0)), Box::new(Standardize)];
+        let std_then_clip: Vec<Box<dyn Preprocessor>> =
+            vec![Box::new(Standardize), Box::new(ClipByNorm(1.0))];
+        let a = aggregate_with_preprocessors(&FedAvg, &updates, &clip_then_std).unwrap();
+        let b = aggregate_with_preprocessors(&FedAvg, &updates, &std_then_clip).unwrap();
+        assert_eq!(a, vec![1.0, -1.0]);
+        let norm_b = (b[0] * b[0] + b[1] * b[1]).sqrt();
+        assert!((norm_b - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sign_vote_outvotes_minority_of_flipped_updates() {
+        use crate::compression::SignCompressor;
+
+        let honest = vec![0.5f32, -1.0, 2.0, -0.25, 0.0, 3.0, -4.0, 1.0, -0.5];
+        let flipped: Vec<f32> = honest.iter().map(|g| -g * 100.0).collect();
+
+        // 4 honest vs 3 Byzantine peers, all shipped through the 1-bit compressor.
+        let mut updates = Vec::new();
+        for gradients in core::iter::repeat(&honest)
+            .take(4)
+            .chain(core::iter::repeat(&flipped).take(3))
+        {
+            let compressed = SignCompressor.compress(gradients).unwrap();
+            updates.push(update(compressed.decompress().unwrap()));
+        }
+
+        let aggregated = SignVoteAggregator.aggregate(&updates).unwrap();
+        let honest_scale = honest.iter().map(|g| g.abs()).sum::<f32>() / honest.len() as f32;
+        for (i, (&a, &h)) in aggregated.iter().zip(&honest).enumerate() {
+            let expected_sign = if h < 0.0 { -1.0 } else { 1.0 };
+            assert_eq!(a.signum(), expected_sign, "coordinate {i}");
+            assert!(
+                (a.abs() - honest_scale).abs() < 1e-6,
+                "median scale ignores inflated minority"
+            );
+        }
+    }
+
+    #[test]
+    fn sign_vote_ties_resolve_positive() {
+        let updates = vec![update(vec![1.0, -1.0]), update(vec![-1.0, 1.0])];
+        let aggregated = SignVoteAggregator.aggregate(&updates).unwrap();
+        assert_eq!(aggregated, vec![1.0, 1.0]);
+    }
 }
@@ -3,13 +3,29 @@
 //! This module provides implementations of swarm intelligence algorithms
 //! for distributed optimization.
 
+#[cfg(feature = "alloc")]
+mod aco;
+#[cfg(feature = "alloc")]
+mod firefly;
+#[cfg(feature = "alloc")]
+mod math;
+#[cfg(feature = "alloc")]
+mod pso;
+#[cfg(feature = "alloc")]
+mod rng;
+#[cfg(feature = "alloc")]
+mod topology;
+#[cfg(feature = "alloc")]
+pub use aco::{AcoError, AntColony};
+#[cfg(feature = "alloc")]
+pub use firefly::{FireflyError, FireflyOptimizer, DEFAULT_ALPHA_DECAY};
+#[cfg(feature = "alloc")]
+pub use pso::{ParticleSwarm, PsoError, PsoParticle, MAX_PSO_DIMENSIONS};
+
 /// Particle Swarm Optimization (PSO) configuration
+///
+/// Executed by [`ParticleSwarm`] (requires `alloc`).
 #[derive(Debug, Clone)]
-#[deprecated(
-    since = "0.1.0-alpha.6x",
-    note = "PSO execution is not implemented. This type will be feature-gated behind \
-            `experimental-pso` in a future breaking-change release."
-)]
 pub struct ParticleSwarmConfig {
     /// Number of particles in the swarm
     pub num_particles: usize,
@@ -23,7 +39,6 @@ pub struct ParticleSwarmConfig {
     pub max_velocity: f32,
 }
 
-#[allow(deprecated)]
 impl Default for ParticleSwarmConfig {
     fn default() -> Self {
         Self {
@@ -40,7 +55,7 @@ impl Default for ParticleSwarmConfig {
 #[derive(Debug, Clone)]
 #[deprecated(
     since = "0.1.0-alpha.6x",
-    note = "PSO execution is not implemented. See `ParticleSwarmConfig` deprecation note."
+    note = "Fixed-size scaffolding; use the alloc-backed `ParticleSwarm` runner instead."
 )]
 pub struct Particle {
     /// Current position (parameters)
@@ -66,6 +81,8 @@ impl Default for Particle {
 }
 
 /// Ant Colony Optimization (ACO) configuration
+///
+/// Executed by [`AntColony`] (requires `alloc`).
 #[derive(Debug, Clone)]
 pub struct AntColonyConfig {
     /// Number of ants
@@ -93,6 +110,8 @@ impl Default for AntColonyConfig {
 }
 
 /// Firefly Algorithm configuration
+///
+/// Executed by [`FireflyOptimizer`] (requires `alloc`).
 #[derive(Debug, Clone)]
 pub struct FireflyConfig {
     /// Number of fireflies
@@ -117,6 +136,8 @@ impl Default for FireflyConfig {
 }
 
 /// Swarm topology configuration
+///
+/// [`Topology::neighbors`] enumerates each node's peers (requires `alloc`).
 #[derive(Debug, Clone, PartialEq)]
 pub enum Topology {
     /// Full mesh - every node connected to every other
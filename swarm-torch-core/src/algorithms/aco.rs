@@ -0,0 +1,295 @@
+//! Ant Colony Optimization for shortest-path routing
+//!
+//! Ants walk from `source` to `target` over a small weighted adjacency matrix, choosing
+//! each next hop among unvisited neighbors with probability proportional to
+//! `pheromone^alpha * (1 / distance)^beta`. After every step pheromone evaporates by
+//! `evaporation_rate` and each ant that reached `target` deposits
+//! `deposit_factor / tour_length` on the edges it used.
+//!
+//! `distances[i][j]` is the cost of edge `i -> j`; a non-finite or non-positive entry
+//! means there is no edge. Pass a symmetric matrix for undirected links.
+
+use alloc::vec::Vec;
+
+use super::math::pow_f32;
+use super::rng::XorShift64;
+use super::AntColonyConfig;
+
+const INITIAL_PHEROMONE: f32 = 1.0;
+/// Floor that keeps every edge reachable after long evaporation.
+const MIN_PHEROMONE: f32 = 1e-6;
+
+/// ACO configuration error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AcoError {
+    /// `num_ants` must be non-zero.
+    NoAnts,
+    /// The adjacency matrix has no nodes.
+    EmptyGraph,
+    /// Row `row` does not have one entry per node.
+    NotSquare { row: usize },
+    /// `source` or `target` is not a node.
+    NodeOutOfRange { node: usize, nodes: usize },
+}
+
+impl core::fmt::Display for AcoError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NoAnts => write!(f, "ant count must be non-zero"),
+            Self::EmptyGraph => write!(f, "graph has no nodes"),
+            Self::NotSquare { row } => write!(f, "adjacency row {row} has the wrong length"),
+            Self::NodeOutOfRange { node, nodes } => {
+                write!(f, "node {node} out of range for {nodes} nodes")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AcoError {}
+
+/// Seeded ant colony searching for the shortest `source -> target` route.
+#[derive(Debug, Clone)]
+pub struct AntColony {
+    config: AntColonyConfig,
+    distances: Vec<Vec<f32>>,
+    pheromone: Vec<Vec<f32>>,
+    source: usize,
+    target: usize,
+    best_tour: Option<Vec<usize>>,
+    best_length: f32,
+    iteration: usize,
+    rng: XorShift64,
+}
+
+impl AntColony {
+    /// Create a colony over `distances` with uniform initial pheromone.
+    pub fn new(
+        config: AntColonyConfig,
+        distances: &[Vec<f32>],
+        source: usize,
+        target: usize,
+        seed: u64,
+    ) -> Result<Self, AcoError> {
+        if config.num_ants == 0 {
+            return Err(AcoError::NoAnts);
+        }
+        let nodes = distances.len();
+        if nodes == 0 {
+            return Err(AcoError::EmptyGraph);
+        }
+        if let Some(row) = distances.iter().position(|row| row.len() != nodes) {
+            return Err(AcoError::NotSquare { row });
+        }
+        for node in [source, target] {
+            if node >= nodes {
+                return Err(AcoError::NodeOutOfRange { node, nodes });
+            }
+        }
+        Ok(Self {
+            config,
+            distances: distances.to_vec(),
+            pheromone: alloc::vec![alloc::vec![INITIAL_PHEROMONE; nodes]; nodes],
+            source,
+            target,
+            best_tour: None,
+            best_length: f32::INFINITY,
+            iteration: 0,
+            rng: XorShift64::new(seed),
+        })
+    }
+
+    /// Let every ant build a tour, then evaporate and deposit pheromone.
+    ///
+    /// Returns the best tour length found so far (`None` until some ant reaches
+    /// `target`).
+    pub fn step(&mut self) -> Option<f32> {
+        let tours: Vec<(Vec<usize>, f32)> = (0..self.config.num_ants)
+            .filter_map(|_| self.construct_tour())
+            .collect();
+
+        let keep = 1.0 - self.config.evaporation_rate.clamp(0.0, 1.0);
+        for row in &mut self.pheromone {
+            for tau in row.iter_mut() {
+                *tau = (*tau * keep).max(MIN_PHEROMONE);
+            }
+        }
+        for (tour, length) in tours {
+            let deposit = self.config.deposit_factor / length;
+            for hop in tour.windows(2) {
+                self.pheromone[hop[0]][hop[1]] += deposit;
+            }
+            if length < self.best_length {
+                self.best_length = length;
+                self.best_tour = Some(tour);
+            }
+        }
+
+        self.iteration += 1;
+        self.best_length()
+    }
+
+    /// Run `iterations` steps; returns the best tour length found.
+    pub fn optimize(&mut self, iterations: usize) -> Option<f32> {
+        for _ in 0..iterations {
+            self.step();
+        }
+        self.best_length()
+    }
+
+    /// Shortest route found so far, `source` first and `target` last.
+    pub fn best_tour(&self) -> Option<&[usize]> {
+        self.best_tour.as_deref()
+    }
+
+    /// Length of [`AntColony::best_tour`].
+    pub fn best_length(&self) -> Option<f32> {
+        self.best_tour.as_ref().map(|_| self.best_length)
+    }
+
+    /// Pheromone on edge `from -> to`.
+    pub fn pheromone(&self, from: usize, to: usize) -> f32 {
+        self.pheromone[from][to]
+    }
+
+    /// Completed steps.
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    fn edge(&self, from: usize, to: usize) -> Option<f32> {
+        let d = self.distances[from][to];
+        (from != to && d.is_finite() && d > 0.0).then_some(d)
+    }
+
+    /// One ant's walk; `None` if it reaches a dead end.
+    fn construct_tour(&mut self) -> Option<(Vec<usize>, f32)> {
+        let nodes = self.distances.len();
+        let mut visited = alloc::vec![false; nodes];
+        let mut tour = alloc::vec![self.source];
+        let mut length = 0.0f32;
+        let mut current = self.source;
+        visited[current] = true;
+        let mut weights = Vec::with_capacity(nodes);
+
+        while current != self.target {
+            weights.clear();
+            let mut total = 0.0f32;
+            for (next, _) in visited.iter().enumerate().filter(|(_, seen)| !**seen) {
+                if let Some(d) = self.edge(current, next) {
+                    let w = pow_f32(self.pheromone[current][next], self.config.alpha)
+                        * pow_f32(1.0 / d, self.config.beta);
+                    if w > 0.0 && w.is_finite() {
+                        weights.push((next, w));
+                        total += w;
+                    }
+                }
+            }
+            let &(last, _) = weights.last()?;
+            let mut pick = self.rng.next_f32() * total;
+            let mut chosen = last;
+            for &(next, w) in &weights {
+                if pick < w {
+                    chosen = next;
+                    break;
+                }
+                pick -= w;
+            }
+            length += self.distances[current][chosen];
+            visited[chosen] = true;
+            tour.push(chosen);
+            current = chosen;
+        }
+        Some((tour, length))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NO: f32 = f32::INFINITY;
+
+    /// 0 -> 1 -> 4 is the unique shortest route (length 2).
+    fn graph() -> Vec<Vec<f32>> {
+        let edges = [
+            (0, 1, 1.0),
+            (1, 4, 1.0),
+            (0, 2, 2.0),
+            (2, 4, 3.0),
+            (0, 3, 1.5),
+            (3, 2, 1.0),
+        ];
+        let mut d = alloc::vec![alloc::vec![NO; 5]; 5];
+        for (a, b, w) in edges {
+            d[a][b] = w;
+            d[b][a] = w;
+        }
+        d
+    }
+
+    fn config() -> AntColonyConfig {
+        AntColonyConfig {
+            num_ants: 10,
+            ..AntColonyConfig::default()
+        }
+    }
+
+    #[test]
+    fn pheromone_concentrates_on_shortest_route() {
+        let mut colony = AntColony::new(config(), &graph(), 0, 4, 11).unwrap();
+        let best = colony.optimize(30).unwrap();
+        assert_eq!(best, 2.0);
+        assert_eq!(colony.best_tour(), Some(&[0, 1, 4][..]));
+        for detour in [2, 3] {
+            assert!(colony.pheromone(0, 1) > 3.0 * colony.pheromone(0, detour));
+        }
+        assert!(colony.pheromone(1, 4) > 3.0 * colony.pheromone(2, 4));
+    }
+
+    #[test]
+    fn same_seed_is_reproducible() {
+        let mut a = AntColony::new(config(), &graph(), 0, 4, 5).unwrap();
+        let mut b = AntColony::new(config(), &graph(), 0, 4, 5).unwrap();
+        for _ in 0..5 {
+            assert_eq!(a.step(), b.step());
+            assert_eq!(a.pheromone, b.pheromone);
+        }
+    }
+
+    #[test]
+    fn unreachable_target_yields_no_tour() {
+        let mut d = graph();
+        for row in &mut d {
+            row[4] = NO;
+        }
+        d[4] = alloc::vec![NO; 5];
+        let mut colony = AntColony::new(config(), &d, 0, 4, 1).unwrap();
+        assert_eq!(colony.optimize(3), None);
+        assert_eq!(colony.best_tour(), None);
+    }
+
+    #[test]
+    fn invalid_configuration_is_rejected() {
+        let no_ants = AntColonyConfig {
+            num_ants: 0,
+            ..AntColonyConfig::default()
+        };
+        assert_eq!(
+            AntColony::new(no_ants, &graph(), 0, 4, 1).unwrap_err(),
+            AcoError::NoAnts
+        );
+        assert_eq!(
+            AntColony::new(config(), &[], 0, 0, 1).unwrap_err(),
+            AcoError::EmptyGraph
+        );
+        assert_eq!(
+            AntColony::new(config(), &[alloc::vec![NO; 2]], 0, 0, 1).unwrap_err(),
+            AcoError::NotSquare { row: 0 }
+        );
+        assert_eq!(
+            AntColony::new(config(), &graph(), 0, 9, 1).unwrap_err(),
+            AcoError::NodeOutOfRange { node: 9, nodes: 5 }
+        );
+    }
+}
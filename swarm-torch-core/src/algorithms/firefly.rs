@@ -0,0 +1,284 @@
+//! Firefly algorithm optimizer
+//!
+//! Minimizes an objective over a bounded box. Each step, every firefly moves toward
+//! each brighter (lower objective) firefly with attractiveness
+//! `beta_0 * exp(-gamma * r^2)` plus a random term `alpha * (u - 0.5) * range`, where
+//! `range` is the dimension's width. Because attraction fades with distance, separate
+//! groups can settle in separate basins, which helps on multimodal objectives where a
+//! global-best PSO collapses early. `alpha` shrinks by `alpha_decay` per step.
+
+use alloc::vec::Vec;
+
+use super::math::exp_f32;
+use super::rng::XorShift64;
+use super::FireflyConfig;
+
+/// Default per-step multiplier applied to `alpha`.
+pub const DEFAULT_ALPHA_DECAY: f32 = 0.97;
+
+/// Firefly configuration error.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FireflyError {
+    /// `num_fireflies` must be non-zero.
+    NoFireflies,
+    /// At least one dimension is required.
+    NoDimensions,
+    /// Region rows must match the search-space dimensions.
+    DimensionMismatch { expected: usize, found: usize },
+    /// Bounds must be finite with `lo <= hi`.
+    InvalidBounds { dim: usize, lo: f32, hi: f32 },
+}
+
+impl core::fmt::Display for FireflyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NoFireflies => write!(f, "firefly count must be non-zero"),
+            Self::NoDimensions => write!(f, "at least one dimension is required"),
+            Self::DimensionMismatch { expected, found } => {
+                write!(f, "expected {expected} dimensions, found {found}")
+            }
+            Self::InvalidBounds { dim, lo, hi } => {
+                write!(f, "invalid bounds [{lo}, {hi}] for dimension {dim}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FireflyError {}
+
+/// Seeded firefly minimizer.
+#[derive(Debug, Clone)]
+pub struct FireflyOptimizer {
+    config: FireflyConfig,
+    bounds: Vec<(f32, f32)>,
+    positions: Vec<Vec<f32>>,
+    alpha_decay: f32,
+    best_position: Vec<f32>,
+    best_fitness: f32,
+    rng: XorShift64,
+}
+
+impl FireflyOptimizer {
+    /// Place `config.num_fireflies` fireflies uniformly within `bounds`.
+    pub fn new(
+        config: FireflyConfig,
+        bounds: &[(f32, f32)],
+        seed: u64,
+    ) -> Result<Self, FireflyError> {
+        if config.num_fireflies == 0 {
+            return Err(FireflyError::NoFireflies);
+        }
+        if bounds.is_empty() {
+            return Err(FireflyError::NoDimensions);
+        }
+        validate_bounds(bounds)?;
+        let mut optimizer = Self {
+            best_position: bounds.iter().map(|&(lo, _)| lo).collect(),
+            positions: Vec::new(),
+            config,
+            bounds: bounds.to_vec(),
+            alpha_decay: DEFAULT_ALPHA_DECAY,
+            best_fitness: f32::INFINITY,
+            rng: XorShift64::new(seed),
+        };
+        optimizer.scatter(bounds);
+        Ok(optimizer)
+    }
+
+    /// Re-scatter the fireflies within `region` (a sub-box of the search bounds).
+    pub fn init_within(mut self, region: &[(f32, f32)]) -> Result<Self, FireflyError> {
+        if region.len() != self.bounds.len() {
+            return Err(FireflyError::DimensionMismatch {
+                expected: self.bounds.len(),
+                found: region.len(),
+            });
+        }
+        validate_bounds(region)?;
+        let clamped: Vec<(f32, f32)> = region
+            .iter()
+            .zip(&self.bounds)
+            .map(|(&(lo, hi), &(min, max))| (lo.clamp(min, max), hi.clamp(min, max)))
+            .collect();
+        self.scatter(&clamped);
+        Ok(self)
+    }
+
+    /// Override the per-step `alpha` multiplier (`1.0` keeps `alpha` constant).
+    pub fn with_alpha_decay(mut self, alpha_decay: f32) -> Self {
+        self.alpha_decay = alpha_decay;
+        self
+    }
+
+    /// Run `max_iters` steps on `objective`; returns the best fitness found.
+    pub fn optimize(&mut self, objective: fn(&[f32]) -> f32, max_iters: usize) -> f32 {
+        let mut fitness: Vec<f32> = self.positions.iter().map(|x| objective(x)).collect();
+        self.update_best(&fitness);
+        let mut alpha = self.config.alpha;
+
+        for _ in 0..max_iters {
+            let n = self.positions.len();
+            for i in 0..n {
+                let mut moved = false;
+                for j in 0..n {
+                    if fitness[j] < fitness[i] {
+                        self.move_toward(i, j, alpha);
+                        moved = true;
+                    }
+                }
+                if !moved {
+                    // The brightest firefly explores on its own.
+                    self.jitter(i, alpha);
+                }
+                fitness[i] = objective(&self.positions[i]);
+            }
+            self.update_best(&fitness);
+            alpha *= self.alpha_decay;
+        }
+        self.best_fitness
+    }
+
+    /// Best position found so far.
+    pub fn best_position(&self) -> &[f32] {
+        &self.best_position
+    }
+
+    /// Objective at [`FireflyOptimizer::best_position`].
+    pub fn best_fitness(&self) -> f32 {
+        self.best_fitness
+    }
+
+    /// Current firefly positions.
+    pub fn positions(&self) -> &[Vec<f32>] {
+        &self.positions
+    }
+
+    fn scatter(&mut self, region: &[(f32, f32)]) {
+        let rng = &mut self.rng;
+        self.positions = (0..self.config.num_fireflies)
+            .map(|_| {
+                region
+                    .iter()
+                    .map(|&(lo, hi)| lo + rng.next_f32() * (hi - lo))
+                    .collect()
+            })
+            .collect();
+    }
+
+    fn move_toward(&mut self, i: usize, j: usize, alpha: f32) {
+        let r2: f32 = self.positions[i]
+            .iter()
+            .zip(&self.positions[j])
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum();
+        let beta = self.config.beta_0 * exp_f32(-self.config.gamma * r2);
+        for d in 0..self.bounds.len() {
+            let (lo, hi) = self.bounds[d];
+            let noise = alpha * (self.rng.next_f32() - 0.5) * (hi - lo);
+            let x = self.positions[i][d];
+            let target = self.positions[j][d];
+            self.positions[i][d] = (x + beta * (target - x) + noise).clamp(lo, hi);
+        }
+    }
+
+    fn jitter(&mut self, i: usize, alpha: f32) {
+        for (x, &(lo, hi)) in self.positions[i].iter_mut().zip(&self.bounds) {
+            let noise = alpha * (self.rng.next_f32() - 0.5) * (hi - lo);
+            *x = (*x + noise).clamp(lo, hi);
+        }
+    }
+
+    fn update_best(&mut self, fitness: &[f32]) {
+        for (position, &f) in self.positions.iter().zip(fitness) {
+            if f < self.best_fitness {
+                self.best_fitness = f;
+                self.best_position.clone_from(position);
+            }
+        }
+    }
+}
+
+fn validate_bounds(bounds: &[(f32, f32)]) -> Result<(), FireflyError> {
+    for (dim, &(lo, hi)) in bounds.iter().enumerate() {
+        if !(lo.is_finite() && hi.is_finite() && lo <= hi) {
+            return Err(FireflyError::InvalidBounds { dim, lo, hi });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Global minimum 0 at (3, 3); local minimum 1 at (-3, -3).
+    fn two_basins(x: &[f32]) -> f32 {
+        let global: f32 = x.iter().map(|v| (v - 3.0) * (v - 3.0)).sum();
+        let local: f32 = x.iter().map(|v| (v + 3.0) * (v + 3.0)).sum::<f32>() + 1.0;
+        global.min(local)
+    }
+
+    fn config() -> FireflyConfig {
+        FireflyConfig {
+            num_fireflies: 20,
+            ..FireflyConfig::default()
+        }
+    }
+
+    #[test]
+    fn escapes_local_basin_given_enough_iterations() {
+        let bounds = [(-6.0, 6.0); 2];
+        let local_basin = [(-4.0, -2.0); 2];
+        let mut optimizer = FireflyOptimizer::new(config(), &bounds, 3)
+            .unwrap()
+            .init_within(&local_basin)
+            .unwrap();
+        assert!(optimizer
+            .positions()
+            .iter()
+            .all(|x| x.iter().all(|v| *v <= -2.0)));
+
+        let best = optimizer.optimize(two_basins, 150);
+        assert!(best < 0.05, "stuck at {best}");
+        assert!(optimizer
+            .best_position()
+            .iter()
+            .all(|v| (v - 3.0).abs() < 0.25));
+    }
+
+    #[test]
+    fn fixed_seed_is_reproducible() {
+        let bounds = [(-6.0, 6.0); 3];
+        let run = |seed| {
+            let mut optimizer = FireflyOptimizer::new(config(), &bounds, seed).unwrap();
+            let best = optimizer.optimize(two_basins, 30);
+            (best, optimizer.positions().to_vec())
+        };
+        assert_eq!(run(9), run(9));
+        assert_ne!(run(9).1, run(10).1);
+    }
+
+    #[test]
+    fn invalid_configuration_is_rejected() {
+        let none = FireflyConfig {
+            num_fireflies: 0,
+            ..FireflyConfig::default()
+        };
+        assert_eq!(
+            FireflyOptimizer::new(none, &[(0.0, 1.0)], 1).unwrap_err(),
+            FireflyError::NoFireflies
+        );
+        assert_eq!(
+            FireflyOptimizer::new(config(), &[], 1).unwrap_err(),
+            FireflyError::NoDimensions
+        );
+        let optimizer = FireflyOptimizer::new(config(), &[(0.0, 1.0)], 1).unwrap();
+        assert_eq!(
+            optimizer.init_within(&[(0.0, 1.0); 2]).unwrap_err(),
+            FireflyError::DimensionMismatch {
+                expected: 1,
+                found: 2
+            }
+        );
+    }
+}
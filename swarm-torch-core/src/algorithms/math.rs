@@ -0,0 +1,140 @@
+//! Software `exp`/`ln` for the swarm algorithms
+//!
+//! `f32::exp`/`ln`/`powf` need `libm` in `no_std`; these fixed-iteration versions are
+//! used in every build so seeded runs produce identical trajectories with or without
+//! `std`. Relative error is below `1e-6` over the ranges the algorithms use.
+
+const LN_2: f32 = core::f32::consts::LN_2;
+// Cody-Waite split of ln 2: `k * LN_2_HI` is exact for the `k` range used by `exp_f32`.
+const LN_2_HI: f32 = 0.693_145_75;
+const LN_2_LO: f32 = 1.428_606_8e-6;
+
+/// `e^x`; saturates to `INFINITY` / `0.0` outside the `f32` exponent range.
+pub(crate) fn exp_f32(x: f32) -> f32 {
+    if x.is_nan() {
+        return x;
+    }
+    if x > 88.7 {
+        return f32::INFINITY;
+    }
+    if x < -103.9 {
+        return 0.0;
+    }
+    // x = k*ln2 + r with |r| <= ln2/2, e^x = 2^k * e^r.
+    let k = round_half_away(x / LN_2);
+    let r = (x - k as f32 * LN_2_HI) - k as f32 * LN_2_LO;
+    let mut term = 1.0f32;
+    let mut sum = 1.0f32;
+    for i in 1..=9 {
+        term *= r / i as f32;
+        sum += term;
+    }
+    scale_pow2(sum, k)
+}
+
+/// Natural log; `NEG_INFINITY` at `0.0`, `NaN` for negative inputs.
+pub(crate) fn ln_f32(x: f32) -> f32 {
+    if x.is_nan() || x < 0.0 {
+        return f32::NAN;
+    }
+    if x == 0.0 {
+        return f32::NEG_INFINITY;
+    }
+    if x.is_infinite() {
+        return x;
+    }
+    // Normalize subnormals so the exponent field is meaningful.
+    let (x, bias) = if x < f32::MIN_POSITIVE {
+        (x * (1u32 << 23) as f32, -23)
+    } else {
+        (x, 0)
+    };
+    let bits = x.to_bits();
+    let mut e = ((bits >> 23) & 0xff) as i32 - 127 + bias;
+    let mut m = f32::from_bits((bits & 0x007f_ffff) | 0x3f80_0000);
+    // Center the mantissa on 1 so the series converges quickly.
+    if m > core::f32::consts::SQRT_2 {
+        m *= 0.5;
+        e += 1;
+    }
+    // ln(m) = 2 * atanh(s), s = (m - 1) / (m + 1), |s| < 0.172.
+    let s = (m - 1.0) / (m + 1.0);
+    let s2 = s * s;
+    let mut term = s;
+    let mut sum = 0.0f32;
+    for i in 0..6 {
+        sum += term / (2 * i + 1) as f32;
+        term *= s2;
+    }
+    2.0 * sum + e as f32 * LN_2
+}
+
+/// `base^exp` for `base >= 0`.
+pub(crate) fn pow_f32(base: f32, exp: f32) -> f32 {
+    if exp == 0.0 {
+        return 1.0;
+    }
+    if base == 0.0 {
+        return if exp > 0.0 { 0.0 } else { f32::INFINITY };
+    }
+    exp_f32(exp * ln_f32(base))
+}
+
+fn round_half_away(x: f32) -> i32 {
+    if x >= 0.0 {
+        (x + 0.5) as i32
+    } else {
+        (x - 0.5) as i32
+    }
+}
+
+/// `value * 2^k`, stepping so intermediate powers stay representable.
+fn scale_pow2(mut value: f32, mut k: i32) -> f32 {
+    while k > 127 {
+        value *= f32::from_bits(254 << 23);
+        k -= 127;
+    }
+    while k < -126 {
+        value *= f32::from_bits(1 << 23);
+        k += 126;
+    }
+    value * f32::from_bits(((k + 127) as u32) << 23)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    fn rel_err(ours: f32, reference: f32) -> f32 {
+        ((ours - reference) / reference).abs()
+    }
+
+    #[test]
+    fn exp_matches_std() {
+        let mut x = -80.0f32;
+        while x < 80.0 {
+            assert!(rel_err(exp_f32(x), x.exp()) < 1e-6, "exp({x})");
+            x += 0.37;
+        }
+        assert_eq!(exp_f32(0.0), 1.0);
+        assert_eq!(exp_f32(100.0), f32::INFINITY);
+        assert_eq!(exp_f32(-200.0), 0.0);
+    }
+
+    #[test]
+    fn ln_and_pow_match_std() {
+        for &x in &[1e-30f32, 1e-3, 0.5, 1.0, 1.5, 2.0, 10.0, 12345.0, 1e30] {
+            assert!(
+                (ln_f32(x) - x.ln()).abs() < 1e-5 * x.ln().abs().max(1.0),
+                "ln({x})"
+            );
+        }
+        assert_eq!(ln_f32(0.0), f32::NEG_INFINITY);
+        assert!(ln_f32(-1.0).is_nan());
+        for &(b, e) in &[(2.0f32, 3.0f32), (0.5, 2.5), (10.0, -1.0), (3.0, 0.5)] {
+            assert!(rel_err(pow_f32(b, e), b.powf(e)) < 1e-5, "{b}^{e}");
+        }
+        assert_eq!(pow_f32(0.0, 2.0), 0.0);
+        assert_eq!(pow_f32(5.0, 0.0), 1.0);
+    }
+}
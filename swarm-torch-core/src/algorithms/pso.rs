@@ -0,0 +1,345 @@
+//! Particle Swarm Optimization runner
+//!
+//! Minimizes an objective over a bounded box. The social term of each particle's
+//! velocity update uses the best personal-best position in its neighborhood:
+//!
+//! - `FullMesh`, `Star`, `Hierarchical`: the whole swarm (global best; information
+//!   reaches every particle through the mesh or the coordinator)
+//! - `Ring`: the particle and its two index neighbors
+//! - `Gossip { fanout }`: the particle plus `fanout` peers resampled every step
+//!
+//! Randomness comes from a seeded xorshift64* generator, so runs are reproducible in
+//! `no_std` and across platforms.
+
+use alloc::vec::Vec;
+
+use super::rng::XorShift64;
+use super::{ParticleSwarmConfig, Topology};
+
+/// Maximum search-space dimensions accepted by [`ParticleSwarm::new`].
+pub const MAX_PSO_DIMENSIONS: usize = 4096;
+
+/// PSO configuration error.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PsoError {
+    /// `num_particles` must be non-zero.
+    NoParticles,
+    /// At least one dimension is required.
+    NoDimensions,
+    /// Dimension count exceeds [`MAX_PSO_DIMENSIONS`].
+    TooManyDimensions { dims: usize, max: usize },
+    /// Bounds must be finite with `lo <= hi`.
+    InvalidBounds { dim: usize, lo: f32, hi: f32 },
+}
+
+impl core::fmt::Display for PsoError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NoParticles => write!(f, "particle count must be non-zero"),
+            Self::NoDimensions => write!(f, "at least one dimension is required"),
+            Self::TooManyDimensions { dims, max } => {
+                write!(f, "{dims} dimensions exceeds maximum {max}")
+            }
+            Self::InvalidBounds { dim, lo, hi } => {
+                write!(f, "invalid bounds [{lo}, {hi}] for dimension {dim}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PsoError {}
+
+/// State of one particle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PsoParticle {
+    /// Current position
+    pub position: Vec<f32>,
+    /// Current velocity
+    pub velocity: Vec<f32>,
+    /// Best position visited
+    pub best_position: Vec<f32>,
+    /// Objective at `best_position` (lower is better)
+    pub best_fitness: f32,
+}
+
+/// Seeded PSO minimizer over `fn(&[f32]) -> f32`.
+#[derive(Debug, Clone)]
+pub struct ParticleSwarm {
+    config: ParticleSwarmConfig,
+    topology: Topology,
+    bounds: Vec<(f32, f32)>,
+    objective: fn(&[f32]) -> f32,
+    particles: Vec<PsoParticle>,
+    best_position: Vec<f32>,
+    best_fitness: f32,
+    iteration: usize,
+    rng: XorShift64,
+}
+
+impl ParticleSwarm {
+    /// Initialize `config.num_particles` particles uniformly within `bounds`.
+    ///
+    /// `bounds[d]` is the inclusive `(lo, hi)` range of dimension `d`; positions are
+    /// clamped to it and velocities to `±config.max_velocity`.
+    pub fn new(
+        config: ParticleSwarmConfig,
+        topology: Topology,
+        bounds: &[(f32, f32)],
+        objective: fn(&[f32]) -> f32,
+        seed: u64,
+    ) -> Result<Self, PsoError> {
+        if config.num_particles == 0 {
+            return Err(PsoError::NoParticles);
+        }
+        if bounds.is_empty() {
+            return Err(PsoError::NoDimensions);
+        }
+        if bounds.len() > MAX_PSO_DIMENSIONS {
+            return Err(PsoError::TooManyDimensions {
+                dims: bounds.len(),
+                max: MAX_PSO_DIMENSIONS,
+            });
+        }
+        for (dim, &(lo, hi)) in bounds.iter().enumerate() {
+            if !(lo.is_finite() && hi.is_finite() && lo <= hi) {
+                return Err(PsoError::InvalidBounds { dim, lo, hi });
+            }
+        }
+
+        let mut rng = XorShift64::new(seed);
+        let vmax = config.max_velocity.abs();
+        let particles: Vec<PsoParticle> = (0..config.num_particles)
+            .map(|_| {
+                let position: Vec<f32> = bounds
+                    .iter()
+                    .map(|&(lo, hi)| lo + rng.next_f32() * (hi - lo))
+                    .collect();
+                let velocity = bounds
+                    .iter()
+                    .map(|_| (rng.next_f32() * 2.0 - 1.0) * vmax)
+                    .collect();
+                let best_fitness = objective(&position);
+                PsoParticle {
+                    best_position: position.clone(),
+                    position,
+                    velocity,
+                    best_fitness,
+                }
+            })
+            .collect();
+
+        let mut swarm = Self {
+            best_position: particles[0].best_position.clone(),
+            best_fitness: f32::INFINITY,
+            config,
+            topology,
+            bounds: bounds.to_vec(),
+            objective,
+            particles,
+            iteration: 0,
+            rng,
+        };
+        swarm.update_global_best();
+        Ok(swarm)
+    }
+
+    /// Advance every particle by one velocity/position update; returns the best fitness.
+    pub fn step(&mut self) -> f32 {
+        // Neighborhood bests are taken from personal bests before this step's moves.
+        let social: Vec<usize> = (0..self.particles.len())
+            .map(|i| self.neighborhood_best(i))
+            .collect();
+        let vmax = self.config.max_velocity.abs();
+
+        for (i, &nbest) in social.iter().enumerate() {
+            let social_best = self.particles[nbest].best_position.clone();
+            let particle = &mut self.particles[i];
+            for (d, (&(lo, hi), &target)) in self.bounds.iter().zip(&social_best).enumerate() {
+                let r1 = self.rng.next_f32();
+                let r2 = self.rng.next_f32();
+                let x = particle.position[d];
+                let v = self.config.inertia * particle.velocity[d]
+                    + self.config.cognitive * r1 * (particle.best_position[d] - x)
+                    + self.config.social * r2 * (target - x);
+                let v = v.clamp(-vmax, vmax);
+                particle.velocity[d] = v;
+                particle.position[d] = (x + v).clamp(lo, hi);
+            }
+            let fitness = (self.objective)(&particle.position);
+            if fitness < particle.best_fitness {
+                particle.best_fitness = fitness;
+                particle.best_position.clone_from(&particle.position);
+            }
+        }
+
+        self.iteration += 1;
+        self.update_global_best();
+        self.best_fitness
+    }
+
+    /// Run up to `max_iters` steps; returns the best fitness found.
+    pub fn optimize(&mut self, max_iters: usize) -> f32 {
+        for _ in 0..max_iters {
+            self.step();
+        }
+        self.best_fitness
+    }
+
+    /// Best position found so far.
+    pub fn best_position(&self) -> &[f32] {
+        &self.best_position
+    }
+
+    /// Objective at [`ParticleSwarm::best_position`].
+    pub fn best_fitness(&self) -> f32 {
+        self.best_fitness
+    }
+
+    /// Completed steps.
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    /// Current particle states.
+    pub fn particles(&self) -> &[PsoParticle] {
+        &self.particles
+    }
+
+    fn update_global_best(&mut self) {
+        for particle in &self.particles {
+            if particle.best_fitness < self.best_fitness {
+                self.best_fitness = particle.best_fitness;
+                self.best_position.clone_from(&particle.best_position);
+            }
+        }
+    }
+
+    /// Index of the best personal best in particle `i`'s neighborhood.
+    fn neighborhood_best(&mut self, i: usize) -> usize {
+        let n = self.particles.len();
+        let better = |particles: &[PsoParticle], a: usize, b: usize| {
+            if particles[b].best_fitness < particles[a].best_fitness {
+                b
+            } else {
+                a
+            }
+        };
+        match self.topology {
+            Topology::Ring => {
+                let left = (i + n - 1) % n;
+                let right = (i + 1) % n;
+                let best = better(&self.particles, i, left);
+                better(&self.particles, best, right)
+            }
+            Topology::Gossip { fanout } => {
+                let mut best = i;
+                for _ in 0..fanout {
+                    let peer = self.rng.next_index(n);
+                    best = better(&self.particles, best, peer);
+                }
+                best
+            }
+            Topology::FullMesh | Topology::Star | Topology::Hierarchical { .. } => {
+                (0..n).fold(i, |best, j| better(&self.particles, best, j))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sphere(x: &[f32]) -> f32 {
+        x.iter().map(|v| v * v).sum()
+    }
+
+    fn config(num_particles: usize) -> ParticleSwarmConfig {
+        ParticleSwarmConfig {
+            num_particles,
+            ..ParticleSwarmConfig::default()
+        }
+    }
+
+    #[test]
+    fn sphere_converges_near_origin_for_each_topology() {
+        let bounds = [(-5.0, 5.0); 4];
+        for topology in [
+            Topology::FullMesh,
+            Topology::Ring,
+            Topology::gossip(3),
+            Topology::Star,
+        ] {
+            let mut swarm =
+                ParticleSwarm::new(config(30), topology.clone(), &bounds, sphere, 42).unwrap();
+            let initial = swarm.best_fitness();
+            let best = swarm.optimize(200);
+            assert!(best < initial);
+            assert!(best < 1e-3, "{topology:?} reached {best}");
+            assert!(swarm.best_position().iter().all(|x| x.abs() < 0.05));
+            assert_eq!(swarm.iteration(), 200);
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_identical_trajectories() {
+        let bounds = [(-5.0, 5.0); 3];
+        let mut a =
+            ParticleSwarm::new(config(10), Topology::gossip(2), &bounds, sphere, 7).unwrap();
+        let mut b =
+            ParticleSwarm::new(config(10), Topology::gossip(2), &bounds, sphere, 7).unwrap();
+        let c = ParticleSwarm::new(config(10), Topology::gossip(2), &bounds, sphere, 8).unwrap();
+        assert_ne!(a.particles(), c.particles());
+        for _ in 0..20 {
+            a.step();
+            b.step();
+            assert_eq!(a.particles(), b.particles());
+        }
+        assert_eq!(a.best_position(), b.best_position());
+    }
+
+    #[test]
+    fn positions_and_velocities_stay_within_limits() {
+        let bounds = [(0.0, 1.0), (-2.0, -1.0)];
+        let mut swarm = ParticleSwarm::new(config(8), Topology::Ring, &bounds, sphere, 1).unwrap();
+        for _ in 0..50 {
+            swarm.step();
+            for particle in swarm.particles() {
+                for (d, &(lo, hi)) in bounds.iter().enumerate() {
+                    assert!((lo..=hi).contains(&particle.position[d]));
+                    assert!(particle.velocity[d].abs() <= 1.0);
+                }
+            }
+        }
+        // Sphere minimum inside the box is the corner closest to the origin.
+        assert!(swarm.best_fitness() < 1.0 + 1e-3);
+    }
+
+    #[test]
+    fn invalid_configuration_is_rejected() {
+        let ok = [(-1.0, 1.0)];
+        assert_eq!(
+            ParticleSwarm::new(config(0), Topology::FullMesh, &ok, sphere, 1).unwrap_err(),
+            PsoError::NoParticles
+        );
+        assert_eq!(
+            ParticleSwarm::new(config(4), Topology::FullMesh, &[], sphere, 1).unwrap_err(),
+            PsoError::NoDimensions
+        );
+        assert_eq!(
+            ParticleSwarm::new(config(4), Topology::FullMesh, &[(1.0, -1.0)], sphere, 1)
+                .unwrap_err(),
+            PsoError::InvalidBounds {
+                dim: 0,
+                lo: 1.0,
+                hi: -1.0
+            }
+        );
+        let too_many = alloc::vec![(-1.0, 1.0); MAX_PSO_DIMENSIONS + 1];
+        assert!(matches!(
+            ParticleSwarm::new(config(1), Topology::FullMesh, &too_many, sphere, 1),
+            Err(PsoError::TooManyDimensions { .. })
+        ));
+    }
+}
@@ -0,0 +1,34 @@
+//! Seedable RNG shared by the swarm algorithms
+//!
+//! The algorithms only need reproducible uniform draws, so a tiny generator avoids
+//! pulling an RNG crate into `no_std` builds. Not suitable for cryptography.
+
+const DEFAULT_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// xorshift64* (Vigna); never yields the all-zero state.
+#[derive(Debug, Clone)]
+pub(crate) struct XorShift64(u64);
+
+impl XorShift64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(if seed == 0 { DEFAULT_SEED } else { seed })
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.0 = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Uniform in `[0, 1)` from the top 24 bits.
+    pub(crate) fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u32 << 24) as f32
+    }
+
+    pub(crate) fn next_index(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
@@ -0,0 +1,195 @@
+//! Neighbor enumeration for [`Topology`]
+//!
+//! Nodes are identified by index `0..total`. Adjacency per variant:
+//!
+//! - `FullMesh`: every other node
+//! - `Ring`: the previous and next index (wrapping)
+//! - `Star`: node `0` is the hub; spokes see only the hub
+//! - `Hierarchical { layers }`: a heap-ordered tree of depth `layers` with the smallest
+//!   branching factor that fits `total` nodes; neighbors are the parent and children
+//!   (`layers <= 1` is a single flat layer, i.e. a full mesh)
+//! - `Gossip { fanout }`: `fanout` distinct peers drawn from a seeded generator, so
+//!   every node computes the same peer sets for a given seed
+//!
+//! Neighbor lists are sorted and never contain `self_index`.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use super::rng::XorShift64;
+use super::Topology;
+
+impl Topology {
+    /// Indices `self_index` sends to in a swarm of `total` nodes.
+    ///
+    /// Gossip peers use seed `0`; see [`Topology::neighbors_seeded`] to resample per
+    /// round. Returns an empty list if `self_index >= total`.
+    pub fn neighbors(&self, self_index: usize, total: usize) -> Vec<usize> {
+        self.neighbors_seeded(self_index, total, 0)
+    }
+
+    /// [`Topology::neighbors`] with an explicit gossip seed (e.g. the round number).
+    ///
+    /// Non-gossip variants ignore `seed`.
+    pub fn neighbors_seeded(&self, self_index: usize, total: usize, seed: u64) -> Vec<usize> {
+        if self_index >= total {
+            return Vec::new();
+        }
+        let mut neighbors = match *self {
+            Self::FullMesh => full_mesh(self_index, total),
+            Self::Hierarchical { layers } if layers <= 1 => full_mesh(self_index, total),
+            Self::Ring => alloc::vec![(self_index + total - 1) % total, (self_index + 1) % total],
+            Self::Star if self_index == 0 => (1..total).collect(),
+            Self::Star => alloc::vec![0],
+            Self::Hierarchical { layers } => {
+                let branching = tree_branching(layers, total);
+                let mut neighbors = Vec::new();
+                if self_index > 0 {
+                    neighbors.push((self_index - 1) / branching);
+                }
+                let first_child = self_index.saturating_mul(branching).saturating_add(1);
+                neighbors.extend(first_child..first_child.saturating_add(branching).min(total));
+                neighbors
+            }
+            Self::Gossip { fanout } => gossip_peers(self_index, total, fanout, seed),
+        };
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        neighbors.retain(|&peer| peer != self_index);
+        neighbors
+    }
+
+    /// Whether the graph over `total` nodes is connected, treating links as undirected.
+    ///
+    /// Gossip is checked with the seed-`0` peer sets used by [`Topology::neighbors`].
+    /// Zero or one node is trivially connected.
+    pub fn is_connected(&self, total: usize) -> bool {
+        if total <= 1 {
+            return true;
+        }
+        let mut adjacency: Vec<Vec<usize>> = (0..total).map(|i| self.neighbors(i, total)).collect();
+        for i in 0..total {
+            for j in adjacency[i].clone() {
+                if !adjacency[j].contains(&i) {
+                    adjacency[j].push(i);
+                }
+            }
+        }
+
+        let mut seen = alloc::vec![false; total];
+        let mut queue = VecDeque::from([0]);
+        seen[0] = true;
+        let mut reached = 1;
+        while let Some(node) = queue.pop_front() {
+            for &next in &adjacency[node] {
+                if !seen[next] {
+                    seen[next] = true;
+                    reached += 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+        reached == total
+    }
+}
+
+fn full_mesh(self_index: usize, total: usize) -> Vec<usize> {
+    (0..total).filter(|&i| i != self_index).collect()
+}
+
+/// Smallest branching factor whose `layers`-deep tree holds `total` nodes.
+fn tree_branching(layers: usize, total: usize) -> usize {
+    let capacity = |branching: usize| {
+        let mut level = 1usize;
+        let mut sum = 0usize;
+        for _ in 0..layers {
+            sum = sum.saturating_add(level);
+            level = level.saturating_mul(branching);
+        }
+        sum
+    };
+    let mut branching = 1;
+    while capacity(branching) < total {
+        branching += 1;
+    }
+    branching
+}
+
+/// `fanout` distinct peers other than `self_index` via partial Fisher-Yates.
+fn gossip_peers(self_index: usize, total: usize, fanout: usize, seed: u64) -> Vec<usize> {
+    let mut candidates: Vec<usize> = full_mesh(self_index, total);
+    let picks = fanout.min(candidates.len());
+    // Mix the node index in so nodes sharing a seed draw independent peer sets.
+    let mut rng =
+        XorShift64::new(seed ^ (self_index as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    for k in 0..picks {
+        let j = k + rng.next_index(candidates.len() - k);
+        candidates.swap(k, j);
+    }
+    candidates.truncate(picks);
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_returns_two_wrapping_neighbors() {
+        let ring = Topology::Ring;
+        assert_eq!(ring.neighbors(0, 5), [1, 4]);
+        assert_eq!(ring.neighbors(3, 5), [2, 4]);
+        assert_eq!(ring.neighbors(0, 2), [1]);
+        assert!(ring.neighbors(0, 1).is_empty());
+        assert!(ring.neighbors(5, 5).is_empty());
+        assert!(ring.is_connected(7));
+    }
+
+    #[test]
+    fn star_hub_sees_spokes_and_spokes_see_hub() {
+        let star = Topology::Star;
+        assert_eq!(star.neighbors(0, 4), [1, 2, 3]);
+        for spoke in 1..4 {
+            assert_eq!(star.neighbors(spoke, 4), [0]);
+        }
+        assert!(star.is_connected(4));
+        assert_eq!(Topology::FullMesh.neighbors(2, 4), [0, 1, 3]);
+    }
+
+    #[test]
+    fn gossip_fanout_is_respected_and_seeded() {
+        let gossip = Topology::gossip(3);
+        for i in 0..10 {
+            let peers = gossip.neighbors(i, 10);
+            assert_eq!(peers.len(), 3);
+            assert!(!peers.contains(&i));
+            assert!(peers.iter().all(|&p| p < 10));
+            assert_eq!(peers, gossip.neighbors(i, 10));
+        }
+        // Different rounds resample; the same round is stable.
+        let rounds: Vec<Vec<usize>> = (0..8).map(|r| gossip.neighbors_seeded(4, 10, r)).collect();
+        assert!(rounds.iter().any(|peers| *peers != rounds[0]));
+        assert_eq!(rounds[5], gossip.neighbors_seeded(4, 10, 5));
+        // Fanout larger than the swarm is capped.
+        assert_eq!(Topology::gossip(8).neighbors(1, 3), [0, 2]);
+    }
+
+    #[test]
+    fn hierarchical_tree_links_parent_and_children() {
+        // Three layers over seven nodes: a binary tree.
+        let tree = Topology::hierarchical(3);
+        assert_eq!(tree.neighbors(0, 7), [1, 2]);
+        assert_eq!(tree.neighbors(1, 7), [0, 3, 4]);
+        assert_eq!(tree.neighbors(6, 7), [2]);
+        assert!(tree.is_connected(7));
+        assert!(tree.is_connected(100));
+        assert_eq!(Topology::hierarchical(1).neighbors(0, 3), [1, 2]);
+    }
+
+    #[test]
+    fn disconnected_graphs_are_detected() {
+        assert!(!Topology::gossip(0).is_connected(3));
+        assert!(Topology::gossip(0).is_connected(1));
+        assert!(Topology::FullMesh.is_connected(5));
+    }
+}
@@ -262,6 +262,242 @@ impl CompressedGradient {
     }
 }
 
+/// Round half away from zero without `f32::round()` (no libm in `no_std`).
+#[cfg(feature = "alloc")]
+fn round_half_away_i32(x: f32) -> i32 {
+    if x >= 0.0 {
+        (x + 0.5) as i32
+    } else {
+        (x - 0.5) as i32
+    }
+}
+
+/// 8-bit affine (min/max) scalar quantizer with a per-vector scale and offset.
+///
+/// Codes span exactly the observed `[min, max]`, so one-sided data keeps the full
+/// 256-level resolution. Reconstruction error is bounded by `scale / 2` per
+/// coordinate, i.e. `range / 510`.
+///
+/// Constant vectors (zero range) are encoded as `min = value`, `scale = 0` and
+/// every code `0`, so they round-trip exactly without dividing by zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Quantizer8 {}
+
+/// Output of [`Quantizer8::quantize`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuantizedGradient {
+    /// Step size between adjacent codes
+    pub scale: f32,
+    /// Value of code `0` (the smallest input)
+    pub min: f32,
+    /// One code per element; element `i` is `min + scale * codes[i]`
+    pub codes: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl Quantizer8 {
+    /// Create a new 8-bit quantizer
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Quantize a gradient vector to 8-bit codes.
+    ///
+    /// Returns `InvalidData` for non-finite inputs and `InvalidScale` if the
+    /// range is too wide (or too narrow) to yield a finite, non-zero scale.
+    pub fn quantize(
+        &self,
+        values: &[f32],
+    ) -> core::result::Result<QuantizedGradient, CompressionError> {
+        if values.iter().any(|v| !v.is_finite()) {
+            return Err(CompressionError::InvalidData);
+        }
+        let (first, rest) = match values.split_first() {
+            Some(split) => split,
+            None => {
+                return Ok(QuantizedGradient {
+                    scale: 0.0,
+                    min: 0.0,
+                    codes: Vec::new(),
+                })
+            }
+        };
+
+        let (min, max) = rest
+            .iter()
+            .fold((*first, *first), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+
+        // Zero range: every element equals `min`.
+        if min == max {
+            return Ok(QuantizedGradient {
+                scale: 0.0,
+                min,
+                codes: alloc::vec![0u8; values.len()],
+            });
+        }
+
+        let scale = (max - min) / 255.0;
+        if !scale.is_finite() || scale <= 0.0 {
+            return Err(CompressionError::InvalidScale);
+        }
+
+        let codes = values
+            .iter()
+            .map(|&v| round_half_away_i32((v - min) / scale).clamp(0, 255) as u8)
+            .collect();
+
+        Ok(QuantizedGradient { scale, min, codes })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl QuantizedGradient {
+    /// Reconstruct the gradient vector.
+    pub fn dequantize(&self) -> core::result::Result<Vec<f32>, CompressionError> {
+        // L-03: reject crafted non-finite parameters. A zero scale is legitimate
+        // (constant vectors).
+        if !self.scale.is_finite() || !self.min.is_finite() {
+            return Err(CompressionError::InvalidScale);
+        }
+        Ok(self
+            .codes
+            .iter()
+            .map(|&c| self.min + self.scale * c as f32)
+            .collect())
+    }
+
+    /// Get the compressed size in bytes (codes + scale + min)
+    pub fn compressed_size(&self) -> usize {
+        self.codes.len() + 2 * core::mem::size_of::<f32>()
+    }
+
+    /// Get the compression ratio relative to dense `f32`
+    pub fn compression_ratio(&self) -> f32 {
+        let original_size = self.codes.len() * 4; // f32 = 4 bytes
+        if original_size == 0 {
+            return 1.0;
+        }
+        original_size as f32 / self.compressed_size() as f32
+    }
+}
+
+/// 1-bit SignSGD compressor.
+///
+/// Emits one sign bit per coordinate (LSB-first within each byte; a set bit means
+/// negative) plus the vector's mean magnitude as the reconstruction scale. Zero
+/// coordinates are encoded as positive so the output is deterministic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SignCompressor;
+
+/// Output of [`SignCompressor::compress`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignCompressedGradient {
+    /// Mean absolute value of the original gradient
+    pub scale: f32,
+    /// Number of encoded coordinates
+    pub num_elements: usize,
+    /// Packed sign bits (`ceil(num_elements / 8)` bytes, unused high bits zero)
+    pub bits: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl SignCompressor {
+    /// Compress a gradient to packed sign bits plus mean magnitude.
+    ///
+    /// Returns `InvalidData` for non-finite inputs.
+    pub fn compress(
+        &self,
+        gradients: &[f32],
+    ) -> core::result::Result<SignCompressedGradient, CompressionError> {
+        if gradients.iter().any(|v| !v.is_finite()) {
+            return Err(CompressionError::InvalidData);
+        }
+        let mut bits = alloc::vec![0u8; gradients.len().div_ceil(8)];
+        let mut abs_sum = 0.0f32;
+        for (i, &v) in gradients.iter().enumerate() {
+            if v < 0.0 {
+                bits[i / 8] |= 1 << (i % 8);
+            }
+            abs_sum += v.abs();
+        }
+        let scale = if gradients.is_empty() {
+            0.0
+        } else {
+            abs_sum / gradients.len() as f32
+        };
+        Ok(SignCompressedGradient {
+            scale,
+            num_elements: gradients.len(),
+            bits,
+        })
+    }
+
+    /// Reconstruct `sign * scale` for each coordinate.
+    pub fn decompress(
+        &self,
+        compressed: &SignCompressedGradient,
+    ) -> core::result::Result<Vec<f32>, CompressionError> {
+        compressed.decompress()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl SignCompressedGradient {
+    /// Unpack the sign bits as `+1` / `-1` (zero coordinates were encoded as `+1`).
+    pub fn signs(&self) -> core::result::Result<Vec<i8>, CompressionError> {
+        let n = self.num_elements;
+        if self.bits.len() != n.div_ceil(8) {
+            return Err(CompressionError::InvalidData);
+        }
+        // Canonical encoding: padding bits in the final byte must be zero.
+        if n % 8 != 0 {
+            if let Some(last) = self.bits.last() {
+                if last >> (n % 8) != 0 {
+                    return Err(CompressionError::InvalidData);
+                }
+            }
+        }
+        Ok((0..n)
+            .map(|i| {
+                if self.bits[i / 8] & (1 << (i % 8)) != 0 {
+                    -1
+                } else {
+                    1
+                }
+            })
+            .collect())
+    }
+
+    /// Reconstruct `sign * scale` for each coordinate.
+    pub fn decompress(&self) -> core::result::Result<Vec<f32>, CompressionError> {
+        // L-03: reject crafted non-finite or negative scales.
+        if !self.scale.is_finite() || self.scale < 0.0 {
+            return Err(CompressionError::InvalidScale);
+        }
+        Ok(self
+            .signs()?
+            .into_iter()
+            .map(|sign| f32::from(sign) * self.scale)
+            .collect())
+    }
+
+    /// Get the compressed size in bytes (bits + scale)
+    pub fn compressed_size(&self) -> usize {
+        self.bits.len() + core::mem::size_of::<f32>()
+    }
+
+    /// Get the compression ratio relative to dense `f32`
+    pub fn compression_ratio(&self) -> f32 {
+        let original_size = self.num_elements * 4; // f32 = 4 bytes
+        if original_size == 0 {
+            return 1.0;
+        }
+        original_size as f32 / self.compressed_size() as f32
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -585,4 +821,166 @@ mod tests {
             }
         }
     }
+
+    /// Pseudo-random values uniform in `[offset, offset + width)`.
+    fn lcg_values(len: usize, offset: f32, width: f32) -> Vec<f32> {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                let x = ((state >> 16) as u32) as f32 / (u32::MAX as f32);
+                offset + x * width
+            })
+            .collect()
+    }
+
+    /// Quantize `gradients` and check the error against their own `range / 255`.
+    fn assert_quantizer8_error_bounded(gradients: &[f32]) {
+        let min = gradients.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = gradients.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let range = max - min;
+
+        let quantized = Quantizer8::new().quantize(gradients).unwrap();
+        assert_eq!(quantized.codes.len(), gradients.len());
+        let restored = quantized.dequantize().unwrap();
+
+        let max_err = gradients
+            .iter()
+            .zip(&restored)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0f32, f32::max);
+        assert!(
+            max_err <= range / 255.0,
+            "max_err={max_err} exceeds bound {}",
+            range / 255.0
+        );
+    }
+
+    #[test]
+    fn quantizer8_error_bounded_by_range() {
+        assert_quantizer8_error_bounded(&lcg_values(257, -3.0, 8.0));
+    }
+
+    #[test]
+    fn quantizer8_error_bounded_by_range_for_one_sided_data() {
+        assert_quantizer8_error_bounded(&lcg_values(257, 100.0, 1.0));
+        assert_quantizer8_error_bounded(&lcg_values(257, -101.0, 1.0));
+        assert_quantizer8_error_bounded(&[100.0, 101.0]);
+        assert_quantizer8_error_bounded(&[-101.0, -100.0, -100.5]);
+    }
+
+    #[test]
+    fn quantizer8_constant_vector_round_trips_exactly() {
+        for value in [3.7f32, -0.25, 0.0, 1.0e-30] {
+            let gradients = vec![value; 17];
+            let quantized = Quantizer8::new().quantize(&gradients).unwrap();
+            assert_eq!(quantized.dequantize().unwrap(), gradients, "value={value}");
+        }
+    }
+
+    #[test]
+    fn quantizer8_round_trips_range_endpoints_exactly() {
+        let gradients = vec![0.0, 1.3, -0.7, 0.0, 2.9];
+        let restored = Quantizer8::new()
+            .quantize(&gradients)
+            .unwrap()
+            .dequantize()
+            .unwrap();
+        assert_eq!(restored[2], -0.7);
+        assert!((restored[4] - 2.9).abs() <= f32::EPSILON * 4.0);
+    }
+
+    #[test]
+    fn quantizer8_rejects_non_finite_input() {
+        let q = Quantizer8::new();
+        assert_eq!(
+            q.quantize(&[1.0, f32::NAN]).unwrap_err(),
+            CompressionError::InvalidData
+        );
+        assert_eq!(
+            q.quantize(&[f32::INFINITY]).unwrap_err(),
+            CompressionError::InvalidData
+        );
+    }
+
+    #[test]
+    fn quantizer8_compression_ratio_approaches_four() {
+        let gradients = vec![0.5f32; 1000];
+        let quantized = Quantizer8::new().quantize(&gradients).unwrap();
+        assert_eq!(quantized.compressed_size(), 1008);
+        assert!(quantized.compression_ratio() > 3.9);
+
+        let empty = Quantizer8::new().quantize(&[]).unwrap();
+        assert_eq!(empty.compression_ratio(), 1.0);
+        assert!(empty.dequantize().unwrap().is_empty());
+    }
+
+    #[test]
+    fn sign_compressor_packs_non_multiple_of_eight_dims() {
+        for len in [1usize, 7, 8, 9, 13, 17] {
+            let gradients: Vec<f32> = (0..len)
+                .map(|i| {
+                    if i % 3 == 0 {
+                        -(i as f32) - 1.0
+                    } else {
+                        i as f32
+                    }
+                })
+                .collect();
+            let compressed = SignCompressor.compress(&gradients).unwrap();
+            assert_eq!(compressed.bits.len(), len.div_ceil(8), "len={len}");
+
+            let signs = compressed.signs().unwrap();
+            assert_eq!(signs.len(), len);
+            for (i, (&g, &s)) in gradients.iter().zip(&signs).enumerate() {
+                let expected = if g < 0.0 { -1 } else { 1 };
+                assert_eq!(s, expected, "len={len} i={i}");
+            }
+
+            let restored = SignCompressor.decompress(&compressed).unwrap();
+            for (&r, &s) in restored.iter().zip(&signs) {
+                assert_eq!(r, f32::from(s) * compressed.scale);
+            }
+        }
+    }
+
+    #[test]
+    fn sign_compressor_treats_zero_as_positive_and_uses_mean_magnitude() {
+        let compressed = SignCompressor.compress(&[0.0, -0.0, -2.0, 4.0]).unwrap();
+        assert_eq!(compressed.bits, vec![0b0000_0100]);
+        assert_eq!(compressed.scale, 1.5);
+        assert_eq!(compressed.decompress().unwrap(), vec![1.5, 1.5, -1.5, 1.5]);
+    }
+
+    #[test]
+    fn sign_compressed_rejects_malformed_payloads() {
+        let mut compressed = SignCompressor.compress(&[1.0, -1.0, 1.0]).unwrap();
+        compressed.bits[0] |= 0b1000_0000; // padding bit set
+        assert_eq!(
+            compressed.decompress().unwrap_err(),
+            CompressionError::InvalidData
+        );
+
+        let short = SignCompressedGradient {
+            scale: 1.0,
+            num_elements: 9,
+            bits: vec![0],
+        };
+        assert_eq!(
+            short.decompress().unwrap_err(),
+            CompressionError::InvalidData
+        );
+
+        let nan_scale = SignCompressedGradient {
+            scale: f32::NAN,
+            num_elements: 1,
+            bits: vec![0],
+        };
+        assert_eq!(
+            nan_scale.decompress().unwrap_err(),
+            CompressionError::InvalidScale
+        );
+    }
 }
@@ -36,21 +36,161 @@ impl Default for GossipConfig {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct RoundId(pub u64);
 
-/// State of a training round
+/// Lifecycle phase of a training round.
+///
+/// `Collecting → Ready → Aggregated → Complete`, or `Collecting → TimedOut` when
+/// the deadline passes before quorum.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum RoundState {
-    /// Waiting for round to start
-    Pending,
-    /// Training in progress
-    Training,
-    /// Collecting updates
+pub enum RoundPhase {
+    /// Collecting updates; quorum not yet reached
     Collecting,
-    /// Aggregating updates
-    Aggregating,
+    /// Quorum reached; late updates are still accepted until aggregation
+    Ready,
+    /// Updates aggregated; awaiting completion broadcast
+    Aggregated,
     /// Round complete
     Complete,
-    /// Round failed
-    Failed,
+    /// Deadline passed without quorum
+    TimedOut,
+}
+
+/// Rejected [`RoundState`] transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundTransitionError {
+    /// Phase the round was in
+    pub from: RoundPhase,
+    /// Phase that was requested
+    pub to: RoundPhase,
+}
+
+impl core::fmt::Display for RoundTransitionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "invalid round transition {:?} -> {:?}",
+            self.from, self.to
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RoundTransitionError {}
+
+/// State machine for one training round (coordinator side).
+///
+/// Tracks which peers have submitted updates against a quorum and a deadline.
+/// Time is supplied by the caller (unix seconds), so the type stays `no_std`.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct RoundState {
+    /// Round this state belongs to
+    pub round_id: RoundId,
+    /// Participants announced in `RoundStart`
+    pub expected_participants: usize,
+    /// Distinct updates needed to reach `Ready`
+    pub quorum: usize,
+    /// Round deadline (unix seconds)
+    pub deadline: u64,
+    received: alloc::collections::BTreeSet<PeerId>,
+    phase: RoundPhase,
+}
+
+#[cfg(feature = "alloc")]
+impl RoundState {
+    /// Start collecting for `round_id`; `quorum` is clamped to `1..=expected_participants`.
+    pub fn new(
+        round_id: RoundId,
+        expected_participants: usize,
+        quorum: usize,
+        deadline: u64,
+    ) -> Self {
+        Self {
+            round_id,
+            expected_participants,
+            quorum: quorum.clamp(1, expected_participants.max(1)),
+            deadline,
+            received: alloc::collections::BTreeSet::new(),
+            phase: RoundPhase::Collecting,
+        }
+    }
+
+    /// Start collecting with quorum `ceil(expected_participants * config.quorum_ratio)`.
+    pub fn from_config(
+        config: &GossipConfig,
+        round_id: RoundId,
+        expected_participants: usize,
+        deadline: u64,
+    ) -> Self {
+        let exact = expected_participants as f32 * config.quorum_ratio;
+        let mut quorum = exact as usize;
+        if (quorum as f32) < exact {
+            quorum += 1;
+        }
+        Self::new(round_id, expected_participants, quorum, deadline)
+    }
+
+    /// Current phase.
+    pub fn phase(&self) -> RoundPhase {
+        self.phase
+    }
+
+    /// Peers whose updates were recorded, in `PeerId` order.
+    pub fn received(&self) -> impl Iterator<Item = &PeerId> + '_ {
+        self.received.iter()
+    }
+
+    /// Number of distinct peers recorded.
+    pub fn received_count(&self) -> usize {
+        self.received.len()
+    }
+
+    /// Whether enough distinct peers have submitted.
+    pub fn quorum_reached(&self) -> bool {
+        self.received.len() >= self.quorum
+    }
+
+    /// Record an update from `peer`. Returns `false` if the peer was already
+    /// recorded or the round no longer accepts updates.
+    pub fn on_update(&mut self, peer: PeerId) -> bool {
+        if !matches!(self.phase, RoundPhase::Collecting | RoundPhase::Ready) {
+            return false;
+        }
+        let inserted = self.received.insert(peer);
+        if self.quorum_reached() {
+            self.phase = RoundPhase::Ready;
+        }
+        inserted
+    }
+
+    /// Apply the deadline at `now` (unix seconds): a round still collecting
+    /// without quorum becomes `TimedOut`. Returns the resulting phase.
+    pub fn on_deadline(&mut self, now: u64) -> RoundPhase {
+        if self.phase == RoundPhase::Collecting && now >= self.deadline {
+            self.phase = RoundPhase::TimedOut;
+        }
+        self.phase
+    }
+
+    /// `Ready → Aggregated`.
+    pub fn mark_aggregated(&mut self) -> Result<(), RoundTransitionError> {
+        self.transition(RoundPhase::Ready, RoundPhase::Aggregated)
+    }
+
+    /// `Aggregated → Complete`.
+    pub fn mark_complete(&mut self) -> Result<(), RoundTransitionError> {
+        self.transition(RoundPhase::Aggregated, RoundPhase::Complete)
+    }
+
+    fn transition(&mut self, from: RoundPhase, to: RoundPhase) -> Result<(), RoundTransitionError> {
+        if self.phase != from {
+            return Err(RoundTransitionError {
+                from: self.phase,
+                to,
+            });
+        }
+        self.phase = to;
+        Ok(())
+    }
 }
 
 /// Membership view of the cluster
@@ -94,6 +234,47 @@ mod tests {
         assert!(!m.is_active(&b));
         assert_eq!(m.active_count(), 1);
     }
+
+    #[test]
+    fn round_reaching_quorum_flips_to_ready_and_completes() {
+        let mut round = RoundState::new(RoundId(1), 3, 2, 100);
+        assert!(round.on_update(PeerId::new([1u8; 32])));
+        assert!(!round.on_update(PeerId::new([1u8; 32])));
+        assert_eq!(round.phase(), RoundPhase::Collecting);
+        assert!(!round.quorum_reached());
+
+        assert!(round.on_update(PeerId::new([2u8; 32])));
+        assert!(round.quorum_reached());
+        assert_eq!(round.phase(), RoundPhase::Ready);
+        // A late update still counts; the deadline no longer times out the round.
+        assert!(round.on_update(PeerId::new([3u8; 32])));
+        assert_eq!(round.on_deadline(200), RoundPhase::Ready);
+
+        round.mark_aggregated().unwrap();
+        assert!(!round.on_update(PeerId::new([4u8; 32])));
+        round.mark_complete().unwrap();
+        assert_eq!(round.phase(), RoundPhase::Complete);
+        assert_eq!(round.received_count(), 3);
+    }
+
+    #[test]
+    fn round_deadline_without_quorum_times_out() {
+        let config = GossipConfig::default();
+        let mut round = RoundState::from_config(&config, RoundId(2), 3, 100);
+        assert_eq!(round.quorum, 3); // ceil(3 * 0.67)
+        round.on_update(PeerId::new([1u8; 32]));
+
+        assert_eq!(round.on_deadline(99), RoundPhase::Collecting);
+        assert_eq!(round.on_deadline(100), RoundPhase::TimedOut);
+        assert!(!round.on_update(PeerId::new([2u8; 32])));
+        assert_eq!(
+            round.mark_aggregated(),
+            Err(RoundTransitionError {
+                from: RoundPhase::TimedOut,
+                to: RoundPhase::Aggregated,
+            })
+        );
+    }
 }
 
 /// Vote in consensus protocol
@@ -0,0 +1,149 @@
+//! Early-stopping detection
+//!
+//! [`ConvergenceMonitor`] is fed the aggregated parameter vector (or a scalar loss)
+//! after every round and reports convergence once the L2 distance between consecutive
+//! rounds stays below `threshold` for `patience` consecutive rounds. The training loop
+//! checks [`ConvergenceMonitor::has_converged`] to stop before `max_rounds`.
+//!
+//! Distances are compared squared, so no `sqrt` (and no `libm`) is needed in `no_std`.
+
+use alloc::vec::Vec;
+
+/// Tracks round-to-round parameter movement for early stopping.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvergenceMonitor {
+    threshold: f32,
+    patience: usize,
+    previous: Option<Vec<f32>>,
+    stable_rounds: usize,
+    rounds: u64,
+}
+
+impl ConvergenceMonitor {
+    /// Converge after `patience` consecutive deltas below `threshold`.
+    ///
+    /// A `patience` of `0` is treated as `1`.
+    pub fn new(threshold: f32, patience: usize) -> Self {
+        Self {
+            threshold,
+            patience: patience.max(1),
+            previous: None,
+            stable_rounds: 0,
+            rounds: 0,
+        }
+    }
+
+    /// Record this round's parameters; returns [`ConvergenceMonitor::has_converged`].
+    ///
+    /// The first round, and any round whose length differs from the previous one, has
+    /// no delta and resets the stable streak.
+    pub fn record(&mut self, params: &[f32]) -> bool {
+        self.rounds += 1;
+        let stable = match &self.previous {
+            Some(prev) if prev.len() == params.len() => {
+                let delta_sq: f32 = prev
+                    .iter()
+                    .zip(params)
+                    .map(|(a, b)| (a - b) * (a - b))
+                    .sum();
+                delta_sq < self.threshold * self.threshold
+            }
+            _ => false,
+        };
+        self.stable_rounds = if stable { self.stable_rounds + 1 } else { 0 };
+        match &mut self.previous {
+            Some(prev) => {
+                prev.clear();
+                prev.extend_from_slice(params);
+            }
+            None => self.previous = Some(params.to_vec()),
+        }
+        self.has_converged()
+    }
+
+    /// Record a scalar metric such as the round's loss.
+    pub fn record_metric(&mut self, value: f32) -> bool {
+        self.record(&[value])
+    }
+
+    /// Whether the last `patience` deltas were all below `threshold`.
+    pub fn has_converged(&self) -> bool {
+        self.stable_rounds >= self.patience
+    }
+
+    /// Current streak of consecutive below-threshold deltas.
+    pub fn stable_rounds(&self) -> usize {
+        self.stable_rounds
+    }
+
+    /// Rounds recorded so far.
+    pub fn rounds(&self) -> u64 {
+        self.rounds
+    }
+
+    /// Convergence threshold on the L2 delta.
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// Consecutive stable rounds required.
+    pub fn patience(&self) -> usize {
+        self.patience
+    }
+
+    /// Forget all history (e.g. after a model reset).
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.stable_rounds = 0;
+        self.rounds = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converges_exactly_at_patience_boundary() {
+        let mut monitor = ConvergenceMonitor::new(0.01, 3);
+        // Deltas: 1.0, 0.1, then 0.005 (below threshold) thereafter.
+        let values = [0.0, 1.0, 1.1, 1.105, 1.11, 1.115, 1.12];
+        let converged: Vec<bool> = values.iter().map(|&v| monitor.record(&[v, -v])).collect();
+        // Two-dimensional delta is 0.005 * sqrt(2) < 0.01.
+        assert_eq!(converged, [false, false, false, false, false, true, true]);
+        assert_eq!(monitor.stable_rounds(), 4);
+        assert_eq!(monitor.rounds(), 7);
+    }
+
+    #[test]
+    fn noisy_sequence_never_converges() {
+        let mut monitor = ConvergenceMonitor::new(0.05, 2);
+        let mut x = 0.0f32;
+        for round in 0..50 {
+            // Alternate one small and one large step so the streak never reaches 2.
+            x += if round % 2 == 0 { 0.01 } else { 0.5 };
+            assert!(!monitor.record(&[x, 1.0, -x]), "round {round}");
+        }
+        assert!(monitor.stable_rounds() <= 1);
+    }
+
+    #[test]
+    fn large_delta_or_shape_change_resets_streak() {
+        let mut monitor = ConvergenceMonitor::new(0.1, 2);
+        for _ in 0..3 {
+            monitor.record_metric(1.0);
+        }
+        assert!(monitor.has_converged());
+
+        assert!(!monitor.record_metric(5.0));
+        assert_eq!(monitor.stable_rounds(), 0);
+
+        monitor.record_metric(5.0);
+        monitor.record_metric(5.0);
+        assert!(monitor.has_converged());
+        assert!(!monitor.record(&[5.0, 5.0]));
+
+        monitor.reset();
+        assert_eq!((monitor.rounds(), monitor.stable_rounds()), (0, 0));
+    }
+}
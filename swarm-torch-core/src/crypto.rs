@@ -1,11 +1,20 @@
 //! Cryptographic utilities for authentication and verification
 //!
-//! This module provides Ed25519 signatures and message authentication.
+//! This module provides Ed25519 signatures and message authentication, plus
+//! X25519 key agreement for transport encryption ([`x25519`]) and pairwise
+//! masking for secure aggregation ([`secure_agg`]).
+
+#[cfg(feature = "alloc")]
+pub mod secure_agg;
+pub mod x25519;
 
 use crate::traits::PeerId;
 use ed25519_dalek::{Signature as DalekSignature, Signer, SigningKey, VerifyingKey};
 use sha2::{Digest, Sha256};
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 /// Key pair for signing messages
 #[derive(Clone)]
 pub struct KeyPair {
@@ -74,6 +83,12 @@ pub enum CryptoError {
     InvalidPublicKey,
     /// Signature verification failed
     VerificationFailed,
+    /// Input `index` has `got` elements where `expected` were required
+    InvalidLength {
+        index: usize,
+        expected: usize,
+        got: usize,
+    },
 }
 
 impl core::fmt::Display for CryptoError {
@@ -83,6 +98,11 @@ impl core::fmt::Display for CryptoError {
             Self::InvalidSignatureEncoding => write!(f, "invalid signature encoding"),
             Self::InvalidPublicKey => write!(f, "invalid public key"),
             Self::VerificationFailed => write!(f, "signature verification failed"),
+            Self::InvalidLength {
+                index,
+                expected,
+                got,
+            } => write!(f, "input {index} has {got} elements, expected {expected}"),
         }
     }
 }
@@ -118,25 +138,34 @@ impl MessageAuth {
         timestamp: u32,
         payload: &[u8],
     ) -> Signature {
-        // Domain separation tag
-        let tag = b"swarmtorch.envelope.v0";
-
-        // 1. Hash the payload first
-        let payload_hash = Sha256::digest(payload);
-
-        // 2. Construct canonical preimage
-        let mut hasher = Sha256::new();
-        hasher.update(tag);
-        hasher.update([version.0, version.1]);
-        hasher.update(self.key_pair.public); // Bind to sender (self)
-        hasher.update(sequence.to_le_bytes());
-        hasher.update(timestamp.to_le_bytes());
-        hasher.update([message_type]);
-        hasher.update(payload_hash);
+        self.sign_with_flags(version, message_type, 0, sequence, timestamp, payload)
+    }
 
-        let canonical = hasher.finalize();
+    /// [`MessageAuth::sign`] with envelope payload `flags` bound into the signature.
+    ///
+    /// `flags == 0` produces exactly the [`MessageAuth::sign`] signature, so
+    /// envelopes without flags stay verifiable by older peers.
+    pub fn sign_with_flags(
+        &self,
+        version: (u8, u8),
+        message_type: u8,
+        flags: u8,
+        sequence: u64,
+        timestamp: u32,
+        payload: &[u8],
+    ) -> Signature {
+        // Bind to sender (self)
+        let canonical = envelope_digest(
+            &self.key_pair.public,
+            version,
+            message_type,
+            flags,
+            sequence,
+            timestamp,
+            payload,
+        );
 
-        // 3. Sign the canonical hash
+        // Sign the canonical hash
         let sig = self.key_pair.secret.sign(&canonical);
         Signature(sig.to_bytes())
     }
@@ -153,6 +182,30 @@ impl MessageAuth {
         timestamp: u32,
         payload: &[u8],
         signature: &Signature,
+    ) -> Result<(), CryptoError> {
+        Self::verify_with_flags(
+            public_key,
+            version,
+            message_type,
+            0,
+            sequence,
+            timestamp,
+            payload,
+            signature,
+        )
+    }
+
+    /// Verify a signature produced by [`MessageAuth::sign_with_flags`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_with_flags(
+        public_key: &[u8; 32],
+        version: (u8, u8),
+        message_type: u8,
+        flags: u8,
+        sequence: u64,
+        timestamp: u32,
+        payload: &[u8],
+        signature: &Signature,
     ) -> Result<(), CryptoError> {
         // Parse public key
         let key =
@@ -162,31 +215,289 @@ impl MessageAuth {
         let sig = signature.to_dalek()?;
 
         // Reconstruct canonical preimage
-        let tag = b"swarmtorch.envelope.v0";
-        let payload_hash = Sha256::digest(payload);
-
-        let mut hasher = Sha256::new();
-        hasher.update(tag);
-        hasher.update([version.0, version.1]);
-        hasher.update(public_key);
-        hasher.update(sequence.to_le_bytes());
-        hasher.update(timestamp.to_le_bytes());
-        hasher.update([message_type]);
-        hasher.update(payload_hash);
-
-        let canonical = hasher.finalize();
+        let canonical = envelope_digest(
+            public_key,
+            version,
+            message_type,
+            flags,
+            sequence,
+            timestamp,
+            payload,
+        );
 
         // Strict verification
         key.verify_strict(&canonical, &sig)
             .map_err(|_| CryptoError::VerificationFailed)
     }
 
+    /// Sign arbitrary `bytes` (e.g. a file) under a caller-chosen `domain` tag.
+    ///
+    /// The preimage is domain-separated from envelope signatures, so a detached signature
+    /// can never be replayed as a message signature or across domains.
+    pub fn sign_detached(&self, domain: &[u8], bytes: &[u8]) -> Signature {
+        let sig = self.key_pair.secret.sign(&detached_digest(domain, bytes));
+        Signature(sig.to_bytes())
+    }
+
+    /// Verify a signature produced by [`MessageAuth::sign_detached`].
+    pub fn verify_detached(
+        public_key: &[u8; 32],
+        domain: &[u8],
+        bytes: &[u8],
+        signature: &Signature,
+    ) -> Result<(), CryptoError> {
+        let key =
+            VerifyingKey::from_bytes(public_key).map_err(|_| CryptoError::InvalidPublicKey)?;
+        let sig = signature.to_dalek()?;
+        key.verify_strict(&detached_digest(domain, bytes), &sig)
+            .map_err(|_| CryptoError::VerificationFailed)
+    }
+
+    /// Verify many envelopes at once; `results[i]` is the outcome for `items[i]`.
+    ///
+    /// With the `batch` feature, well-formed items are checked with a single
+    /// Ed25519 batch equation. Weak (small-order) public keys are rejected up
+    /// front as in `verify_strict`; if the batch fails, each item is re-checked
+    /// with [`MessageAuth::verify_with_flags`] so the failing indices are reported
+    /// exactly. Without the feature this is sequential `verify_with_flags` over `items`.
+    ///
+    /// Unlike `verify_strict`, the batch equation does not reject a small-order or
+    /// non-canonically encoded `R`. Such signatures can only come from the key
+    /// holder or from re-encoding a valid signature, so they are not forgeries;
+    /// replay protection still keys on the (signed) sequence number.
+    #[cfg(feature = "alloc")]
+    pub fn verify_batch(items: &[BatchVerifyItem<'_>]) -> Vec<Result<(), CryptoError>> {
+        #[cfg(feature = "batch")]
+        {
+            verify_batch_dalek(items)
+        }
+        #[cfg(not(feature = "batch"))]
+        {
+            items.iter().map(verify_item).collect()
+        }
+    }
+
     /// Get the key pair
     pub fn key_pair(&self) -> &KeyPair {
         &self.key_pair
     }
 }
 
+/// One envelope for [`MessageAuth::verify_batch`], in `verify_with_flags` argument
+/// order: `(public_key, version, message_type, flags, sequence, timestamp, payload,
+/// signature)`. Use `flags = 0` for envelopes signed with [`MessageAuth::sign`].
+pub type BatchVerifyItem<'a> = (
+    &'a [u8; 32],
+    (u8, u8),
+    u8,
+    u8,
+    u64,
+    u32,
+    &'a [u8],
+    &'a Signature,
+);
+
+/// Canonical signed digest for an envelope (domain tag + header + payload hash).
+///
+/// Non-zero `flags` are hashed after the message type; zero flags leave the
+/// preimage unchanged from the flagless digest.
+fn envelope_digest(
+    public_key: &[u8; 32],
+    version: (u8, u8),
+    message_type: u8,
+    flags: u8,
+    sequence: u64,
+    timestamp: u32,
+    payload: &[u8],
+) -> [u8; 32] {
+    // Domain separation tag
+    let tag = b"swarmtorch.envelope.v0";
+
+    // Hash the payload first, then the canonical preimage
+    let payload_hash = Sha256::digest(payload);
+
+    let mut hasher = Sha256::new();
+    hasher.update(tag);
+    hasher.update([version.0, version.1]);
+    hasher.update(public_key);
+    hasher.update(sequence.to_le_bytes());
+    hasher.update(timestamp.to_le_bytes());
+    hasher.update([message_type]);
+    if flags != 0 {
+        hasher.update([flags]);
+    }
+    hasher.update(payload_hash);
+
+    hasher.finalize().into()
+}
+
+fn detached_digest(domain: &[u8], bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"swarmtorch.detached.v0");
+    hasher.update((domain.len() as u64).to_le_bytes());
+    hasher.update(domain);
+    hasher.update(Sha256::digest(bytes));
+    hasher.finalize().into()
+}
+
+#[cfg(feature = "alloc")]
+fn verify_item(item: &BatchVerifyItem<'_>) -> Result<(), CryptoError> {
+    let (public_key, version, message_type, flags, sequence, timestamp, payload, signature) = *item;
+    MessageAuth::verify_with_flags(
+        public_key,
+        version,
+        message_type,
+        flags,
+        sequence,
+        timestamp,
+        payload,
+        signature,
+    )
+}
+
+#[cfg(feature = "batch")]
+fn verify_batch_dalek(items: &[BatchVerifyItem<'_>]) -> Vec<Result<(), CryptoError>> {
+    let mut results: Vec<Result<(), CryptoError>> = Vec::with_capacity(items.len());
+    let mut indices = Vec::new();
+    let mut digests = Vec::new();
+    let mut signatures = Vec::new();
+    let mut keys = Vec::new();
+
+    for (index, item) in items.iter().enumerate() {
+        let (public_key, version, message_type, flags, sequence, timestamp, payload, signature) =
+            *item;
+        let parsed = VerifyingKey::from_bytes(public_key)
+            .map_err(|_| CryptoError::InvalidPublicKey)
+            .and_then(|key| Ok((key, signature.to_dalek()?)));
+        match parsed {
+            // verify_strict rejects weak keys; the batch equation does not.
+            Ok((key, _)) if key.is_weak() => results.push(Err(CryptoError::VerificationFailed)),
+            Ok((key, sig)) => {
+                indices.push(index);
+                digests.push(envelope_digest(
+                    public_key,
+                    version,
+                    message_type,
+                    flags,
+                    sequence,
+                    timestamp,
+                    payload,
+                ));
+                signatures.push(sig);
+                keys.push(key);
+                results.push(Ok(()));
+            }
+            Err(err) => results.push(Err(err)),
+        }
+    }
+
+    if indices.is_empty() {
+        return results;
+    }
+
+    let messages: Vec<&[u8]> = digests.iter().map(|digest| digest.as_slice()).collect();
+    if ed25519_dalek::verify_batch(&messages, &signatures, &keys).is_err() {
+        // Locate the offending items with strict per-item verification.
+        for index in indices {
+            results[index] = verify_item(&items[index]);
+        }
+    }
+    results
+}
+
+/// Default number of superseded signing keys a [`KeyRing`] keeps for verification.
+pub const DEFAULT_KEY_RETENTION: usize = 2;
+
+/// Rotating signing keys for one sender, addressed by envelope `key_id`.
+///
+/// The identity (default) key is the sender's long-lived key: envelopes without a
+/// `key_id` verify against it. `rotate` registers a new signing key under the next
+/// id and keeps the previous `retention` rotated keys for a grace period, so
+/// in-flight envelopes signed before a rotation still verify.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct KeyRing {
+    identity: [u8; 32],
+    /// Rotated keys, oldest first.
+    keys: Vec<(u16, [u8; 32])>,
+    next_id: u16,
+    retention: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl KeyRing {
+    /// Create a ring for `identity` with [`DEFAULT_KEY_RETENTION`].
+    pub fn new(identity: [u8; 32]) -> Result<Self, CryptoError> {
+        Self::with_retention(identity, DEFAULT_KEY_RETENTION)
+    }
+
+    /// Create a ring retaining `retention` superseded keys after each rotation.
+    pub fn with_retention(identity: [u8; 32], retention: usize) -> Result<Self, CryptoError> {
+        validate_public_key(&identity)?;
+        Ok(Self {
+            identity,
+            keys: Vec::new(),
+            next_id: 0,
+            retention,
+        })
+    }
+
+    /// Register `new_key` as the current signing key and return its `key_id`.
+    ///
+    /// Ids increase by one per rotation (wrapping at `u16::MAX`). Keys older than
+    /// the retention window are evicted and no longer verify.
+    pub fn rotate(&mut self, new_key: [u8; 32]) -> Result<u16, CryptoError> {
+        validate_public_key(&new_key)?;
+        let key_id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        // A wrapped id must not alias a retained key.
+        self.keys.retain(|(id, _)| *id != key_id);
+        self.keys.push((key_id, new_key));
+        let excess = self
+            .keys
+            .len()
+            .saturating_sub(self.retention.saturating_add(1));
+        self.keys.drain(..excess);
+        Ok(key_id)
+    }
+
+    /// Identity key used for envelopes without a `key_id`.
+    pub fn default_key(&self) -> &[u8; 32] {
+        &self.identity
+    }
+
+    /// Key registered under `key_id`, if still retained.
+    pub fn get(&self, key_id: u16) -> Option<&[u8; 32]> {
+        self.keys
+            .iter()
+            .find(|(id, _)| *id == key_id)
+            .map(|(_, key)| key)
+    }
+
+    /// Resolve an envelope's optional `key_id` to the verifying key.
+    pub fn resolve(&self, key_id: Option<u16>) -> Option<&[u8; 32]> {
+        match key_id {
+            Some(id) => self.get(id),
+            None => Some(&self.identity),
+        }
+    }
+
+    /// Id of the most recently rotated key, if any.
+    pub fn current_key_id(&self) -> Option<u16> {
+        self.keys.last().map(|(id, _)| *id)
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn validate_public_key(key: &[u8; 32]) -> Result<(), CryptoError> {
+    if *key == [0u8; 32] {
+        return Err(CryptoError::InvalidPublicKey);
+    }
+    VerifyingKey::from_bytes(key)
+        .map(|_| ())
+        .map_err(|_| CryptoError::InvalidPublicKey)
+}
+
 /// Configuration surface for security features.
 ///
 /// Current enforcement reality (Wave 8):
@@ -235,40 +546,126 @@ impl Default for GradientValidator {
 
 impl GradientValidator {
     /// Validate a gradient vector
+    ///
+    /// One-shot wrapper over [`GradientValidator::validate_chunk`] + [`GradientValidator::finish`].
     pub fn validate(&self, gradients: &[f32]) -> Result<(), GradientValidationError> {
-        // Check for NaN/Inf
-        for (i, &g) in gradients.iter().enumerate() {
-            if g.is_nan() {
-                return Err(GradientValidationError::NaN { index: i });
-            }
-            if g.is_infinite() {
-                return Err(GradientValidationError::Infinite { index: i });
-            }
-            let abs_g = if g < 0.0 { -g } else { g };
-            if abs_g > self.max_coordinate_value {
-                return Err(GradientValidationError::CoordinateTooLarge {
-                    index: i,
-                    value: g,
-                    max: self.max_coordinate_value,
-                });
+        let mut state = ValidatorState::new();
+        self.validate_chunk(gradients, &mut state)?;
+        self.finish(state)
+    }
+
+    /// Validate the next chunk of a streamed gradient.
+    ///
+    /// Checks NaN/Inf/coordinate bounds (indices are global across chunks) and
+    /// accumulates the running `norm_sq`. Errors are sticky: once a chunk fails,
+    /// later chunks and `finish` return the same error.
+    pub fn validate_chunk(
+        &self,
+        chunk: &[f32],
+        state: &mut ValidatorState,
+    ) -> Result<(), GradientValidationError> {
+        if let Some(err) = &state.error {
+            return Err(err.clone());
+        }
+        for &g in chunk {
+            let index = state.elements;
+            let result = if g.is_nan() {
+                Err(GradientValidationError::NaN { index })
+            } else if g.is_infinite() {
+                Err(GradientValidationError::Infinite { index })
+            } else {
+                let abs_g = if g < 0.0 { -g } else { g };
+                if abs_g > self.max_coordinate_value {
+                    Err(GradientValidationError::CoordinateTooLarge {
+                        index,
+                        value: g,
+                        max: self.max_coordinate_value,
+                    })
+                } else {
+                    if abs_g > state.max_abs {
+                        state.max_abs = abs_g;
+                    }
+                    Ok(())
+                }
+            };
+            if let Err(err) = result {
+                state.error = Some(err.clone());
+                return Err(err);
             }
+            state.norm_sq += g * g;
+            state.elements += 1;
         }
+        Ok(())
+    }
 
-        // Check L2 norm
-        let norm_sq: f32 = gradients.iter().map(|g| g * g).sum();
-        let norm = sqrt_f32(norm_sq);
+    /// Finish a streamed validation: apply the L2 norm bound.
+    pub fn finish(&self, state: ValidatorState) -> Result<(), GradientValidationError> {
+        if let Some(err) = state.error {
+            return Err(err);
+        }
+        let norm = sqrt_f32(state.norm_sq);
         if norm > self.max_gradient_norm {
             return Err(GradientValidationError::NormTooLarge {
                 norm,
                 max: self.max_gradient_norm,
             });
         }
-
         Ok(())
     }
 }
 
-/// Software square root for gradient L2-norm validation only.
+/// Running state for streamed gradient validation (bounded memory).
+#[derive(Debug, Clone, Default)]
+pub struct ValidatorState {
+    elements: usize,
+    norm_sq: f32,
+    max_abs: f32,
+    error: Option<GradientValidationError>,
+}
+
+impl ValidatorState {
+    /// Fresh state for a new gradient.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of coordinates accepted so far.
+    pub fn elements(&self) -> usize {
+        self.elements
+    }
+
+    /// Running sum of squares over accepted coordinates.
+    pub fn norm_sq(&self) -> f32 {
+        self.norm_sq
+    }
+
+    /// Largest absolute coordinate accepted so far.
+    pub fn max_abs(&self) -> f32 {
+        self.max_abs
+    }
+}
+
+/// Constant-time equality for security-relevant digests and signatures.
+///
+/// Runtime depends only on the input lengths, never on where the inputs first
+/// differ: every byte pair is folded into one accumulator before the single final
+/// branch. A length mismatch returns early, so lengths are not secret (fine for
+/// fixed-size digests and their hex encodings).
+pub fn ct_eq_bytes(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    // Keep the optimizer from turning the fold back into an early-exit comparison.
+    core::hint::black_box(diff) == 0
+}
+
+/// [`ct_eq_bytes`] over string bytes (e.g. lowercase-hex digests).
+pub fn ct_eq(a: &str, b: &str) -> bool {
+    ct_eq_bytes(a.as_bytes(), b.as_bytes())
+}
+
+/// Software square root for gradient L2-norm validation and pre-processing only.
 ///
 /// Uses `std::f32::sqrt` when available, otherwise 8 Newton-Raphson
 /// iterations from initial guess `x`. Relative error < 1e-7 for inputs
@@ -276,7 +673,7 @@ impl GradientValidator {
 ///
 /// **NOT** used in canonical hashing or deterministic artifact paths.
 #[inline]
-fn sqrt_f32(x: f32) -> f32 {
+pub(crate) fn sqrt_f32(x: f32) -> f32 {
     #[cfg(feature = "std")]
     {
         x.sqrt()
@@ -307,12 +704,54 @@ pub enum GradientValidationError {
     CoordinateTooLarge { index: usize, value: f32, max: f32 },
     /// Gradient norm too large
     NormTooLarge { norm: f32, max: f32 },
+    /// Gradient length differs from the rest of the batch
+    DimensionMismatch { expected: usize, got: usize },
+    /// Gradient length is zero or above the supported maximum
+    InvalidDimension { dim: usize, max: usize },
+    /// Update is not [`UpdateEncoding::Full`](crate::traits::UpdateEncoding::Full);
+    /// reconstruct it with `GradientUpdate::apply_delta` first
+    NotFullEncoding,
 }
 
+impl core::fmt::Display for GradientValidationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NaN { index } => write!(f, "NaN at coordinate {index}"),
+            Self::Infinite { index } => write!(f, "infinite value at coordinate {index}"),
+            Self::CoordinateTooLarge { index, value, max } => {
+                write!(f, "coordinate {index} value {value} exceeds {max}")
+            }
+            Self::NormTooLarge { norm, max } => write!(f, "L2 norm {norm} exceeds {max}"),
+            Self::DimensionMismatch { expected, got } => {
+                write!(f, "dimension {got} does not match expected {expected}")
+            }
+            Self::InvalidDimension { dim, max } => {
+                write!(f, "dimension {dim} outside supported range 1..={max}")
+            }
+            Self::NotFullEncoding => write!(f, "update is not fully encoded"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GradientValidationError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn ct_eq_matches_plain_equality() {
+        let digest = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+        assert!(ct_eq(digest, digest));
+        assert!(ct_eq("", ""));
+        assert!(!ct_eq(digest, &digest.replace('9', "8")));
+        assert!(!ct_eq(&digest[..63], digest));
+        assert!(ct_eq_bytes(&[1, 2, 3], &[1, 2, 3]));
+        assert!(!ct_eq_bytes(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!ct_eq_bytes(&[0x80], &[0x00]));
+    }
+
     // Helper to change one byte in a slice
     fn tamper(bytes: &mut [u8]) {
         bytes[0] ^= 0xFF;
@@ -338,6 +777,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn signature_binds_flags_and_zero_flags_match_plain_sign() {
+        let pair = KeyPair::from_seed([3u8; 32]).expect("non-zero seed");
+        let auth = MessageAuth::new(pair.clone());
+        let (version, msg_type, seq, ts, payload) = ((0, 3), 2, 7, 1_000, b"bytes");
+
+        let plain = auth.sign(version, msg_type, seq, ts, payload);
+        let zero = auth.sign_with_flags(version, msg_type, 0, seq, ts, payload);
+        assert_eq!(plain.as_bytes(), zero.as_bytes());
+
+        let flagged = auth.sign_with_flags(version, msg_type, 1, seq, ts, payload);
+        let verify = |flags, sig: &Signature| {
+            MessageAuth::verify_with_flags(
+                pair.public_key(),
+                version,
+                msg_type,
+                flags,
+                seq,
+                ts,
+                payload,
+                sig,
+            )
+        };
+        assert!(verify(1, &flagged).is_ok());
+        assert!(verify(0, &flagged).is_err());
+        assert!(verify(1, &plain).is_err());
+    }
+
+    #[test]
+    fn detached_signature_is_domain_separated() {
+        let pair = KeyPair::from_seed([5u8; 32]).expect("non-zero seed");
+        let auth = MessageAuth::new(pair.clone());
+        let sig = auth.sign_detached(b"manifest", b"file bytes");
+
+        assert!(
+            MessageAuth::verify_detached(pair.public_key(), b"manifest", b"file bytes", &sig)
+                .is_ok()
+        );
+        assert_eq!(
+            MessageAuth::verify_detached(pair.public_key(), b"other", b"file bytes", &sig),
+            Err(CryptoError::VerificationFailed)
+        );
+        assert_eq!(
+            MessageAuth::verify_detached(pair.public_key(), b"manifest", b"file bytez", &sig),
+            Err(CryptoError::VerificationFailed)
+        );
+    }
+
     #[test]
     fn signature_verification_fails_for_tampered_payload() {
         let seed = [2u8; 32];
@@ -399,6 +886,159 @@ mod tests {
         assert_eq!(result, Err(CryptoError::VerificationFailed));
     }
 
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn key_ring_rotation_retains_and_evicts() {
+        let identity = *KeyPair::from_seed([1u8; 32]).unwrap().public_key();
+        let rotated: Vec<[u8; 32]> = (2u8..=5)
+            .map(|i| *KeyPair::from_seed([i; 32]).unwrap().public_key())
+            .collect();
+        let mut ring = KeyRing::with_retention(identity, 1).unwrap();
+        assert_eq!(ring.current_key_id(), None);
+        assert_eq!(ring.resolve(None), Some(&identity));
+
+        let ids: Vec<u16> = rotated.iter().map(|k| ring.rotate(*k).unwrap()).collect();
+        assert_eq!(ids, vec![0, 1, 2, 3]);
+        assert_eq!(ring.current_key_id(), Some(3));
+
+        // Current + one retained; older ids are evicted.
+        assert_eq!(ring.get(3), Some(&rotated[3]));
+        assert_eq!(ring.get(2), Some(&rotated[2]));
+        assert_eq!(ring.get(1), None);
+        assert_eq!(ring.resolve(Some(0)), None);
+        assert_eq!(ring.resolve(None), Some(&identity));
+
+        assert_eq!(
+            KeyRing::new([0u8; 32]).unwrap_err(),
+            CryptoError::InvalidPublicKey
+        );
+        assert_eq!(ring.rotate([0u8; 32]), Err(CryptoError::InvalidPublicKey));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn verify_batch_results_match_sequential_order() {
+        let signers: Vec<MessageAuth> = (1u8..=4)
+            .map(|i| MessageAuth::new(KeyPair::from_seed([i; 32]).expect("non-zero seed")))
+            .collect();
+        let version = (0, 1);
+        let payloads: [&[u8]; 4] = [b"alpha", b"beta", b"gamma", b"delta"];
+        let sigs: Vec<Signature> = signers
+            .iter()
+            .zip(payloads)
+            .enumerate()
+            .map(|(i, (auth, payload))| auth.sign(version, 1, i as u64, 1000, payload))
+            .collect();
+        // Signed with flags: verifies only when the item carries the same flags.
+        let flagged_sig = signers[1].sign_with_flags(version, 1, 0x01, 7, 1000, payloads[1]);
+        let mut bad_sig = sigs[0];
+        tamper(&mut bad_sig.0[32..]);
+        let zero_key = [0u8; 32];
+
+        let items: Vec<BatchVerifyItem<'_>> = vec![
+            (
+                signers[0].key_pair().public_key(),
+                version,
+                1,
+                0,
+                0,
+                1000,
+                payloads[0],
+                &sigs[0],
+            ),
+            // Wrong sequence for a valid signature.
+            (
+                signers[1].key_pair().public_key(),
+                version,
+                1,
+                0,
+                99,
+                1000,
+                payloads[1],
+                &sigs[1],
+            ),
+            (
+                signers[2].key_pair().public_key(),
+                version,
+                1,
+                0,
+                2,
+                1000,
+                payloads[2],
+                &sigs[2],
+            ),
+            // Signature checked against another peer's key.
+            (
+                signers[0].key_pair().public_key(),
+                version,
+                1,
+                0,
+                3,
+                1000,
+                payloads[3],
+                &sigs[3],
+            ),
+            (&zero_key, version, 1, 0, 0, 1000, payloads[0], &sigs[0]),
+            (
+                signers[0].key_pair().public_key(),
+                version,
+                1,
+                0,
+                0,
+                1000,
+                payloads[0],
+                &bad_sig,
+            ),
+            (
+                signers[3].key_pair().public_key(),
+                version,
+                1,
+                0,
+                3,
+                1000,
+                payloads[3],
+                &sigs[3],
+            ),
+            (
+                signers[1].key_pair().public_key(),
+                version,
+                1,
+                0x01,
+                7,
+                1000,
+                payloads[1],
+                &flagged_sig,
+            ),
+            (
+                signers[1].key_pair().public_key(),
+                version,
+                1,
+                0,
+                7,
+                1000,
+                payloads[1],
+                &flagged_sig,
+            ),
+        ];
+
+        let sequential: Vec<_> = items.iter().map(verify_item).collect();
+        let batch = MessageAuth::verify_batch(&items);
+        assert_eq!(batch, sequential);
+        assert_eq!(batch.len(), items.len());
+        assert!(batch[0].is_ok() && batch[2].is_ok() && batch[6].is_ok());
+        assert_eq!(batch[1], Err(CryptoError::VerificationFailed));
+        assert_eq!(batch[3], Err(CryptoError::VerificationFailed));
+        assert!(batch[4].is_err());
+        assert!(batch[5].is_err());
+        assert!(batch[7].is_ok());
+        assert_eq!(batch[8], Err(CryptoError::VerificationFailed));
+
+        // All-valid batch takes the fast path and still reports per-item results.
+        let valid: Vec<_> = [0usize, 2, 6, 7].iter().map(|&i| items[i]).collect();
+        assert!(MessageAuth::verify_batch(&valid).iter().all(Result::is_ok));
+        assert!(MessageAuth::verify_batch(&[]).is_empty());
+    }
+
     #[test]
     fn signature_is_deterministic_for_fixed_seed_and_message() {
         let seed = [5u8; 32];
@@ -673,4 +1313,62 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn chunked_gradient_validation_matches_one_shot() {
+        let validator = GradientValidator {
+            max_gradient_norm: 10.0,
+            max_coordinate_value: 5.0,
+        };
+        let ok: [f32; 9] = [0.5, -1.0, 2.0, 0.25, -0.75, 1.5, 0.0, -2.5, 1.0];
+        let mut nan = ok;
+        nan[6] = f32::NAN;
+        let mut inf = ok;
+        inf[4] = f32::NEG_INFINITY;
+        let mut too_large = ok;
+        too_large[7] = -6.0;
+        let big_norm = [4.0f32; 9];
+
+        for gradient in [&ok, &nan, &inf, &too_large, &big_norm] {
+            let expected = format!("{:?}", validator.validate(gradient));
+            for chunk_len in [1usize, 2, 4, 9] {
+                let mut state = ValidatorState::new();
+                let mut chunk_result = Ok(());
+                for chunk in gradient.chunks(chunk_len) {
+                    chunk_result = validator.validate_chunk(chunk, &mut state);
+                    if chunk_result.is_err() {
+                        break;
+                    }
+                }
+                let result = chunk_result.and_then(|()| validator.finish(state));
+                assert_eq!(format!("{result:?}"), expected, "chunk_len={chunk_len}");
+            }
+        }
+
+        assert!(matches!(
+            validator.validate(&nan),
+            Err(GradientValidationError::NaN { index: 6 })
+        ));
+    }
+
+    #[test]
+    fn streaming_validator_errors_are_sticky_and_track_max() {
+        let validator = GradientValidator::default();
+        let mut state = ValidatorState::new();
+        assert!(validator.validate_chunk(&[1.0, -3.0], &mut state).is_ok());
+        assert_eq!(state.elements(), 2);
+        assert_eq!(state.max_abs(), 3.0);
+        assert_eq!(state.norm_sq(), 10.0);
+
+        assert!(validator.validate_chunk(&[f32::NAN], &mut state).is_err());
+        // A later clean chunk cannot clear the failure.
+        assert!(matches!(
+            validator.validate_chunk(&[0.0], &mut state),
+            Err(GradientValidationError::NaN { index: 2 })
+        ));
+        assert!(matches!(
+            validator.finish(state),
+            Err(GradientValidationError::NaN { index: 2 })
+        ));
+    }
 }
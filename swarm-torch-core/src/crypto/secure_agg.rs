@@ -0,0 +1,350 @@
+//! Pairwise additive masking for secure aggregation.
+//!
+//! Every pair of participants `(i, j)` agrees on a seed via X25519 and expands
+//! it into a pseudo-random vector `r_ij`. The lower index adds `r_ij`, the higher
+//! index subtracts it, so all pairwise terms cancel in the sum of masked updates
+//! and the aggregator only learns the total.
+//!
+//! Masking works in the ring `Z_2^32`: gradients are [`quantize`]d to fixed point
+//! with [`FIXED_POINT_SCALE`] (two's complement `i32`), and mask elements are
+//! uniform `u32` values added with wrapping arithmetic. A masked element is then
+//! uniformly distributed regardless of the gradient, and the masks cancel exactly
+//! in the [`masked_sum`]. [`dequantize`] recovers the sum, which is exact for the
+//! quantized inputs as long as the true sum stays within `±2^15`.
+//!
+//! If a participant drops out after masks were agreed, the surviving peers'
+//! pairwise terms with it no longer cancel. Survivors reveal their seed with the
+//! dropped index ([`SecureAggMask::pair_seed`]) and the coordinator subtracts
+//! (wrapping) [`dropout_correction`] from the masked sum.
+
+use alloc::vec::Vec;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+use super::x25519::{derive_session_key, EphemeralKeyPair};
+use super::CryptoError;
+
+/// Domain tag for pairwise mask seeds.
+const MASK_SEED_DOMAIN: &[u8] = b"swarmtorch.secagg.mask.v0";
+/// Fixed-point scale: one quantization step is `2^-16`.
+pub const FIXED_POINT_SCALE: f32 = 65536.0;
+
+/// Quantize `gradients` to two's complement fixed point (round to nearest).
+///
+/// Values outside the `i32` range saturate; NaN maps to zero.
+pub fn quantize(gradients: &[f32]) -> Vec<u32> {
+    gradients
+        .iter()
+        .map(|g| {
+            let scaled = g * FIXED_POINT_SCALE;
+            // Round half away from zero; `as` saturates and truncates toward zero.
+            let half = if scaled < 0.0 { -0.5 } else { 0.5 };
+            (scaled + half) as i32 as u32
+        })
+        .collect()
+}
+
+/// Decode a fixed-point vector (e.g. an unmasked [`masked_sum`]) back to `f32`.
+pub fn dequantize(values: &[u32]) -> Vec<f32> {
+    values
+        .iter()
+        .map(|v| *v as i32 as f32 / FIXED_POINT_SCALE)
+        .collect()
+}
+
+/// Element-wise wrapping sum of masked updates of length `dim`.
+///
+/// Returns `InvalidLength` for the first update whose length is not `dim`.
+pub fn masked_sum(masked: &[Vec<u32>], dim: usize) -> Result<Vec<u32>, CryptoError> {
+    let mut sum = alloc::vec![0u32; dim];
+    for (index, update) in masked.iter().enumerate() {
+        if update.len() != dim {
+            return Err(CryptoError::InvalidLength {
+                index,
+                expected: dim,
+                got: update.len(),
+            });
+        }
+        for (slot, value) in sum.iter_mut().zip(update) {
+            *slot = slot.wrapping_add(*value);
+        }
+    }
+    Ok(sum)
+}
+
+/// One participant's pairwise masks for a single round.
+///
+/// Pair seeds are zeroized on drop.
+pub struct SecureAggMask {
+    index: usize,
+    /// `(peer_index, seed)` in ascending peer order.
+    pair_seeds: Vec<(usize, [u8; 32])>,
+}
+
+impl SecureAggMask {
+    /// Derive pairwise seeds for participant `index` of `public_keys`.
+    ///
+    /// `public_keys[k]` is participant `k`'s X25519 public key (the entry at
+    /// `index` is this node's own key and is skipped). `round_nonce` must be
+    /// fresh per round so masks are never reused across rounds.
+    pub fn new(
+        keypair: &EphemeralKeyPair,
+        index: usize,
+        public_keys: &[[u8; 32]],
+        round_nonce: &[u8; 32],
+    ) -> Result<Self, CryptoError> {
+        let mut pair_seeds = Vec::with_capacity(public_keys.len().saturating_sub(1));
+        for (peer, public_key) in public_keys.iter().enumerate() {
+            if peer == index {
+                continue;
+            }
+            let shared = keypair.diffie_hellman(public_key)?;
+            let (lo, hi) = (index.min(peer) as u64, index.max(peer) as u64);
+            let mut info = [0u8; MASK_SEED_DOMAIN.len() + 32 + 16];
+            info[..MASK_SEED_DOMAIN.len()].copy_from_slice(MASK_SEED_DOMAIN);
+            let rest = &mut info[MASK_SEED_DOMAIN.len()..];
+            rest[..32].copy_from_slice(round_nonce);
+            rest[32..40].copy_from_slice(&lo.to_le_bytes());
+            rest[40..].copy_from_slice(&hi.to_le_bytes());
+            pair_seeds.push((peer, derive_session_key(&shared, &info)));
+        }
+        Ok(Self { index, pair_seeds })
+    }
+
+    /// This participant's index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Seed shared with `peer_index`, to reveal to the coordinator if that peer
+    /// drops out. Never reveal the seed of a peer that submitted an update.
+    pub fn pair_seed(&self, peer_index: usize) -> Option<[u8; 32]> {
+        self.pair_seeds
+            .iter()
+            .find(|(peer, _)| *peer == peer_index)
+            .map(|(_, seed)| *seed)
+    }
+
+    /// Full mask of length `dim` (wrapping sum of all signed pairwise terms).
+    pub fn mask(&self, dim: usize) -> Vec<u32> {
+        let mut mask = alloc::vec![0u32; dim];
+        for (peer, seed) in &self.pair_seeds {
+            add_pair_term(&mut mask, seed, self.index, *peer);
+        }
+        mask
+    }
+
+    /// Quantize `gradients` and add this participant's mask.
+    pub fn apply(&self, gradients: &[f32]) -> Vec<u32> {
+        let mut masked = quantize(gradients);
+        for (peer, seed) in &self.pair_seeds {
+            add_pair_term(&mut masked, seed, self.index, *peer);
+        }
+        masked
+    }
+}
+
+impl Drop for SecureAggMask {
+    fn drop(&mut self) {
+        for (_, seed) in self.pair_seeds.iter_mut() {
+            seed.zeroize();
+        }
+    }
+}
+
+impl core::fmt::Debug for SecureAggMask {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SecureAggMask")
+            .field("index", &self.index)
+            .field("peers", &self.pair_seeds.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Residual mask left in the sum of survivors' updates when `dropped` never
+/// submitted. `revealed` holds each survivor's `(index, pair_seed(dropped))`.
+///
+/// Subtract the result (wrapping) from the masked sum to recover the survivors'
+/// quantized sum.
+pub fn dropout_correction(revealed: &[(usize, [u8; 32])], dropped: usize, dim: usize) -> Vec<u32> {
+    let mut correction = alloc::vec![0u32; dim];
+    for (survivor, seed) in revealed {
+        add_pair_term(&mut correction, seed, *survivor, dropped);
+    }
+    correction
+}
+
+/// Add `+r` (own < peer) or `-r` (own > peer) mod 2^32 for the pair's seed to `out`.
+fn add_pair_term(out: &mut [u32], seed: &[u8; 32], own: usize, peer: usize) {
+    for (block_index, chunk) in out.chunks_mut(8).enumerate() {
+        let mut hasher = Sha256::new();
+        hasher.update(seed);
+        hasher.update((block_index as u64).to_le_bytes());
+        let block: [u8; 32] = hasher.finalize().into();
+        for (slot, bytes) in chunk.iter_mut().zip(block.chunks_exact(4)) {
+            let r = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            *slot = if own < peer {
+                slot.wrapping_add(r)
+            } else {
+                slot.wrapping_sub(r)
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn participants(n: usize) -> (Vec<EphemeralKeyPair>, Vec<[u8; 32]>) {
+        let keys: Vec<_> = (0..n)
+            .map(|i| EphemeralKeyPair::from_seed([i as u8 + 1; 32]).unwrap())
+            .collect();
+        let publics = keys.iter().map(|k| *k.public_key()).collect();
+        (keys, publics)
+    }
+
+    fn masks(n: usize, nonce: [u8; 32]) -> Vec<SecureAggMask> {
+        let (keys, publics) = participants(n);
+        (0..n)
+            .map(|i| SecureAggMask::new(&keys[i], i, &publics, &nonce).unwrap())
+            .collect()
+    }
+
+    /// Wrapping sum of the plain quantized gradients.
+    fn quantized_sum(gradients: &[&[f32]]) -> Vec<u32> {
+        let quantized: Vec<_> = gradients.iter().map(|g| quantize(g)).collect();
+        masked_sum(&quantized, gradients[0].len()).unwrap()
+    }
+
+    const GRADIENTS: [[f32; 3]; 3] = [[0.5, -1.25, 2.0], [1.0, 0.25, -0.75], [-2.5, 3.0, 0.125]];
+
+    #[test]
+    fn three_node_masks_cancel_exactly() {
+        let masks = masks(3, [42u8; 32]);
+
+        // Masks are non-trivial and differ per participant.
+        assert_ne!(masks[0].mask(3), Vec::from([0u32; 3]));
+        assert_ne!(masks[0].mask(3), masks[1].mask(3));
+        // Both ends of a pair derive the same seed.
+        assert_eq!(masks[0].pair_seed(1), masks[1].pair_seed(0));
+
+        let masked: Vec<_> = (0..3).map(|i| masks[i].apply(&GRADIENTS[i])).collect();
+        let sum = dequantize(&masked_sum(&masked, 3).unwrap());
+        assert_eq!(sum, vec![-1.0, 2.0, 1.375]);
+    }
+
+    #[test]
+    fn off_grid_gradients_cancel_exactly_after_quantization() {
+        let gradients: [&[f32]; 4] = [
+            &[0.1, -1.0 / 3.0, 1e-3, 7.77],
+            &[0.2, 2.0 / 3.0, -2e-3, -123.456],
+            &[-0.3, 0.123_456_7, 3e-5, 0.0],
+            &[1e-6, -0.999_99, 5.5, 31.4159],
+        ];
+        let masks = masks(4, [9u8; 32]);
+        let masked: Vec<_> = (0..4).map(|i| masks[i].apply(gradients[i])).collect();
+        // A masked update carries no gradient structure.
+        assert_ne!(masked[0], quantize(gradients[0]));
+
+        let sum = masked_sum(&masked, 4).unwrap();
+        assert_eq!(sum, quantized_sum(&gradients));
+        for (k, value) in dequantize(&sum).into_iter().enumerate() {
+            let expected: f32 = gradients.iter().map(|g| g[k]).sum();
+            // Each input is off by at most half a step.
+            assert!((value - expected).abs() <= 4.0 * 0.5 / FIXED_POINT_SCALE + 1e-5);
+        }
+    }
+
+    #[test]
+    fn dropped_participant_masks_are_removed() {
+        let masks = masks(3, [7u8; 32]);
+
+        // Participant 1 drops out after masks were agreed.
+        let masked = [masks[0].apply(&GRADIENTS[0]), masks[2].apply(&GRADIENTS[2])];
+        let mut sum = masked_sum(&masked, 3).unwrap();
+        let revealed = [
+            (0, masks[0].pair_seed(1).unwrap()),
+            (2, masks[2].pair_seed(1).unwrap()),
+        ];
+        let correction = dropout_correction(&revealed, 1, 3);
+        for (slot, c) in sum.iter_mut().zip(correction) {
+            *slot = slot.wrapping_sub(c);
+        }
+
+        assert_eq!(sum, quantized_sum(&[&GRADIENTS[0], &GRADIENTS[2]]));
+        let expected: Vec<f32> = (0..3).map(|k| GRADIENTS[0][k] + GRADIENTS[2][k]).collect();
+        assert_eq!(dequantize(&sum), expected);
+    }
+
+    #[test]
+    fn unmasked_mean_matches_fedavg() {
+        use crate::aggregation::{FedAvg, RobustAggregator};
+        use crate::traits::{GradientUpdate, UpdateEncoding};
+
+        let gradients: [&[f32]; 4] = [
+            &[0.1, -1.0 / 3.0, 1e-3, 7.77],
+            &[0.2, 2.0 / 3.0, -2e-3, -123.456],
+            &[-0.3, 0.123_456_7, 3e-5, 0.0],
+            &[1e-6, -0.999_99, 5.5, 31.4159],
+        ];
+        let masks = masks(4, [3u8; 32]);
+        let masked: Vec<_> = (0..4).map(|i| masks[i].apply(gradients[i])).collect();
+        let mean: Vec<f32> = dequantize(&masked_sum(&masked, 4).unwrap())
+            .into_iter()
+            .map(|sum| sum / 4.0)
+            .collect();
+
+        let updates: Vec<_> = gradients
+            .iter()
+            .enumerate()
+            .map(|(i, g)| GradientUpdate {
+                sender: [i as u8; 32],
+                sequence: 1,
+                gradients: g.to_vec(),
+                round_id: 1,
+                encoding: UpdateEncoding::Full,
+            })
+            .collect();
+        let fedavg = FedAvg.aggregate(&updates).unwrap();
+
+        for (secure, plain) in mean.iter().zip(&fedavg) {
+            // Each input is off by at most half a step before averaging.
+            assert!(
+                (secure - plain).abs() <= 0.5 / FIXED_POINT_SCALE + 1e-5,
+                "{secure} vs {plain}"
+            );
+        }
+    }
+
+    #[test]
+    fn masked_sum_rejects_wrong_length_updates() {
+        let masks = masks(2, [5u8; 32]);
+        let good = masks[0].apply(&[1.0, 2.0]);
+        let long = masks[1].apply(&[1.0, 2.0, 3.0]);
+        assert_eq!(
+            masked_sum(&[good.clone(), long], 2).unwrap_err(),
+            CryptoError::InvalidLength {
+                index: 1,
+                expected: 2,
+                got: 3
+            }
+        );
+        assert_eq!(
+            masked_sum(&[good[..1].to_vec()], 2).unwrap_err(),
+            CryptoError::InvalidLength {
+                index: 0,
+                expected: 2,
+                got: 1
+            }
+        );
+    }
+
+    #[test]
+    fn different_round_nonce_changes_masks() {
+        let (keys, publics) = participants(2);
+        let a = SecureAggMask::new(&keys[0], 0, &publics, &[1u8; 32]).unwrap();
+        let b = SecureAggMask::new(&keys[0], 0, &publics, &[2u8; 32]).unwrap();
+        assert_ne!(a.mask(4), b.mask(4));
+    }
+}
@@ -0,0 +1,235 @@
+//! X25519 ephemeral key agreement and HKDF-SHA256 session key derivation.
+//!
+//! Building block for `SecurityConfig::encrypt_transport`: each side generates an
+//! [`EphemeralKeyPair`], exchanges public keys (inside signed envelopes), computes
+//! the same [`SharedSecret`], and derives a per-purpose session key with
+//! [`derive_session_key`]. The raw shared secret must never be used as a key.
+
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+use super::CryptoError;
+
+/// HKDF salt (domain separation for SwarmTorch session keys).
+const SESSION_KEY_SALT: &[u8] = b"swarmtorch.x25519.session.v0";
+const SHA256_BLOCK_LEN: usize = 64;
+
+/// X25519 key pair for a single session.
+///
+/// The secret scalar is zeroized on drop.
+pub struct EphemeralKeyPair {
+    secret: [u8; 32],
+    public: [u8; 32],
+}
+
+impl EphemeralKeyPair {
+    /// Create a key pair from seed bytes (clamped per RFC 7748).
+    ///
+    /// Returns `Err(CryptoError::AllZeroSeed)` if `seed` is all zeros.
+    /// The caller must ensure the seed is cryptographically random and never reused.
+    pub fn from_seed(seed: [u8; 32]) -> Result<Self, CryptoError> {
+        if seed == [0u8; 32] {
+            return Err(CryptoError::AllZeroSeed);
+        }
+        let public = MontgomeryPoint::mul_base_clamped(seed).to_bytes();
+        Ok(Self {
+            secret: seed,
+            public,
+        })
+    }
+
+    /// Public key bytes to send to the peer.
+    pub fn public_key(&self) -> &[u8; 32] {
+        &self.public
+    }
+
+    /// Compute the shared secret with a peer's public key.
+    ///
+    /// Returns `Err(CryptoError::InvalidPublicKey)` if the result is all zeros,
+    /// i.e. the peer supplied a small-order point (non-contributory exchange).
+    pub fn diffie_hellman(&self, their_public: &[u8; 32]) -> Result<SharedSecret, CryptoError> {
+        let shared = MontgomeryPoint(*their_public)
+            .mul_clamped(self.secret)
+            .to_bytes();
+        if shared == [0u8; 32] {
+            return Err(CryptoError::InvalidPublicKey);
+        }
+        Ok(SharedSecret(shared))
+    }
+}
+
+impl Drop for EphemeralKeyPair {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+impl core::fmt::Debug for EphemeralKeyPair {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EphemeralKeyPair")
+            .field("public", &self.public)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Raw X25519 output. Feed it to [`derive_session_key`]; do not use it directly.
+///
+/// Zeroized on drop.
+pub struct SharedSecret([u8; 32]);
+
+impl SharedSecret {
+    /// Raw shared secret bytes.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl core::fmt::Debug for SharedSecret {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("SharedSecret(..)")
+    }
+}
+
+/// Derive a 32-byte session key from a shared secret with HKDF-SHA256.
+///
+/// `info` binds the key to its purpose (e.g. direction, protocol version, peer IDs);
+/// distinct `info` values yield independent keys from the same shared secret.
+pub fn derive_session_key(shared: &SharedSecret, info: &[u8]) -> [u8; 32] {
+    hkdf_sha256(SESSION_KEY_SALT, shared.as_bytes(), info)
+}
+
+/// HKDF-SHA256 (RFC 5869) with a single 32-byte output block.
+fn hkdf_sha256(salt: &[u8], ikm: &[u8], info: &[u8]) -> [u8; 32] {
+    let mut prk = hmac_sha256(salt, &[ikm]);
+    let okm = hmac_sha256(&prk, &[info, &[1u8]]);
+    prk.zeroize();
+    okm
+}
+
+/// HMAC-SHA256 (RFC 2104) over the concatenation of `parts`.
+fn hmac_sha256(key: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut block = [0u8; SHA256_BLOCK_LEN];
+    if key.len() > SHA256_BLOCK_LEN {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut pad = [0u8; SHA256_BLOCK_LEN];
+    for (p, k) in pad.iter_mut().zip(block.iter()) {
+        *p = k ^ 0x36;
+    }
+    let mut inner = Sha256::new();
+    inner.update(pad);
+    for part in parts {
+        inner.update(part);
+    }
+    let inner_hash = inner.finalize();
+
+    for (p, k) in pad.iter_mut().zip(block.iter()) {
+        *p = k ^ 0x5c;
+    }
+    let mut outer = Sha256::new();
+    outer.update(pad);
+    outer.update(inner_hash);
+
+    block.zeroize();
+    pad.zeroize();
+    outer.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex32(s: &str) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap();
+        }
+        out
+    }
+
+    #[test]
+    fn x25519_matches_rfc7748_vector() {
+        let alice = EphemeralKeyPair::from_seed(hex32(
+            "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a",
+        ))
+        .unwrap();
+        let bob = EphemeralKeyPair::from_seed(hex32(
+            "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            alice.public_key(),
+            &hex32("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+        );
+        assert_eq!(
+            bob.public_key(),
+            &hex32("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+        );
+        let expected = hex32("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+        assert_eq!(
+            alice.diffie_hellman(bob.public_key()).unwrap().as_bytes(),
+            &expected
+        );
+        assert_eq!(
+            bob.diffie_hellman(alice.public_key()).unwrap().as_bytes(),
+            &expected
+        );
+    }
+
+    #[test]
+    fn both_sides_derive_identical_session_keys() {
+        let a = EphemeralKeyPair::from_seed([7u8; 32]).unwrap();
+        let b = EphemeralKeyPair::from_seed([9u8; 32]).unwrap();
+
+        let key_a = derive_session_key(&a.diffie_hellman(b.public_key()).unwrap(), b"payload");
+        let key_b = derive_session_key(&b.diffie_hellman(a.public_key()).unwrap(), b"payload");
+        assert_eq!(key_a, key_b);
+    }
+
+    #[test]
+    fn different_info_yields_different_keys() {
+        let a = EphemeralKeyPair::from_seed([7u8; 32]).unwrap();
+        let b = EphemeralKeyPair::from_seed([9u8; 32]).unwrap();
+        let shared = a.diffie_hellman(b.public_key()).unwrap();
+
+        let k1 = derive_session_key(&shared, b"a->b");
+        let k2 = derive_session_key(&shared, b"b->a");
+        assert_ne!(k1, k2);
+        assert_ne!(&k1, shared.as_bytes());
+    }
+
+    #[test]
+    fn hkdf_matches_rfc5869_case1_first_block() {
+        let ikm = [0x0bu8; 22];
+        let salt: [u8; 13] = core::array::from_fn(|i| i as u8);
+        let info: [u8; 10] = core::array::from_fn(|i| 0xf0 + i as u8);
+        assert_eq!(
+            hkdf_sha256(&salt, &ikm, &info),
+            hex32("3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf")
+        );
+    }
+
+    #[test]
+    fn rejects_zero_seed_and_small_order_peer_key() {
+        assert!(matches!(
+            EphemeralKeyPair::from_seed([0u8; 32]),
+            Err(CryptoError::AllZeroSeed)
+        ));
+        let a = EphemeralKeyPair::from_seed([7u8; 32]).unwrap();
+        assert!(matches!(
+            a.diffie_hellman(&[0u8; 32]),
+            Err(CryptoError::InvalidPublicKey)
+        ));
+    }
+}
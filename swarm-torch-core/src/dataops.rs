@@ -10,9 +10,14 @@
 //! - `schema_hash_v0` = sha256(postcard(normalized schema descriptor))
 //! - `recipe_hash_v0` = sha256(postcard({ node_def_hash, upstream_fingerprints }))
 //! - `dataset_fingerprint_v0` = sha256(postcard({ source_fingerprint, schema_hash, recipe_hash }))
+//!
+//! The digest is selectable via [`HashAlgo`] (`*_with_algo` variants); SHA-256 is the
+//! default, and BLAKE3 is available behind the `blake3` feature. Entries record the
+//! algorithm in [`DatasetEntryV1::hash_algo`]. `node_def_hash` stays SHA-256 so node
+//! identity does not depend on the fingerprint digest.
 
 #[cfg(feature = "alloc")]
-use alloc::collections::BTreeMap;
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
 #[cfg(feature = "alloc")]
 use alloc::format;
 #[cfg(feature = "alloc")]
@@ -24,13 +29,120 @@ use alloc::vec::Vec;
 
 use sha2::{Digest, Sha256};
 
-use crate::run_graph::{node_def_hash_v1, NodeId, NodeV1, OpKind};
+use crate::run_graph::{node_def_hash, GraphV1, HashVersion, NodeId, NodeV1, OpKind};
 
 pub const DATAOPS_SCHEMA_V1: u32 = 1;
 pub const MATERIALIZATION_SCHEMA_V2: u32 = 2;
 pub const MAX_SOURCE_URI_LEN: usize = 2048;
 pub const MAX_ETAG_OR_VERSION_LEN: usize = 512;
 
+/// Digest used for dataset fingerprints (and artifact manifests).
+///
+/// `Sha256` (default) keeps existing fingerprints reproducible; `Blake3` (feature
+/// `blake3`) trades compatibility for hashing throughput.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HashAlgo {
+    #[default]
+    Sha256,
+    #[cfg(feature = "blake3")]
+    Blake3,
+}
+
+impl HashAlgo {
+    /// Wire name (`"sha256"`, `"blake3"`), as written to registries and manifests.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HashAlgo::Sha256 => "sha256",
+            #[cfg(feature = "blake3")]
+            HashAlgo::Blake3 => "blake3",
+        }
+    }
+
+    /// Parse a wire name; `None` for unknown names or algorithms not compiled in.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sha256" => Some(HashAlgo::Sha256),
+            #[cfg(feature = "blake3")]
+            "blake3" => Some(HashAlgo::Blake3),
+            _ => None,
+        }
+    }
+
+    /// 32-byte digest of `bytes`.
+    pub fn digest(self, bytes: &[u8]) -> [u8; 32] {
+        match self {
+            HashAlgo::Sha256 => Sha256::digest(bytes).into(),
+            #[cfg(feature = "blake3")]
+            HashAlgo::Blake3 => blake3::hash(bytes).into(),
+        }
+    }
+
+    /// Incremental digest, for inputs streamed in chunks (e.g. artifact files).
+    pub fn hasher(self) -> StreamingDigest {
+        StreamingDigest(match self {
+            HashAlgo::Sha256 => StreamingDigestInner::Sha256(Sha256::new()),
+            #[cfg(feature = "blake3")]
+            HashAlgo::Blake3 => StreamingDigestInner::Blake3(alloc::boxed::Box::default()),
+        })
+    }
+
+    /// Whether this is [`HashAlgo::Sha256`] (omitted from serialized entries).
+    pub fn is_sha256(&self) -> bool {
+        *self == HashAlgo::Sha256
+    }
+}
+
+impl core::fmt::Display for HashAlgo {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Incremental [`HashAlgo`] digest; see [`HashAlgo::hasher`].
+#[derive(Clone)]
+pub struct StreamingDigest(StreamingDigestInner);
+
+#[derive(Clone)]
+enum StreamingDigestInner {
+    Sha256(Sha256),
+    #[cfg(feature = "blake3")]
+    Blake3(alloc::boxed::Box<blake3::Hasher>),
+}
+
+impl StreamingDigest {
+    /// Feed `bytes` into the running digest.
+    pub fn update(&mut self, bytes: &[u8]) {
+        match &mut self.0 {
+            StreamingDigestInner::Sha256(hasher) => hasher.update(bytes),
+            #[cfg(feature = "blake3")]
+            StreamingDigestInner::Blake3(hasher) => {
+                hasher.update(bytes);
+            }
+        }
+    }
+
+    /// Equals [`HashAlgo::digest`] over the concatenated updates.
+    pub fn finalize(self) -> [u8; 32] {
+        match self.0 {
+            StreamingDigestInner::Sha256(hasher) => hasher.finalize().into(),
+            #[cfg(feature = "blake3")]
+            StreamingDigestInner::Blake3(hasher) => hasher.finalize().into(),
+        }
+    }
+}
+
+impl core::fmt::Debug for StreamingDigest {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let algo = match self.0 {
+            StreamingDigestInner::Sha256(_) => HashAlgo::Sha256,
+            #[cfg(feature = "blake3")]
+            StreamingDigestInner::Blake3(_) => HashAlgo::Blake3,
+        };
+        f.debug_tuple("StreamingDigest").field(&algo).finish()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum TrustClass {
@@ -62,6 +174,73 @@ pub struct SourceDescriptorV0 {
     pub etag_or_version: Option<String>,
 }
 
+/// Canonical content types recognized by [`is_recognized_content_type`].
+pub const KNOWN_CONTENT_TYPES: &[&str] = &[
+    "application/parquet",
+    "text/csv",
+    "text/tab-separated-values",
+    "application/json",
+    "application/x-ndjson",
+    "application/vnd.apache.arrow.file",
+    "application/vnd.apache.arrow.stream",
+    "application/avro",
+    "application/x-orc",
+    "application/octet-stream",
+    "text/plain",
+];
+
+/// `(alias, canonical)` pairs applied by [`canonical_content_type`].
+const CONTENT_TYPE_ALIASES: &[(&str, &str)] = &[
+    ("application/x-parquet", "application/parquet"),
+    ("application/vnd.apache.parquet", "application/parquet"),
+    ("application/csv", "text/csv"),
+    ("application/x-csv", "text/csv"),
+    ("text/x-csv", "text/csv"),
+    ("text/comma-separated-values", "text/csv"),
+    ("text/tsv", "text/tab-separated-values"),
+    ("text/json", "application/json"),
+    ("application/x-json", "application/json"),
+    ("application/ndjson", "application/x-ndjson"),
+    ("application/jsonl", "application/x-ndjson"),
+    ("application/x-jsonlines", "application/x-ndjson"),
+    ("application/jsonlines", "application/x-ndjson"),
+    ("application/x-arrow", "application/vnd.apache.arrow.file"),
+    ("application/arrow", "application/vnd.apache.arrow.file"),
+    ("avro/binary", "application/avro"),
+    ("application/x-avro", "application/avro"),
+    ("application/orc", "application/x-orc"),
+];
+
+/// Canonical spelling of a content type, as used in source fingerprints.
+///
+/// Lowercases and trims, strips whitespace around `;`-separated parameters, and maps
+/// common aliases (e.g. `application/x-parquet`) to one canonical type, so trivial
+/// spelling differences do not change fingerprints. Unknown types pass through
+/// normalized but otherwise unchanged.
+pub fn canonical_content_type(content_type: &str) -> String {
+    let lowered = normalize_lower(content_type);
+    let mut parts = lowered.split(';').map(str::trim);
+    let essence = parts.next().unwrap_or_default();
+    let essence = CONTENT_TYPE_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == essence)
+        .map_or(essence, |(_, canonical)| *canonical);
+    let mut out = String::from(essence);
+    for param in parts.filter(|p| !p.is_empty()) {
+        out.push(';');
+        out.push_str(param);
+    }
+    out
+}
+
+/// Whether `content_type` canonicalizes to one of [`KNOWN_CONTENT_TYPES`]
+/// (parameters ignored). Unrecognized types are allowed but worth a warning.
+pub fn is_recognized_content_type(content_type: &str) -> bool {
+    let canonical = canonical_content_type(content_type);
+    let essence = canonical.split(';').next().unwrap_or_default();
+    KNOWN_CONTENT_TYPES.contains(&essence)
+}
+
 /// Schema descriptor used for schema hashing.
 ///
 /// `canonical` SHOULD be a canonical representation for stable hashing.
@@ -78,12 +257,16 @@ pub struct SchemaDescriptorV0 {
 pub struct DatasetEntryV1 {
     pub asset_key: String,
 
-    /// Dataset fingerprint v0 (lowercase hex sha256).
+    /// Dataset fingerprint v0 (lowercase hex digest, see `hash_algo`).
     pub fingerprint_v0: String,
     pub source_fingerprint_v0: String,
     pub schema_hash_v0: String,
     pub recipe_hash_v0: String,
 
+    /// Digest behind the fingerprint fields; absent means SHA-256.
+    #[serde(default, skip_serializing_if = "HashAlgo::is_sha256")]
+    pub hash_algo: HashAlgo,
+
     #[serde(default)]
     pub trust: TrustClass,
 
@@ -107,6 +290,41 @@ pub struct DatasetRegistryV1 {
     pub datasets: Vec<DatasetEntryV1>,
 }
 
+impl DatasetRegistryV1 {
+    /// Lookup index by `asset_key`, for callers resolving many keys against one
+    /// registry (O(log n) per lookup instead of a linear scan).
+    ///
+    /// Keys are unique in registries written by SwarmTorch; on duplicates the first
+    /// entry wins, matching `datasets.iter().find(..)`.
+    pub fn index(&self) -> BTreeMap<&str, &DatasetEntryV1> {
+        let mut index = BTreeMap::new();
+        for entry in &self.datasets {
+            index.entry(entry.asset_key.as_str()).or_insert(entry);
+        }
+        index
+    }
+
+    /// Asset keys of entries no node in `graph` reads or writes (sorted, deduplicated).
+    ///
+    /// Typically left behind by a pipeline change; candidates for registry cleanup.
+    pub fn orphans(&self, graph: &GraphV1) -> Vec<String> {
+        let referenced: BTreeSet<&str> = graph
+            .nodes
+            .iter()
+            .flat_map(|node| node.inputs.iter().chain(&node.outputs))
+            .map(|asset| asset.asset_key.as_str())
+            .collect();
+        self.datasets
+            .iter()
+            .map(|entry| entry.asset_key.as_str())
+            .filter(|key| !referenced.contains(key))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+}
+
 impl Default for DatasetRegistryV1 {
     fn default() -> Self {
         Self {
@@ -142,6 +360,81 @@ impl Default for DatasetLineageV1 {
     }
 }
 
+impl DatasetLineageV1 {
+    /// Fingerprints reachable downstream of `fingerprint` (sorted, excluding itself).
+    ///
+    /// Answers "what depends on this dataset?" for blast-radius analysis.
+    pub fn descendants(&self, fingerprint: &str) -> Vec<String> {
+        self.reachable(fingerprint, |edge| {
+            (&edge.input_fingerprint_v0, &edge.output_fingerprint_v0)
+        })
+    }
+
+    /// Fingerprints reachable upstream of `fingerprint` (sorted, excluding itself).
+    pub fn ancestors(&self, fingerprint: &str) -> Vec<String> {
+        self.reachable(fingerprint, |edge| {
+            (&edge.output_fingerprint_v0, &edge.input_fingerprint_v0)
+        })
+    }
+
+    /// One shortest path of fingerprints from `from` to `to` (inclusive), following
+    /// edges downstream. Returns `None` if `to` is not reachable.
+    pub fn provenance_path(&self, from: &str, to: &str) -> Option<Vec<String>> {
+        if from == to {
+            return Some(vec![from.to_string()]);
+        }
+        let mut parent: BTreeMap<&str, &str> = BTreeMap::new();
+        let mut queue: VecDeque<&str> = VecDeque::new();
+        queue.push_back(from);
+        while let Some(current) = queue.pop_front() {
+            for edge in self
+                .edges
+                .iter()
+                .filter(|edge| edge.input_fingerprint_v0 == current)
+            {
+                let next = edge.output_fingerprint_v0.as_str();
+                // Cycle guard: `from` and already-reached nodes are never revisited.
+                if next == from || parent.contains_key(next) {
+                    continue;
+                }
+                parent.insert(next, current);
+                if next == to {
+                    let mut path = vec![to.to_string()];
+                    let mut cursor = to;
+                    while let Some(prev) = parent.get(cursor) {
+                        path.push(prev.to_string());
+                        cursor = prev;
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back(next);
+            }
+        }
+        None
+    }
+
+    /// BFS over `edges` oriented by `direction` (`(from, to)` per edge), with a visited-set cycle guard.
+    fn reachable<'a>(
+        &'a self,
+        fingerprint: &str,
+        direction: impl Fn(&'a LineageEdgeV1) -> (&'a String, &'a String),
+    ) -> Vec<String> {
+        let mut seen: BTreeSet<&str> = BTreeSet::new();
+        let mut queue: VecDeque<&str> = VecDeque::new();
+        queue.push_back(fingerprint);
+        while let Some(current) = queue.pop_front() {
+            for edge in &self.edges {
+                let (from, to) = direction(edge);
+                if from == current && to != fingerprint && seen.insert(to.as_str()) {
+                    queue.push_back(to.as_str());
+                }
+            }
+        }
+        seen.into_iter().map(ToString::to_string).collect()
+    }
+}
+
 /// One materialization record per node output (NDJSON line schema v1).
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct MaterializationRecordV1 {
@@ -251,6 +544,66 @@ pub struct QualitySummaryV0 {
     pub row_count_delta: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schema_changed: Option<bool>,
+    /// Regression flags (e.g. [`QUALITY_FLAG_SCHEMA_CHANGED`]).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quality_flags: Vec<String>,
+}
+
+/// Default `null_rate` above which [`QUALITY_FLAG_NULL_RATE_EXCEEDED`] is raised.
+pub const DEFAULT_NULL_RATE_THRESHOLD: f64 = 0.1;
+/// Quality flag: output schema differs from the input schema.
+pub const QUALITY_FLAG_SCHEMA_CHANGED: &str = "schema_changed";
+/// Quality flag: `null_rate` exceeded the configured threshold.
+pub const QUALITY_FLAG_NULL_RATE_EXCEEDED: &str = "null_rate_exceeded";
+
+/// Row/schema observations for one materialization, summarized into [`QualitySummaryV0`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct QualityInputs {
+    pub input_rows: u64,
+    pub output_rows: u64,
+    /// Output rows containing at least one null.
+    pub null_count: u64,
+    pub schema_before: Option<SchemaDescriptorV0>,
+    pub schema_after: Option<SchemaDescriptorV0>,
+}
+
+impl QualityInputs {
+    /// Summarize with [`DEFAULT_NULL_RATE_THRESHOLD`].
+    pub fn summarize(&self) -> Result<QualitySummaryV0, postcard::Error> {
+        self.summarize_with_threshold(DEFAULT_NULL_RATE_THRESHOLD)
+    }
+
+    /// Compute `null_rate` (`null_count / output_rows`, `None` for empty outputs),
+    /// `row_count_delta` (`output_rows - input_rows`, saturating) and `schema_changed`
+    /// (normalized `schema_hash_v0` comparison, `None` unless both schemas are known).
+    pub fn summarize_with_threshold(
+        &self,
+        null_rate_threshold: f64,
+    ) -> Result<QualitySummaryV0, postcard::Error> {
+        let null_rate =
+            (self.output_rows > 0).then(|| self.null_count as f64 / self.output_rows as f64);
+        let delta = i128::from(self.output_rows) - i128::from(self.input_rows);
+        let row_count_delta =
+            i64::try_from(delta).unwrap_or(if delta < 0 { i64::MIN } else { i64::MAX });
+        let schema_changed = match (&self.schema_before, &self.schema_after) {
+            (Some(before), Some(after)) => Some(schema_hash_v0(before)? != schema_hash_v0(after)?),
+            _ => None,
+        };
+
+        let mut quality_flags = Vec::new();
+        if schema_changed == Some(true) {
+            quality_flags.push(QUALITY_FLAG_SCHEMA_CHANGED.to_string());
+        }
+        if null_rate.is_some_and(|rate| rate > null_rate_threshold) {
+            quality_flags.push(QUALITY_FLAG_NULL_RATE_EXCEEDED.to_string());
+        }
+        Ok(QualitySummaryV0 {
+            null_rate,
+            row_count_delta: Some(row_count_delta),
+            schema_changed,
+            quality_flags,
+        })
+    }
 }
 
 /// One materialization record per node output (NDJSON line schema v2).
@@ -363,11 +716,15 @@ fn hex_lower(bytes: &[u8]) -> String {
 }
 
 fn sha256_postcard<T: serde::Serialize>(value: &T) -> Result<[u8; 32], postcard::Error> {
+    hash_postcard(value, HashAlgo::Sha256)
+}
+
+fn hash_postcard<T: serde::Serialize>(
+    value: &T,
+    algo: HashAlgo,
+) -> Result<[u8; 32], postcard::Error> {
     let bytes = postcard::to_allocvec(value)?;
-    let digest = Sha256::digest(&bytes);
-    let mut out = [0u8; 32];
-    out.copy_from_slice(&digest[..]);
-    Ok(out)
+    Ok(algo.digest(&bytes))
 }
 
 fn normalize_lower(s: &str) -> String {
@@ -419,7 +776,7 @@ fn normalize_and_redact_source_descriptor(source: &SourceDescriptorV0) -> Source
     let redacted = redact_uri_userinfo(&normalize_trim(&source.uri));
     SourceDescriptorV0 {
         uri: strip_query_fragment(&redacted).to_string(),
-        content_type: normalize_lower(&source.content_type),
+        content_type: canonical_content_type(&source.content_type),
         auth_mode: source.auth_mode.clone(),
         etag_or_version: source.etag_or_version.as_ref().map(|v| normalize_trim(v)),
     }
@@ -495,14 +852,22 @@ struct SourceFingerprintCanonicalV0 {
 
 /// Compute `source_fingerprint` (v0) from a normalized source descriptor.
 pub fn source_fingerprint_v0(source: &SourceDescriptorV0) -> Result<[u8; 32], postcard::Error> {
+    source_fingerprint_v0_with_algo(source, HashAlgo::Sha256)
+}
+
+/// [`source_fingerprint_v0`] with an explicit digest.
+pub fn source_fingerprint_v0_with_algo(
+    source: &SourceDescriptorV0,
+    algo: HashAlgo,
+) -> Result<[u8; 32], postcard::Error> {
     let source = normalize_and_redact_source_descriptor(source);
     let canonical = SourceFingerprintCanonicalV0 {
         uri: source.uri,
-        content_type: normalize_lower(&source.content_type),
+        content_type: canonical_content_type(&source.content_type),
         auth_mode: auth_mode_marker_str(&source.auth_mode),
         etag_or_version: source.etag_or_version,
     };
-    sha256_postcard(&canonical)
+    hash_postcard(&canonical, algo)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
@@ -513,11 +878,19 @@ struct SchemaHashCanonicalV0 {
 
 /// Compute `schema_hash` (v0) from a normalized schema descriptor.
 pub fn schema_hash_v0(schema: &SchemaDescriptorV0) -> Result<[u8; 32], postcard::Error> {
+    schema_hash_v0_with_algo(schema, HashAlgo::Sha256)
+}
+
+/// [`schema_hash_v0`] with an explicit digest.
+pub fn schema_hash_v0_with_algo(
+    schema: &SchemaDescriptorV0,
+    algo: HashAlgo,
+) -> Result<[u8; 32], postcard::Error> {
     let canonical = SchemaHashCanonicalV0 {
         format: normalize_lower(&schema.format),
         canonical: normalize_trim(&schema.canonical),
     };
-    sha256_postcard(&canonical)
+    hash_postcard(&canonical, algo)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
@@ -529,16 +902,38 @@ struct RecipeHashCanonicalV0 {
 /// Compute `recipe_hash` (v0) for a transform definition.
 ///
 /// This is stable without raw rows: it depends on the node definition hash and upstream fingerprints.
+/// Uses `node_def_hash_v1` ([`HashVersion::V1`]); see [`recipe_hash_v0_with_version`].
 pub fn recipe_hash_v0(
     node: &NodeV1,
     upstream_fingerprints: &[[u8; 32]],
 ) -> Result<[u8; 32], postcard::Error> {
-    let node_def_hash = node_def_hash_v1(node)?;
+    recipe_hash_v0_with_version(node, upstream_fingerprints, HashVersion::default())
+}
+
+/// [`recipe_hash_v0`] with an explicit `node_def_hash` version.
+pub fn recipe_hash_v0_with_version(
+    node: &NodeV1,
+    upstream_fingerprints: &[[u8; 32]],
+    hash_version: HashVersion,
+) -> Result<[u8; 32], postcard::Error> {
+    recipe_hash_v0_with_algo(node, upstream_fingerprints, hash_version, HashAlgo::Sha256)
+}
+
+/// [`recipe_hash_v0_with_version`] with an explicit digest.
+///
+/// `algo` applies to the recipe digest only; `node_def_hash` is always SHA-256.
+pub fn recipe_hash_v0_with_algo(
+    node: &NodeV1,
+    upstream_fingerprints: &[[u8; 32]],
+    hash_version: HashVersion,
+    algo: HashAlgo,
+) -> Result<[u8; 32], postcard::Error> {
+    let node_def_hash = node_def_hash(node, hash_version)?;
     let canonical = RecipeHashCanonicalV0 {
         node_def_hash,
         upstream_fingerprints: upstream_fingerprints.to_vec(),
     };
-    sha256_postcard(&canonical)
+    hash_postcard(&canonical, algo)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
@@ -553,13 +948,28 @@ pub fn dataset_fingerprint_v0(
     source_fingerprint: [u8; 32],
     schema_hash: [u8; 32],
     recipe_hash: [u8; 32],
+) -> Result<[u8; 32], postcard::Error> {
+    dataset_fingerprint_v0_with_algo(
+        source_fingerprint,
+        schema_hash,
+        recipe_hash,
+        HashAlgo::Sha256,
+    )
+}
+
+/// [`dataset_fingerprint_v0`] with an explicit digest.
+pub fn dataset_fingerprint_v0_with_algo(
+    source_fingerprint: [u8; 32],
+    schema_hash: [u8; 32],
+    recipe_hash: [u8; 32],
+    algo: HashAlgo,
 ) -> Result<[u8; 32], postcard::Error> {
     let canonical = DatasetFingerprintCanonicalV0 {
         source_fingerprint,
         schema_hash,
         recipe_hash,
     };
-    sha256_postcard(&canonical)
+    hash_postcard(&canonical, algo)
 }
 
 // ---------------------------------------------------------------------------
@@ -570,7 +980,12 @@ pub fn dataset_fingerprint_v0(
 ///
 /// **ADR-0017:** `sha256(postcard("no_schema_v0"))`
 pub fn no_schema_hash_v0() -> Result<[u8; 32], postcard::Error> {
-    sha256_postcard(&"no_schema_v0")
+    no_schema_hash_v0_with_algo(HashAlgo::Sha256)
+}
+
+/// [`no_schema_hash_v0`] with an explicit digest.
+pub fn no_schema_hash_v0_with_algo(algo: HashAlgo) -> Result<[u8; 32], postcard::Error> {
+    hash_postcard(&"no_schema_v0", algo)
 }
 
 /// Placeholder source fingerprint for derived (non-source) datasets.
@@ -579,7 +994,15 @@ pub fn no_schema_hash_v0() -> Result<[u8; 32], postcard::Error> {
 ///
 /// **ADR-0017:** `sha256(postcard("derived_v0:{asset_key}"))`
 pub fn derived_source_fingerprint_v0(asset_key: &str) -> Result<[u8; 32], postcard::Error> {
-    sha256_postcard(&format!("derived_v0:{}", asset_key))
+    derived_source_fingerprint_v0_with_algo(asset_key, HashAlgo::Sha256)
+}
+
+/// [`derived_source_fingerprint_v0`] with an explicit digest.
+pub fn derived_source_fingerprint_v0_with_algo(
+    asset_key: &str,
+    algo: HashAlgo,
+) -> Result<[u8; 32], postcard::Error> {
+    hash_postcard(&format!("derived_v0:{}", asset_key), algo)
 }
 
 /// Convenience: build a registry entry (v1) from the provided descriptors.
@@ -592,24 +1015,45 @@ pub fn dataset_entry_v1(
     source: Option<SourceDescriptorV0>,
     schema: Option<SchemaDescriptorV0>,
     recipe_hash: [u8; 32],
+) -> Result<DatasetEntryV1, postcard::Error> {
+    dataset_entry_v1_with_algo(
+        asset_key,
+        trust,
+        source,
+        schema,
+        recipe_hash,
+        HashAlgo::Sha256,
+    )
+}
+
+/// [`dataset_entry_v1`] with an explicit digest, recorded in the entry.
+///
+/// `recipe_hash` must have been computed with the same `algo`.
+pub fn dataset_entry_v1_with_algo(
+    asset_key: impl Into<String>,
+    trust: TrustClass,
+    source: Option<SourceDescriptorV0>,
+    schema: Option<SchemaDescriptorV0>,
+    recipe_hash: [u8; 32],
+    algo: HashAlgo,
 ) -> Result<DatasetEntryV1, postcard::Error> {
     let asset_key = asset_key.into();
 
     // Use canonical helper for missing schema
     let schema_fp = match schema.as_ref() {
-        Some(s) => schema_hash_v0(s)?,
-        None => no_schema_hash_v0()?,
+        Some(s) => schema_hash_v0_with_algo(s, algo)?,
+        None => no_schema_hash_v0_with_algo(algo)?,
     };
 
     // For source: if None, this function uses a non-salted placeholder
     // which is ONLY correct for root sources without upstream.
     // For derived outputs, callers SHOULD use derived_dataset_entry_v1().
     let source_fp = match source.as_ref() {
-        Some(s) => source_fingerprint_v0(s)?,
-        None => sha256_postcard(&"root_source_v0")?,
+        Some(s) => source_fingerprint_v0_with_algo(s, algo)?,
+        None => hash_postcard(&"root_source_v0", algo)?,
     };
 
-    let dataset_fp = dataset_fingerprint_v0(source_fp, schema_fp, recipe_hash)?;
+    let dataset_fp = dataset_fingerprint_v0_with_algo(source_fp, schema_fp, recipe_hash, algo)?;
 
     Ok(DatasetEntryV1 {
         asset_key,
@@ -617,6 +1061,7 @@ pub fn dataset_entry_v1(
         source_fingerprint_v0: hex_lower(&source_fp),
         schema_hash_v0: hex_lower(&schema_fp),
         recipe_hash_v0: hex_lower(&recipe_hash),
+        hash_algo: algo,
         trust,
         source,
         schema,
@@ -634,15 +1079,26 @@ pub fn derived_dataset_entry_v1(
     trust: TrustClass,
     schema: Option<SchemaDescriptorV0>,
     recipe_hash: [u8; 32],
+) -> Result<DatasetEntryV1, postcard::Error> {
+    derived_dataset_entry_v1_with_algo(asset_key, trust, schema, recipe_hash, HashAlgo::Sha256)
+}
+
+/// [`derived_dataset_entry_v1`] with an explicit digest, recorded in the entry.
+pub fn derived_dataset_entry_v1_with_algo(
+    asset_key: impl Into<String>,
+    trust: TrustClass,
+    schema: Option<SchemaDescriptorV0>,
+    recipe_hash: [u8; 32],
+    algo: HashAlgo,
 ) -> Result<DatasetEntryV1, postcard::Error> {
     let asset_key = asset_key.into();
 
-    let source_fp = derived_source_fingerprint_v0(&asset_key)?;
+    let source_fp = derived_source_fingerprint_v0_with_algo(&asset_key, algo)?;
     let schema_fp = match schema.as_ref() {
-        Some(s) => schema_hash_v0(s)?,
-        None => no_schema_hash_v0()?,
+        Some(s) => schema_hash_v0_with_algo(s, algo)?,
+        None => no_schema_hash_v0_with_algo(algo)?,
     };
-    let dataset_fp = dataset_fingerprint_v0(source_fp, schema_fp, recipe_hash)?;
+    let dataset_fp = dataset_fingerprint_v0_with_algo(source_fp, schema_fp, recipe_hash, algo)?;
 
     Ok(DatasetEntryV1 {
         asset_key,
@@ -650,6 +1106,7 @@ pub fn derived_dataset_entry_v1(
         source_fingerprint_v0: hex_lower(&source_fp),
         schema_hash_v0: hex_lower(&schema_fp),
         recipe_hash_v0: hex_lower(&recipe_hash),
+        hash_algo: algo,
         trust,
         source: None,
         schema,
@@ -695,16 +1152,26 @@ pub fn predict_output_fingerprints(
     outputs: &[OutputSpecCore],
     upstream_fps: &[[u8; 32]],
 ) -> Result<Vec<PredictedOutput>, postcard::Error> {
-    let recipe = recipe_hash_v0(node, upstream_fps)?;
+    predict_output_fingerprints_with_algo(node, outputs, upstream_fps, HashAlgo::Sha256)
+}
+
+/// [`predict_output_fingerprints`] with an explicit digest.
+pub fn predict_output_fingerprints_with_algo(
+    node: &NodeV1,
+    outputs: &[OutputSpecCore],
+    upstream_fps: &[[u8; 32]],
+    algo: HashAlgo,
+) -> Result<Vec<PredictedOutput>, postcard::Error> {
+    let recipe = recipe_hash_v0_with_algo(node, upstream_fps, HashVersion::default(), algo)?;
 
     let mut results = Vec::with_capacity(outputs.len());
     for out in outputs {
-        let source_fp = derived_source_fingerprint_v0(&out.asset_key)?;
+        let source_fp = derived_source_fingerprint_v0_with_algo(&out.asset_key, algo)?;
         let schema_fp = match out.schema.as_ref() {
-            Some(s) => schema_hash_v0(s)?,
-            None => no_schema_hash_v0()?,
+            Some(s) => schema_hash_v0_with_algo(s, algo)?,
+            None => no_schema_hash_v0_with_algo(algo)?,
         };
-        let fp = dataset_fingerprint_v0(source_fp, schema_fp, recipe)?;
+        let fp = dataset_fingerprint_v0_with_algo(source_fp, schema_fp, recipe, algo)?;
         results.push(PredictedOutput {
             asset_key: out.asset_key.clone(),
             fingerprint_v0: hex_lower(&fp),
@@ -721,13 +1188,30 @@ struct CacheKeyCanonicalV0 {
 }
 
 /// Deterministic cache key for materialization reuse checks.
+///
+/// Uses `node_def_hash_v1` ([`HashVersion::V1`]); see [`cache_key_v0_with_version`].
 pub fn cache_key_v0(
     node: &NodeV1,
     upstream_fps: &[[u8; 32]],
     execution_profile: &str,
+) -> Result<String, postcard::Error> {
+    cache_key_v0_with_version(
+        node,
+        upstream_fps,
+        execution_profile,
+        HashVersion::default(),
+    )
+}
+
+/// [`cache_key_v0`] with an explicit `node_def_hash` version.
+pub fn cache_key_v0_with_version(
+    node: &NodeV1,
+    upstream_fps: &[[u8; 32]],
+    execution_profile: &str,
+    hash_version: HashVersion,
 ) -> Result<String, postcard::Error> {
     let canonical = CacheKeyCanonicalV0 {
-        node_def_hash: node_def_hash_v1(node)?,
+        node_def_hash: node_def_hash(node, hash_version)?,
         upstream_fingerprints: upstream_fps.to_vec(),
         execution_profile: normalize_lower(execution_profile),
     };
@@ -757,6 +1241,111 @@ mod tests {
     use super::*;
     use crate::run_graph::{AssetRefV1, CanonParams, ExecutionTrust, NodeV1};
 
+    #[test]
+    fn registry_index_matches_linear_lookup() {
+        let entry =
+            |key: &str, trust| dataset_entry_v1(key, trust, None, None, [7u8; 32]).expect("entry");
+        let registry = DatasetRegistryV1 {
+            schema_version: DATAOPS_SCHEMA_V1,
+            datasets: vec![
+                entry("dataset://ns/a", TrustClass::Trusted),
+                entry("dataset://ns/b", TrustClass::Untrusted),
+                entry("dataset://ns/c", TrustClass::Trusted),
+                // Duplicate key: first entry wins, as with `find`.
+                entry("dataset://ns/a", TrustClass::Untrusted),
+            ],
+        };
+        let index = registry.index();
+        assert_eq!(index.len(), 3);
+        for key in [
+            "dataset://ns/a",
+            "dataset://ns/b",
+            "dataset://ns/c",
+            "dataset://ns/missing",
+        ] {
+            let linear = registry.datasets.iter().find(|e| e.asset_key == key);
+            assert_eq!(index.get(key).copied(), linear, "{key}");
+        }
+    }
+
+    #[test]
+    fn registry_orphans_are_entries_no_node_references() {
+        let entry = |key: &str| {
+            dataset_entry_v1(key, TrustClass::Trusted, None, None, [7u8; 32]).expect("entry")
+        };
+        let registry = DatasetRegistryV1 {
+            schema_version: DATAOPS_SCHEMA_V1,
+            datasets: vec![
+                entry("dataset://ns/source"),
+                entry("dataset://ns/clean"),
+                entry("dataset://ns/stale"),
+            ],
+        };
+        let asset = |key: &str| AssetRefV1 {
+            asset_key: key.to_string(),
+            fingerprint: None,
+        };
+        // The source is only consumed (registered out-of-graph), never produced.
+        let graph = GraphV1 {
+            nodes: vec![NodeV1 {
+                node_key: "prep/clean".to_string(),
+                node_id: None,
+                op_kind: OpKind::Data,
+                op_type: "clean".to_string(),
+                inputs: vec![asset("dataset://ns/source")],
+                outputs: vec![asset("dataset://ns/clean")],
+                params: CanonParams::new(),
+                code_ref: None,
+                unsafe_surface: false,
+                execution_trust: ExecutionTrust::Core,
+                node_def_hash: None,
+                execution_hint: None,
+                cache_policy: None,
+                materialization_policy: None,
+                resources: None,
+                op_hash: None,
+            }],
+            ..GraphV1::default()
+        };
+        assert_eq!(registry.orphans(&graph), vec!["dataset://ns/stale"]);
+        assert_eq!(registry.orphans(&GraphV1::default()).len(), 3);
+    }
+
+    #[test]
+    fn content_type_aliases_share_source_fingerprint() {
+        let source = |content_type: &str| SourceDescriptorV0 {
+            uri: "s3://bucket/path".to_string(),
+            content_type: content_type.to_string(),
+            auth_mode: AuthModeMarker::None,
+            etag_or_version: None,
+        };
+        let canonical = source_fingerprint_v0(&source("application/parquet")).unwrap();
+        for alias in ["application/x-parquet", " Application/VND.Apache.Parquet "] {
+            assert_eq!(source_fingerprint_v0(&source(alias)).unwrap(), canonical);
+            assert_eq!(
+                sanitize_source_descriptor_v0(&source(alias))
+                    .unwrap()
+                    .content_type,
+                "application/parquet"
+            );
+        }
+        assert_ne!(
+            source_fingerprint_v0(&source("text/csv")).unwrap(),
+            canonical
+        );
+
+        assert_eq!(
+            canonical_content_type("Text/CSV ; charset=UTF-8"),
+            "text/csv;charset=utf-8"
+        );
+        assert_eq!(
+            canonical_content_type("application/csv;charset=utf-8"),
+            "text/csv;charset=utf-8"
+        );
+        assert!(is_recognized_content_type("application/jsonl"));
+        assert!(!is_recognized_content_type("application/x-custom"));
+    }
+
     #[test]
     fn dataset_fingerprint_is_deterministic() {
         let source = SourceDescriptorV0 {
@@ -819,6 +1408,141 @@ mod tests {
         assert_eq!(a.source_fingerprint_v0, b.source_fingerprint_v0);
     }
 
+    fn golden_node() -> NodeV1 {
+        NodeV1 {
+            node_key: "prep/clean".to_string(),
+            node_id: None,
+            op_kind: OpKind::Data,
+            op_type: "validate".to_string(),
+            inputs: vec![AssetRefV1 {
+                asset_key: "dataset://ns/raw".to_string(),
+                fingerprint: None,
+            }],
+            outputs: vec![AssetRefV1 {
+                asset_key: "dataset://ns/clean".to_string(),
+                fingerprint: None,
+            }],
+            params: CanonParams::new(),
+            code_ref: Some("swarm-torch-data@0.1.0".to_string()),
+            unsafe_surface: false,
+            execution_trust: ExecutionTrust::Core,
+            node_def_hash: None,
+            execution_hint: None,
+            cache_policy: None,
+            materialization_policy: None,
+            resources: None,
+            op_hash: None,
+        }
+    }
+
+    #[test]
+    fn v1_hashes_match_goldens() {
+        let node = golden_node();
+        let upstream = [[7u8; 32]];
+        assert_eq!(
+            hex_lower(&crate::run_graph::node_def_hash_v1(&node).unwrap()),
+            "9a11cf4f9f186e2f42b513bfbcdc5b872bbbdc440443820f4a30d997e1d2aea0"
+        );
+        for recipe in [
+            recipe_hash_v0(&node, &upstream).unwrap(),
+            recipe_hash_v0_with_version(&node, &upstream, HashVersion::V1).unwrap(),
+        ] {
+            assert_eq!(
+                hex_lower(&recipe),
+                "5f98133343d72680e54790bcf204ec373b2fef9df9c15a0ab84e602b6a4b1c84"
+            );
+        }
+        for key in [
+            cache_key_v0(&node, &upstream, "edge_std").unwrap(),
+            cache_key_v0_with_version(&node, &upstream, "edge_std", HashVersion::V1).unwrap(),
+        ] {
+            assert_eq!(
+                key,
+                "b09822f95edd4295259e5c69bee0a221bc51e27e1ff11c50cce89b1b4a38e010"
+            );
+        }
+    }
+
+    #[test]
+    fn v2_hashes_change_when_only_trust_changes() {
+        let core = golden_node();
+        let mut unsafe_ext = core.clone();
+        unsafe_ext.execution_trust = ExecutionTrust::UnsafeExtension;
+        let upstream = [[7u8; 32]];
+
+        assert_eq!(
+            recipe_hash_v0(&core, &upstream).unwrap(),
+            recipe_hash_v0(&unsafe_ext, &upstream).unwrap()
+        );
+        assert_ne!(
+            recipe_hash_v0_with_version(&core, &upstream, HashVersion::V2).unwrap(),
+            recipe_hash_v0_with_version(&unsafe_ext, &upstream, HashVersion::V2).unwrap()
+        );
+        assert_eq!(
+            cache_key_v0(&core, &upstream, "edge_std").unwrap(),
+            cache_key_v0(&unsafe_ext, &upstream, "edge_std").unwrap()
+        );
+        assert_ne!(
+            cache_key_v0_with_version(&core, &upstream, "edge_std", HashVersion::V2).unwrap(),
+            cache_key_v0_with_version(&unsafe_ext, &upstream, "edge_std", HashVersion::V2).unwrap()
+        );
+    }
+
+    fn lineage(edges: &[(&str, &str)]) -> DatasetLineageV1 {
+        DatasetLineageV1 {
+            edges: edges
+                .iter()
+                .map(|(input, output)| LineageEdgeV1 {
+                    input_fingerprint_v0: input.to_string(),
+                    output_fingerprint_v0: output.to_string(),
+                    node_id: crate::run_graph::node_id_from_key(output),
+                    op_kind: OpKind::Data,
+                })
+                .collect(),
+            ..DatasetLineageV1::default()
+        }
+    }
+
+    #[test]
+    fn lineage_queries_follow_three_hop_chain() {
+        let lineage = lineage(&[
+            ("raw", "clean"),
+            ("clean", "features"),
+            ("features", "model"),
+        ]);
+
+        assert_eq!(
+            lineage.descendants("raw"),
+            vec!["clean", "features", "model"]
+        );
+        assert_eq!(lineage.ancestors("model"), vec!["clean", "features", "raw"]);
+        assert_eq!(lineage.descendants("model"), Vec::<String>::new());
+        assert_eq!(
+            lineage.provenance_path("raw", "model"),
+            Some(vec![
+                "raw".to_string(),
+                "clean".to_string(),
+                "features".to_string(),
+                "model".to_string(),
+            ])
+        );
+        assert_eq!(lineage.provenance_path("model", "raw"), None);
+    }
+
+    #[test]
+    fn lineage_queries_handle_unrelated_fingerprints_and_cycles() {
+        let lineage = lineage(&[("a", "b"), ("b", "c"), ("c", "a"), ("x", "y")]);
+
+        assert_eq!(lineage.descendants("unrelated"), Vec::<String>::new());
+        assert_eq!(lineage.ancestors("unrelated"), Vec::<String>::new());
+        assert_eq!(lineage.provenance_path("unrelated", "a"), None);
+
+        // Cycle guard: terminates and excludes the start fingerprint.
+        assert_eq!(lineage.descendants("a"), vec!["b", "c"]);
+        assert_eq!(lineage.ancestors("a"), vec!["b", "c"]);
+        assert_eq!(lineage.provenance_path("a", "y"), None);
+    }
+
     #[test]
     fn canonical_placeholder_no_schema_is_deterministic() {
         let a = no_schema_hash_v0().unwrap();
@@ -886,6 +1610,7 @@ mod tests {
                 null_rate: Some(0.0),
                 row_count_delta: Some(0),
                 schema_changed: Some(false),
+                quality_flags: Vec::new(),
             }),
         };
 
@@ -894,6 +1619,59 @@ mod tests {
         assert_eq!(decoded, row);
     }
 
+    fn schema(canonical: &str) -> SchemaDescriptorV0 {
+        SchemaDescriptorV0 {
+            format: "arrow-json".to_string(),
+            canonical: canonical.to_string(),
+        }
+    }
+
+    #[test]
+    fn quality_summary_reports_row_drop_and_null_rate() {
+        let inputs = QualityInputs {
+            input_rows: 100,
+            output_rows: 80,
+            null_count: 4,
+            schema_before: Some(schema("{\"x\":\"i64\"}")),
+            // Normalization ignores surrounding whitespace.
+            schema_after: Some(schema(" {\"x\":\"i64\"} ")),
+        };
+        let summary = inputs.summarize().unwrap();
+        assert_eq!(summary.row_count_delta, Some(-20));
+        assert_eq!(summary.null_rate, Some(0.05));
+        assert_eq!(summary.schema_changed, Some(false));
+        assert!(summary.quality_flags.is_empty());
+
+        let flagged = inputs.summarize_with_threshold(0.01).unwrap();
+        assert_eq!(
+            flagged.quality_flags,
+            vec![QUALITY_FLAG_NULL_RATE_EXCEEDED.to_string()]
+        );
+    }
+
+    #[test]
+    fn quality_summary_flags_schema_change() {
+        let summary = QualityInputs {
+            input_rows: 10,
+            output_rows: 10,
+            null_count: 0,
+            schema_before: Some(schema("{\"x\":\"i64\"}")),
+            schema_after: Some(schema("{\"x\":\"i64\",\"y\":\"f32\"}")),
+        }
+        .summarize()
+        .unwrap();
+        assert_eq!(summary.schema_changed, Some(true));
+        assert_eq!(summary.row_count_delta, Some(0));
+        assert_eq!(
+            summary.quality_flags,
+            vec![QUALITY_FLAG_SCHEMA_CHANGED.to_string()]
+        );
+
+        let empty = QualityInputs::default().summarize().unwrap();
+        assert_eq!(empty.null_rate, None);
+        assert_eq!(empty.schema_changed, None);
+    }
+
     #[test]
     fn transform_audit_v0_serialization_roundtrip() {
         let audit = TransformAuditV0 {
@@ -1079,4 +1857,85 @@ mod tests {
         };
         assert!(validate_source_descriptor_bounds(&desc).is_ok());
     }
+
+    // ── Pluggable fingerprint digest ──
+
+    #[test]
+    fn sha256_entries_omit_hash_algo_and_default_on_read() {
+        let entry = dataset_entry_v1("dataset://ns/a", TrustClass::Trusted, None, None, [1u8; 32])
+            .expect("entry");
+        assert_eq!(entry.hash_algo, HashAlgo::Sha256);
+        let json = serde_json::to_string(&entry).expect("serialize");
+        assert!(!json.contains("hash_algo"), "{json}");
+        let back: DatasetEntryV1 = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(back, entry);
+
+        assert_eq!(HashAlgo::from_name("sha256"), Some(HashAlgo::Sha256));
+        assert_eq!(HashAlgo::from_name("md5"), None);
+    }
+
+    #[test]
+    fn streaming_digest_matches_one_shot_digest() {
+        let algos = [
+            HashAlgo::Sha256,
+            #[cfg(feature = "blake3")]
+            HashAlgo::Blake3,
+        ];
+        for algo in algos {
+            let mut hasher = algo.hasher();
+            hasher.update(b"swarm");
+            hasher.update(b"torch");
+            assert_eq!(hasher.finalize(), algo.digest(b"swarmtorch"), "{algo}");
+        }
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn blake3_fingerprints_are_deterministic_and_distinct_from_sha256() {
+        let source = SourceDescriptorV0 {
+            uri: "s3://bucket/data.parquet".to_string(),
+            content_type: "application/parquet".to_string(),
+            auth_mode: AuthModeMarker::None,
+            etag_or_version: Some("v1".to_string()),
+        };
+        let a = source_fingerprint_v0_with_algo(&source, HashAlgo::Blake3).unwrap();
+        let b = source_fingerprint_v0_with_algo(&source, HashAlgo::Blake3).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, source_fingerprint_v0(&source).unwrap());
+
+        let blake = dataset_fingerprint_v0_with_algo(a, [2u8; 32], [3u8; 32], HashAlgo::Blake3);
+        let sha = dataset_fingerprint_v0(a, [2u8; 32], [3u8; 32]);
+        assert_ne!(blake.unwrap(), sha.unwrap());
+        assert_eq!(
+            HashAlgo::Blake3.digest(b"abc"),
+            <[u8; 32]>::from(blake3::hash(b"abc"))
+        );
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn blake3_entries_record_hash_algo() {
+        let entry = derived_dataset_entry_v1_with_algo(
+            "dataset://ns/derived",
+            TrustClass::Trusted,
+            None,
+            [4u8; 32],
+            HashAlgo::Blake3,
+        )
+        .expect("entry");
+        assert_eq!(entry.hash_algo, HashAlgo::Blake3);
+        let sha =
+            derived_dataset_entry_v1("dataset://ns/derived", TrustClass::Trusted, None, [4u8; 32])
+                .expect("entry");
+        assert_ne!(entry.fingerprint_v0, sha.fingerprint_v0);
+
+        let json = serde_json::to_string(&entry).expect("serialize");
+        assert!(json.contains(r#""hash_algo":"blake3""#), "{json}");
+        let back: DatasetEntryV1 = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(back.hash_algo, HashAlgo::Blake3);
+        assert_eq!(
+            HashAlgo::from_name(HashAlgo::Blake3.as_str()),
+            Some(HashAlgo::Blake3)
+        );
+    }
 }
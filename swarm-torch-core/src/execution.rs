@@ -6,6 +6,8 @@
 //! - keep execution auditable/testable
 //! - swap native vs sandboxed runners later without changing `graph.json` semantics
 
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
 #[cfg(feature = "alloc")]
 use alloc::format;
 #[cfg(feature = "alloc")]
@@ -13,7 +15,7 @@ use alloc::string::String;
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
-use crate::dataops::DatasetRegistryV1;
+use crate::dataops::{DatasetRegistryV1, SchemaDescriptorV0};
 use crate::observe::RunEventEmitter;
 use crate::run_graph::NodeV1;
 
@@ -27,17 +29,32 @@ pub enum PolicyDecision {
 /// Execution policy boundary (ADR-0018).
 pub trait ExecutionPolicy: Send + Sync {
     fn allow(&self, node: &NodeV1, registry: &DatasetRegistryV1) -> PolicyDecision;
+
+    /// Name used to attribute denials in combined policies ([`AllOf`], [`AnyOf`]).
+    fn name(&self) -> &str {
+        core::any::type_name::<Self>()
+    }
 }
 
 /// A runtime-resolved asset instance.
 ///
 /// This is metadata-only: the actual payload is always a pointer + hash, not embedded bytes.
+///
+/// Runners MAY report `rows`/`bytes`/`schema` for the outputs they produce so the
+/// orchestrator can materialize them without restating the stats. All three are optional
+/// and omitted from the wire form when unset.
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct AssetInstanceV1 {
     pub asset_key: String,
     pub fingerprint_v0: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rows: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<SchemaDescriptorV0>,
 }
 
 /// Node runner boundary (ADR-0018).
@@ -85,6 +102,10 @@ use crate::run_graph::ExecutionTrust;
 pub struct CoreOnlyPolicy;
 
 impl ExecutionPolicy for CoreOnlyPolicy {
+    fn name(&self) -> &str {
+        "CoreOnlyPolicy"
+    }
+
     fn allow(&self, node: &NodeV1, _registry: &DatasetRegistryV1) -> PolicyDecision {
         match node.execution_trust {
             ExecutionTrust::Core => PolicyDecision::Allowed,
@@ -102,15 +123,141 @@ impl ExecutionPolicy for CoreOnlyPolicy {
 pub struct PermissivePolicy;
 
 impl ExecutionPolicy for PermissivePolicy {
+    fn name(&self) -> &str {
+        "PermissivePolicy"
+    }
+
     fn allow(&self, _node: &NodeV1, _registry: &DatasetRegistryV1) -> PolicyDecision {
         PolicyDecision::Allowed
     }
 }
 
+/// Data governance policy: denies nodes whose inputs carry restricted registry tags.
+///
+/// Inputs are resolved against the registry by fingerprint when the input ref pins
+/// one, otherwise by `asset_key` (every registered instance is checked). Inputs not
+/// in the registry carry no tags and are allowed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DataGovernancePolicy {
+    /// Deny any input with a non-empty `pii_tags`.
+    pub deny_pii: bool,
+    /// `license_flags` values inputs may carry; any other flag is denied.
+    pub allowed_licenses: Vec<String>,
+}
+
+impl ExecutionPolicy for DataGovernancePolicy {
+    fn name(&self) -> &str {
+        "DataGovernancePolicy"
+    }
+
+    fn allow(&self, node: &NodeV1, registry: &DatasetRegistryV1) -> PolicyDecision {
+        for input in &node.inputs {
+            let entries = registry
+                .datasets
+                .iter()
+                .filter(|entry| match &input.fingerprint {
+                    Some(fingerprint) => entry.fingerprint_v0 == *fingerprint,
+                    None => entry.asset_key == input.asset_key,
+                });
+            for entry in entries {
+                if self.deny_pii {
+                    if let Some(tag) = entry.pii_tags.first() {
+                        return PolicyDecision::Denied {
+                            reason: format!(
+                                "node {} input {} carries PII tag {}",
+                                node.node_key, entry.asset_key, tag
+                            ),
+                        };
+                    }
+                }
+                if let Some(flag) = entry
+                    .license_flags
+                    .iter()
+                    .find(|flag| !self.allowed_licenses.contains(flag))
+                {
+                    return PolicyDecision::Denied {
+                        reason: format!(
+                            "node {} input {} carries license flag {} not in allowed licenses",
+                            node.node_key, entry.asset_key, flag
+                        ),
+                    };
+                }
+            }
+        }
+        PolicyDecision::Allowed
+    }
+}
+
+/// Allows a node only if every child policy allows it.
+///
+/// All children are evaluated so the denial lists every objection, as
+/// `"<policy>: <reason>"` entries joined by `"; "`. An empty list allows everything.
+pub struct AllOf(pub Vec<Box<dyn ExecutionPolicy>>);
+
+impl ExecutionPolicy for AllOf {
+    fn name(&self) -> &str {
+        "AllOf"
+    }
+
+    fn allow(&self, node: &NodeV1, registry: &DatasetRegistryV1) -> PolicyDecision {
+        let denials = denial_reasons(&self.0, node, registry);
+        if denials.is_empty() {
+            PolicyDecision::Allowed
+        } else {
+            PolicyDecision::Denied {
+                reason: denials.join("; "),
+            }
+        }
+    }
+}
+
+/// Allows a node if any child policy allows it.
+///
+/// When every child denies, the reasons are combined as in [`AllOf`]. An empty list
+/// denies everything.
+pub struct AnyOf(pub Vec<Box<dyn ExecutionPolicy>>);
+
+impl ExecutionPolicy for AnyOf {
+    fn name(&self) -> &str {
+        "AnyOf"
+    }
+
+    fn allow(&self, node: &NodeV1, registry: &DatasetRegistryV1) -> PolicyDecision {
+        if self.0.is_empty() {
+            return PolicyDecision::Denied {
+                reason: format!("node {}: AnyOf has no policies", node.node_key),
+            };
+        }
+        let denials = denial_reasons(&self.0, node, registry);
+        if denials.len() < self.0.len() {
+            PolicyDecision::Allowed
+        } else {
+            PolicyDecision::Denied {
+                reason: denials.join("; "),
+            }
+        }
+    }
+}
+
+fn denial_reasons(
+    policies: &[Box<dyn ExecutionPolicy>],
+    node: &NodeV1,
+    registry: &DatasetRegistryV1,
+) -> Vec<String> {
+    policies
+        .iter()
+        .filter_map(|policy| match policy.allow(node, registry) {
+            PolicyDecision::Allowed => None,
+            PolicyDecision::Denied { reason } => Some(format!("{}: {}", policy.name(), reason)),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::run_graph::{CanonParams, NodeV1, OpKind};
+    use crate::dataops::{DatasetEntryV1, HashAlgo, TrustClass};
+    use crate::run_graph::{AssetRefV1, CanonParams, NodeV1, OpKind};
 
     fn test_node(trust: ExecutionTrust) -> NodeV1 {
         NodeV1 {
@@ -185,6 +332,175 @@ mod tests {
         );
     }
 
+    fn tagged_entry(asset_key: &str, pii: &[&str], licenses: &[&str]) -> DatasetEntryV1 {
+        DatasetEntryV1 {
+            asset_key: asset_key.to_string(),
+            fingerprint_v0: format!("fp-{asset_key}"),
+            source_fingerprint_v0: String::new(),
+            schema_hash_v0: String::new(),
+            recipe_hash_v0: String::new(),
+            hash_algo: HashAlgo::Sha256,
+            trust: TrustClass::Trusted,
+            source: None,
+            schema: None,
+            license_flags: licenses.iter().map(|s| s.to_string()).collect(),
+            pii_tags: pii.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn consuming(inputs: &[&str]) -> NodeV1 {
+        let mut node = test_node(ExecutionTrust::Core);
+        node.inputs = inputs
+            .iter()
+            .map(|key| AssetRefV1 {
+                asset_key: key.to_string(),
+                fingerprint: None,
+            })
+            .collect();
+        node
+    }
+
+    fn governance() -> DataGovernancePolicy {
+        DataGovernancePolicy {
+            deny_pii: true,
+            allowed_licenses: vec!["cc-by-4.0".to_string(), "mit".to_string()],
+        }
+    }
+
+    #[test]
+    fn governance_denies_pii_tagged_input() {
+        let registry = DatasetRegistryV1 {
+            datasets: vec![
+                tagged_entry("dataset://clean", &[], &["mit"]),
+                tagged_entry("dataset://users", &["email"], &["mit"]),
+            ],
+            ..DatasetRegistryV1::default()
+        };
+        let node = consuming(&["dataset://clean", "dataset://users"]);
+        match governance().allow(&node, &registry) {
+            PolicyDecision::Denied { reason } => {
+                assert!(reason.contains("dataset://users"), "{reason}");
+                assert!(reason.contains("PII tag email"), "{reason}");
+            }
+            other => panic!("expected Denied, got {other:?}"),
+        }
+
+        let lenient = DataGovernancePolicy {
+            deny_pii: false,
+            ..governance()
+        };
+        assert_eq!(lenient.allow(&node, &registry), PolicyDecision::Allowed);
+    }
+
+    #[test]
+    fn governance_allows_only_allowed_licenses() {
+        let registry = DatasetRegistryV1 {
+            datasets: vec![
+                tagged_entry("dataset://a", &[], &["mit"]),
+                tagged_entry("dataset://b", &[], &["cc-by-4.0", "mit"]),
+                tagged_entry("dataset://c", &[], &["proprietary"]),
+            ],
+            ..DatasetRegistryV1::default()
+        };
+        assert_eq!(
+            governance().allow(&consuming(&["dataset://a", "dataset://b"]), &registry),
+            PolicyDecision::Allowed
+        );
+        match governance().allow(&consuming(&["dataset://a", "dataset://c"]), &registry) {
+            PolicyDecision::Denied { reason } => {
+                assert!(reason.contains("dataset://c"), "{reason}");
+                assert!(reason.contains("license flag proprietary"), "{reason}");
+            }
+            other => panic!("expected Denied, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn governance_resolves_pinned_fingerprint() {
+        let mut clean = tagged_entry("dataset://users", &[], &[]);
+        clean.fingerprint_v0 = "fp-clean".to_string();
+        let registry = DatasetRegistryV1 {
+            datasets: vec![clean, tagged_entry("dataset://users", &["ssn"], &[])],
+            ..DatasetRegistryV1::default()
+        };
+        let mut node = consuming(&["dataset://users"]);
+        assert!(matches!(
+            governance().allow(&node, &registry),
+            PolicyDecision::Denied { .. }
+        ));
+        node.inputs[0].fingerprint = Some("fp-clean".to_string());
+        assert_eq!(
+            governance().allow(&node, &registry),
+            PolicyDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn all_of_denies_when_any_child_denies() {
+        let registry = DatasetRegistryV1 {
+            datasets: vec![tagged_entry("dataset://a", &[], &["mit"])],
+            ..DatasetRegistryV1::default()
+        };
+        let mut node = consuming(&["dataset://a"]);
+        node.execution_trust = ExecutionTrust::UnsafeExtension;
+        assert_eq!(
+            governance().allow(&node, &registry),
+            PolicyDecision::Allowed
+        );
+
+        let policy = AllOf(vec![Box::new(CoreOnlyPolicy), Box::new(governance())]);
+        match policy.allow(&node, &registry) {
+            PolicyDecision::Denied { reason } => {
+                assert!(reason.starts_with("CoreOnlyPolicy: "), "{reason}");
+                assert!(!reason.contains("DataGovernancePolicy"), "{reason}");
+            }
+            other => panic!("expected Denied, got {other:?}"),
+        }
+
+        node.execution_trust = ExecutionTrust::Core;
+        assert_eq!(policy.allow(&node, &registry), PolicyDecision::Allowed);
+        assert_eq!(
+            AllOf(vec![]).allow(&node, &registry),
+            PolicyDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn all_of_concatenates_every_denial() {
+        let registry = DatasetRegistryV1 {
+            datasets: vec![tagged_entry("dataset://users", &["email"], &[])],
+            ..DatasetRegistryV1::default()
+        };
+        let mut node = consuming(&["dataset://users"]);
+        node.execution_trust = ExecutionTrust::SandboxedExtension;
+        let policy = AllOf(vec![Box::new(CoreOnlyPolicy), Box::new(governance())]);
+        let PolicyDecision::Denied { reason } = policy.allow(&node, &registry) else {
+            panic!("expected Denied");
+        };
+        let parts: Vec<&str> = reason.split("; ").collect();
+        assert_eq!(parts.len(), 2, "{reason}");
+        assert!(parts[0].starts_with("CoreOnlyPolicy: "));
+        assert!(parts[1].starts_with("DataGovernancePolicy: "));
+    }
+
+    #[test]
+    fn any_of_allows_when_one_child_allows() {
+        let registry = DatasetRegistryV1::default();
+        let node = test_node(ExecutionTrust::UnsafeExtension);
+        let policy = AnyOf(vec![Box::new(CoreOnlyPolicy), Box::new(PermissivePolicy)]);
+        assert_eq!(policy.allow(&node, &registry), PolicyDecision::Allowed);
+
+        let strict = AnyOf(vec![Box::new(CoreOnlyPolicy)]);
+        match strict.allow(&node, &registry) {
+            PolicyDecision::Denied { reason } => assert!(reason.contains("CoreOnlyPolicy")),
+            other => panic!("expected Denied, got {other:?}"),
+        }
+        assert!(matches!(
+            AnyOf(vec![]).allow(&node, &registry),
+            PolicyDecision::Denied { .. }
+        ));
+    }
+
     #[cfg(feature = "std")]
     #[test]
     fn op_runner_error_contract_accepts_io_error() {
@@ -2,9 +2,89 @@
 //!
 //! This module defines node identities and their roles in the swarm.
 
+use core::fmt;
+use core::str::FromStr;
+
 use crate::crypto::KeyPair;
+use crate::observe::{derive_id, is_all_zero, parse_hex_exact, write_hex_lower, ParseIdError};
 use crate::traits::PeerId;
 
+/// Hex characters kept by [`PeerId::short`].
+pub const PEER_ID_SHORT_LEN: usize = 8;
+
+impl PeerId {
+    /// Parse 64 hex chars (either case); rejects all-zero IDs like `TraceId`.
+    pub fn parse_hex(s: &str) -> core::result::Result<Self, ParseIdError> {
+        let bytes = parse_hex_exact::<32>(s)?;
+        if is_all_zero(&bytes) {
+            return Err(ParseIdError::AllZeroInvalid);
+        }
+        Ok(Self(bytes))
+    }
+
+    /// Write the 64-char lowercase hex form into `out`.
+    pub fn write_lower_hex(&self, out: &mut [u8; 64]) {
+        write_hex_lower(&self.0, out);
+    }
+
+    /// Deterministic ID from a human-readable name: `sha256(name)`, never all-zero.
+    ///
+    /// Test/dev convenience for readable fixtures (`PeerId::from_name("coordinator")`).
+    /// Anyone can compute it, so it is **not** a security identity; real peers use
+    /// [`PeerId::from_public_key`].
+    pub fn from_name(name: &str) -> Self {
+        Self(derive_id::<32>(&[name.as_bytes()]))
+    }
+
+    /// First [`PEER_ID_SHORT_LEN`] hex chars, for compact logs (not unique).
+    pub fn short(&self) -> ShortPeerId {
+        let mut buf = [0u8; PEER_ID_SHORT_LEN];
+        write_hex_lower(&self.0[..PEER_ID_SHORT_LEN / 2], &mut buf);
+        ShortPeerId(buf)
+    }
+}
+
+impl fmt::Display for PeerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [0u8; 64];
+        self.write_lower_hex(&mut buf);
+        let s = core::str::from_utf8(&buf).map_err(|_| fmt::Error)?;
+        f.write_str(s)
+    }
+}
+
+impl FromStr for PeerId {
+    type Err = ParseIdError;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        Self::parse_hex(s)
+    }
+}
+
+/// Abbreviated [`PeerId`] hex prefix returned by [`PeerId::short`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShortPeerId([u8; PEER_ID_SHORT_LEN]);
+
+impl ShortPeerId {
+    /// The prefix as a string slice.
+    pub fn as_str(&self) -> &str {
+        // Only ever filled with ASCII hex digits.
+        core::str::from_utf8(&self.0).unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for ShortPeerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for ShortPeerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Role of a node in the swarm
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "alloc", derive(serde::Serialize, serde::Deserialize))]
@@ -116,6 +196,61 @@ mod tests {
         assert_eq!(identity.key_pair().peer_id(), expected_id);
         assert_eq!(identity.role, NodeRole::Coordinator);
     }
+
+    #[test]
+    fn peer_id_hex_round_trips_and_shortens() {
+        let mut bytes = [0u8; 32];
+        bytes[0] = 0xab;
+        bytes[1] = 0x01;
+        bytes[31] = 0xff;
+        let peer = PeerId::new(bytes);
+
+        let hex = std::string::ToString::to_string(&peer);
+        assert_eq!(hex.len(), 64);
+        assert!(hex.starts_with("ab01") && hex.ends_with("ff"));
+        assert_eq!(hex.parse::<PeerId>(), Ok(peer));
+        assert_eq!(PeerId::parse_hex(&hex.to_uppercase()), Ok(peer));
+
+        let short = peer.short();
+        assert_eq!(short.as_str().len(), PEER_ID_SHORT_LEN);
+        assert_eq!(short.as_str(), &hex[..PEER_ID_SHORT_LEN]);
+    }
+
+    #[test]
+    fn peer_id_from_name_is_stable_distinct_and_non_zero() {
+        let coordinator = PeerId::from_name("coordinator");
+        assert_eq!(coordinator, PeerId::from_name("coordinator"));
+        assert_ne!(coordinator, PeerId::from_name("worker-1"));
+        assert_ne!(PeerId::from_name("worker-1"), PeerId::from_name("worker-2"));
+        for name in ["", "a", "coordinator", "worker-1"] {
+            assert!(PeerId::from_name(name).as_bytes().iter().any(|b| *b != 0));
+        }
+        // Plain SHA-256 of the name.
+        assert_eq!(
+            std::string::ToString::to_string(&PeerId::from_name("")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn peer_id_parse_rejects_bad_input() {
+        assert_eq!(
+            PeerId::parse_hex(&"ab".repeat(31)),
+            Err(ParseIdError::InvalidLength)
+        );
+        assert_eq!(
+            PeerId::parse_hex(&"ab".repeat(33)),
+            Err(ParseIdError::InvalidLength)
+        );
+        assert_eq!(
+            PeerId::parse_hex(&"zz".repeat(32)),
+            Err(ParseIdError::InvalidHex)
+        );
+        assert_eq!(
+            PeerId::parse_hex(&"00".repeat(32)),
+            Err(ParseIdError::AllZeroInvalid)
+        );
+    }
 }
 
 /// Participant configuration for swarm learning
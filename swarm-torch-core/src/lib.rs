@@ -5,6 +5,8 @@
 //! This crate is `no_std` compatible and provides:
 //! - Swarm optimization algorithms (PSO, ACO, Firefly)
 //! - Robust aggregation (Krum/Trimmed Mean/Median; requires `alloc`)
+//! - Round-to-round convergence detection for early stopping (requires `alloc`)
+//! - Peer reputation scoring from verification and aggregation outcomes (requires `alloc`)
 //! - Core traits and abstractions
 //! - Gradient compression utilities
 //! - Offline-first observability IDs + span/event/metric record schemas
@@ -29,6 +31,8 @@ pub mod aggregation;
 pub mod algorithms;
 pub mod compression;
 pub mod consensus;
+#[cfg(feature = "alloc")]
+pub mod convergence;
 pub mod crypto;
 #[cfg(feature = "alloc")]
 pub mod dataops;
@@ -39,6 +43,8 @@ pub mod observe;
 #[cfg(feature = "alloc")]
 pub mod replay;
 #[cfg(feature = "alloc")]
+pub mod reputation;
+#[cfg(feature = "alloc")]
 pub mod run_graph;
 pub mod traits;
 
@@ -79,12 +85,15 @@ pub enum Error {
     Serialization,
     /// Cryptographic verification failed
     VerificationFailed,
-    /// Gradient validation failed
-    InvalidGradient,
+    /// Gradient validation failed for the update at `index` of the input batch
+    InvalidGradient {
+        index: usize,
+        reason: crypto::GradientValidationError,
+    },
     /// Aggregation failed
-    AggregationFailed,
-    /// Insufficient updates for aggregation
-    InsufficientUpdates,
+    AggregationFailed { reason: &'static str },
+    /// Fewer updates than the aggregator needs
+    InsufficientUpdates { got: usize, need: usize },
     /// Resource limit exceeded
     ResourceExhausted,
 }
@@ -92,14 +101,31 @@ pub enum Error {
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
+impl From<postcard::Error> for Error {
+    fn from(_: postcard::Error) -> Self {
+        Error::Serialization
+    }
+}
+
+impl From<crypto::CryptoError> for Error {
+    fn from(_: crypto::CryptoError) -> Self {
+        Error::VerificationFailed
+    }
+}
+
 impl core::fmt::Display for Error {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Error::Serialization => write!(f, "serialization error"),
             Error::VerificationFailed => write!(f, "cryptographic verification failed"),
-            Error::InvalidGradient => write!(f, "gradient validation failed"),
-            Error::AggregationFailed => write!(f, "aggregation failed"),
-            Error::InsufficientUpdates => write!(f, "insufficient updates for aggregation"),
+            Error::InvalidGradient { index, reason } => {
+                write!(f, "gradient validation failed for update {index}: {reason}")
+            }
+            Error::AggregationFailed { reason } => write!(f, "aggregation failed: {reason}"),
+            Error::InsufficientUpdates { got, need } => write!(
+                f,
+                "insufficient updates for aggregation: got {got}, need {need}"
+            ),
             Error::ResourceExhausted => write!(f, "resource limit exceeded"),
         }
     }
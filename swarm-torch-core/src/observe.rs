@@ -7,9 +7,17 @@
 //! Note (L-15): all `Display`/`Debug` implementations in this module use
 //! stack-based fixed buffers. No heap allocation occurs on the span formatting
 //! hot path.
+//!
+//! Metric summaries (count/sum/min/max/last, fixed-bucket histograms) live in [`agg`];
+//! per-round gradient-norm metrics in [`norms`].
 
 use core::fmt;
 
+#[cfg(feature = "alloc")]
+pub mod agg;
+#[cfg(feature = "alloc")]
+pub mod norms;
+
 /// Error parsing a hex-encoded ID.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ParseIdError {
@@ -19,6 +27,8 @@ pub enum ParseIdError {
     InvalidHex,
     /// All-zero IDs are invalid by contract.
     AllZeroInvalid,
+    /// A `traceparent` header did not match `<version>-<trace_id>-<span_id>-<flags>`.
+    InvalidTraceparent,
 }
 
 impl fmt::Display for ParseIdError {
@@ -27,6 +37,7 @@ impl fmt::Display for ParseIdError {
             ParseIdError::InvalidLength => write!(f, "invalid id length"),
             ParseIdError::InvalidHex => write!(f, "invalid hex in id"),
             ParseIdError::AllZeroInvalid => write!(f, "all-zero id is invalid"),
+            ParseIdError::InvalidTraceparent => write!(f, "malformed traceparent header"),
         }
     }
 }
@@ -36,7 +47,7 @@ impl std::error::Error for ParseIdError {}
 
 const HEX_LOWER: &[u8; 16] = b"0123456789abcdef";
 
-fn write_hex_lower(bytes: &[u8], out: &mut [u8]) {
+pub(crate) fn write_hex_lower(bytes: &[u8], out: &mut [u8]) {
     debug_assert_eq!(out.len(), bytes.len() * 2);
     for (i, b) in bytes.iter().enumerate() {
         out[i * 2] = HEX_LOWER[(b >> 4) as usize];
@@ -53,7 +64,9 @@ fn decode_hex_nibble(b: u8) -> Option<u8> {
     }
 }
 
-fn parse_hex_exact<const N: usize>(s: &str) -> core::result::Result<[u8; N], ParseIdError> {
+pub(crate) fn parse_hex_exact<const N: usize>(
+    s: &str,
+) -> core::result::Result<[u8; N], ParseIdError> {
     let expected = N * 2;
     if s.len() != expected {
         return Err(ParseIdError::InvalidLength);
@@ -68,10 +81,28 @@ fn parse_hex_exact<const N: usize>(s: &str) -> core::result::Result<[u8; N], Par
     Ok(out)
 }
 
-fn is_all_zero(bytes: &[u8]) -> bool {
+pub(crate) fn is_all_zero(bytes: &[u8]) -> bool {
     bytes.iter().all(|b| *b == 0)
 }
 
+/// `sha256(parts[0] || parts[1] || ...)[0..N]`, with the last bit set if the prefix is
+/// all zero (all-zero IDs are invalid by contract).
+pub(crate) fn derive_id<const N: usize>(parts: &[&[u8]]) -> [u8; N] {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    let hash = hasher.finalize();
+    let mut out = [0u8; N];
+    out.copy_from_slice(&hash[..N]);
+    if is_all_zero(&out) {
+        out[N - 1] = 1;
+    }
+    out
+}
+
 fn serialize_id_hex_or_bytes<S, const N: usize>(
     bytes: &[u8; N],
     serializer: S,
@@ -187,6 +218,11 @@ impl TraceId {
         Self(bytes)
     }
 
+    /// Content-addressed id: `sha256(seed)[0..16]` (never all-zero).
+    pub fn derive(seed: &[u8]) -> Self {
+        Self(derive_id(&[seed]))
+    }
+
     pub const fn as_bytes(&self) -> &[u8; 16] {
         &self.0
     }
@@ -254,6 +290,20 @@ impl SpanId {
         Self(bytes)
     }
 
+    /// Content-addressed id: `sha256(seed)[0..8]` (never all-zero).
+    pub fn derive(seed: &[u8]) -> Self {
+        Self(derive_id(&[seed]))
+    }
+
+    /// Span id for one execution of a graph node:
+    /// `sha256(node_id || ts_nanos_be)[0..8]` (never all-zero).
+    ///
+    /// This is the native runner's original scheme, so span ids in existing bundles
+    /// stay reproducible. Spans are already scoped by `trace_id = run_id`.
+    pub fn from_parts(node_id: &TraceId, ts_nanos: u64) -> Self {
+        Self(derive_id(&[node_id.as_bytes(), &ts_nanos.to_be_bytes()]))
+    }
+
     pub const fn as_bytes(&self) -> &[u8; 8] {
         &self.0
     }
@@ -321,6 +371,18 @@ impl RunId {
         Self(bytes)
     }
 
+    /// Content-addressed id: `sha256(seed)[0..16]` (never all-zero).
+    pub fn derive(seed: &[u8]) -> Self {
+        Self(derive_id(&[seed]))
+    }
+
+    /// The run id for a run rooted at `trace` (same 16 bytes), so `trace_id == run_id`.
+    ///
+    /// An all-zero trace yields an all-zero run id, which fails [`RunId::is_valid`].
+    pub const fn from_trace_root(trace: &TraceId) -> Self {
+        Self(trace.0)
+    }
+
     pub const fn as_bytes(&self) -> &[u8; 16] {
         &self.0
     }
@@ -379,8 +441,78 @@ impl<'de> serde::Deserialize<'de> for RunId {
     }
 }
 
+/// W3C Trace Context (`traceparent`) view of a span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: TraceId,
+    pub span_id: SpanId,
+    pub sampled: bool,
+}
+
+/// Length of a version-`00` `traceparent` header.
+pub const TRACEPARENT_LEN: usize = 55;
+
+impl TraceContext {
+    /// Parse `<2hex version>-<32hex trace_id>-<16hex span_id>-<2hex flags>` (lowercase).
+    ///
+    /// Version `ff` is rejected; version `00` must be exactly 55 chars, while higher
+    /// versions may carry extra `-`-prefixed fields, which are ignored.
+    pub fn parse_traceparent(s: &str) -> core::result::Result<Self, ParseIdError> {
+        let s = s.trim();
+        let bytes = s.as_bytes();
+        if bytes.len() < TRACEPARENT_LEN
+            || !bytes[..TRACEPARENT_LEN].is_ascii()
+            || bytes[2] != b'-'
+            || bytes[35] != b'-'
+            || bytes[52] != b'-'
+        {
+            return Err(ParseIdError::InvalidTraceparent);
+        }
+        let (version, trace_id, span_id, flags) = (&s[..2], &s[3..35], &s[36..52], &s[53..55]);
+        if ![version, trace_id, span_id, flags].iter().all(|field| {
+            field
+                .bytes()
+                .all(|b| decode_hex_nibble(b).is_some() && !b.is_ascii_uppercase())
+        }) {
+            return Err(ParseIdError::InvalidTraceparent);
+        }
+        let [version] = parse_hex_exact::<1>(version)?;
+        let valid_tail = match version {
+            0xff => false,
+            0x00 => bytes.len() == TRACEPARENT_LEN,
+            _ => bytes.len() == TRACEPARENT_LEN || bytes[TRACEPARENT_LEN] == b'-',
+        };
+        if !valid_tail {
+            return Err(ParseIdError::InvalidTraceparent);
+        }
+        let [flags] = parse_hex_exact::<1>(flags)?;
+        Ok(Self {
+            trace_id: TraceId::parse_hex(trace_id)?,
+            span_id: SpanId::parse_hex(span_id)?,
+            sampled: flags & 0x01 != 0,
+        })
+    }
+
+    /// Write the version-`00` header into a fixed buffer (no allocation).
+    pub fn write_traceparent(&self, out: &mut [u8; TRACEPARENT_LEN]) {
+        out[..3].copy_from_slice(b"00-");
+        write_hex_lower(&self.trace_id.0, &mut out[3..35]);
+        out[35] = b'-';
+        write_hex_lower(&self.span_id.0, &mut out[36..52]);
+        out[52..].copy_from_slice(if self.sampled { b"-01" } else { b"-00" });
+    }
+
+    /// The version-`00` header, e.g. `00-<trace_id>-<span_id>-01`.
+    #[cfg(feature = "alloc")]
+    pub fn to_traceparent(&self) -> String {
+        let mut buf = [0u8; TRACEPARENT_LEN];
+        self.write_traceparent(&mut buf);
+        buf.iter().map(|b| *b as char).collect()
+    }
+}
+
 #[cfg(feature = "alloc")]
-use alloc::{collections::BTreeMap, string::String};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
 
 /// A sink for SwarmTorch span/event/metric records.
 ///
@@ -397,15 +529,318 @@ pub trait RunEventEmitter: Send + Sync {
 }
 
 /// Attribute values for spans/events/metrics.
+///
+/// Human-readable encodings (JSON) keep the untagged scalar shapes; `Bytes` is
+/// written as a tagged object `{"bytes": "<lowercase hex>"}` so it can never be
+/// confused with `Str`. Non-negative integers decode as `I64` when they fit.
+/// Binary encodings (postcard) use an explicit variant index.
 #[cfg(feature = "alloc")]
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-#[serde(untagged)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum AttrValue {
     Str(String),
     Bool(bool),
     I64(i64),
     U64(u64),
     F64(f64),
+    /// Small binary payloads (e.g. a gradient hash).
+    Bytes(Vec<u8>),
+}
+
+#[cfg(feature = "alloc")]
+impl AttrValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Signed view; also accepts `U64` values that fit in an `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::I64(v) => Some(*v),
+            Self::U64(v) => i64::try_from(*v).ok(),
+            _ => None,
+        }
+    }
+
+    /// Unsigned view; also accepts non-negative `I64` values.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::U64(v) => Some(*v),
+            Self::I64(v) => u64::try_from(*v).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::F64(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(b) => Some(b),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+const ATTR_VALUE_VARIANTS: &[&str] = &["Str", "Bool", "I64", "U64", "F64", "Bytes"];
+
+#[cfg(feature = "alloc")]
+const ATTR_BYTES_KEY: &str = "bytes";
+
+#[cfg(feature = "alloc")]
+struct RawBytes<'a>(&'a [u8]);
+
+#[cfg(feature = "alloc")]
+impl serde::Serialize for RawBytes<'_> {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl serde::Serialize for AttrValue {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        if serializer.is_human_readable() {
+            return match self {
+                Self::Str(s) => serializer.serialize_str(s),
+                Self::Bool(b) => serializer.serialize_bool(*b),
+                Self::I64(v) => serializer.serialize_i64(*v),
+                Self::U64(v) => serializer.serialize_u64(*v),
+                Self::F64(v) => serializer.serialize_f64(*v),
+                Self::Bytes(b) => {
+                    let mut hex = alloc::vec![0u8; b.len() * 2];
+                    write_hex_lower(b, &mut hex);
+                    let hex = core::str::from_utf8(&hex).map_err(serde::ser::Error::custom)?;
+                    let mut map = serializer.serialize_map(Some(1))?;
+                    map.serialize_entry(ATTR_BYTES_KEY, hex)?;
+                    map.end()
+                }
+            };
+        }
+
+        let name = "AttrValue";
+        match self {
+            Self::Str(s) => serializer.serialize_newtype_variant(name, 0, "Str", s),
+            Self::Bool(b) => serializer.serialize_newtype_variant(name, 1, "Bool", b),
+            Self::I64(v) => serializer.serialize_newtype_variant(name, 2, "I64", v),
+            Self::U64(v) => serializer.serialize_newtype_variant(name, 3, "U64", v),
+            Self::F64(v) => serializer.serialize_newtype_variant(name, 4, "F64", v),
+            Self::Bytes(b) => serializer.serialize_newtype_variant(name, 5, "Bytes", &RawBytes(b)),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn decode_hex_vec(s: &str) -> Option<Vec<u8>> {
+    let raw = s.as_bytes();
+    if raw.len() % 2 != 0 {
+        return None;
+    }
+    raw.chunks_exact(2)
+        .map(|pair| Some((decode_hex_nibble(pair[0])? << 4) | decode_hex_nibble(pair[1])?))
+        .collect()
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> serde::Deserialize<'de> for AttrValue {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::{self, EnumAccess, MapAccess, VariantAccess};
+
+        struct HumanVisitor;
+
+        impl<'de> de::Visitor<'de> for HumanVisitor {
+            type Value = AttrValue;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a string, bool, number, or {\"bytes\": \"<hex>\"} object")
+            }
+
+            fn visit_bool<E: de::Error>(self, v: bool) -> core::result::Result<AttrValue, E> {
+                Ok(AttrValue::Bool(v))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> core::result::Result<AttrValue, E> {
+                Ok(AttrValue::I64(v))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> core::result::Result<AttrValue, E> {
+                Ok(match i64::try_from(v) {
+                    Ok(v) => AttrValue::I64(v),
+                    Err(_) => AttrValue::U64(v),
+                })
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> core::result::Result<AttrValue, E> {
+                Ok(AttrValue::F64(v))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> core::result::Result<AttrValue, E> {
+                Ok(AttrValue::Str(v.into()))
+            }
+
+            fn visit_string<E: de::Error>(self, v: String) -> core::result::Result<AttrValue, E> {
+                Ok(AttrValue::Str(v))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> core::result::Result<AttrValue, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let key: String = map
+                    .next_key()?
+                    .ok_or_else(|| de::Error::missing_field(ATTR_BYTES_KEY))?;
+                if key != ATTR_BYTES_KEY {
+                    return Err(de::Error::unknown_field(&key, &[ATTR_BYTES_KEY]));
+                }
+                let hex: String = map.next_value()?;
+                if map.next_key::<String>()?.is_some() {
+                    return Err(de::Error::custom(
+                        "bytes attribute must have exactly one key",
+                    ));
+                }
+                decode_hex_vec(&hex).map(AttrValue::Bytes).ok_or_else(|| {
+                    de::Error::custom("bytes attribute must be an even-length hex string")
+                })
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_any(HumanVisitor);
+        }
+
+        struct Tag(u8);
+
+        impl<'de> serde::Deserialize<'de> for Tag {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct TagVisitor;
+
+                impl de::Visitor<'_> for TagVisitor {
+                    type Value = Tag;
+
+                    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        f.write_str("an AttrValue variant index or name")
+                    }
+
+                    fn visit_u64<E: de::Error>(self, v: u64) -> core::result::Result<Tag, E> {
+                        if (v as usize) < ATTR_VALUE_VARIANTS.len() {
+                            Ok(Tag(v as u8))
+                        } else {
+                            Err(E::invalid_value(de::Unexpected::Unsigned(v), &self))
+                        }
+                    }
+
+                    fn visit_str<E: de::Error>(self, v: &str) -> core::result::Result<Tag, E> {
+                        ATTR_VALUE_VARIANTS
+                            .iter()
+                            .position(|name| *name == v)
+                            .map(|i| Tag(i as u8))
+                            .ok_or_else(|| E::unknown_variant(v, ATTR_VALUE_VARIANTS))
+                    }
+                }
+
+                deserializer.deserialize_identifier(TagVisitor)
+            }
+        }
+
+        struct BytesVisitor;
+
+        impl<'de> de::Visitor<'de> for BytesVisitor {
+            type Value = Vec<u8>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a byte array")
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> core::result::Result<Vec<u8>, E> {
+                Ok(v.to_vec())
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> core::result::Result<Vec<u8>, E> {
+                Ok(v)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> core::result::Result<Vec<u8>, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut out = Vec::new();
+                while let Some(b) = seq.next_element()? {
+                    out.push(b);
+                }
+                Ok(out)
+            }
+        }
+
+        struct RawBytesBuf(Vec<u8>);
+
+        impl<'de> serde::Deserialize<'de> for RawBytesBuf {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                deserializer
+                    .deserialize_byte_buf(BytesVisitor)
+                    .map(RawBytesBuf)
+            }
+        }
+
+        struct EnumVisitor;
+
+        impl<'de> de::Visitor<'de> for EnumVisitor {
+            type Value = AttrValue;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an AttrValue enum")
+            }
+
+            fn visit_enum<A>(self, data: A) -> core::result::Result<AttrValue, A::Error>
+            where
+                A: EnumAccess<'de>,
+            {
+                let (Tag(tag), variant) = data.variant()?;
+                match tag {
+                    0 => variant.newtype_variant().map(AttrValue::Str),
+                    1 => variant.newtype_variant().map(AttrValue::Bool),
+                    2 => variant.newtype_variant().map(AttrValue::I64),
+                    3 => variant.newtype_variant().map(AttrValue::U64),
+                    4 => variant.newtype_variant().map(AttrValue::F64),
+                    _ => variant
+                        .newtype_variant::<RawBytesBuf>()
+                        .map(|b| AttrValue::Bytes(b.0)),
+                }
+            }
+        }
+
+        deserializer.deserialize_enum("AttrValue", ATTR_VALUE_VARIANTS, EnumVisitor)
+    }
 }
 
 /// Canonical attribute map type (deterministic ordering via BTreeMap).
@@ -421,22 +856,47 @@ pub const MAX_RECORD_ATTRS: usize = 64;
 /// Maximum allowed length for attribute keys.
 #[cfg(feature = "alloc")]
 pub const MAX_ATTR_KEY_LEN: usize = 128;
-/// Maximum allowed length for string attribute values.
+/// Maximum allowed length for string attribute values (and byte length of `Bytes` values).
 #[cfg(feature = "alloc")]
 pub const MAX_ATTR_VALUE_STR_LEN: usize = 1024;
 /// Maximum allowed length for metric unit strings.
 #[cfg(feature = "alloc")]
 pub const MAX_METRIC_UNIT_LEN: usize = 64;
+/// Default total attribute byte budget per record (see [`attr_map_bytes`]).
+#[cfg(feature = "alloc")]
+pub const DEFAULT_MAX_ATTR_BYTES: usize = 16 * 1024;
+/// Marker attribute added by [`AttrLimitPolicy::Truncate`]; its value is the
+/// number of dropped attributes.
+#[cfg(feature = "alloc")]
+pub const TRUNCATED_ATTR_KEY: &str = "_truncated";
 
 /// Validation error for span/event/metric records.
 #[cfg(feature = "alloc")]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RecordValidationError {
-    NameTooLong { len: usize },
-    TooManyAttrs { count: usize },
-    AttrKeyTooLong { key: String, len: usize },
-    AttrValueTooLong { key: String, len: usize },
-    MetricUnitTooLong { len: usize },
+    NameTooLong {
+        len: usize,
+    },
+    TooManyAttrs {
+        count: usize,
+    },
+    AttrKeyTooLong {
+        key: String,
+        len: usize,
+    },
+    AttrValueTooLong {
+        key: String,
+        len: usize,
+    },
+    MetricUnitTooLong {
+        len: usize,
+    },
+    /// The attribute map exceeded an emitter-configured [`AttrLimits`] budget.
+    AttrBudgetExceeded {
+        count: usize,
+        bytes: usize,
+        limits: AttrLimits,
+    },
 }
 
 #[cfg(feature = "alloc")]
@@ -459,6 +919,15 @@ impl fmt::Display for RecordValidationError {
             Self::MetricUnitTooLong { len } => {
                 write!(f, "metric unit length {len} exceeds maximum")
             }
+            Self::AttrBudgetExceeded {
+                count,
+                bytes,
+                limits,
+            } => write!(
+                f,
+                "attribute map ({count} attrs, {bytes} bytes) exceeds budget ({} attrs, {} bytes)",
+                limits.max_attrs, limits.max_attr_bytes
+            ),
         }
     }
 }
@@ -481,18 +950,130 @@ fn validate_name_and_attrs(name: &str, attrs: &AttrMap) -> Result<(), RecordVali
                 len: key.len(),
             });
         }
-        if let AttrValue::Str(s) = value {
-            if s.len() > MAX_ATTR_VALUE_STR_LEN {
-                return Err(RecordValidationError::AttrValueTooLong {
-                    key: key.clone(),
-                    len: s.len(),
-                });
-            }
+        let value_len = match value {
+            AttrValue::Str(s) => s.len(),
+            AttrValue::Bytes(b) => b.len(),
+            _ => 0,
+        };
+        if value_len > MAX_ATTR_VALUE_STR_LEN {
+            return Err(RecordValidationError::AttrValueTooLong {
+                key: key.clone(),
+                len: value_len,
+            });
         }
     }
     Ok(())
 }
 
+/// What to do with a record whose attributes exceed its [`AttrLimits`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttrLimitPolicy {
+    /// Fail with [`RecordValidationError::AttrBudgetExceeded`].
+    Reject,
+    /// Keep attributes in key order while they fit, then add a
+    /// [`TRUNCATED_ATTR_KEY`] marker counting the dropped ones.
+    Truncate,
+}
+
+/// Per-record attribute budget, enforced by emitters (e.g. the artifact sink)
+/// to protect NDJSON streams from runaway producers.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttrLimits {
+    pub max_attrs: usize,
+    pub max_attr_bytes: usize,
+    pub policy: AttrLimitPolicy,
+}
+
+#[cfg(feature = "alloc")]
+impl AttrLimits {
+    pub const fn reject(max_attrs: usize, max_attr_bytes: usize) -> Self {
+        Self {
+            max_attrs,
+            max_attr_bytes,
+            policy: AttrLimitPolicy::Reject,
+        }
+    }
+
+    pub const fn truncate(max_attrs: usize, max_attr_bytes: usize) -> Self {
+        Self {
+            max_attrs,
+            max_attr_bytes,
+            policy: AttrLimitPolicy::Truncate,
+        }
+    }
+
+    pub fn is_within(&self, attrs: &AttrMap) -> bool {
+        attrs.len() <= self.max_attrs && attr_map_bytes(attrs) <= self.max_attr_bytes
+    }
+
+    /// Enforce the budget on `attrs` in place.
+    ///
+    /// Returns `Ok(true)` if attributes were dropped (truncate policy only).
+    pub fn apply(&self, attrs: &mut AttrMap) -> Result<bool, RecordValidationError> {
+        if self.is_within(attrs) {
+            return Ok(false);
+        }
+        if self.policy == AttrLimitPolicy::Reject {
+            return Err(RecordValidationError::AttrBudgetExceeded {
+                count: attrs.len(),
+                bytes: attr_map_bytes(attrs),
+                limits: *self,
+            });
+        }
+
+        let total = attrs.len();
+        // Reserve room for the marker itself.
+        let marker_bytes = attr_bytes(TRUNCATED_ATTR_KEY, &AttrValue::U64(0));
+        let max_kept = self.max_attrs.saturating_sub(1);
+        let mut budget = self.max_attr_bytes.saturating_sub(marker_bytes);
+        let mut kept = AttrMap::new();
+        for (key, value) in core::mem::take(attrs) {
+            let size = attr_bytes(&key, &value);
+            if kept.len() >= max_kept || size > budget {
+                break;
+            }
+            budget -= size;
+            kept.insert(key, value);
+        }
+        let dropped = total - kept.len();
+        if self.max_attrs > 0 && marker_bytes <= self.max_attr_bytes {
+            kept.insert(TRUNCATED_ATTR_KEY.into(), AttrValue::U64(dropped as u64));
+        }
+        *attrs = kept;
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for AttrLimits {
+    fn default() -> Self {
+        Self::reject(MAX_RECORD_ATTRS, DEFAULT_MAX_ATTR_BYTES)
+    }
+}
+
+/// Approximate encoded size of one attribute: key bytes plus value payload
+/// (string/hex length; 8 bytes for scalars).
+#[cfg(feature = "alloc")]
+fn attr_bytes(key: &str, value: &AttrValue) -> usize {
+    let value_len = match value {
+        AttrValue::Str(s) => s.len(),
+        AttrValue::Bytes(b) => b.len().saturating_mul(2),
+        AttrValue::Bool(_) => 1,
+        AttrValue::I64(_) | AttrValue::U64(_) | AttrValue::F64(_) => 8,
+    };
+    key.len().saturating_add(value_len)
+}
+
+/// Total [`AttrLimits`] byte cost of an attribute map.
+#[cfg(feature = "alloc")]
+pub fn attr_map_bytes(attrs: &AttrMap) -> usize {
+    attrs
+        .iter()
+        .fold(0usize, |acc, (k, v)| acc.saturating_add(attr_bytes(k, v)))
+}
+
 /// A span record (NDJSON line schema v1).
 #[cfg(feature = "alloc")]
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -507,6 +1088,20 @@ pub struct SpanRecord {
     pub attrs: AttrMap,
 }
 
+#[cfg(feature = "alloc")]
+impl SpanRecord {
+    /// Replace the attributes, enforcing `limits` (reject or truncate).
+    pub fn with_attrs_bounded(
+        mut self,
+        mut attrs: AttrMap,
+        limits: &AttrLimits,
+    ) -> Result<Self, RecordValidationError> {
+        limits.apply(&mut attrs)?;
+        self.attrs = attrs;
+        Ok(self)
+    }
+}
+
 /// An event record (NDJSON line schema v1).
 #[cfg(feature = "alloc")]
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -557,11 +1152,621 @@ pub fn validate_metric_record(r: &MetricRecord) -> Result<(), RecordValidationEr
     Ok(())
 }
 
+/// Deterministic head-based sampling: keep a trace iff the big-endian value of its
+/// low 8 bytes falls below `ratio * 2^64`.
+///
+/// The decision depends only on `trace_id`, so every record of a trace (on every
+/// node) gets the same answer. `ratio <= 0.0` (or NaN) keeps nothing; `ratio >= 1.0`
+/// keeps everything.
+pub fn should_sample(trace_id: &TraceId, ratio: f32) -> bool {
+    if ratio >= 1.0 {
+        return true;
+    }
+    if ratio.is_nan() || ratio <= 0.0 {
+        return false;
+    }
+    let mut low = [0u8; 8];
+    low.copy_from_slice(&trace_id.0[8..]);
+    let threshold = (f64::from(ratio) * u64::MAX as f64) as u64;
+    u64::from_be_bytes(low) < threshold
+}
+
+/// A fixed-ratio [`should_sample`] policy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sampler {
+    ratio: f32,
+}
+
+impl Sampler {
+    pub const fn new(ratio: f32) -> Self {
+        Self { ratio }
+    }
+
+    /// Keep every trace.
+    pub const fn always() -> Self {
+        Self::new(1.0)
+    }
+
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    pub fn should_sample(&self, trace_id: &TraceId) -> bool {
+        should_sample(trace_id, self.ratio)
+    }
+}
+
+/// Caps accepted records per one-second window (std-only; uses a monotonic clock).
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct RateLimitedSampler {
+    max_per_second: u32,
+    // (window start, accepted in window)
+    window: std::sync::Mutex<(std::time::Instant, u32)>,
+}
+
+#[cfg(feature = "std")]
+impl RateLimitedSampler {
+    pub fn new(max_per_second: u32) -> Self {
+        Self {
+            max_per_second,
+            window: std::sync::Mutex::new((std::time::Instant::now(), 0)),
+        }
+    }
+
+    pub fn max_per_second(&self) -> u32 {
+        self.max_per_second
+    }
+
+    /// Accept one record if the current window still has budget.
+    pub fn try_acquire(&self) -> bool {
+        self.try_acquire_at(std::time::Instant::now())
+    }
+
+    fn try_acquire_at(&self, now: std::time::Instant) -> bool {
+        let mut window = self
+            .window
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if now.duration_since(window.0) >= std::time::Duration::from_secs(1) {
+            *window = (now, 0);
+        }
+        if window.1 < self.max_per_second {
+            window.1 += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// A [`RunEventEmitter`] that forwards only sampled records to `inner`.
+///
+/// Spans, events and metrics are all head-sampled by `trace_id`. With `std`, an
+/// optional [`RateLimitedSampler`] additionally caps metrics (the high-volume kind).
+/// Dropped records return `Ok(())`.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct SampledEmitter<E> {
+    inner: E,
+    sampler: Sampler,
+    #[cfg(feature = "std")]
+    metric_rate_limit: Option<RateLimitedSampler>,
+}
+
+#[cfg(feature = "alloc")]
+impl<E: RunEventEmitter> SampledEmitter<E> {
+    pub fn new(inner: E, sampler: Sampler) -> Self {
+        Self {
+            inner,
+            sampler,
+            #[cfg(feature = "std")]
+            metric_rate_limit: None,
+        }
+    }
+
+    /// Also cap forwarded metrics per second.
+    #[cfg(feature = "std")]
+    pub fn with_metric_rate_limit(mut self, limiter: RateLimitedSampler) -> Self {
+        self.metric_rate_limit = Some(limiter);
+        self
+    }
+
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: RunEventEmitter> RunEventEmitter for SampledEmitter<E> {
+    type Error = E::Error;
+
+    fn emit_span(&self, span: &SpanRecord) -> core::result::Result<(), E::Error> {
+        if !self.sampler.should_sample(&span.trace_id) {
+            return Ok(());
+        }
+        self.inner.emit_span(span)
+    }
+
+    fn emit_event(&self, event: &EventRecord) -> core::result::Result<(), E::Error> {
+        if !self.sampler.should_sample(&event.trace_id) {
+            return Ok(());
+        }
+        self.inner.emit_event(event)
+    }
+
+    fn emit_metric(&self, metric: &MetricRecord) -> core::result::Result<(), E::Error> {
+        if !self.sampler.should_sample(&metric.trace_id) {
+            return Ok(());
+        }
+        #[cfg(feature = "std")]
+        if let Some(limiter) = &self.metric_rate_limit {
+            if !limiter.try_acquire() {
+                return Ok(());
+            }
+        }
+        self.inner.emit_metric(metric)
+    }
+}
+
+/// Replacement value for attributes matched by an [`AttrRedactor`].
+#[cfg(feature = "alloc")]
+pub const REDACTED_ATTR_VALUE: &str = "<redacted>";
+
+/// Key fragments redacted by [`AttrRedactor::default`].
+#[cfg(feature = "alloc")]
+pub const DEFAULT_REDACTED_KEY_PATTERNS: &[&str] =
+    &["token", "password", "secret", "authorization", "api_key"];
+
+/// Replaces secret-looking attribute values with [`REDACTED_ATTR_VALUE`].
+///
+/// Key patterns are case-insensitive substrings, so they also match flattened
+/// nested attributes (`db.credentials.password`). Value patterns are
+/// case-sensitive substrings of `Str` values (e.g. `"Bearer "`); a plain
+/// substring keeps `core` free of a regex engine.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttrRedactor {
+    key_patterns: Vec<String>,
+    value_patterns: Vec<String>,
+}
+
+#[cfg(feature = "alloc")]
+impl AttrRedactor {
+    /// A redactor with no patterns (redacts nothing).
+    pub fn new() -> Self {
+        Self {
+            key_patterns: Vec::new(),
+            value_patterns: Vec::new(),
+        }
+    }
+
+    pub fn with_key_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.key_patterns.push(pattern.into().to_ascii_lowercase());
+        self
+    }
+
+    pub fn with_value_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.value_patterns.push(pattern.into());
+        self
+    }
+
+    pub fn should_redact(&self, key: &str, value: &AttrValue) -> bool {
+        if *value == AttrValue::Str(REDACTED_ATTR_VALUE.into()) {
+            return false;
+        }
+        let key = key.to_ascii_lowercase();
+        if self.key_patterns.iter().any(|p| key.contains(p.as_str())) {
+            return true;
+        }
+        match value {
+            AttrValue::Str(s) => self.value_patterns.iter().any(|p| s.contains(p.as_str())),
+            _ => false,
+        }
+    }
+
+    pub fn needs_redaction(&self, attrs: &AttrMap) -> bool {
+        attrs.iter().any(|(k, v)| self.should_redact(k, v))
+    }
+
+    /// Redact `attrs` in place, returning the number of values replaced.
+    pub fn redact(&self, attrs: &mut AttrMap) -> usize {
+        let mut redacted = 0;
+        for (key, value) in attrs.iter_mut() {
+            if self.should_redact(key, value) {
+                *value = AttrValue::Str(REDACTED_ATTR_VALUE.into());
+                redacted += 1;
+            }
+        }
+        redacted
+    }
+
+    /// `Some(redacted copy)` if any attribute matched; `None` leaves `attrs` as-is.
+    fn redacted_copy(&self, attrs: &AttrMap) -> Option<AttrMap> {
+        if !self.needs_redaction(attrs) {
+            return None;
+        }
+        let mut attrs = attrs.clone();
+        self.redact(&mut attrs);
+        Some(attrs)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for AttrRedactor {
+    fn default() -> Self {
+        DEFAULT_REDACTED_KEY_PATTERNS
+            .iter()
+            .fold(Self::new(), |r, p| r.with_key_pattern(*p))
+    }
+}
+
+/// A [`RunEventEmitter`] that applies an [`AttrRedactor`] before forwarding to
+/// `inner`, so secrets never reach persisted artifacts.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct RedactingEmitter<E> {
+    inner: E,
+    redactor: AttrRedactor,
+}
+
+#[cfg(feature = "alloc")]
+impl<E: RunEventEmitter> RedactingEmitter<E> {
+    pub fn new(inner: E, redactor: AttrRedactor) -> Self {
+        Self { inner, redactor }
+    }
+
+    pub fn redactor(&self) -> &AttrRedactor {
+        &self.redactor
+    }
+
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: RunEventEmitter> RunEventEmitter for RedactingEmitter<E> {
+    type Error = E::Error;
+
+    fn emit_span(&self, span: &SpanRecord) -> core::result::Result<(), E::Error> {
+        match self.redactor.redacted_copy(&span.attrs) {
+            Some(attrs) => self.inner.emit_span(&SpanRecord {
+                attrs,
+                ..span.clone()
+            }),
+            None => self.inner.emit_span(span),
+        }
+    }
+
+    fn emit_event(&self, event: &EventRecord) -> core::result::Result<(), E::Error> {
+        match self.redactor.redacted_copy(&event.attrs) {
+            Some(attrs) => self.inner.emit_event(&EventRecord {
+                attrs,
+                ..event.clone()
+            }),
+            None => self.inner.emit_event(event),
+        }
+    }
+
+    fn emit_metric(&self, metric: &MetricRecord) -> core::result::Result<(), E::Error> {
+        match self.redactor.redacted_copy(&metric.attrs) {
+            Some(attrs) => self.inner.emit_metric(&MetricRecord {
+                attrs,
+                ..metric.clone()
+            }),
+            None => self.inner.emit_metric(metric),
+        }
+    }
+}
+
+/// Span factory for one trace: owns the clock and the span-id sequence.
+///
+/// Span ids are deterministic: `sha256(trace_id || seq_be)[0..8]`, where `seq` counts
+/// spans started through this tracer. Parent linkage is explicit (no thread-locals, so
+/// this works in `no_std`): children are started from their parent via
+/// [`ActiveSpan::child`].
+#[cfg(feature = "alloc")]
+pub struct Tracer<'e, E: RunEventEmitter> {
+    emitter: &'e E,
+    trace_id: TraceId,
+    clock_nanos: fn() -> u64,
+    next_seq: core::cell::Cell<u64>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'e, E: RunEventEmitter> Tracer<'e, E> {
+    pub fn new(emitter: &'e E, trace_id: TraceId, clock_nanos: fn() -> u64) -> Self {
+        Self {
+            emitter,
+            trace_id,
+            clock_nanos,
+            next_seq: core::cell::Cell::new(0),
+        }
+    }
+
+    pub fn trace_id(&self) -> TraceId {
+        self.trace_id
+    }
+
+    /// Begin building a root span.
+    pub fn span(&self, name: impl Into<String>) -> SpanBuilder<'_, 'e, E> {
+        SpanBuilder {
+            tracer: self,
+            name: name.into(),
+            parent_span_id: None,
+            attrs: AttrMap::new(),
+        }
+    }
+
+    fn next_span_id(&self) -> SpanId {
+        let seq = self.next_seq.get();
+        self.next_seq.set(seq.wrapping_add(1));
+        SpanId(derive_id(&[self.trace_id.as_bytes(), &seq.to_be_bytes()]))
+    }
+}
+
+/// Configures a span before it starts (see [`Tracer::span`] / [`ActiveSpan::child`]).
+#[cfg(feature = "alloc")]
+pub struct SpanBuilder<'t, 'e, E: RunEventEmitter> {
+    tracer: &'t Tracer<'e, E>,
+    name: String,
+    parent_span_id: Option<SpanId>,
+    attrs: AttrMap,
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, 'e, E: RunEventEmitter> SpanBuilder<'t, 'e, E> {
+    /// Override the parent (e.g. a span from another process).
+    pub fn parent(mut self, parent_span_id: SpanId) -> Self {
+        self.parent_span_id = Some(parent_span_id);
+        self
+    }
+
+    pub fn attr(mut self, key: impl Into<String>, value: AttrValue) -> Self {
+        self.attrs.insert(key.into(), value);
+        self
+    }
+
+    /// Assign a span id and record the start time.
+    pub fn start(self) -> ActiveSpan<'t, 'e, E> {
+        let record = SpanRecord {
+            schema_version: 1,
+            trace_id: self.tracer.trace_id,
+            span_id: self.tracer.next_span_id(),
+            parent_span_id: self.parent_span_id,
+            name: self.name,
+            start_unix_nanos: (self.tracer.clock_nanos)(),
+            end_unix_nanos: None,
+            attrs: self.attrs,
+        };
+        ActiveSpan {
+            tracer: self.tracer,
+            span_id: record.span_id,
+            parent_span_id: record.parent_span_id,
+            record: Some(record),
+        }
+    }
+}
+
+/// A started span. Emitted once, on [`ActiveSpan::finish`] or (errors ignored) on drop.
+#[cfg(feature = "alloc")]
+pub struct ActiveSpan<'t, 'e, E: RunEventEmitter> {
+    tracer: &'t Tracer<'e, E>,
+    span_id: SpanId,
+    parent_span_id: Option<SpanId>,
+    // `None` once emitted.
+    record: Option<SpanRecord>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, 'e, E: RunEventEmitter> ActiveSpan<'t, 'e, E> {
+    pub fn span_id(&self) -> SpanId {
+        self.span_id
+    }
+
+    pub fn parent_span_id(&self) -> Option<SpanId> {
+        self.parent_span_id
+    }
+
+    /// Begin building a child span whose parent is this span.
+    pub fn child(&self, name: impl Into<String>) -> SpanBuilder<'t, 'e, E> {
+        self.tracer.span(name).parent(self.span_id)
+    }
+
+    pub fn add_attr(&mut self, key: impl Into<String>, value: AttrValue) {
+        if let Some(record) = self.record.as_mut() {
+            record.attrs.insert(key.into(), value);
+        }
+    }
+
+    /// Emit an event linked to this span.
+    pub fn event(&self, name: impl Into<String>) -> core::result::Result<(), E::Error> {
+        self.tracer.emitter.emit_event(&EventRecord {
+            schema_version: 1,
+            ts_unix_nanos: (self.tracer.clock_nanos)(),
+            trace_id: self.tracer.trace_id,
+            span_id: Some(self.span_id),
+            name: name.into(),
+            attrs: AttrMap::new(),
+        })
+    }
+
+    /// Emit a metric linked to this span.
+    pub fn metric(
+        &self,
+        name: impl Into<String>,
+        value: f64,
+    ) -> core::result::Result<(), E::Error> {
+        self.tracer.emitter.emit_metric(&MetricRecord {
+            schema_version: 1,
+            ts_unix_nanos: (self.tracer.clock_nanos)(),
+            trace_id: self.tracer.trace_id,
+            span_id: Some(self.span_id),
+            name: name.into(),
+            value,
+            unit: None,
+            attrs: AttrMap::new(),
+        })
+    }
+
+    /// Record the end time and emit the span.
+    pub fn finish(mut self) -> core::result::Result<(), E::Error> {
+        self.emit()
+    }
+
+    fn emit(&mut self) -> core::result::Result<(), E::Error> {
+        let Some(mut record) = self.record.take() else {
+            return Ok(());
+        };
+        record.end_unix_nanos = Some((self.tracer.clock_nanos)());
+        self.tracer.emitter.emit_span(&record)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: RunEventEmitter> Drop for ActiveSpan<'_, '_, E> {
+    fn drop(&mut self) {
+        let _ = self.emit();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde::Serialize;
 
+    #[test]
+    fn attr_redactor_matches_keys_nested_keys_and_values() {
+        let redactor = AttrRedactor::default().with_value_pattern("Bearer ");
+        let mut attrs = AttrMap::new();
+        attrs.insert("auth_token".into(), AttrValue::Str("abc123".into()));
+        attrs.insert("node_key".into(), AttrValue::Str("ingest/0".into()));
+        attrs.insert("db.credentials.Password".into(), AttrValue::I64(1234));
+        attrs.insert("db.credentials.host".into(), AttrValue::Str("db1".into()));
+        attrs.insert("client_secret".into(), AttrValue::Bytes(vec![0xde, 0xad]));
+        attrs.insert("header".into(), AttrValue::Str("Bearer xyz".into()));
+
+        assert!(redactor.needs_redaction(&attrs));
+        assert_eq!(redactor.redact(&mut attrs), 4);
+        let redacted = AttrValue::Str(REDACTED_ATTR_VALUE.into());
+        assert_eq!(attrs["auth_token"], redacted);
+        assert_eq!(attrs["db.credentials.Password"], redacted);
+        assert_eq!(attrs["client_secret"], redacted);
+        assert_eq!(attrs["header"], redacted);
+        assert_eq!(attrs["node_key"].as_str(), Some("ingest/0"));
+        assert_eq!(attrs["db.credentials.host"].as_str(), Some("db1"));
+
+        // Idempotent: already-redacted maps need no further work.
+        assert!(!redactor.needs_redaction(&attrs));
+        assert_eq!(redactor.redact(&mut attrs), 0);
+        assert!(!AttrRedactor::new().needs_redaction(&attrs));
+    }
+
+    #[test]
+    fn attr_limits_reject_or_truncate_over_budget_maps() {
+        let mut attrs = AttrMap::new();
+        for i in 0..8u64 {
+            attrs.insert(format!("k{i}"), AttrValue::U64(i));
+        }
+        let span = SpanRecord {
+            schema_version: 1,
+            trace_id: TraceId::from_bytes([1u8; 16]),
+            span_id: SpanId::from_bytes([1u8; 8]),
+            parent_span_id: None,
+            name: "op".into(),
+            start_unix_nanos: 0,
+            end_unix_nanos: None,
+            attrs: AttrMap::new(),
+        };
+
+        let under = span
+            .clone()
+            .with_attrs_bounded(attrs.clone(), &AttrLimits::reject(8, 1024))
+            .unwrap();
+        assert_eq!(under.attrs, attrs);
+
+        let err = span
+            .clone()
+            .with_attrs_bounded(attrs.clone(), &AttrLimits::reject(4, 1024))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            RecordValidationError::AttrBudgetExceeded { count: 8, .. }
+        ));
+        // The byte budget is enforced independently of the count.
+        assert!(span
+            .clone()
+            .with_attrs_bounded(attrs.clone(), &AttrLimits::reject(64, 16))
+            .is_err());
+
+        let truncated = span
+            .with_attrs_bounded(attrs, &AttrLimits::truncate(4, 1024))
+            .unwrap();
+        assert_eq!(truncated.attrs.len(), 4);
+        assert_eq!(
+            truncated.attrs.keys().collect::<Vec<_>>(),
+            ["_truncated", "k0", "k1", "k2"]
+        );
+        assert_eq!(truncated.attrs[TRUNCATED_ATTR_KEY], AttrValue::U64(5));
+        assert!(AttrLimits::truncate(4, 1024).is_within(&truncated.attrs));
+    }
+
+    #[test]
+    fn attr_value_accessors_return_matching_types() {
+        let s = AttrValue::Str("train".into());
+        assert_eq!(s.as_str(), Some("train"));
+        assert_eq!(s.as_bool(), None);
+        assert_eq!(AttrValue::Bool(true).as_bool(), Some(true));
+        assert_eq!(AttrValue::I64(-3).as_i64(), Some(-3));
+        assert_eq!(AttrValue::I64(-3).as_u64(), None);
+        assert_eq!(AttrValue::I64(7).as_u64(), Some(7));
+        assert_eq!(AttrValue::U64(7).as_i64(), Some(7));
+        assert_eq!(AttrValue::U64(u64::MAX).as_u64(), Some(u64::MAX));
+        assert_eq!(AttrValue::U64(u64::MAX).as_i64(), None);
+        assert_eq!(AttrValue::F64(0.5).as_f64(), Some(0.5));
+        assert_eq!(AttrValue::F64(0.5).as_i64(), None);
+        let b = AttrValue::Bytes(vec![0xde, 0xad]);
+        assert_eq!(b.as_bytes(), Some(&[0xde, 0xad][..]));
+        assert_eq!(b.as_str(), None);
+    }
+
+    #[test]
+    fn attr_value_bytes_roundtrips_json_and_postcard() {
+        let bytes = AttrValue::Bytes(vec![0x00, 0xab, 0xff]);
+        let json = serde_json::to_string(&bytes).unwrap();
+        assert_eq!(json, r#"{"bytes":"00abff"}"#);
+        assert_eq!(serde_json::from_str::<AttrValue>(&json).unwrap(), bytes);
+
+        // A hex-looking string stays a string.
+        let s = AttrValue::Str("00abff".into());
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(serde_json::from_str::<AttrValue>(&json).unwrap(), s);
+
+        for value in [
+            bytes,
+            s,
+            AttrValue::Bool(false),
+            AttrValue::I64(-1),
+            AttrValue::U64(u64::MAX),
+            AttrValue::F64(1.5),
+        ] {
+            let encoded = postcard::to_allocvec(&value).unwrap();
+            assert_eq!(postcard::from_bytes::<AttrValue>(&encoded).unwrap(), value);
+        }
+
+        assert!(serde_json::from_str::<AttrValue>(r#"{"bytes":"abc"}"#).is_err());
+        assert!(serde_json::from_str::<AttrValue>(r#"{"other":"ab"}"#).is_err());
+    }
+
     #[test]
     fn trace_id_hex_roundtrip() {
         let id = TraceId::from_bytes([0x11u8; 16]);
@@ -578,6 +1783,21 @@ mod tests {
         assert_eq!(parsed.as_bytes(), id.as_bytes());
     }
 
+    #[test]
+    fn run_id_from_trace_root_shares_bytes() {
+        let trace = TraceId::derive(b"run-root");
+        let run_id = RunId::from_trace_root(&trace);
+        assert_eq!(run_id.as_bytes(), trace.as_bytes());
+        assert!(run_id.is_valid());
+
+        let zero = RunId::from_trace_root(&TraceId::from_bytes([0u8; 16]));
+        assert!(!zero.is_valid());
+        assert_eq!(
+            RunId::parse_hex(&zero.to_string()).unwrap_err(),
+            ParseIdError::AllZeroInvalid
+        );
+    }
+
     #[test]
     fn all_zero_invalid() {
         assert_eq!(
@@ -704,4 +1924,296 @@ mod tests {
             })
         );
     }
+
+    #[derive(Default)]
+    struct CaptureEmitter {
+        spans: std::sync::Mutex<Vec<SpanRecord>>,
+        events: std::sync::Mutex<Vec<EventRecord>>,
+        metrics: std::sync::Mutex<Vec<MetricRecord>>,
+    }
+
+    impl RunEventEmitter for CaptureEmitter {
+        type Error = ();
+
+        fn emit_span(&self, span: &SpanRecord) -> Result<(), ()> {
+            self.spans.lock().unwrap().push(span.clone());
+            Ok(())
+        }
+
+        fn emit_event(&self, event: &EventRecord) -> Result<(), ()> {
+            self.events.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+
+        fn emit_metric(&self, metric: &MetricRecord) -> Result<(), ()> {
+            self.metrics.lock().unwrap().push(metric.clone());
+            Ok(())
+        }
+    }
+
+    fn test_clock() -> u64 {
+        static NOW: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1_000);
+        NOW.fetch_add(10, std::sync::atomic::Ordering::SeqCst)
+    }
+
+    #[test]
+    fn nested_spans_link_parents_and_inherit_ids() {
+        let emitter = CaptureEmitter::default();
+        let trace_id = TraceId::from_bytes([7u8; 16]);
+        let tracer = Tracer::new(&emitter, trace_id, test_clock);
+
+        let root = tracer
+            .span("run")
+            .attr("phase", AttrValue::Str("train".to_string()))
+            .start();
+        let mut child = root.child("epoch").start();
+        child.add_attr("epoch", AttrValue::U64(1));
+        let grandchild = child.child("batch").start();
+        assert_ne!(root.span_id(), child.span_id());
+        assert_ne!(child.span_id(), grandchild.span_id());
+
+        grandchild.event("checkpoint").unwrap();
+        child.metric("loss", 0.5).unwrap();
+        let (root_id, child_id, grandchild_id) =
+            (root.span_id(), child.span_id(), grandchild.span_id());
+        grandchild.finish().unwrap();
+        child.finish().unwrap();
+        root.finish().unwrap();
+
+        let spans = emitter.spans.lock().unwrap();
+        let names: Vec<&str> = spans.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["batch", "epoch", "run"]);
+        assert_eq!(spans[2].parent_span_id, None);
+        assert_eq!(spans[1].parent_span_id, Some(root_id));
+        assert_eq!(spans[0].parent_span_id, Some(child_id));
+        assert!(spans.iter().all(|s| s.trace_id == trace_id));
+        assert!(spans
+            .iter()
+            .all(|s| s.end_unix_nanos.unwrap() > s.start_unix_nanos));
+        assert_eq!(spans[1].attrs.get("epoch"), Some(&AttrValue::U64(1)));
+        assert!(spans.iter().all(|s| validate_span_record(s).is_ok()));
+
+        let events = emitter.events.lock().unwrap();
+        assert_eq!(events[0].span_id, Some(grandchild_id));
+        assert_eq!(events[0].trace_id, trace_id);
+        let metrics = emitter.metrics.lock().unwrap();
+        assert_eq!(metrics[0].span_id, Some(child_id));
+        assert_eq!(metrics[0].value, 0.5);
+    }
+
+    #[test]
+    fn dropped_span_is_emitted_closed_exactly_once() {
+        let emitter = CaptureEmitter::default();
+        let tracer = Tracer::new(&emitter, TraceId::from_bytes([8u8; 16]), test_clock);
+        {
+            let _forgotten = tracer.span("forgotten").start();
+        }
+        tracer.span("finished").start().finish().unwrap();
+
+        let spans = emitter.spans.lock().unwrap();
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].name, "forgotten");
+        assert!(spans[0].end_unix_nanos.is_some());
+        assert_eq!(spans[1].name, "finished");
+    }
+
+    #[test]
+    fn tracer_span_ids_are_deterministic_per_trace() {
+        let emitter = CaptureEmitter::default();
+        let trace_id = TraceId::from_bytes([9u8; 16]);
+        let ids = |tracer: &Tracer<'_, CaptureEmitter>| {
+            let a = tracer.span("a").start();
+            let b = tracer.span("b").start();
+            [a.span_id(), b.span_id()]
+        };
+        let first = ids(&Tracer::new(&emitter, trace_id, test_clock));
+        let second = ids(&Tracer::new(&emitter, trace_id, test_clock));
+        assert_eq!(first, second);
+        assert_ne!(first[0], first[1]);
+        assert!(first.iter().all(SpanId::is_valid));
+    }
+
+    #[test]
+    fn derived_ids_are_stable_per_seed() {
+        assert_eq!(RunId::derive(b"run-1"), RunId::derive(b"run-1"));
+        assert_ne!(RunId::derive(b"run-1"), RunId::derive(b"run-2"));
+        assert_eq!(TraceId::derive(b"trace"), TraceId::derive(b"trace"));
+        assert_ne!(TraceId::derive(b"trace"), TraceId::derive(b"trace2"));
+        assert_eq!(SpanId::derive(b"span"), SpanId::derive(b"span"));
+        assert_ne!(SpanId::derive(b"span"), SpanId::derive(b"span2"));
+        // Same seed, different widths: TraceId is a prefix-extension of SpanId.
+        assert_eq!(
+            &TraceId::derive(b"x").as_bytes()[..8],
+            SpanId::derive(b"x").as_bytes()
+        );
+    }
+
+    #[test]
+    fn derived_ids_are_never_all_zero() {
+        for i in 0u32..2048 {
+            let seed = i.to_be_bytes();
+            assert!(RunId::derive(&seed).is_valid());
+            assert!(TraceId::derive(&seed).is_valid());
+            assert!(SpanId::derive(&seed).is_valid());
+        }
+        // Force the fallback: find a seed whose 1-byte prefix hashes to zero.
+        let seed = (0u16..)
+            .map(u16::to_be_bytes)
+            .find(|seed| {
+                use sha2::{Digest, Sha256};
+                Sha256::digest(seed)[0] == 0
+            })
+            .unwrap();
+        assert_eq!(derive_id::<1>(&[&seed]), [1]);
+    }
+
+    #[test]
+    fn span_id_from_parts_is_stable() {
+        let node_a = TraceId::from_bytes([42u8; 16]);
+        let node_b = TraceId::from_bytes([43u8; 16]);
+        let id = SpanId::from_parts(&node_a, 1_000_000_000);
+        assert_eq!(id, SpanId::from_parts(&node_a, 1_000_000_000));
+        assert_ne!(id, SpanId::from_parts(&node_a, 2_000_000_000));
+        assert_ne!(id, SpanId::from_parts(&node_b, 1_000_000_000));
+    }
+
+    #[test]
+    fn span_id_from_parts_matches_native_runner_scheme() {
+        use sha2::{Digest, Sha256};
+
+        let node = TraceId::from_bytes([42u8; 16]);
+        let mut hasher = Sha256::new();
+        hasher.update(node.as_bytes());
+        hasher.update(1_000_000_000u64.to_be_bytes());
+        let expected: [u8; 8] = hasher.finalize()[..8].try_into().unwrap();
+        assert_eq!(
+            *SpanId::from_parts(&node, 1_000_000_000).as_bytes(),
+            expected
+        );
+    }
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn traceparent_roundtrip() {
+        let ctx = TraceContext::parse_traceparent(TRACEPARENT).unwrap();
+        assert_eq!(ctx.trace_id.to_string(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(ctx.span_id.to_string(), "00f067aa0ba902b7");
+        assert!(ctx.sampled);
+        assert_eq!(ctx.to_traceparent(), TRACEPARENT);
+
+        let unsampled = TraceContext {
+            sampled: false,
+            ..ctx
+        };
+        let header = unsampled.to_traceparent();
+        assert!(header.ends_with("-00"));
+        assert_eq!(TraceContext::parse_traceparent(&header), Ok(unsampled));
+
+        // Future versions may append fields; only the known prefix is read.
+        let future = "cc-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra";
+        assert_eq!(TraceContext::parse_traceparent(future), Ok(ctx));
+    }
+
+    #[test]
+    fn traceparent_rejects_malformed_headers() {
+        let short_trace = "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01";
+        assert_eq!(
+            TraceContext::parse_traceparent(short_trace),
+            Err(ParseIdError::InvalidTraceparent)
+        );
+        let zero_trace = "00-00000000000000000000000000000000-00f067aa0ba902b7-01";
+        assert_eq!(
+            TraceContext::parse_traceparent(zero_trace),
+            Err(ParseIdError::AllZeroInvalid)
+        );
+        let zero_span = "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01";
+        assert_eq!(
+            TraceContext::parse_traceparent(zero_span),
+            Err(ParseIdError::AllZeroInvalid)
+        );
+        for bad in [
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "0x-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "00_4bf92f3577b34da6a3ce929d0e0e4736_00f067aa0ba902b7_01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-0\u{e9}",
+        ] {
+            assert_eq!(
+                TraceContext::parse_traceparent(bad),
+                Err(ParseIdError::InvalidTraceparent),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn sampler_ratio_bounds() {
+        let ids: Vec<TraceId> = (0u32..256)
+            .map(|i| TraceId::derive(&i.to_be_bytes()))
+            .collect();
+        assert!(ids.iter().all(|id| !should_sample(id, 0.0)));
+        assert!(ids.iter().all(|id| !should_sample(id, f32::NAN)));
+        assert!(ids.iter().all(|id| should_sample(id, 1.0)));
+        assert!(ids.iter().all(|id| Sampler::always().should_sample(id)));
+
+        let kept = ids.iter().filter(|id| should_sample(id, 0.5)).count();
+        assert!((64..192).contains(&kept), "kept {kept}/256 at ratio 0.5");
+    }
+
+    #[test]
+    fn sampler_decision_is_stable_per_trace() {
+        let sampler = Sampler::new(0.3);
+        for i in 0u32..64 {
+            let id = TraceId::derive(&i.to_be_bytes());
+            let first = sampler.should_sample(&id);
+            assert!((0..8).all(|_| sampler.should_sample(&id) == first));
+            // Only the low 8 bytes matter.
+            let mut high_changed = id;
+            high_changed.0[0] ^= 0xff;
+            assert_eq!(sampler.should_sample(&high_changed), first);
+        }
+    }
+
+    #[test]
+    fn rate_limited_sampler_resets_each_second() {
+        let limiter = RateLimitedSampler::new(2);
+        let start = std::time::Instant::now();
+        assert!(limiter.try_acquire_at(start));
+        assert!(limiter.try_acquire_at(start));
+        assert!(!limiter.try_acquire_at(start));
+        let later = start + std::time::Duration::from_secs(1);
+        assert!(limiter.try_acquire_at(later));
+    }
+
+    #[test]
+    fn sampled_emitter_drops_unsampled_records() {
+        let metric = |trace_id: TraceId| MetricRecord {
+            schema_version: 1,
+            ts_unix_nanos: 1,
+            trace_id,
+            span_id: None,
+            name: "loss".to_string(),
+            value: 0.1,
+            unit: None,
+            attrs: AttrMap::new(),
+        };
+        let trace_id = TraceId::from_bytes([3u8; 16]);
+
+        let none = SampledEmitter::new(CaptureEmitter::default(), Sampler::new(0.0));
+        none.emit_metric(&metric(trace_id)).unwrap();
+        assert!(none.inner().metrics.lock().unwrap().is_empty());
+
+        let capped = SampledEmitter::new(CaptureEmitter::default(), Sampler::always())
+            .with_metric_rate_limit(RateLimitedSampler::new(3));
+        for _ in 0..10 {
+            capped.emit_metric(&metric(trace_id)).unwrap();
+        }
+        let tracer = Tracer::new(&capped, trace_id, test_clock);
+        tracer.span("kept").start().finish().unwrap();
+        let inner = capped.into_inner();
+        assert_eq!(inner.metrics.lock().unwrap().len(), 3);
+        assert_eq!(inner.spans.lock().unwrap().len(), 1);
+    }
 }
@@ -0,0 +1,185 @@
+//! Per-name aggregation over [`MetricRecord`] points (report summaries).
+//!
+//! Grouping uses a `BTreeMap` keyed by metric `name`, so output order is deterministic.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::MetricRecord;
+
+/// Gauge/counter summary of all points sharing one metric name.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MetricSummary {
+    pub count: u64,
+    /// Sum of values (the total, for counters).
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Value of the latest point (greatest `ts_unix_nanos`; ties go to the later record).
+    pub last: f64,
+    pub last_ts_unix_nanos: u64,
+    /// Unit of the latest point that carried one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+}
+
+impl MetricSummary {
+    fn from_point(metric: &MetricRecord) -> Self {
+        Self {
+            count: 1,
+            sum: metric.value,
+            min: metric.value,
+            max: metric.value,
+            last: metric.value,
+            last_ts_unix_nanos: metric.ts_unix_nanos,
+            unit: metric.unit.clone(),
+        }
+    }
+
+    fn add(&mut self, metric: &MetricRecord) {
+        self.count += 1;
+        self.sum += metric.value;
+        self.min = self.min.min(metric.value);
+        self.max = self.max.max(metric.value);
+        if metric.ts_unix_nanos >= self.last_ts_unix_nanos {
+            self.last = metric.value;
+            self.last_ts_unix_nanos = metric.ts_unix_nanos;
+            if metric.unit.is_some() {
+                self.unit = metric.unit.clone();
+            }
+        }
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+/// Fixed-bucket histogram.
+///
+/// `counts[i]` counts values `<= bounds[i]` (and above the previous bound); the final
+/// `counts[bounds.len()]` bucket counts values above the last bound (and NaN).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Histogram {
+    pub bounds: Vec<f64>,
+    pub counts: Vec<u64>,
+}
+
+impl Histogram {
+    /// Empty histogram; `bounds` are sorted ascending (NaN bounds are dropped).
+    pub fn with_bounds(bounds: &[f64]) -> Self {
+        let mut bounds: Vec<f64> = bounds.iter().copied().filter(|b| !b.is_nan()).collect();
+        bounds.sort_by(f64::total_cmp);
+        bounds.dedup();
+        let counts = alloc::vec![0; bounds.len() + 1];
+        Self { bounds, counts }
+    }
+
+    pub fn observe(&mut self, value: f64) {
+        let bucket = self
+            .bounds
+            .iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(self.bounds.len());
+        self.counts[bucket] += 1;
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+}
+
+/// Summarize metric points by `name`.
+pub fn summarize_metrics(records: &[MetricRecord]) -> BTreeMap<String, MetricSummary> {
+    let mut out: BTreeMap<String, MetricSummary> = BTreeMap::new();
+    for metric in records {
+        match out.get_mut(&metric.name) {
+            Some(summary) => summary.add(metric),
+            None => {
+                out.insert(metric.name.clone(), MetricSummary::from_point(metric));
+            }
+        }
+    }
+    out
+}
+
+/// Fixed-bucket histograms of metric values by `name`, all sharing `bounds`.
+pub fn histogram_metrics(records: &[MetricRecord], bounds: &[f64]) -> BTreeMap<String, Histogram> {
+    let empty = Histogram::with_bounds(bounds);
+    let mut out: BTreeMap<String, Histogram> = BTreeMap::new();
+    for metric in records {
+        out.entry(metric.name.clone())
+            .or_insert_with(|| empty.clone())
+            .observe(metric.value);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observe::{AttrMap, TraceId};
+
+    fn point(name: &str, ts: u64, value: f64) -> MetricRecord {
+        MetricRecord {
+            schema_version: 1,
+            ts_unix_nanos: ts,
+            trace_id: TraceId::from_bytes([1u8; 16]),
+            span_id: None,
+            name: name.into(),
+            value,
+            unit: None,
+            attrs: AttrMap::new(),
+        }
+    }
+
+    #[test]
+    fn summarize_groups_by_name_with_sum_min_max_last() {
+        let records = [
+            point("loss", 30, 0.5),
+            point("throughput", 10, 100.0),
+            point("loss", 10, 2.0),
+            point("loss", 20, 1.0),
+            point("throughput", 20, 300.0),
+        ];
+        let summaries = summarize_metrics(&records);
+        let names: Vec<&str> = summaries.keys().map(String::as_str).collect();
+        assert_eq!(names, ["loss", "throughput"]);
+
+        let loss = &summaries["loss"];
+        assert_eq!(loss.count, 3);
+        assert_eq!(loss.sum, 3.5);
+        assert_eq!(loss.min, 0.5);
+        assert_eq!(loss.max, 2.0);
+        assert_eq!(loss.last, 0.5, "last is by timestamp, not input order");
+        assert_eq!(loss.last_ts_unix_nanos, 30);
+
+        let throughput = &summaries["throughput"];
+        assert_eq!(throughput.mean(), 200.0);
+        assert_eq!(throughput.last, 300.0);
+
+        let mut reversed = records.to_vec();
+        reversed.reverse();
+        let reversed = summarize_metrics(&reversed);
+        assert_eq!(reversed["loss"].last, loss.last);
+        assert_eq!(reversed["loss"].sum, loss.sum);
+    }
+
+    #[test]
+    fn histogram_uses_upper_inclusive_buckets() {
+        let records = [
+            point("latency", 1, 0.5),
+            point("latency", 2, 1.0),
+            point("latency", 3, 5.0),
+            point("latency", 4, 50.0),
+            point("other", 5, 7.0),
+        ];
+        let histograms = histogram_metrics(&records, &[10.0, 1.0]);
+        let latency = &histograms["latency"];
+        assert_eq!(latency.bounds, [1.0, 10.0]);
+        assert_eq!(latency.counts, [2, 1, 1]);
+        assert_eq!(latency.total(), 4);
+        assert_eq!(histograms["other"].counts, [0, 1, 0]);
+    }
+}
@@ -0,0 +1,208 @@
+//! Per-round gradient L2-norm metrics.
+//!
+//! [`emit_gradient_norm_metrics`] turns one round's updates into `MetricRecord`s
+//! (min/median/max/mean norm plus a fixed-bucket histogram), so diverging nodes
+//! show up in the report timeline without custom instrumentation.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use super::agg::Histogram;
+use super::{AttrMap, AttrValue, MetricRecord, RunEventEmitter, SpanId, TraceId};
+use crate::crypto::sqrt_f32;
+use crate::traits::GradientUpdate;
+
+/// Upper bucket bounds used by [`emit_gradient_norm_metrics`].
+pub const GRADIENT_NORM_BUCKETS: [f64; 6] = [0.01, 0.1, 1.0, 10.0, 100.0, 1000.0];
+
+/// Metric name prefix; summaries are `gradient_norm.{min,median,max,mean}`.
+pub const GRADIENT_NORM_METRIC: &str = "gradient_norm";
+
+/// L2-norm statistics of one round's updates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradientNormStats {
+    pub count: usize,
+    pub min: f64,
+    pub median: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub histogram: Histogram,
+}
+
+impl GradientNormStats {
+    /// Statistics over `updates`, bucketed by `bounds`. `None` for an empty round.
+    ///
+    /// Norms are ordered with [`f64::total_cmp`], so a NaN norm sorts last and
+    /// lands in the histogram's overflow bucket.
+    pub fn from_updates(updates: &[GradientUpdate], bounds: &[f64]) -> Option<Self> {
+        if updates.is_empty() {
+            return None;
+        }
+        let mut norms: Vec<f64> = updates
+            .iter()
+            .map(|u| f64::from(sqrt_f32(u.gradients.iter().map(|g| g * g).sum())))
+            .collect();
+        norms.sort_by(f64::total_cmp);
+
+        let n = norms.len();
+        let median = if n % 2 == 0 {
+            (norms[n / 2 - 1] + norms[n / 2]) / 2.0
+        } else {
+            norms[n / 2]
+        };
+        let mut histogram = Histogram::with_bounds(bounds);
+        for norm in &norms {
+            histogram.observe(*norm);
+        }
+        Some(Self {
+            count: n,
+            min: norms[0],
+            median,
+            max: norms[n - 1],
+            mean: norms.iter().sum::<f64>() / n as f64,
+            histogram,
+        })
+    }
+}
+
+/// Emit norm metrics for one round's `updates` through `emitter`.
+///
+/// Emits `gradient_norm.{min,median,max,mean}` and one
+/// `gradient_norm.bucket` point per [`GRADIENT_NORM_BUCKETS`] bucket (value =
+/// count, attr `le` = upper bound, `"+Inf"` for the overflow bucket). Every
+/// point carries a `round_id` attr. An empty round emits nothing.
+pub fn emit_gradient_norm_metrics<E: RunEventEmitter>(
+    emitter: &E,
+    trace_id: TraceId,
+    span_id: Option<SpanId>,
+    ts_unix_nanos: u64,
+    round_id: u64,
+    updates: &[GradientUpdate],
+) -> core::result::Result<(), E::Error> {
+    let Some(stats) = GradientNormStats::from_updates(updates, &GRADIENT_NORM_BUCKETS) else {
+        return Ok(());
+    };
+    let emit = |suffix: &str, value: f64, le: Option<AttrValue>| {
+        let mut attrs = AttrMap::new();
+        attrs.insert("round_id".to_string(), AttrValue::U64(round_id));
+        if let Some(le) = le {
+            attrs.insert("le".to_string(), le);
+        }
+        let mut name = String::from(GRADIENT_NORM_METRIC);
+        name.push('.');
+        name.push_str(suffix);
+        emitter.emit_metric(&MetricRecord {
+            schema_version: 1,
+            ts_unix_nanos,
+            trace_id,
+            span_id,
+            name,
+            value,
+            unit: None,
+            attrs,
+        })
+    };
+
+    emit("min", stats.min, None)?;
+    emit("median", stats.median, None)?;
+    emit("max", stats.max, None)?;
+    emit("mean", stats.mean, None)?;
+    for (i, count) in stats.histogram.counts.iter().enumerate() {
+        let le = match stats.histogram.bounds.get(i) {
+            Some(bound) => AttrValue::F64(*bound),
+            None => AttrValue::Str("+Inf".to_string()),
+        };
+        emit("bucket", *count as f64, Some(le))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observe::{EventRecord, SpanRecord};
+    use crate::traits::UpdateEncoding;
+
+    #[derive(Default)]
+    struct CaptureEmitter {
+        metrics: std::sync::Mutex<Vec<MetricRecord>>,
+    }
+
+    impl RunEventEmitter for CaptureEmitter {
+        type Error = ();
+
+        fn emit_span(&self, _span: &SpanRecord) -> Result<(), ()> {
+            Ok(())
+        }
+
+        fn emit_event(&self, _event: &EventRecord) -> Result<(), ()> {
+            Ok(())
+        }
+
+        fn emit_metric(&self, metric: &MetricRecord) -> Result<(), ()> {
+            self.metrics.lock().unwrap().push(metric.clone());
+            Ok(())
+        }
+    }
+
+    fn update(gradients: &[f32]) -> GradientUpdate {
+        GradientUpdate {
+            sender: [0u8; 32],
+            sequence: 0,
+            gradients: gradients.to_vec(),
+            round_id: 4,
+            encoding: UpdateEncoding::Full,
+        }
+    }
+
+    #[test]
+    fn emits_summary_and_bucket_metrics() {
+        // Norms 0.05, 5, 5, 50.
+        let updates = [
+            update(&[0.03, 0.04]),
+            update(&[3.0, 4.0]),
+            update(&[0.0, -5.0]),
+            update(&[30.0, 40.0]),
+        ];
+        let emitter = CaptureEmitter::default();
+        let trace_id = TraceId::from_bytes([1u8; 16]);
+        emit_gradient_norm_metrics(&emitter, trace_id, None, 100, 4, &updates).unwrap();
+
+        let metrics = emitter.metrics.lock().unwrap();
+        let value = |name: &str| metrics.iter().find(|m| m.name == name).unwrap().value;
+        let expected_mean = (0.05 + 5.0 + 5.0 + 50.0) / 4.0;
+        assert!((value("gradient_norm.mean") - expected_mean).abs() < 1e-4);
+        assert!((value("gradient_norm.min") - 0.05).abs() < 1e-6);
+        assert!((value("gradient_norm.median") - 5.0).abs() < 1e-5);
+        assert!((value("gradient_norm.max") - 50.0).abs() < 1e-4);
+        assert!(metrics
+            .iter()
+            .all(|m| m.attrs.get("round_id") == Some(&AttrValue::U64(4))));
+
+        let buckets: Vec<(Option<f64>, f64)> = metrics
+            .iter()
+            .filter(|m| m.name == "gradient_norm.bucket")
+            .map(|m| (m.attrs["le"].as_f64(), m.value))
+            .collect();
+        assert_eq!(
+            buckets,
+            [
+                (Some(0.01), 0.0),
+                (Some(0.1), 1.0),
+                (Some(1.0), 0.0),
+                (Some(10.0), 2.0),
+                (Some(100.0), 1.0),
+                (Some(1000.0), 0.0),
+                (None, 0.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_round_emits_nothing() {
+        let emitter = CaptureEmitter::default();
+        emit_gradient_norm_metrics(&emitter, TraceId::from_bytes([1u8; 16]), None, 0, 0, &[])
+            .unwrap();
+        assert!(emitter.metrics.lock().unwrap().is_empty());
+    }
+}
@@ -7,15 +7,19 @@
 //!
 //! - **Per-peer state**: Isolated sequence tracking prevents cross-peer attacks
 //! - **LRU cache**: Bounded memory (default 1000 peers)
-//! - **Timestamp validation**: Clock skew tolerance (default ±60s)
+//! - **Timestamp validation**: Clock skew tolerance (default ±60s), optionally
+//!   overridden per message type
 //! - **Sequence window**: Small out-of-order tolerance (16 messages) for network reordering
 //!
 //! ## Known Limitations
 //!
-//! **Memory-only cache (non-persistent):**
+//! **Memory-only cache by default (non-persistent):**
 //! - Node restart resets the replay cache
 //! - Brief replay vulnerability window after restart (≤ max_clock_skew_secs)
-//! - Acceptable for alpha; persistent cache requires storage backend (future work)
+//! - With the `replay-store` feature, a `ReplayStore` can be attached via
+//!   `ReplayProtection::with_store` so per-peer state survives restart and LRU eviction.
+//!   Core ships only the trait (plus an in-memory store for tests); durable backends
+//!   are provided by the embedding runtime.
 //!
 //! ## Assumptions
 //!
@@ -29,9 +33,13 @@ use crate::traits::PeerId;
 extern crate alloc;
 
 #[cfg(feature = "alloc")]
-use alloc::collections::BTreeSet;
+use alloc::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "replay-store")]
+use alloc::{boxed::Box, vec::Vec};
 #[cfg(feature = "alloc")]
 use lru::LruCache;
+#[cfg(feature = "replay-store")]
+use serde::{Deserialize, Serialize};
 
 /// Default sequence tolerance window size (messages).
 ///
@@ -71,6 +79,24 @@ impl core::fmt::Display for ReplayConfigError {
 #[cfg(feature = "std")]
 impl std::error::Error for ReplayConfigError {}
 
+/// Replay-guard health counters, for dashboards and alerting.
+///
+/// Counters are cumulative since construction; a spike in rejections can
+/// indicate a replay attack (or a misbehaving peer).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplayStats {
+    /// Peers currently held in the LRU cache
+    pub tracked_peers: usize,
+    /// Sequences accepted by `validate_sequence`
+    pub total_accepted: u64,
+    /// Duplicate sequences rejected ([`ReplayError::Replay`])
+    pub total_rejected_replay: u64,
+    /// Sequences rejected beyond the tolerance window ([`ReplayError::TooOld`])
+    pub total_rejected_too_old: u64,
+    /// Peers evicted from the LRU cache to make room for new ones
+    pub evictions: u64,
+}
+
 /// Replay protection state
 ///
 /// Tracks per-peer sequence numbers and validates timestamp freshness.
@@ -81,9 +107,16 @@ pub struct ReplayProtection {
     peer_state: LruCache<PeerId, PeerReplayState>,
     /// Maximum clock skew tolerance (seconds)
     max_clock_skew_secs: u32,
+    /// Per-message-type skew overrides, keyed by wire discriminant
+    skew_overrides: BTreeMap<u8, u32>,
     /// Sequence tolerance window (messages). Out-of-order delivery
     /// within this window is accepted; beyond it is rejected as `TooOld`.
     tolerance_window: usize,
+    /// Cumulative counters (`tracked_peers` is filled in by `stats`)
+    stats: ReplayStats,
+    /// Optional persistent backing store (write-through, lazily hydrated).
+    #[cfg(feature = "replay-store")]
+    store: Option<StoreHandle>,
 }
 
 #[cfg(feature = "alloc")]
@@ -97,12 +130,20 @@ impl ReplayProtection {
             Some(capacity) => Self {
                 peer_state: LruCache::new(capacity),
                 max_clock_skew_secs: DEFAULT_MAX_CLOCK_SKEW_SECS,
+                skew_overrides: BTreeMap::new(),
                 tolerance_window: DEFAULT_SEQUENCE_TOLERANCE_WINDOW,
+                stats: ReplayStats::default(),
+                #[cfg(feature = "replay-store")]
+                store: None,
             },
             None => Self {
                 peer_state: LruCache::new(core::num::NonZeroUsize::MIN),
                 max_clock_skew_secs: DEFAULT_MAX_CLOCK_SKEW_SECS,
+                skew_overrides: BTreeMap::new(),
                 tolerance_window: DEFAULT_SEQUENCE_TOLERANCE_WINDOW,
+                stats: ReplayStats::default(),
+                #[cfg(feature = "replay-store")]
+                store: None,
             },
         }
     }
@@ -117,7 +158,11 @@ impl ReplayProtection {
         Ok(Self {
             peer_state: LruCache::new(non_zero_capacity),
             max_clock_skew_secs,
+            skew_overrides: BTreeMap::new(),
             tolerance_window: DEFAULT_SEQUENCE_TOLERANCE_WINDOW,
+            stats: ReplayStats::default(),
+            #[cfg(feature = "replay-store")]
+            store: None,
         })
     }
 
@@ -145,10 +190,25 @@ impl ReplayProtection {
         Ok(Self {
             peer_state: LruCache::new(non_zero_capacity),
             max_clock_skew_secs,
+            skew_overrides: BTreeMap::new(),
             tolerance_window,
+            stats: ReplayStats::default(),
+            #[cfg(feature = "replay-store")]
+            store: None,
         })
     }
 
+    /// Attach a persistent replay store.
+    ///
+    /// Peer state missing from the LRU cache is hydrated lazily from the store on the
+    /// next `validate_sequence` call, and every accepted sequence is written back, so a
+    /// rebuilt `ReplayProtection` over the same store keeps rejecting seen sequences.
+    #[cfg(feature = "replay-store")]
+    pub fn with_store<S: ReplayStore + 'static>(mut self, store: S) -> Self {
+        self.store = Some(StoreHandle(Box::new(store)));
+        self
+    }
+
     /// Create with custom configuration
     ///
     /// # Arguments
@@ -169,16 +229,48 @@ impl ReplayProtection {
         }
     }
 
+    /// Override the clock-skew window for one message type.
+    ///
+    /// `message_type` is the envelope's wire discriminant (`MessageType as u8`
+    /// in `swarm-torch-net`), e.g. a tight window for frequent heartbeats and a
+    /// looser one for large checkpoints. Types without an override use the
+    /// global window.
+    pub fn with_message_type_skew(mut self, message_type: u8, max_clock_skew_secs: u32) -> Self {
+        self.skew_overrides
+            .insert(message_type, max_clock_skew_secs);
+        self
+    }
+
+    /// Clock-skew window (seconds) applied to `message_type`.
+    pub fn max_clock_skew_for(&self, message_type: u8) -> u32 {
+        self.skew_overrides
+            .get(&message_type)
+            .copied()
+            .unwrap_or(self.max_clock_skew_secs)
+    }
+
     /// Check timestamp validity without mutating state (fail-fast optimization)
     ///
-    /// Validates that the message timestamp is within the acceptable clock skew window.
+    /// Validates that the message timestamp is within the global clock skew
+    /// window, ignoring per-message-type overrides.
     pub fn check_timestamp_only(&self, ts: u32, now: u32) -> Result<(), ReplayError> {
-        if !self.is_timestamp_valid(ts, now) {
-            return Err(ReplayError::Expired {
-                ts,
-                now,
-                window: self.max_clock_skew_secs,
-            });
+        Self::check_timestamp_window(ts, now, self.max_clock_skew_secs)
+    }
+
+    /// [`Self::check_timestamp_only`] using the window for `message_type`
+    /// (see [`Self::with_message_type_skew`]).
+    pub fn check_timestamp_for_type(
+        &self,
+        ts: u32,
+        now: u32,
+        message_type: u8,
+    ) -> Result<(), ReplayError> {
+        Self::check_timestamp_window(ts, now, self.max_clock_skew_for(message_type))
+    }
+
+    fn check_timestamp_window(ts: u32, now: u32, window: u32) -> Result<(), ReplayError> {
+        if now.abs_diff(ts) > window {
+            return Err(ReplayError::Expired { ts, now, window });
         }
         Ok(())
     }
@@ -188,13 +280,49 @@ impl ReplayProtection {
     /// Checks for duplicate or retrograde sequences and updates peer state.
     pub fn validate_sequence(&mut self, peer: &PeerId, seq: u64) -> Result<(), ReplayError> {
         let tw = self.tolerance_window;
-        match self.peer_state.get_mut(peer) {
+        #[cfg(feature = "replay-store")]
+        self.hydrate_peer(peer);
+        let result = match self.peer_state.get_mut(peer) {
             Some(state) => state.validate_and_update(seq, *peer, tw),
             None => {
                 // First message from this peer
-                self.peer_state.put(*peer, PeerReplayState::new(seq));
+                self.insert_peer(*peer, PeerReplayState::new(seq));
                 Ok(())
             }
+        };
+        match result {
+            Ok(()) => self.stats.total_accepted += 1,
+            Err(ReplayError::Replay { .. }) => self.stats.total_rejected_replay += 1,
+            Err(ReplayError::TooOld { .. }) => self.stats.total_rejected_too_old += 1,
+            Err(ReplayError::Expired { .. }) => {}
+        }
+        #[cfg(feature = "replay-store")]
+        if result.is_ok() {
+            self.persist_peer(peer);
+        }
+        result
+    }
+
+    /// Load peer state from the store if it is not already cached.
+    #[cfg(feature = "replay-store")]
+    fn hydrate_peer(&mut self, peer: &PeerId) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        if self.peer_state.contains(peer) {
+            return;
+        }
+        if let Some(snapshot) = store.0.load_peer(peer) {
+            let state = PeerReplayState::from_snapshot(snapshot, self.tolerance_window);
+            self.insert_peer(*peer, state);
+        }
+    }
+
+    /// Write the cached peer state through to the store.
+    #[cfg(feature = "replay-store")]
+    fn persist_peer(&self, peer: &PeerId) {
+        if let (Some(store), Some(state)) = (&self.store, self.peer_state.peek(peer)) {
+            store.0.save_peer(peer, &state.snapshot());
         }
     }
 
@@ -217,16 +345,25 @@ impl ReplayProtection {
         Ok(())
     }
 
-    /// Check if timestamp is within acceptable window
-    fn is_timestamp_valid(&self, ts: u32, now: u32) -> bool {
-        let diff = now.abs_diff(ts);
-        diff <= self.max_clock_skew_secs
+    /// Cache a peer not yet present, counting an LRU eviction if one occurs.
+    fn insert_peer(&mut self, peer: PeerId, state: PeerReplayState) {
+        if self.peer_state.push(peer, state).is_some() {
+            self.stats.evictions += 1;
+        }
     }
 
     /// Get current cache size (for testing/monitoring)
     pub fn cache_size(&self) -> usize {
         self.peer_state.len()
     }
+
+    /// Snapshot of the replay-guard counters.
+    pub fn stats(&self) -> ReplayStats {
+        ReplayStats {
+            tracked_peers: self.peer_state.len(),
+            ..self.stats
+        }
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -293,6 +430,121 @@ impl PeerReplayState {
         // Remove sequences below threshold to bound memory
         self.recent_sequences.retain(|&s| s > threshold);
     }
+
+    #[cfg(feature = "replay-store")]
+    fn snapshot(&self) -> PeerReplaySnapshot {
+        PeerReplaySnapshot {
+            last_sequence: self.last_sequence,
+            recent_sequences: self.recent_sequences.iter().copied().collect(),
+        }
+    }
+
+    /// Rebuild state from a stored snapshot, dropping entries outside the window.
+    #[cfg(feature = "replay-store")]
+    fn from_snapshot(snapshot: PeerReplaySnapshot, tolerance_window: usize) -> Self {
+        let last_sequence = snapshot.last_sequence;
+        let mut state = Self {
+            last_sequence,
+            recent_sequences: snapshot
+                .recent_sequences
+                .into_iter()
+                .filter(|&s| s <= last_sequence)
+                .collect(),
+        };
+        state.prune_old_sequences(last_sequence.saturating_sub(tolerance_window as u64));
+        state
+    }
+}
+
+/// Persisted per-peer replay state: highest sequence plus the in-window set.
+#[cfg(feature = "replay-store")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerReplaySnapshot {
+    /// Highest sequence number accepted from the peer.
+    pub last_sequence: u64,
+    /// Accepted sequences still inside the tolerance window (ascending).
+    pub recent_sequences: Vec<u64>,
+}
+
+/// Backing store that lets replay state survive process restart.
+///
+/// Implementations must be durable enough for the deployment's threat model;
+/// a store that drops writes reopens the replay window for affected peers.
+#[cfg(feature = "replay-store")]
+pub trait ReplayStore: Send + Sync {
+    /// Load the last persisted state for `peer`, if any.
+    fn load_peer(&self, peer: &PeerId) -> Option<PeerReplaySnapshot>;
+
+    /// Persist the current state for `peer`, replacing any previous snapshot.
+    fn save_peer(&self, peer: &PeerId, snapshot: &PeerReplaySnapshot);
+}
+
+#[cfg(all(feature = "replay-store", feature = "std"))]
+impl<S: ReplayStore + ?Sized> ReplayStore for std::sync::Arc<S> {
+    fn load_peer(&self, peer: &PeerId) -> Option<PeerReplaySnapshot> {
+        (**self).load_peer(peer)
+    }
+
+    fn save_peer(&self, peer: &PeerId, snapshot: &PeerReplaySnapshot) {
+        (**self).save_peer(peer, snapshot)
+    }
+}
+
+#[cfg(feature = "replay-store")]
+struct StoreHandle(Box<dyn ReplayStore>);
+
+#[cfg(feature = "replay-store")]
+impl core::fmt::Debug for StoreHandle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ReplayStore")
+    }
+}
+
+/// In-memory [`ReplayStore`] for tests and single-process simulations.
+///
+/// Share it across `ReplayProtection` instances via `Arc` to model a restart.
+#[cfg(all(feature = "replay-store", feature = "std"))]
+#[derive(Debug, Default)]
+pub struct MemoryReplayStore {
+    peers: std::sync::Mutex<std::collections::BTreeMap<PeerId, PeerReplaySnapshot>>,
+}
+
+#[cfg(all(feature = "replay-store", feature = "std"))]
+impl MemoryReplayStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of peers with a persisted snapshot.
+    pub fn len(&self) -> usize {
+        self.lock_peers().len()
+    }
+
+    /// Whether no peer state has been persisted.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock_peers(
+        &self,
+    ) -> std::sync::MutexGuard<'_, std::collections::BTreeMap<PeerId, PeerReplaySnapshot>> {
+        // A poisoned lock still holds valid snapshots; never fail open by hiding them.
+        self.peers
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(all(feature = "replay-store", feature = "std"))]
+impl ReplayStore for MemoryReplayStore {
+    fn load_peer(&self, peer: &PeerId) -> Option<PeerReplaySnapshot> {
+        self.lock_peers().get(peer).cloned()
+    }
+
+    fn save_peer(&self, peer: &PeerId, snapshot: &PeerReplaySnapshot) {
+        self.lock_peers().insert(*peer, snapshot.clone());
+    }
 }
 
 /// Replay protection errors
@@ -373,6 +625,47 @@ mod tests {
         assert!(guard.validate(&peer, 1, now, now).is_ok());
     }
 
+    #[test]
+    fn message_type_skew_override_falls_back_to_global_window() {
+        let guard = ReplayProtection::new().with_message_type_skew(0x04, 5);
+        assert_eq!(guard.max_clock_skew_for(0x04), 5);
+        assert_eq!(guard.max_clock_skew_for(0x02), DEFAULT_MAX_CLOCK_SKEW_SECS);
+
+        assert!(guard.check_timestamp_for_type(994, 1000, 0x04).is_err());
+        assert!(guard.check_timestamp_for_type(995, 1000, 0x04).is_ok());
+        assert!(guard.check_timestamp_for_type(994, 1000, 0x02).is_ok());
+        // The type-agnostic check keeps using the global window.
+        assert!(guard.check_timestamp_only(994, 1000).is_ok());
+    }
+
+    #[test]
+    fn stats_count_accepted_rejected_and_evicted() {
+        let mut guard = ReplayProtection::try_with_tolerance_window(2, 60, 2).unwrap();
+        let (a, b, c) = (make_peer(1), make_peer(2), make_peer(3));
+
+        assert!(guard.validate_sequence(&a, 10).is_ok());
+        assert!(guard.validate_sequence(&a, 11).is_ok());
+        assert!(guard.validate_sequence(&a, 11).is_err()); // duplicate
+        assert!(guard.validate_sequence(&a, 8).is_err()); // beyond window
+        assert!(guard.validate_sequence(&b, 1).is_ok());
+        assert_eq!(
+            guard.stats(),
+            ReplayStats {
+                tracked_peers: 2,
+                total_accepted: 3,
+                total_rejected_replay: 1,
+                total_rejected_too_old: 1,
+                evictions: 0,
+            }
+        );
+
+        // Third peer exceeds capacity 2 and evicts the least recently used (a).
+        assert!(guard.validate_sequence(&c, 1).is_ok());
+        let stats = guard.stats();
+        assert_eq!((stats.tracked_peers, stats.evictions), (2, 1));
+        assert_eq!(stats.total_accepted, 4);
+    }
+
     #[test]
     fn try_with_config_rejects_zero_capacity() {
         let result = ReplayProtection::try_with_config(0, 60);
@@ -765,6 +1058,52 @@ mod tests {
         assert!(guard.validate(&peer, 11, now, now).is_ok());
     }
 
+    /// M-03: Maximum tolerance window accepts deep reordering on high-reorder links.
+    #[test]
+    fn configurable_tolerance_window_large_accepts_deep_reordering() {
+        let window = MAX_SEQUENCE_TOLERANCE_WINDOW;
+        let mut guard = ReplayProtection::try_with_tolerance_window(100, 60, window).unwrap();
+        let peer = make_peer(202);
+        let now = 1000;
+
+        let frontier: u64 = 1000;
+        assert!(guard.validate(&peer, frontier, now, now).is_ok());
+
+        // Deliver the previous 255 sequences in reverse order; all are in-window.
+        let oldest_inside = frontier - window as u64 + 1;
+        for seq in (oldest_inside..frontier).rev() {
+            assert!(
+                guard.validate(&peer, seq, now, now).is_ok(),
+                "seq {seq} must be accepted within window {window}"
+            );
+        }
+
+        // Every delivered sequence is now a duplicate.
+        assert_eq!(
+            guard.validate(&peer, oldest_inside, now, now),
+            Err(ReplayError::Replay {
+                peer,
+                seq: oldest_inside,
+            })
+        );
+        // One past the window is still TooOld.
+        assert_eq!(
+            guard.validate(&peer, oldest_inside - 1, now, now),
+            Err(ReplayError::TooOld {
+                peer,
+                seq: oldest_inside - 1,
+                last_seen: frontier,
+            })
+        );
+
+        // The default window would have rejected the same depth.
+        let mut default_guard = ReplayProtection::new();
+        assert!(default_guard.validate(&peer, frontier, now, now).is_ok());
+        assert!(default_guard
+            .validate(&peer, oldest_inside, now, now)
+            .is_err());
+    }
+
     /// M-03: Tolerance window exceeding maximum is rejected.
     #[test]
     fn tolerance_window_too_large_rejected() {
@@ -780,4 +1119,73 @@ mod tests {
         // Max value should be accepted.
         assert!(ReplayProtection::try_with_tolerance_window(100, 60, 256).is_ok());
     }
+
+    #[cfg(all(feature = "replay-store", feature = "std"))]
+    #[test]
+    fn replay_store_state_survives_restart() {
+        use std::sync::Arc;
+
+        let store = Arc::new(MemoryReplayStore::new());
+        let peer = make_peer(1);
+        let now = 1000;
+
+        {
+            let mut guard = ReplayProtection::new().with_store(Arc::clone(&store));
+            assert!(guard.validate(&peer, 10, now, now).is_ok());
+            assert!(guard.validate(&peer, 12, now, now).is_ok());
+        }
+        assert_eq!(store.len(), 1);
+
+        // "Restart": fresh cache, same store.
+        let mut guard = ReplayProtection::new().with_store(Arc::clone(&store));
+        assert_eq!(guard.cache_size(), 0);
+        assert_eq!(
+            guard.validate(&peer, 12, now, now),
+            Err(ReplayError::Replay { peer, seq: 12 })
+        );
+        assert_eq!(guard.cache_size(), 1);
+
+        // In-window gap is still accepted once, then rejected.
+        assert!(guard.validate(&peer, 11, now, now).is_ok());
+        let mut guard = ReplayProtection::new().with_store(Arc::clone(&store));
+        assert_eq!(
+            guard.validate(&peer, 11, now, now),
+            Err(ReplayError::Replay { peer, seq: 11 })
+        );
+    }
+
+    #[cfg(all(feature = "replay-store", feature = "std"))]
+    #[test]
+    fn replay_store_rehydrates_evicted_peer() {
+        use std::sync::Arc;
+
+        let store = Arc::new(MemoryReplayStore::new());
+        let mut guard = ReplayProtection::try_with_config(1, 60)
+            .unwrap()
+            .with_store(Arc::clone(&store));
+        let (a, b) = (make_peer(1), make_peer(2));
+        let now = 1000;
+
+        assert!(guard.validate(&a, 5, now, now).is_ok());
+        assert!(guard.validate(&b, 5, now, now).is_ok()); // evicts `a` from the LRU
+        assert_eq!(
+            guard.validate(&a, 5, now, now),
+            Err(ReplayError::Replay { peer: a, seq: 5 })
+        );
+    }
+
+    #[cfg(all(feature = "replay-store", feature = "std"))]
+    #[test]
+    fn replay_snapshot_hydration_drops_out_of_window_entries() {
+        let snapshot = PeerReplaySnapshot {
+            last_sequence: 100,
+            recent_sequences: vec![1, 90, 99, 100, 500],
+        };
+        let state = PeerReplayState::from_snapshot(snapshot, 16);
+        assert_eq!(state.last_sequence, 100);
+        assert_eq!(
+            state.recent_sequences.into_iter().collect::<Vec<_>>(),
+            vec![90, 99, 100]
+        );
+    }
 }
@@ -0,0 +1,308 @@
+//! Peer reputation tracking
+//!
+//! Accumulates per-peer outcomes across rounds (accepted updates, signature and replay
+//! rejections, Byzantine flags from robust aggregation) into a score that recovers over
+//! time. Peers whose score falls below a caller-chosen threshold can be dropped before
+//! verification or aggregation.
+//!
+//! ## Scoring
+//!
+//! - Scores start at `0.0`; accepted updates add `accepted_reward` up to `max_score`,
+//!   rejections subtract their penalty.
+//! - Scores decay linearly toward `0.0` at `decay_per_sec` (no `libm` needed in `no_std`),
+//!   so a banned peer is eventually readmitted and old goodwill does not last forever.
+//! - Time is caller-supplied Unix seconds, so decay is deterministic under test.
+
+use alloc::collections::BTreeMap;
+
+use crate::replay::ReplayError;
+use crate::traits::PeerId;
+
+/// Outcome of processing one message or update from a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerOutcome {
+    /// Update verified and accepted by aggregation.
+    Accepted,
+    /// Signature missing or invalid.
+    RejectedSignature,
+    /// Duplicate or stale sequence number.
+    RejectedReplay,
+    /// Flagged as Byzantine by robust aggregation.
+    FlaggedByzantine,
+}
+
+/// Scoring weights and decay rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReputationConfig {
+    /// Score added per accepted update.
+    pub accepted_reward: f32,
+    /// Score subtracted per signature rejection.
+    pub signature_penalty: f32,
+    /// Score subtracted per replay rejection.
+    pub replay_penalty: f32,
+    /// Score subtracted per Byzantine flag.
+    pub byzantine_penalty: f32,
+    /// Upper bound on accumulated goodwill.
+    pub max_score: f32,
+    /// Score units per second by which the score returns toward `0.0`.
+    pub decay_per_sec: f32,
+}
+
+impl Default for ReputationConfig {
+    fn default() -> Self {
+        Self {
+            accepted_reward: 1.0,
+            signature_penalty: 10.0,
+            replay_penalty: 5.0,
+            byzantine_penalty: 20.0,
+            max_score: 10.0,
+            decay_per_sec: 0.01,
+        }
+    }
+}
+
+impl ReputationConfig {
+    fn delta(&self, outcome: PeerOutcome) -> f32 {
+        match outcome {
+            PeerOutcome::Accepted => self.accepted_reward,
+            PeerOutcome::RejectedSignature => -self.signature_penalty,
+            PeerOutcome::RejectedReplay => -self.replay_penalty,
+            PeerOutcome::FlaggedByzantine => -self.byzantine_penalty,
+        }
+    }
+}
+
+/// Outcome counts and current score for one peer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PeerReputation {
+    /// Accepted updates.
+    pub accepted: u64,
+    /// Signature rejections.
+    pub rejected_sig: u64,
+    /// Replay rejections.
+    pub rejected_replay: u64,
+    /// Byzantine flags.
+    pub flagged_byzantine: u64,
+    score: f32,
+    updated_at_secs: u64,
+}
+
+impl PeerReputation {
+    /// Score as of the last update or [`PeerReputation::decay_to`].
+    pub fn score(&self) -> f32 {
+        self.score
+    }
+
+    /// Whether the score is below `threshold` (typically negative).
+    pub fn is_banned(&self, threshold: f32) -> bool {
+        self.score < threshold
+    }
+
+    /// Apply decay for the time elapsed up to `now_secs` (earlier times are ignored).
+    pub fn decay_to(&mut self, now_secs: u64, decay_per_sec: f32) {
+        let elapsed = now_secs.saturating_sub(self.updated_at_secs);
+        self.updated_at_secs = self.updated_at_secs.max(now_secs);
+        let decay = elapsed as f32 * decay_per_sec;
+        self.score = if self.score > 0.0 {
+            (self.score - decay).max(0.0)
+        } else {
+            (self.score + decay).min(0.0)
+        };
+    }
+}
+
+/// Reputation registry keyed by `PeerId`.
+#[derive(Debug, Clone, Default)]
+pub struct ReputationRegistry {
+    config: ReputationConfig,
+    peers: BTreeMap<PeerId, PeerReputation>,
+}
+
+impl ReputationRegistry {
+    /// Create an empty registry.
+    pub fn new(config: ReputationConfig) -> Self {
+        Self {
+            config,
+            peers: BTreeMap::new(),
+        }
+    }
+
+    /// Scoring configuration.
+    pub fn config(&self) -> &ReputationConfig {
+        &self.config
+    }
+
+    /// Record `outcome` for `peer` at `now_secs`, decaying its score first.
+    pub fn record(&mut self, peer: PeerId, outcome: PeerOutcome, now_secs: u64) {
+        let config = self.config;
+        let entry = self.peers.entry(peer).or_insert_with(|| PeerReputation {
+            updated_at_secs: now_secs,
+            ..PeerReputation::default()
+        });
+        entry.decay_to(now_secs, config.decay_per_sec);
+        match outcome {
+            PeerOutcome::Accepted => entry.accepted += 1,
+            PeerOutcome::RejectedSignature => entry.rejected_sig += 1,
+            PeerOutcome::RejectedReplay => entry.rejected_replay += 1,
+            PeerOutcome::FlaggedByzantine => entry.flagged_byzantine += 1,
+        }
+        entry.score = (entry.score + config.delta(outcome)).min(config.max_score);
+    }
+
+    /// Record a replay rejection for the peer named in `err`.
+    ///
+    /// Returns `false` for [`ReplayError::Expired`], which does not identify a peer.
+    pub fn record_replay_error(&mut self, err: &ReplayError, now_secs: u64) -> bool {
+        match err {
+            ReplayError::Replay { peer, .. } | ReplayError::TooOld { peer, .. } => {
+                self.record(*peer, PeerOutcome::RejectedReplay, now_secs);
+                true
+            }
+            ReplayError::Expired { .. } => false,
+        }
+    }
+
+    /// Decay every peer's score up to `now_secs`.
+    pub fn decay_to(&mut self, now_secs: u64) {
+        let decay_per_sec = self.config.decay_per_sec;
+        for reputation in self.peers.values_mut() {
+            reputation.decay_to(now_secs, decay_per_sec);
+        }
+    }
+
+    /// Reputation of `peer`, if any outcome was recorded.
+    pub fn get(&self, peer: &PeerId) -> Option<&PeerReputation> {
+        self.peers.get(peer)
+    }
+
+    /// Whether `peer` is below `threshold`; unknown peers are not banned.
+    pub fn is_banned(&self, peer: &PeerId, threshold: f32) -> bool {
+        self.get(peer)
+            .is_some_and(|reputation| reputation.is_banned(threshold))
+    }
+
+    /// Peers currently below `threshold`.
+    pub fn banned(&self, threshold: f32) -> impl Iterator<Item = &PeerId> + '_ {
+        self.peers
+            .iter()
+            .filter(move |(_, reputation)| reputation.is_banned(threshold))
+            .map(|(peer, _)| peer)
+    }
+
+    /// Every tracked peer with its reputation, in `PeerId` order.
+    pub fn iter(&self) -> impl Iterator<Item = (&PeerId, &PeerReputation)> + '_ {
+        self.peers.iter()
+    }
+
+    /// Number of tracked peers.
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Whether no peer has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BAN: f32 = -25.0;
+
+    fn make_peer(id: u8) -> PeerId {
+        PeerId::new([id; 32])
+    }
+
+    #[test]
+    fn repeated_rejections_drive_score_below_threshold() {
+        let mut registry = ReputationRegistry::default();
+        let bad = make_peer(1);
+        let good = make_peer(2);
+
+        registry.record(bad, PeerOutcome::RejectedSignature, 100);
+        registry.record(bad, PeerOutcome::RejectedReplay, 101);
+        assert!(!registry.is_banned(&bad, BAN));
+        registry.record(bad, PeerOutcome::FlaggedByzantine, 102);
+        assert!(registry.is_banned(&bad, BAN));
+
+        for t in 100..110 {
+            registry.record(good, PeerOutcome::Accepted, t);
+        }
+        assert!(!registry.is_banned(&good, BAN));
+        assert_eq!(registry.banned(BAN).collect::<alloc::vec::Vec<_>>(), [&bad]);
+
+        let rep = registry.get(&bad).unwrap();
+        assert_eq!(
+            (
+                rep.accepted,
+                rep.rejected_sig,
+                rep.rejected_replay,
+                rep.flagged_byzantine
+            ),
+            (0, 1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn decay_restores_banned_peer_over_time() {
+        let config = ReputationConfig {
+            decay_per_sec: 1.0,
+            ..ReputationConfig::default()
+        };
+        let mut registry = ReputationRegistry::new(config);
+        let peer = make_peer(3);
+        for _ in 0..2 {
+            registry.record(peer, PeerOutcome::FlaggedByzantine, 1_000);
+        }
+        assert_eq!(registry.get(&peer).unwrap().score(), -40.0);
+        assert!(registry.is_banned(&peer, BAN));
+
+        registry.decay_to(1_010);
+        assert_eq!(registry.get(&peer).unwrap().score(), -30.0);
+        assert!(registry.is_banned(&peer, BAN));
+
+        registry.decay_to(1_020);
+        assert!(!registry.is_banned(&peer, BAN));
+
+        // Decay stops at neutral rather than overshooting into goodwill.
+        registry.decay_to(10_000);
+        assert_eq!(registry.get(&peer).unwrap().score(), 0.0);
+    }
+
+    #[test]
+    fn goodwill_is_capped_and_decays() {
+        let mut registry = ReputationRegistry::default();
+        let peer = make_peer(4);
+        for _ in 0..50 {
+            registry.record(peer, PeerOutcome::Accepted, 0);
+        }
+        assert_eq!(registry.get(&peer).unwrap().score(), 10.0);
+        registry.decay_to(100);
+        assert_eq!(registry.get(&peer).unwrap().score(), 9.0);
+        // Time going backwards does not undo decay.
+        registry.decay_to(50);
+        assert_eq!(registry.get(&peer).unwrap().score(), 9.0);
+    }
+
+    #[test]
+    fn replay_errors_are_attributed_to_their_peer() {
+        let mut registry = ReputationRegistry::default();
+        let peer = make_peer(5);
+        let mut guard = crate::replay::ReplayProtection::new();
+        guard.validate(&peer, 1, 1_000, 1_000).unwrap();
+        let err = guard.validate(&peer, 1, 1_000, 1_000).unwrap_err();
+
+        assert!(registry.record_replay_error(&err, 1_000));
+        assert_eq!(registry.get(&peer).unwrap().rejected_replay, 1);
+
+        let expired = ReplayError::Expired {
+            ts: 0,
+            now: 1_000,
+            window: 60,
+        };
+        assert!(!registry.record_replay_error(&expired, 1_000));
+        assert_eq!(registry.len(), 1);
+    }
+}
@@ -120,9 +120,134 @@ pub enum CanonValue {
     Object(BTreeMap<String, CanonValue>),
 }
 
+/// Bit pattern every NaN `F64` normalizes to (quiet NaN, no payload).
+pub const CANON_NAN_BITS: u64 = 0x7ff8_0000_0000_0000;
+
+impl CanonValue {
+    /// Copy with floats canonicalized: `-0.0` becomes `0.0` and every NaN becomes
+    /// [`CANON_NAN_BITS`], so logically equal values encode (and hash) identically.
+    pub fn normalized(&self) -> Self {
+        match self {
+            Self::F64(f) if f.is_nan() => Self::F64(f64::from_bits(CANON_NAN_BITS)),
+            Self::F64(f) if *f == 0.0 => Self::F64(0.0),
+            Self::Array(items) => Self::Array(items.iter().map(Self::normalized).collect()),
+            Self::Object(map) => Self::Object(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), v.normalized()))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+}
+
 /// Canonical parameters map (stable ordering via BTreeMap).
 pub type CanonParams = BTreeMap<String, CanonValue>;
 
+/// [`CanonValue::normalized`] applied to every param; all hashes take params through this.
+pub fn normalized_params(params: &CanonParams) -> CanonParams {
+    params
+        .iter()
+        .map(|(k, v)| (k.clone(), v.normalized()))
+        .collect()
+}
+
+/// Failure converting JSON into a [`CanonValue`].
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CanonJsonError {
+    /// JSON text failed to parse (JSON has no `NaN`/`Infinity` literals).
+    Parse(String),
+    /// A number is not a finite `f64`; it would break deterministic hashing.
+    NonFiniteFloat,
+}
+
+#[cfg(feature = "json")]
+impl core::fmt::Display for CanonJsonError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Parse(msg) => write!(f, "invalid JSON: {msg}"),
+            Self::NonFiniteFloat => write!(f, "non-finite float in canonical value"),
+        }
+    }
+}
+
+#[cfg(all(feature = "json", feature = "std"))]
+impl std::error::Error for CanonJsonError {}
+
+#[cfg(feature = "json")]
+impl CanonValue {
+    /// Parse JSON text into a canonical value (see `TryFrom<serde_json::Value>`).
+    pub fn from_json_str(json: &str) -> Result<Self, CanonJsonError> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| CanonJsonError::Parse(e.to_string()))?;
+        Self::try_from(value)
+    }
+}
+
+/// Objects become sorted `Object` maps; integers map to `I64` (or `U64` above
+/// `i64::MAX`) and other numbers to `F64`, so `1` and `1.0` stay distinct.
+#[cfg(feature = "json")]
+impl TryFrom<serde_json::Value> for CanonValue {
+    type Error = CanonJsonError;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        use serde_json::Value;
+
+        Ok(match value {
+            Value::Null => Self::Null,
+            Value::Bool(b) => Self::Bool(b),
+            Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    Self::I64(i)
+                } else if let Some(u) = n.as_u64() {
+                    Self::U64(u)
+                } else {
+                    match n.as_f64() {
+                        Some(f) if f.is_finite() => Self::F64(f),
+                        _ => return Err(CanonJsonError::NonFiniteFloat),
+                    }
+                }
+            }
+            Value::String(s) => Self::Str(s),
+            Value::Array(items) => Self::Array(
+                items
+                    .into_iter()
+                    .map(Self::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+            Value::Object(map) => Self::Object(
+                map.into_iter()
+                    .map(|(k, v)| Ok((k, Self::try_from(v)?)))
+                    .collect::<Result<_, CanonJsonError>>()?,
+            ),
+        })
+    }
+}
+
+/// Non-finite `F64` values (not representable in JSON) become `null`.
+#[cfg(feature = "json")]
+impl From<CanonValue> for serde_json::Value {
+    fn from(value: CanonValue) -> Self {
+        use serde_json::Value;
+
+        match value {
+            CanonValue::Null => Value::Null,
+            CanonValue::Bool(b) => Value::Bool(b),
+            CanonValue::I64(i) => Value::from(i),
+            CanonValue::U64(u) => Value::from(u),
+            CanonValue::F64(f) => {
+                serde_json::Number::from_f64(f).map_or(Value::Null, Value::Number)
+            }
+            CanonValue::Str(s) => Value::String(s),
+            CanonValue::Array(items) => Value::Array(items.into_iter().map(Value::from).collect()),
+            CanonValue::Object(map) => {
+                Value::Object(map.into_iter().map(|(k, v)| (k, Value::from(v))).collect())
+            }
+        }
+    }
+}
+
 /// Reference to an asset (dataset or intermediate).
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct AssetRefV1 {
@@ -133,6 +258,97 @@ pub struct AssetRefV1 {
     pub fingerprint: Option<String>,
 }
 
+/// Asset key schemes accepted by [`AssetRefV1::validate`].
+pub const ASSET_KEY_SCHEMES: &[&str] = &["dataset", "model", "checkpoint"];
+
+/// Malformed asset key (expected `scheme://namespace/name`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetKeyError {
+    /// No `://` separator.
+    MissingScheme,
+    /// Scheme is not one of [`ASSET_KEY_SCHEMES`].
+    UnknownScheme { scheme: String },
+    /// Empty namespace (nothing before the first `/` after the scheme).
+    MissingNamespace,
+    /// Empty name (nothing after the namespace `/`).
+    MissingName,
+    /// Whitespace or control characters in the key.
+    InvalidCharacter,
+    /// Scheme or namespace is not lowercase; `normalized` is the canonical key.
+    NotNormalized { normalized: String },
+}
+
+impl core::fmt::Display for AssetKeyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingScheme => write!(
+                f,
+                "asset key has no scheme (expected scheme://namespace/name)"
+            ),
+            Self::UnknownScheme { scheme } => write!(f, "unknown asset key scheme: {scheme}"),
+            Self::MissingNamespace => write!(f, "asset key has an empty namespace"),
+            Self::MissingName => write!(f, "asset key has an empty name"),
+            Self::InvalidCharacter => {
+                write!(f, "asset key contains whitespace or control characters")
+            }
+            Self::NotNormalized { normalized } => {
+                write!(f, "asset key is not normalized (expected {normalized})")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AssetKeyError {}
+
+/// Canonical form of `asset_key`: scheme and namespace lowercased, name unchanged.
+///
+/// Checks the `scheme://namespace/name` shape and scheme allow-list, but not case.
+pub fn normalize_asset_key(asset_key: &str) -> Result<String, AssetKeyError> {
+    if asset_key
+        .chars()
+        .any(|c| c.is_whitespace() || c.is_control())
+    {
+        return Err(AssetKeyError::InvalidCharacter);
+    }
+    let (scheme, rest) = asset_key
+        .split_once("://")
+        .ok_or(AssetKeyError::MissingScheme)?;
+    let scheme = scheme.to_ascii_lowercase();
+    if !ASSET_KEY_SCHEMES.contains(&scheme.as_str()) {
+        return Err(AssetKeyError::UnknownScheme { scheme });
+    }
+    let (namespace, name) = rest.split_once('/').ok_or(AssetKeyError::MissingName)?;
+    if namespace.is_empty() {
+        return Err(AssetKeyError::MissingNamespace);
+    }
+    if name.is_empty() {
+        return Err(AssetKeyError::MissingName);
+    }
+    Ok(alloc::format!(
+        "{scheme}://{}/{name}",
+        namespace.to_ascii_lowercase()
+    ))
+}
+
+impl AssetRefV1 {
+    /// Check `asset_key` is a canonical `scheme://namespace/name` with an allowed
+    /// scheme, so a typo like `datset://` cannot create a phantom asset.
+    pub fn validate(&self) -> Result<(), AssetKeyError> {
+        let normalized = normalize_asset_key(&self.asset_key)?;
+        if normalized != self.asset_key {
+            return Err(AssetKeyError::NotNormalized { normalized });
+        }
+        Ok(())
+    }
+
+    /// Rewrite `asset_key` into canonical form (see [`normalize_asset_key`]).
+    pub fn normalize(&mut self) -> Result<(), AssetKeyError> {
+        self.asset_key = normalize_asset_key(&self.asset_key)?;
+        Ok(())
+    }
+}
+
 /// A run graph edge (optional; inputs/outputs can imply edges).
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct EdgeV1 {
@@ -245,6 +461,35 @@ struct NodeDefCanonicalV1<'a> {
     // execution_hint is intentionally excluded: it is planner metadata, not op identity (F1).
 }
 
+/// Canonical struct for `node_def_hash_v2`: `NodeDefCanonicalV1` plus `execution_trust`.
+///
+/// Trust changes the safety semantics of a node's outputs, so nodes that differ only
+/// in trust must not share a fingerprint or cache entry.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+struct NodeDefCanonicalV2<'a> {
+    schema_version: u32,
+    op_kind: OpKind,
+    op_type: &'a str,
+    code_ref: &'a str,
+    inputs: &'a [AssetRefV1],
+    outputs: &'a [AssetRefV1],
+    params: &'a CanonParams,
+    execution_trust: ExecutionTrust,
+}
+
+/// Selects which `node_def_hash` derivation feeds downstream hashes.
+///
+/// `V1` (default) keeps existing fingerprints and cache keys reproducible;
+/// `V2` additionally binds `execution_trust`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashVersion {
+    /// [`node_def_hash_v1`].
+    #[default]
+    V1,
+    /// [`node_def_hash_v2`].
+    V2,
+}
+
 /// Canonical struct used for op hash derivation.
 ///
 /// This is narrower than `NodeDefCanonicalV1` by design: it excludes wiring (`inputs`, `outputs`)
@@ -276,9 +521,11 @@ pub fn node_id_from_key(node_key: &str) -> NodeId {
 
 /// Compute `node_def_hash` for a node (canonical binary encoding).
 ///
-/// Excludes runtime/planner metadata such as `execution_hint`.
+/// Excludes runtime/planner metadata such as `execution_hint`. Params are hashed in
+/// [`normalized_params`] form, so `-0.0`/`0.0` and NaN payloads do not split cache keys.
 pub fn node_def_hash_v1(node: &NodeV1) -> Result<[u8; 32], postcard::Error> {
     let code_ref = node.code_ref.as_deref().unwrap_or("");
+    let params = normalized_params(&node.params);
     let canonical = NodeDefCanonicalV1 {
         schema_version: GRAPH_SCHEMA_V1,
         op_kind: node.op_kind,
@@ -286,7 +533,7 @@ pub fn node_def_hash_v1(node: &NodeV1) -> Result<[u8; 32], postcard::Error> {
         code_ref,
         inputs: &node.inputs,
         outputs: &node.outputs,
-        params: &node.params,
+        params: &params,
     };
 
     // Postcard provides a deterministic binary encoding when the input types are deterministic
@@ -298,15 +545,45 @@ pub fn node_def_hash_v1(node: &NodeV1) -> Result<[u8; 32], postcard::Error> {
     Ok(out)
 }
 
+/// Compute `node_def_hash` v2: like [`node_def_hash_v1`], but includes `execution_trust`.
+pub fn node_def_hash_v2(node: &NodeV1) -> Result<[u8; 32], postcard::Error> {
+    let code_ref = node.code_ref.as_deref().unwrap_or("");
+    let params = normalized_params(&node.params);
+    let canonical = NodeDefCanonicalV2 {
+        schema_version: GRAPH_SCHEMA_V1,
+        op_kind: node.op_kind,
+        op_type: &node.op_type,
+        code_ref,
+        inputs: &node.inputs,
+        outputs: &node.outputs,
+        params: &params,
+        execution_trust: node.execution_trust,
+    };
+    let bytes = postcard::to_allocvec(&canonical)?;
+    let digest = Sha256::digest(&bytes);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&digest[..]);
+    Ok(out)
+}
+
+/// Compute `node_def_hash` with the selected derivation.
+pub fn node_def_hash(node: &NodeV1, version: HashVersion) -> Result<[u8; 32], postcard::Error> {
+    match version {
+        HashVersion::V1 => node_def_hash_v1(node),
+        HashVersion::V2 => node_def_hash_v2(node),
+    }
+}
+
 /// Compute canonical op hash from operation definition semantics only.
 pub fn op_hash_v0(node: &NodeV1) -> Result<[u8; 32], postcard::Error> {
     let code_ref = node.code_ref.as_deref().unwrap_or("");
+    let params = normalized_params(&node.params);
     let canonical = NodeOpCanonicalV0 {
         schema_version: GRAPH_SCHEMA_V1,
         op_kind: node.op_kind,
         op_type: &node.op_type,
         code_ref,
-        params: &node.params,
+        params: &params,
     };
     let bytes = postcard::to_allocvec(&canonical)?;
     let digest = Sha256::digest(&bytes);
@@ -315,6 +592,11 @@ pub fn op_hash_v0(node: &NodeV1) -> Result<[u8; 32], postcard::Error> {
     Ok(out)
 }
 
+fn effective_node_id(node: &NodeV1) -> NodeId {
+    node.node_id
+        .unwrap_or_else(|| node_id_from_key(&node.node_key))
+}
+
 fn hex_lower(bytes: &[u8]) -> String {
     const HEX: &[u8; 16] = b"0123456789abcdef";
     let mut out = String::with_capacity(bytes.len() * 2);
@@ -448,6 +730,22 @@ pub enum GraphValidationError {
         to_node_id: NodeId,
         missing_node_id: NodeId,
     },
+    /// Two nodes declare the same output `asset_key` (ambiguous producer).
+    DuplicateOutputAsset {
+        asset_key: String,
+        first_node_key: String,
+        duplicate_node_key: String,
+    },
+    /// A node lists the same `asset_key` as both input and output.
+    OutputIsOwnInput { node_key: String, asset_key: String },
+    /// `op_type` is empty.
+    EmptyOpType { node_key: String },
+    /// An input or output `asset_key` is malformed.
+    InvalidAssetKey {
+        node_key: String,
+        asset_key: String,
+        reason: AssetKeyError,
+    },
 }
 
 impl core::fmt::Display for GraphValidationError {
@@ -473,6 +771,27 @@ impl core::fmt::Display for GraphValidationError {
                 "graph edge references unknown node_id: {} -> {} (missing: {})",
                 from_node_id, to_node_id, missing_node_id
             ),
+            Self::DuplicateOutputAsset {
+                asset_key,
+                first_node_key,
+                duplicate_node_key,
+            } => write!(
+                f,
+                "output asset_key {asset_key} produced by multiple nodes (first node_key: {first_node_key}, duplicate node_key: {duplicate_node_key})"
+            ),
+            Self::OutputIsOwnInput {
+                node_key,
+                asset_key,
+            } => write!(
+                f,
+                "node {node_key} lists asset_key {asset_key} as both input and output"
+            ),
+            Self::EmptyOpType { node_key } => write!(f, "node {node_key} has empty op_type"),
+            Self::InvalidAssetKey {
+                node_key,
+                asset_key,
+                reason,
+            } => write!(f, "node {node_key} has invalid asset_key {asset_key}: {reason}"),
         }
     }
 }
@@ -545,12 +864,536 @@ impl GraphV1 {
             .collect::<Result<Vec<_>, _>>()?;
         Ok(self)
     }
+
+    /// Check structural integrity, collecting every violation instead of failing fast.
+    ///
+    /// Reports everything [`validate_graph_v1`] checks, plus outputs claimed by more
+    /// than one node, outputs that are also the node's own input, and empty `op_type`.
+    /// Errors are returned in `nodes[]` order, followed by edge errors.
+    pub fn validate(&self) -> Result<(), Vec<GraphValidationError>> {
+        let mut errors = Vec::new();
+        let mut seen_keys: BTreeSet<&str> = BTreeSet::new();
+        let mut node_ids: BTreeMap<[u8; 16], &str> = BTreeMap::new();
+        let mut producers: BTreeMap<&str, &str> = BTreeMap::new();
+
+        for node in &self.nodes {
+            if !seen_keys.insert(node.node_key.as_str()) {
+                errors.push(GraphValidationError::DuplicateNodeKey {
+                    node_key: node.node_key.clone(),
+                });
+            }
+
+            let node_id = effective_node_id(node);
+            match node_ids.get(&node_id.0) {
+                // Same key already reported as `DuplicateNodeKey`.
+                Some(first_key) if *first_key != node.node_key => {
+                    errors.push(GraphValidationError::DuplicateNodeId {
+                        node_id: node_id.to_string(),
+                        first_node_key: first_key.to_string(),
+                        duplicate_node_key: node.node_key.clone(),
+                    });
+                }
+                Some(_) => {}
+                None => {
+                    node_ids.insert(node_id.0, node.node_key.as_str());
+                }
+            }
+
+            if node.op_type.is_empty() {
+                errors.push(GraphValidationError::EmptyOpType {
+                    node_key: node.node_key.clone(),
+                });
+            }
+
+            for asset in node.inputs.iter().chain(&node.outputs) {
+                if let Err(reason) = asset.validate() {
+                    errors.push(GraphValidationError::InvalidAssetKey {
+                        node_key: node.node_key.clone(),
+                        asset_key: asset.asset_key.clone(),
+                        reason,
+                    });
+                }
+            }
+
+            for output in &node.outputs {
+                let asset_key = output.asset_key.as_str();
+                if node.inputs.iter().any(|input| input.asset_key == asset_key) {
+                    errors.push(GraphValidationError::OutputIsOwnInput {
+                        node_key: node.node_key.clone(),
+                        asset_key: output.asset_key.clone(),
+                    });
+                }
+                match producers.get(asset_key) {
+                    Some(first_key) if *first_key != node.node_key => {
+                        errors.push(GraphValidationError::DuplicateOutputAsset {
+                            asset_key: output.asset_key.clone(),
+                            first_node_key: first_key.to_string(),
+                            duplicate_node_key: node.node_key.clone(),
+                        });
+                    }
+                    Some(_) => {}
+                    None => {
+                        producers.insert(asset_key, node.node_key.as_str());
+                    }
+                }
+            }
+        }
+
+        for edge in &self.edges {
+            for endpoint in [edge.from_node_id, edge.to_node_id] {
+                if !node_ids.contains_key(&endpoint.0) {
+                    errors.push(GraphValidationError::UnknownEdgeEndpoint {
+                        from_node_id: edge.from_node_id,
+                        to_node_id: edge.to_node_id,
+                        missing_node_id: endpoint,
+                    });
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Deterministic execution order (Kahn's algorithm, `node_key` tie-break).
+    ///
+    /// Uses explicit `edges` when present; otherwise derives producer → consumer edges
+    /// from matching output/input `asset_key`s. Inputs with no producing node are
+    /// treated as external sources; use [`GraphV1::topo_order_with_sources`] to
+    /// reject inputs that are not registered.
+    pub fn topo_order(&self) -> Result<Vec<NodeId>, GraphError> {
+        self.topo_order_inner(None)
+    }
+
+    /// Like [`GraphV1::topo_order`], but every input `asset_key` must be produced by a
+    /// node or listed in `registered_sources` (`GraphError::UnresolvedInput` otherwise).
+    pub fn topo_order_with_sources(
+        &self,
+        registered_sources: &BTreeSet<String>,
+    ) -> Result<Vec<NodeId>, GraphError> {
+        self.topo_order_inner(Some(registered_sources))
+    }
+
+    fn topo_order_inner(
+        &self,
+        registered_sources: Option<&BTreeSet<String>>,
+    ) -> Result<Vec<NodeId>, GraphError> {
+        validate_graph_v1(self).map_err(GraphError::Invalid)?;
+
+        let producers = self.asset_producers();
+        if let Some(sources) = registered_sources {
+            for node in &self.nodes {
+                for input in &node.inputs {
+                    if !producers.contains_key(input.asset_key.as_str())
+                        && !sources.contains(&input.asset_key)
+                    {
+                        return Err(GraphError::UnresolvedInput {
+                            node_id: effective_node_id(node),
+                            asset_key: input.asset_key.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        let edges = self.dependency_edges(&producers);
+
+        let mut keys_by_id: BTreeMap<[u8; 16], &str> = BTreeMap::new();
+        let mut indegree: BTreeMap<[u8; 16], usize> = BTreeMap::new();
+        let mut adjacency: BTreeMap<[u8; 16], Vec<[u8; 16]>> = BTreeMap::new();
+        for node in &self.nodes {
+            let id = effective_node_id(node).0;
+            keys_by_id.insert(id, node.node_key.as_str());
+            indegree.insert(id, 0);
+        }
+        for (from, to) in &edges {
+            adjacency.entry(*from).or_default().push(*to);
+            if let Some(degree) = indegree.get_mut(to) {
+                *degree += 1;
+            }
+        }
+
+        let mut ready: BTreeSet<(&str, [u8; 16])> = indegree
+            .iter()
+            .filter(|(_, degree)| **degree == 0)
+            .filter_map(|(id, _)| keys_by_id.get(id).map(|key| (*key, *id)))
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some((_, id)) = ready.pop_first() {
+            order.push(NodeId::from_bytes(id));
+            for neighbor in adjacency.get(&id).into_iter().flatten() {
+                if let Some(degree) = indegree.get_mut(neighbor) {
+                    *degree = degree.saturating_sub(1);
+                    if *degree == 0 {
+                        if let Some(key) = keys_by_id.get(neighbor) {
+                            ready.insert((*key, *neighbor));
+                        }
+                    }
+                }
+            }
+        }
+
+        if order.len() != self.nodes.len() {
+            let mut remaining: Vec<(&str, [u8; 16])> = indegree
+                .iter()
+                .filter(|(_, degree)| **degree > 0)
+                .filter_map(|(id, _)| keys_by_id.get(id).map(|key| (*key, *id)))
+                .collect();
+            remaining.sort();
+            return Err(GraphError::Cycle {
+                node_ids: remaining
+                    .into_iter()
+                    .map(|(_, id)| NodeId::from_bytes(id))
+                    .collect(),
+            });
+        }
+        Ok(order)
+    }
+
+    /// Transitive consumers of `node_id`, in `node_key` order (excluding `node_id` itself).
+    ///
+    /// Dependencies are the same as [`GraphV1::topo_order`]: explicit `edges`, or
+    /// producer → consumer asset wiring (every producer of an input counts). These
+    /// are the nodes whose materializations go stale when `node_id`'s
+    /// `node_def_hash` changes. Unknown ids have no consumers.
+    pub fn downstream_of(&self, node_id: NodeId) -> Vec<NodeId> {
+        let producers = self.asset_producers();
+        let mut adjacency: BTreeMap<[u8; 16], Vec<[u8; 16]>> = BTreeMap::new();
+        for (from, to) in self.dependency_edges(&producers) {
+            adjacency.entry(from).or_default().push(to);
+        }
+
+        let mut seen: BTreeSet<[u8; 16]> = BTreeSet::new();
+        let mut stack = Vec::from([node_id.0]);
+        while let Some(id) = stack.pop() {
+            for next in adjacency.get(&id).into_iter().flatten() {
+                if *next != node_id.0 && seen.insert(*next) {
+                    stack.push(*next);
+                }
+            }
+        }
+
+        let mut downstream: Vec<(&str, [u8; 16])> = self
+            .nodes
+            .iter()
+            .map(|node| (node.node_key.as_str(), effective_node_id(node).0))
+            .filter(|(_, id)| seen.remove(id))
+            .collect();
+        downstream.sort();
+        downstream
+            .into_iter()
+            .map(|(_, id)| NodeId::from_bytes(id))
+            .collect()
+    }
+
+    /// `asset_key` -> ids of every node listing it in `outputs`.
+    fn asset_producers(&self) -> BTreeMap<&str, Vec<NodeId>> {
+        let mut producers: BTreeMap<&str, Vec<NodeId>> = BTreeMap::new();
+        for node in &self.nodes {
+            for output in &node.outputs {
+                producers
+                    .entry(output.asset_key.as_str())
+                    .or_default()
+                    .push(effective_node_id(node));
+            }
+        }
+        producers
+    }
+
+    /// Explicit `edges` when present, otherwise producer → consumer asset wiring.
+    fn dependency_edges(
+        &self,
+        producers: &BTreeMap<&str, Vec<NodeId>>,
+    ) -> BTreeSet<([u8; 16], [u8; 16])> {
+        let mut edges: BTreeSet<([u8; 16], [u8; 16])> = BTreeSet::new();
+        if self.edges.is_empty() {
+            for node in &self.nodes {
+                let to = effective_node_id(node).0;
+                for input in &node.inputs {
+                    for from in producers
+                        .get(input.asset_key.as_str())
+                        .into_iter()
+                        .flatten()
+                    {
+                        edges.insert((from.0, to));
+                    }
+                }
+            }
+        } else {
+            // Duplicate edges count once.
+            edges.extend(
+                self.edges
+                    .iter()
+                    .map(|edge| (edge.from_node_id.0, edge.to_node_id.0)),
+            );
+        }
+        edges
+    }
+}
+
+/// Error type for [`GraphV1::topo_order`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    /// Graph-scope invariant violated.
+    Invalid(GraphValidationError),
+    /// Nodes that could not be ordered (on or downstream of a cycle), in `node_key` order.
+    Cycle { node_ids: Vec<NodeId> },
+    /// Input `asset_key` with no producing node and no registered source.
+    UnresolvedInput { node_id: NodeId, asset_key: String },
+}
+
+impl core::fmt::Display for GraphError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Invalid(error) => write!(f, "invalid graph: {error}"),
+            Self::Cycle { node_ids } => {
+                write!(f, "graph contains cycle(s) through node_ids:")?;
+                for node_id in node_ids {
+                    write!(f, " {node_id}")?;
+                }
+                Ok(())
+            }
+            Self::UnresolvedInput { node_id, asset_key } => {
+                write!(
+                    f,
+                    "node {node_id} input {asset_key} has no producer or registered source"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GraphError {}
+
+/// An `execution_trust` reclassification reported by [`graph_diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustChange {
+    pub node_key: String,
+    pub old: ExecutionTrust,
+    pub new: ExecutionTrust,
+}
+
+/// Node-level difference between two graphs, keyed by `node_key` (see [`graph_diff`]).
+///
+/// All lists are sorted by `node_key`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphDiff {
+    /// Present only in the new graph.
+    pub added: Vec<String>,
+    /// Present only in the old graph.
+    pub removed: Vec<String>,
+    /// Present in both with a different `node_def_hash` (invalidates cached outputs).
+    pub modified: Vec<String>,
+    /// Present in both with a different `execution_trust`.
+    pub trust_changed: Vec<TrustChange>,
+}
+
+impl GraphDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && self.trust_changed.is_empty()
+    }
+}
+
+/// Compare two graphs node by node.
+///
+/// `node_def_hash` is recomputed with [`node_def_hash_v1`] rather than trusted from
+/// the stored field, so unnormalized graphs diff the same as normalized ones and
+/// `nodes[]` order is irrelevant. Trust is reported separately because the v1 hash
+/// does not cover it. With duplicate `node_key`s the last node wins.
+pub fn graph_diff(old: &GraphV1, new: &GraphV1) -> Result<GraphDiff, postcard::Error> {
+    let index = |graph: &GraphV1| -> Result<BTreeMap<String, (ExecutionTrust, [u8; 32])>, _> {
+        graph
+            .nodes
+            .iter()
+            .map(|node| {
+                node_def_hash_v1(node)
+                    .map(|hash| (node.node_key.clone(), (node.execution_trust, hash)))
+            })
+            .collect()
+    };
+    let old_nodes = index(old)?;
+    let new_nodes = index(new)?;
+
+    let mut diff = GraphDiff::default();
+    for (node_key, (old_trust, old_hash)) in &old_nodes {
+        let Some((new_trust, new_hash)) = new_nodes.get(node_key) else {
+            diff.removed.push(node_key.clone());
+            continue;
+        };
+        if old_hash != new_hash {
+            diff.modified.push(node_key.clone());
+        }
+        if old_trust != new_trust {
+            diff.trust_changed.push(TrustChange {
+                node_key: node_key.clone(),
+                old: *old_trust,
+                new: *new_trust,
+            });
+        }
+    }
+    diff.added = new_nodes
+        .keys()
+        .filter(|node_key| !old_nodes.contains_key(*node_key))
+        .cloned()
+        .collect();
+    Ok(diff)
+}
+
+/// Canonical struct used for whole-run plan hashing.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+struct RunPlanCanonicalV0<'a> {
+    schema_version: u32,
+    /// `node_def_hash` of every node, in deterministic topological order.
+    node_def_hashes: &'a [[u8; 32]],
+    /// External input `asset_key -> fingerprint` (BTreeMap: sorted, deterministic).
+    input_fingerprints: &'a BTreeMap<String, String>,
+}
+
+/// Error type for [`run_plan_hash`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunPlanHashError {
+    /// Graph-scope invariant violated.
+    Graph(GraphValidationError),
+    /// Edges form a cycle; sorted `node_key`s of the nodes that could not be ordered.
+    CycleDetected { node_keys: Vec<String> },
+    /// Input `asset_key` with no producing node and no registered source.
+    UnresolvedInput { node_id: NodeId, asset_key: String },
+    /// Canonical encoding failed.
+    Encoding,
+}
+
+impl core::fmt::Display for RunPlanHashError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Graph(error) => write!(f, "invalid graph: {error}"),
+            Self::CycleDetected { node_keys } => {
+                write!(f, "graph contains cycle(s): {}", node_keys.join(","))
+            }
+            Self::UnresolvedInput { node_id, asset_key } => {
+                write!(
+                    f,
+                    "node {node_id} input {asset_key} has no producer or registered source"
+                )
+            }
+            Self::Encoding => write!(f, "run plan canonical encoding failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RunPlanHashError {}
+
+/// Compute a whole-run cache key: the graph's `node_def_hash`es in execution order plus
+/// the external input fingerprints.
+///
+/// Execution order is [`GraphV1::topo_order_with_sources`] with the `input_fingerprints`
+/// keys as registered sources (explicit `edges`, or edges derived from asset wiring when
+/// none are declared; `node_key` tie-break), so every input must be produced by a node
+/// or fingerprinted. `node_def_hash` is recomputed from each node rather than trusted
+/// from the stored field. Two runs with identical graph definitions and inputs share the
+/// hash.
+pub fn run_plan_hash(
+    graph: &GraphV1,
+    input_fingerprints: &BTreeMap<String, String>,
+) -> Result<[u8; 32], RunPlanHashError> {
+    let sources: BTreeSet<String> = input_fingerprints.keys().cloned().collect();
+    let order = graph
+        .topo_order_with_sources(&sources)
+        .map_err(|error| match error {
+            GraphError::Invalid(error) => RunPlanHashError::Graph(error),
+            GraphError::Cycle { node_ids } => {
+                let mut node_keys: Vec<String> = graph
+                    .nodes
+                    .iter()
+                    .filter(|node| node_ids.contains(&effective_node_id(node)))
+                    .map(|node| node.node_key.clone())
+                    .collect();
+                node_keys.sort();
+                RunPlanHashError::CycleDetected { node_keys }
+            }
+            GraphError::UnresolvedInput { node_id, asset_key } => {
+                RunPlanHashError::UnresolvedInput { node_id, asset_key }
+            }
+        })?;
+
+    let nodes_by_id: BTreeMap<[u8; 16], &NodeV1> = graph
+        .nodes
+        .iter()
+        .map(|node| (effective_node_id(node).0, node))
+        .collect();
+    let mut node_def_hashes = Vec::with_capacity(order.len());
+    for id in &order {
+        let Some(node) = nodes_by_id.get(&id.0) else {
+            continue;
+        };
+        node_def_hashes.push(node_def_hash_v1(node).map_err(|_| RunPlanHashError::Encoding)?);
+    }
+
+    let canonical = RunPlanCanonicalV0 {
+        schema_version: GRAPH_SCHEMA_V1,
+        node_def_hashes: &node_def_hashes,
+        input_fingerprints,
+    };
+    let bytes = postcard::to_allocvec(&canonical).map_err(|_| RunPlanHashError::Encoding)?;
+    let digest = Sha256::digest(&bytes);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&digest[..]);
+    Ok(out)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "json")]
+    #[test]
+    fn canon_value_round_trips_nested_json_with_sorted_keys() {
+        let json = r#"{"zeta": {"b": [1, 2.0, -3], "a": null}, "alpha": true, "big": 18446744073709551615}"#;
+        let value = CanonValue::from_json_str(json).unwrap();
+
+        let CanonValue::Object(map) = &value else {
+            panic!("expected object");
+        };
+        let keys: Vec<&str> = map.keys().map(String::as_str).collect();
+        assert_eq!(keys, ["alpha", "big", "zeta"]);
+        assert_eq!(map["big"], CanonValue::U64(u64::MAX));
+        let CanonValue::Object(zeta) = &map["zeta"] else {
+            panic!("expected nested object");
+        };
+        assert_eq!(
+            zeta["b"],
+            CanonValue::Array(vec![
+                CanonValue::I64(1),
+                CanonValue::F64(2.0),
+                CanonValue::I64(-3)
+            ])
+        );
+
+        let back = serde_json::Value::from(value.clone());
+        assert_eq!(
+            back.to_string(),
+            r#"{"alpha":true,"big":18446744073709551615,"zeta":{"a":null,"b":[1,2.0,-3]}}"#
+        );
+        assert_eq!(CanonValue::try_from(back).unwrap(), value);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn canon_value_rejects_non_finite_json() {
+        assert!(matches!(
+            CanonValue::from_json_str(r#"{"lr": NaN}"#),
+            Err(CanonJsonError::Parse(_))
+        ));
+        assert!(CanonValue::from_json_str("[1e400]").is_err());
+    }
+
     #[test]
     fn node_id_is_stable_for_key() {
         let a = node_id_from_key("prep/clean_users").to_string();
@@ -598,6 +1441,32 @@ mod tests {
         assert_ne!(h1, h2);
     }
 
+    #[test]
+    fn node_def_hash_ignores_zero_sign_and_nan_payload() {
+        let with_param = |value: f64| {
+            let mut node = make_valid_node();
+            let mut nested = BTreeMap::new();
+            nested.insert("eps".to_string(), CanonValue::F64(value));
+            node.params
+                .insert("opts".to_string(), CanonValue::Object(nested));
+            node
+        };
+        let (pos, neg) = (with_param(0.0), with_param(-0.0));
+        assert_eq!(node_def_hash_v1(&pos), node_def_hash_v1(&neg));
+        assert_eq!(node_def_hash_v2(&pos), node_def_hash_v2(&neg));
+        assert_eq!(op_hash_v0(&pos), op_hash_v0(&neg));
+
+        let quiet = with_param(f64::NAN);
+        let payload = with_param(f64::from_bits(0xfff0_0000_0000_0001));
+        assert_eq!(node_def_hash_v1(&quiet), node_def_hash_v1(&payload));
+        assert_ne!(node_def_hash_v1(&quiet), node_def_hash_v1(&pos));
+        // Params themselves are stored as authored.
+        let CanonValue::Object(opts) = &neg.params["opts"] else {
+            panic!("expected object");
+        };
+        assert!(matches!(opts["eps"], CanonValue::F64(f) if f.is_sign_negative()));
+    }
+
     // ── M-09 validation tests ──
 
     fn make_valid_node() -> NodeV1 {
@@ -670,6 +1539,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn node_def_hash_v2_binds_execution_trust() {
+        let core = make_valid_node();
+        let mut unsafe_ext = core.clone();
+        unsafe_ext.execution_trust = ExecutionTrust::UnsafeExtension;
+
+        assert_eq!(
+            node_def_hash_v1(&core).unwrap(),
+            node_def_hash_v1(&unsafe_ext).unwrap(),
+            "v1 intentionally ignores execution_trust"
+        );
+        assert_ne!(
+            node_def_hash_v2(&core).unwrap(),
+            node_def_hash_v2(&unsafe_ext).unwrap()
+        );
+        assert_ne!(
+            node_def_hash_v1(&core).unwrap(),
+            node_def_hash_v2(&core).unwrap()
+        );
+        assert_eq!(
+            node_def_hash(&core, HashVersion::default()).unwrap(),
+            node_def_hash_v1(&core).unwrap()
+        );
+        assert_eq!(
+            node_def_hash(&core, HashVersion::V2).unwrap(),
+            node_def_hash_v2(&core).unwrap()
+        );
+    }
+
     #[test]
     fn execution_hint_excluded_from_node_def_hash() {
         let mut a = make_valid_node();
@@ -872,4 +1770,451 @@ mod tests {
             GraphValidationError::UnknownEdgeEndpoint { .. }
         ));
     }
+
+    fn plan_graph() -> (GraphV1, BTreeMap<String, String>) {
+        let mut ingest = make_valid_node();
+        ingest.node_key = "ingest/raw".to_string();
+        ingest.outputs = vec![AssetRefV1 {
+            asset_key: "dataset://ns/raw".to_string(),
+            fingerprint: None,
+        }];
+        let mut clean = make_valid_node();
+        clean.node_key = "prep/clean".to_string();
+        clean.inputs = ingest.outputs.clone();
+        clean.outputs = vec![AssetRefV1 {
+            asset_key: "dataset://ns/clean".to_string(),
+            fingerprint: None,
+        }];
+
+        let graph = GraphV1 {
+            schema_version: GRAPH_SCHEMA_V1,
+            graph_id: Some("plan".to_string()),
+            edges: vec![EdgeV1 {
+                from_node_id: node_id_from_key(&ingest.node_key),
+                to_node_id: node_id_from_key(&clean.node_key),
+                asset_key: Some("dataset://ns/raw".to_string()),
+            }],
+            // Declared out of order: the hash must follow execution order.
+            nodes: vec![clean, ingest],
+        };
+        let mut inputs = BTreeMap::new();
+        inputs.insert("s3://bucket/raw.parquet".to_string(), "a".repeat(64));
+        (graph, inputs)
+    }
+
+    #[test]
+    fn run_plan_hash_is_stable_and_sensitive_to_params_and_inputs() {
+        let (graph, inputs) = plan_graph();
+        let h1 = run_plan_hash(&graph, &inputs).unwrap();
+        let h2 = run_plan_hash(&graph.clone().normalize().unwrap(), &inputs).unwrap();
+        assert_eq!(
+            h1, h2,
+            "stored derived fields must not affect the plan hash"
+        );
+
+        let mut changed_params = graph.clone();
+        changed_params.nodes[0]
+            .params
+            .insert("null_policy".to_string(), CanonValue::Str("drop".into()));
+        assert_ne!(run_plan_hash(&changed_params, &inputs).unwrap(), h1);
+
+        let mut changed_inputs = inputs.clone();
+        changed_inputs.insert("s3://bucket/raw.parquet".to_string(), "b".repeat(64));
+        assert_ne!(run_plan_hash(&graph, &changed_inputs).unwrap(), h1);
+    }
+
+    #[test]
+    fn run_plan_hash_rejects_inputs_without_producer_or_fingerprint() {
+        let (mut graph, inputs) = plan_graph();
+        graph.nodes[0].inputs.push(AssetRefV1 {
+            asset_key: "dataset://ns/missing".to_string(),
+            fingerprint: None,
+        });
+        let err = run_plan_hash(&graph, &inputs).unwrap_err();
+        assert_eq!(
+            err,
+            RunPlanHashError::UnresolvedInput {
+                node_id: node_id_from_key("prep/clean"),
+                asset_key: "dataset://ns/missing".to_string(),
+            }
+        );
+
+        let mut with_source = inputs.clone();
+        with_source.insert("dataset://ns/missing".to_string(), "c".repeat(64));
+        assert!(run_plan_hash(&graph, &with_source).is_ok());
+    }
+
+    #[test]
+    fn run_plan_hash_rejects_cycles() {
+        let (mut graph, inputs) = plan_graph();
+        graph.edges.push(EdgeV1 {
+            from_node_id: node_id_from_key("prep/clean"),
+            to_node_id: node_id_from_key("ingest/raw"),
+            asset_key: None,
+        });
+        let err = run_plan_hash(&graph, &inputs).unwrap_err();
+        assert_eq!(
+            err,
+            RunPlanHashError::CycleDetected {
+                node_keys: vec!["ingest/raw".to_string(), "prep/clean".to_string()]
+            }
+        );
+    }
+
+    // ── topo_order tests ──
+
+    /// Bare names become `dataset://ns/<name>`; keys with a scheme are kept.
+    fn wired_node(key: &str, inputs: &[&str], outputs: &[&str]) -> NodeV1 {
+        let asset = |name: &&str| AssetRefV1 {
+            asset_key: if name.contains("://") {
+                name.to_string()
+            } else {
+                format!("dataset://ns/{name}")
+            },
+            fingerprint: None,
+        };
+        let mut node = make_valid_node();
+        node.node_key = key.to_string();
+        node.inputs = inputs.iter().map(asset).collect();
+        node.outputs = outputs.iter().map(asset).collect();
+        node
+    }
+
+    fn wired_graph(nodes: Vec<NodeV1>) -> GraphV1 {
+        GraphV1 {
+            schema_version: GRAPH_SCHEMA_V1,
+            graph_id: None,
+            nodes,
+            edges: vec![],
+        }
+    }
+
+    fn ids(keys: &[&str]) -> Vec<NodeId> {
+        keys.iter().map(|key| node_id_from_key(key)).collect()
+    }
+
+    #[test]
+    fn topo_order_linear_chain_from_asset_wiring() {
+        let graph = wired_graph(vec![
+            wired_node("c/train", &["b"], &["c"]),
+            wired_node("a/ingest", &["src"], &["a"]),
+            wired_node("b/clean", &["a"], &["b"]),
+        ]);
+        assert_eq!(
+            graph.topo_order().unwrap(),
+            ids(&["a/ingest", "b/clean", "c/train"])
+        );
+    }
+
+    #[test]
+    fn topo_order_diamond_uses_node_key_tie_break() {
+        let graph = wired_graph(vec![
+            wired_node("d/join", &["left", "right"], &["out"]),
+            wired_node("c/right", &["root"], &["right"]),
+            wired_node("b/left", &["root"], &["left"]),
+            wired_node("a/root", &[], &["root"]),
+        ]);
+        assert_eq!(
+            graph.topo_order().unwrap(),
+            ids(&["a/root", "b/left", "c/right", "d/join"])
+        );
+    }
+
+    #[test]
+    fn topo_order_rejects_self_cycle() {
+        let graph = wired_graph(vec![
+            wired_node("a/ok", &[], &["x"]),
+            wired_node("b/loop", &["x", "y"], &["y"]),
+        ]);
+        assert_eq!(
+            graph.topo_order(),
+            Err(GraphError::Cycle {
+                node_ids: ids(&["b/loop"])
+            })
+        );
+    }
+
+    #[test]
+    fn topo_order_rejects_multi_node_cycle() {
+        let graph = wired_graph(vec![
+            wired_node("c/third", &["b"], &["c"]),
+            wired_node("a/first", &["c"], &["a"]),
+            wired_node("b/second", &["a"], &["b"]),
+            wired_node("z/independent", &[], &["z"]),
+        ]);
+        assert_eq!(
+            graph.topo_order(),
+            Err(GraphError::Cycle {
+                node_ids: ids(&["a/first", "b/second", "c/third"])
+            })
+        );
+    }
+
+    #[test]
+    fn topo_order_with_sources_rejects_unresolved_inputs() {
+        let graph = wired_graph(vec![
+            wired_node("a/ingest", &["s3://raw"], &["a"]),
+            wired_node("b/clean", &["a", "dataset://missing"], &["b"]),
+        ]);
+        // Without a source registry, unproduced inputs are assumed external.
+        assert!(graph.topo_order().is_ok());
+
+        let mut sources = BTreeSet::new();
+        sources.insert("s3://raw".to_string());
+        assert_eq!(
+            graph.topo_order_with_sources(&sources),
+            Err(GraphError::UnresolvedInput {
+                node_id: node_id_from_key("b/clean"),
+                asset_key: "dataset://missing".to_string(),
+            })
+        );
+
+        sources.insert("dataset://missing".to_string());
+        assert_eq!(
+            graph.topo_order_with_sources(&sources).unwrap(),
+            ids(&["a/ingest", "b/clean"])
+        );
+    }
+
+    #[test]
+    fn topo_order_prefers_explicit_edges() {
+        let (graph, _) = plan_graph();
+        assert_eq!(
+            graph.topo_order().unwrap(),
+            ids(&["ingest/raw", "prep/clean"])
+        );
+    }
+
+    // ── GraphV1::validate tests ──
+
+    #[test]
+    fn validate_accepts_clean_graph() {
+        let (graph, _) = plan_graph();
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_reports_duplicate_node_key() {
+        let graph = wired_graph(vec![
+            wired_node("a/ingest", &[], &["a"]),
+            wired_node("a/ingest", &[], &["b"]),
+        ]);
+        assert_eq!(
+            graph.validate(),
+            Err(vec![GraphValidationError::DuplicateNodeKey {
+                node_key: "a/ingest".to_string()
+            }])
+        );
+    }
+
+    #[test]
+    fn validate_reports_ambiguous_producer() {
+        let graph = wired_graph(vec![
+            wired_node("a/first", &[], &["shared"]),
+            wired_node("b/second", &[], &["shared"]),
+        ]);
+        assert_eq!(
+            graph.validate(),
+            Err(vec![GraphValidationError::DuplicateOutputAsset {
+                asset_key: "dataset://ns/shared".to_string(),
+                first_node_key: "a/first".to_string(),
+                duplicate_node_key: "b/second".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn validate_reports_output_equal_to_own_input() {
+        let graph = wired_graph(vec![wired_node("a/loop", &["x"], &["x"])]);
+        assert_eq!(
+            graph.validate(),
+            Err(vec![GraphValidationError::OutputIsOwnInput {
+                node_key: "a/loop".to_string(),
+                asset_key: "dataset://ns/x".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn validate_reports_empty_op_type() {
+        let mut node = wired_node("a/blank", &[], &["a"]);
+        node.op_type.clear();
+        assert_eq!(
+            wired_graph(vec![node]).validate(),
+            Err(vec![GraphValidationError::EmptyOpType {
+                node_key: "a/blank".to_string()
+            }])
+        );
+    }
+
+    #[test]
+    fn validate_collects_all_errors_in_one_pass() {
+        let mut blank = wired_node("c/blank", &[], &["c"]);
+        blank.op_type.clear();
+        let graph = wired_graph(vec![
+            wired_node("a/first", &[], &["shared"]),
+            wired_node("b/loop", &["y"], &["shared", "y"]),
+            blank,
+            wired_node("a/first", &[], &["z"]),
+        ]);
+        let errors = graph.validate().unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                GraphValidationError::DuplicateOutputAsset {
+                    asset_key: "dataset://ns/shared".to_string(),
+                    first_node_key: "a/first".to_string(),
+                    duplicate_node_key: "b/loop".to_string(),
+                },
+                GraphValidationError::OutputIsOwnInput {
+                    node_key: "b/loop".to_string(),
+                    asset_key: "dataset://ns/y".to_string(),
+                },
+                GraphValidationError::EmptyOpType {
+                    node_key: "c/blank".to_string()
+                },
+                GraphValidationError::DuplicateNodeKey {
+                    node_key: "a/first".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_reports_malformed_asset_key() {
+        let mut node = wired_node("a/typo", &[], &["a"]);
+        node.outputs[0].asset_key = "datset://ns/a".to_string();
+        assert_eq!(
+            wired_graph(vec![node]).validate(),
+            Err(vec![GraphValidationError::InvalidAssetKey {
+                node_key: "a/typo".to_string(),
+                asset_key: "datset://ns/a".to_string(),
+                reason: AssetKeyError::UnknownScheme {
+                    scheme: "datset".to_string()
+                },
+            }])
+        );
+
+        for (key, reason) in [
+            ("ns/a", AssetKeyError::MissingScheme),
+            ("model://ns", AssetKeyError::MissingName),
+            ("model://ns/", AssetKeyError::MissingName),
+            ("model:///a", AssetKeyError::MissingNamespace),
+            ("model://ns/a b", AssetKeyError::InvalidCharacter),
+        ] {
+            let asset = AssetRefV1 {
+                asset_key: key.to_string(),
+                fingerprint: None,
+            };
+            assert_eq!(asset.validate(), Err(reason), "{key}");
+        }
+    }
+
+    #[test]
+    fn normalize_lowercases_scheme_and_namespace_only() {
+        let mut asset = AssetRefV1 {
+            asset_key: "Dataset://NS/x".to_string(),
+            fingerprint: None,
+        };
+        assert_eq!(
+            asset.validate(),
+            Err(AssetKeyError::NotNormalized {
+                normalized: "dataset://ns/x".to_string()
+            })
+        );
+        asset.normalize().unwrap();
+        assert_eq!(asset.asset_key, "dataset://ns/x");
+        assert_eq!(asset.validate(), Ok(()));
+        assert_eq!(
+            normalize_asset_key("CHECKPOINT://Run7/Epoch/Best").unwrap(),
+            "checkpoint://run7/Epoch/Best"
+        );
+    }
+
+    // ── downstream_of tests ──
+
+    #[test]
+    fn downstream_of_follows_chain_and_skips_sibling_branch() {
+        let graph = wired_graph(vec![
+            wired_node("a/ingest", &[], &["raw"]),
+            wired_node("b/clean", &["raw"], &["clean"]),
+            wired_node("c/train", &["clean"], &["model"]),
+            wired_node("s/other", &[], &["side"]),
+            wired_node("t/side", &["side"], &["side_out"]),
+        ]);
+
+        assert_eq!(
+            graph.downstream_of(node_id_from_key("a/ingest")),
+            ids(&["b/clean", "c/train"])
+        );
+        assert_eq!(
+            graph.downstream_of(node_id_from_key("b/clean")),
+            ids(&["c/train"])
+        );
+        assert!(graph.downstream_of(node_id_from_key("c/train")).is_empty());
+        assert!(graph.downstream_of(node_id_from_key("missing")).is_empty());
+    }
+
+    #[test]
+    fn downstream_of_handles_multiple_producers() {
+        let graph = wired_graph(vec![
+            wired_node("a/left", &[], &["l"]),
+            wired_node("b/right", &[], &["r"]),
+            wired_node("c/join", &["l", "r"], &["joined"]),
+            wired_node("d/report", &["joined", "r"], &["report"]),
+        ]);
+
+        let expected = ids(&["c/join", "d/report"]);
+        assert_eq!(graph.downstream_of(node_id_from_key("a/left")), expected);
+        assert_eq!(graph.downstream_of(node_id_from_key("b/right")), expected);
+    }
+
+    // ── graph_diff tests ──
+
+    #[test]
+    fn graph_diff_reports_added_removed_modified_and_trust() {
+        let old = wired_graph(vec![
+            wired_node("a/ingest", &[], &["raw"]),
+            wired_node("b/clean", &["raw"], &["clean"]),
+            wired_node("c/drop", &["raw"], &["dropped"]),
+        ]);
+
+        let mut clean = wired_node("b/clean", &["raw"], &["clean"]);
+        clean
+            .params
+            .insert("threshold".to_string(), CanonValue::U64(3));
+        let mut ingest = wired_node("a/ingest", &[], &["raw"]);
+        ingest.execution_trust = ExecutionTrust::SandboxedExtension;
+        let new = wired_graph(vec![
+            ingest,
+            clean,
+            wired_node("d/train", &["clean"], &["model"]),
+        ]);
+
+        let diff = graph_diff(&old, &new).unwrap();
+        assert_eq!(diff.added, vec!["d/train".to_string()]);
+        assert_eq!(diff.removed, vec!["c/drop".to_string()]);
+        assert_eq!(diff.modified, vec!["b/clean".to_string()]);
+        assert_eq!(
+            diff.trust_changed,
+            vec![TrustChange {
+                node_key: "a/ingest".to_string(),
+                old: ExecutionTrust::Core,
+                new: ExecutionTrust::SandboxedExtension,
+            }]
+        );
+    }
+
+    #[test]
+    fn graph_diff_ignores_node_order_and_normalization() {
+        let old = wired_graph(vec![
+            wired_node("a/ingest", &[], &["raw"]),
+            wired_node("b/clean", &["raw"], &["clean"]),
+        ]);
+        let mut new = old.clone().normalize().unwrap();
+        new.nodes.reverse();
+
+        let diff = graph_diff(&old, &new).unwrap();
+        assert!(diff.is_empty(), "unexpected diff: {diff:?}");
+    }
 }
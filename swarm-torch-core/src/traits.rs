@@ -48,6 +48,314 @@ pub struct GradientUpdate {
     pub gradients: Vec<f32>,
     /// Round this update belongs to
     pub round_id: u64,
+    /// How `gradients` is encoded.
+    ///
+    /// Trailing on the postcard wire and absent from pre-delta peers' encodings;
+    /// decode with [`GradientUpdate::from_postcard_bytes`] to accept both.
+    pub encoding: UpdateEncoding,
+}
+
+/// Wire encoding of [`GradientUpdate::gradients`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "alloc", derive(serde::Serialize, serde::Deserialize))]
+pub enum UpdateEncoding {
+    /// Absolute gradient values
+    #[default]
+    Full,
+    /// Bitwise XOR against the sender's update with sequence `base_sequence`.
+    ///
+    /// Elements are not gradient values until [`GradientUpdate::apply_delta`]
+    /// reconstructs them; never validate or aggregate a delta directly.
+    Delta { base_sequence: u64 },
+}
+
+/// Failure to reconstruct a delta-encoded [`GradientUpdate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeltaError {
+    /// No cached update for the sender; request a full send
+    MissingBaseline { base_sequence: u64 },
+    /// Cached baseline is a different update than the delta was taken against
+    BaselineMismatch { expected: u64, found: u64 },
+    /// Baseline and delta lengths differ
+    DimensionMismatch { expected: usize, got: usize },
+}
+
+impl core::fmt::Display for DeltaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DeltaError::MissingBaseline { base_sequence } => {
+                write!(
+                    f,
+                    "no baseline update for delta (base sequence {base_sequence})"
+                )
+            }
+            DeltaError::BaselineMismatch { expected, found } => write!(
+                f,
+                "delta baseline mismatch: expected sequence {expected}, found {found}"
+            ),
+            DeltaError::DimensionMismatch { expected, got } => write!(
+                f,
+                "delta dimension mismatch: baseline has {expected} elements, delta has {got}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DeltaError {}
+
+/// Magic prefix of [`GradientUpdate::to_canonical_bytes`].
+pub const CANONICAL_UPDATE_MAGIC: [u8; 4] = *b"STGU";
+/// Layout version of [`GradientUpdate::to_canonical_bytes`].
+pub const CANONICAL_UPDATE_VERSION: u8 = 1;
+/// Bit pattern every NaN gradient is written as (quiet NaN, no payload).
+pub const CANONICAL_NAN_BITS: u32 = 0x7fc0_0000;
+
+/// Failure to decode [`GradientUpdate::from_canonical_bytes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CanonicalDecodeError {
+    /// Input ended before the declared layout
+    Truncated,
+    /// Input does not start with [`CANONICAL_UPDATE_MAGIC`]
+    BadMagic,
+    /// Layout version this build cannot read
+    UnsupportedVersion(u8),
+    /// Unknown [`UpdateEncoding`] tag
+    UnknownEncoding(u8),
+    /// Bytes left over after the last gradient
+    TrailingBytes,
+}
+
+impl core::fmt::Display for CanonicalDecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CanonicalDecodeError::Truncated => write!(f, "canonical update is truncated"),
+            CanonicalDecodeError::BadMagic => write!(f, "canonical update has bad magic"),
+            CanonicalDecodeError::UnsupportedVersion(v) => {
+                write!(f, "unsupported canonical update version {v}")
+            }
+            CanonicalDecodeError::UnknownEncoding(tag) => {
+                write!(f, "unknown canonical update encoding tag {tag}")
+            }
+            CanonicalDecodeError::TrailingBytes => {
+                write!(f, "trailing bytes after canonical update")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CanonicalDecodeError {}
+
+#[cfg(feature = "alloc")]
+impl GradientUpdate {
+    /// Encode `self` as a delta against `prev`, the sender's previous update.
+    ///
+    /// Elements are XORed bit patterns, so reconstruction is exact and slowly
+    /// changing gradients yield mostly-zero high bits (which compress well).
+    /// Falls back to a [`UpdateEncoding::Full`] copy when `prev` is not a full
+    /// update from the same sender with the same dimension.
+    pub fn delta_from(&self, prev: &GradientUpdate) -> GradientUpdate {
+        let usable = prev.encoding == UpdateEncoding::Full
+            && self.encoding == UpdateEncoding::Full
+            && prev.sender == self.sender
+            && prev.gradients.len() == self.gradients.len();
+        if !usable {
+            let mut full = self.clone();
+            full.encoding = UpdateEncoding::Full;
+            return full;
+        }
+        GradientUpdate {
+            sender: self.sender,
+            sequence: self.sequence,
+            gradients: xor_bits(&self.gradients, &prev.gradients),
+            round_id: self.round_id,
+            encoding: UpdateEncoding::Delta {
+                base_sequence: prev.sequence,
+            },
+        }
+    }
+
+    /// Reconstruct the full update from `self` and the receiver's cached
+    /// `baseline` for the sender. A full update is returned unchanged.
+    pub fn apply_delta(
+        &self,
+        baseline: Option<&GradientUpdate>,
+    ) -> core::result::Result<GradientUpdate, DeltaError> {
+        let UpdateEncoding::Delta { base_sequence } = self.encoding else {
+            return Ok(self.clone());
+        };
+        let baseline = baseline.ok_or(DeltaError::MissingBaseline { base_sequence })?;
+        if baseline.sequence != base_sequence
+            || baseline.sender != self.sender
+            || baseline.encoding != UpdateEncoding::Full
+        {
+            return Err(DeltaError::BaselineMismatch {
+                expected: base_sequence,
+                found: baseline.sequence,
+            });
+        }
+        if baseline.gradients.len() != self.gradients.len() {
+            return Err(DeltaError::DimensionMismatch {
+                expected: baseline.gradients.len(),
+                got: self.gradients.len(),
+            });
+        }
+        Ok(GradientUpdate {
+            sender: self.sender,
+            sequence: self.sequence,
+            gradients: xor_bits(&self.gradients, &baseline.gradients),
+            round_id: self.round_id,
+            encoding: UpdateEncoding::Full,
+        })
+    }
+
+    /// Stable, language-neutral byte layout for hashing and interop.
+    ///
+    /// All integers are little-endian:
+    ///
+    /// ```text
+    /// magic "STGU" | version u8 (=1) | sender [u8; 32] | sequence u64 | round_id u64
+    /// | encoding u8 (0 = Full, 1 = Delta) [| base_sequence u64 if Delta]
+    /// | len u32 | gradients [f32; len]
+    /// ```
+    ///
+    /// In a full update every NaN is written as [`CANONICAL_NAN_BITS`], so
+    /// updates differing only in NaN payload encode (and hash) identically.
+    /// Delta elements are XORed bit patterns, not values, and are written as-is.
+    /// `-0.0` and `0.0` keep distinct encodings.
+    pub fn to_canonical_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(66 + 4 * self.gradients.len());
+        out.extend_from_slice(&CANONICAL_UPDATE_MAGIC);
+        out.push(CANONICAL_UPDATE_VERSION);
+        out.extend_from_slice(&self.sender);
+        out.extend_from_slice(&self.sequence.to_le_bytes());
+        out.extend_from_slice(&self.round_id.to_le_bytes());
+        match self.encoding {
+            UpdateEncoding::Full => out.push(0),
+            UpdateEncoding::Delta { base_sequence } => {
+                out.push(1);
+                out.extend_from_slice(&base_sequence.to_le_bytes());
+            }
+        }
+        out.extend_from_slice(&(self.gradients.len() as u32).to_le_bytes());
+        let canonicalize_nan = self.encoding == UpdateEncoding::Full;
+        for g in &self.gradients {
+            let bits = if canonicalize_nan && g.is_nan() {
+                CANONICAL_NAN_BITS
+            } else {
+                g.to_bits()
+            };
+            out.extend_from_slice(&bits.to_le_bytes());
+        }
+        out
+    }
+
+    /// Decode [`Self::to_canonical_bytes`] output. The input must be consumed
+    /// exactly.
+    pub fn from_canonical_bytes(
+        bytes: &[u8],
+    ) -> core::result::Result<GradientUpdate, CanonicalDecodeError> {
+        let mut reader = CanonicalReader { bytes };
+        if reader.take::<4>()? != CANONICAL_UPDATE_MAGIC {
+            return Err(CanonicalDecodeError::BadMagic);
+        }
+        let [version] = reader.take::<1>()?;
+        if version != CANONICAL_UPDATE_VERSION {
+            return Err(CanonicalDecodeError::UnsupportedVersion(version));
+        }
+        let sender = reader.take::<32>()?;
+        let sequence = u64::from_le_bytes(reader.take()?);
+        let round_id = u64::from_le_bytes(reader.take()?);
+        let encoding = match reader.take::<1>()? {
+            [0] => UpdateEncoding::Full,
+            [1] => UpdateEncoding::Delta {
+                base_sequence: u64::from_le_bytes(reader.take()?),
+            },
+            [tag] => return Err(CanonicalDecodeError::UnknownEncoding(tag)),
+        };
+        let len = u32::from_le_bytes(reader.take()?) as usize;
+        if reader.bytes.len() / 4 < len {
+            return Err(CanonicalDecodeError::Truncated);
+        }
+        let mut gradients = Vec::with_capacity(len);
+        for _ in 0..len {
+            gradients.push(f32::from_bits(u32::from_le_bytes(reader.take()?)));
+        }
+        if !reader.bytes.is_empty() {
+            return Err(CanonicalDecodeError::TrailingBytes);
+        }
+        Ok(GradientUpdate {
+            sender,
+            sequence,
+            gradients,
+            round_id,
+            encoding,
+        })
+    }
+
+    /// Decode a postcard-encoded update, also accepting the layout from before
+    /// `encoding` existed.
+    ///
+    /// Wire break: `encoding` was appended as a trailing field. Pre-delta bytes
+    /// end before it and decode here as [`UpdateEncoding::Full`]. Pre-delta
+    /// peers ignore the trailing tag and would read a delta's bit patterns as
+    /// values, so only send deltas to peers known to decode with this method.
+    pub fn from_postcard_bytes(bytes: &[u8]) -> core::result::Result<Self, postcard::Error> {
+        match postcard::from_bytes::<GradientUpdate>(bytes) {
+            Err(postcard::Error::DeserializeUnexpectedEnd) => {
+                let (legacy, rest) = postcard::take_from_bytes::<GradientUpdateV0>(bytes)?;
+                if !rest.is_empty() {
+                    return Err(postcard::Error::DeserializeUnexpectedEnd);
+                }
+                Ok(GradientUpdate {
+                    sender: legacy.sender,
+                    sequence: legacy.sequence,
+                    gradients: legacy.gradients,
+                    round_id: legacy.round_id,
+                    encoding: UpdateEncoding::Full,
+                })
+            }
+            result => result,
+        }
+    }
+}
+
+/// Postcard layout of [`GradientUpdate`] before the trailing `encoding` field.
+#[cfg(feature = "alloc")]
+#[derive(serde::Deserialize)]
+struct GradientUpdateV0 {
+    sender: [u8; 32],
+    sequence: u64,
+    gradients: Vec<f32>,
+    round_id: u64,
+}
+
+#[cfg(feature = "alloc")]
+struct CanonicalReader<'a> {
+    bytes: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+impl CanonicalReader<'_> {
+    fn take<const N: usize>(&mut self) -> core::result::Result<[u8; N], CanonicalDecodeError> {
+        if self.bytes.len() < N {
+            return Err(CanonicalDecodeError::Truncated);
+        }
+        let (head, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+        let mut out = [0u8; N];
+        out.copy_from_slice(head);
+        Ok(out)
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn xor_bits(a: &[f32], b: &[f32]) -> Vec<f32> {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| f32::from_bits(x.to_bits() ^ y.to_bits()))
+        .collect()
 }
 
 #[cfg(feature = "alloc")]
@@ -202,6 +510,133 @@ mod tests {
         assert_eq!(result.unwrap(), PeerId::from_public_key(&key));
     }
 
+    fn update(sequence: u64, gradients: &[f32]) -> GradientUpdate {
+        GradientUpdate {
+            sender: [5u8; 32],
+            sequence,
+            gradients: gradients.to_vec(),
+            round_id: sequence,
+            encoding: UpdateEncoding::Full,
+        }
+    }
+
+    #[test]
+    fn delta_then_apply_reproduces_full_update_exactly() {
+        let prev = update(1, &[0.5, -1.25, 1e-8, f32::MIN_POSITIVE, 3.0]);
+        let next = update(2, &[0.5001, -1.5, 1.0, -0.0, 3.0]);
+
+        let delta = next.delta_from(&prev);
+        assert_eq!(delta.encoding, UpdateEncoding::Delta { base_sequence: 1 });
+        // Unchanged elements encode as zero bits.
+        assert_eq!(delta.gradients[4].to_bits(), 0);
+
+        let restored = delta.apply_delta(Some(&prev)).unwrap();
+        assert_eq!(restored.encoding, UpdateEncoding::Full);
+        assert_eq!((restored.sequence, restored.round_id), (2, 2));
+        let bits = |u: &GradientUpdate| u.gradients.iter().map(|g| g.to_bits()).collect::<Vec<_>>();
+        assert_eq!(bits(&restored), bits(&next));
+    }
+
+    #[test]
+    fn first_round_send_without_baseline_is_full() {
+        let first = update(1, &[1.0, 2.0]);
+        assert_eq!(first.encoding, UpdateEncoding::Full);
+        // A full update needs no baseline on the receiver.
+        assert_eq!(first.apply_delta(None).unwrap().gradients, first.gradients);
+
+        // Dimension change falls back to a flagged full send.
+        let resized = update(2, &[1.0, 2.0, 3.0]);
+        let sent = resized.delta_from(&first);
+        assert_eq!(sent.encoding, UpdateEncoding::Full);
+        assert_eq!(sent.gradients, resized.gradients);
+    }
+
+    #[test]
+    fn delta_without_matching_baseline_is_rejected() {
+        let prev = update(1, &[1.0, 2.0]);
+        let delta = update(2, &[1.5, 2.0]).delta_from(&prev);
+        assert_eq!(
+            delta.apply_delta(None).unwrap_err(),
+            DeltaError::MissingBaseline { base_sequence: 1 }
+        );
+        assert_eq!(
+            delta
+                .apply_delta(Some(&update(0, &[1.0, 2.0])))
+                .unwrap_err(),
+            DeltaError::BaselineMismatch {
+                expected: 1,
+                found: 0
+            }
+        );
+        assert_eq!(
+            delta.apply_delta(Some(&update(1, &[1.0]))).unwrap_err(),
+            DeltaError::DimensionMismatch {
+                expected: 1,
+                got: 2
+            }
+        );
+    }
+
+    #[test]
+    fn postcard_decode_accepts_updates_without_encoding() {
+        let legacy = update(4, &[1.5, -2.0]);
+        let bytes = postcard::to_allocvec(&(
+            legacy.sender,
+            legacy.sequence,
+            &legacy.gradients,
+            legacy.round_id,
+        ))
+        .unwrap();
+        let decoded = GradientUpdate::from_postcard_bytes(&bytes).unwrap();
+        assert_eq!(decoded.encoding, UpdateEncoding::Full);
+        assert_eq!(decoded.gradients, legacy.gradients);
+        assert_eq!((decoded.sequence, decoded.round_id), (4, 4));
+
+        let delta = update(5, &[1.0, -2.0]).delta_from(&legacy);
+        let decoded =
+            GradientUpdate::from_postcard_bytes(&postcard::to_allocvec(&delta).unwrap()).unwrap();
+        assert_eq!(decoded.encoding, UpdateEncoding::Delta { base_sequence: 4 });
+
+        assert!(GradientUpdate::from_postcard_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn canonical_bytes_round_trip() {
+        let full = update(3, &[0.5, -0.0, f32::INFINITY, f32::MIN_POSITIVE]);
+        let bytes = full.to_canonical_bytes();
+        assert_eq!(&bytes[..5], b"STGU\x01");
+        let decoded = GradientUpdate::from_canonical_bytes(&bytes).unwrap();
+        assert_eq!(decoded.encoding, UpdateEncoding::Full);
+        assert_eq!((decoded.sequence, decoded.round_id), (3, 3));
+        assert_eq!(decoded.to_canonical_bytes(), bytes);
+
+        let delta = update(4, &[0.25, 1.0, 2.0, 3.0]).delta_from(&full);
+        let decoded = GradientUpdate::from_canonical_bytes(&delta.to_canonical_bytes()).unwrap();
+        assert_eq!(decoded.encoding, UpdateEncoding::Delta { base_sequence: 3 });
+        assert_eq!(decoded.to_canonical_bytes(), delta.to_canonical_bytes());
+
+        assert_eq!(
+            GradientUpdate::from_canonical_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+            CanonicalDecodeError::Truncated
+        );
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            GradientUpdate::from_canonical_bytes(&trailing).unwrap_err(),
+            CanonicalDecodeError::TrailingBytes
+        );
+    }
+
+    #[test]
+    fn canonical_bytes_collapse_nan_payloads() {
+        let a = update(1, &[1.0, f32::NAN]);
+        let b = update(1, &[1.0, f32::from_bits(0xffc0_1234)]);
+        assert_ne!(a.gradients[1].to_bits(), b.gradients[1].to_bits());
+        assert_eq!(a.to_canonical_bytes(), b.to_canonical_bytes());
+        let decoded = GradientUpdate::from_canonical_bytes(&b.to_canonical_bytes()).unwrap();
+        assert_eq!(decoded.gradients[1].to_bits(), CANONICAL_NAN_BITS);
+    }
+
     #[test]
     fn peer_id_ord_is_byte_lexicographic() {
         let a = PeerId::new([0u8; 32]);
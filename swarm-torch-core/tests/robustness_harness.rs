@@ -1,7 +1,7 @@
 #[cfg(feature = "krum")]
 use swarm_torch_core::aggregation::Krum;
 use swarm_torch_core::aggregation::{CoordinateMedian, FedAvg, RobustAggregator, TrimmedMean};
-use swarm_torch_core::traits::GradientUpdate;
+use swarm_torch_core::traits::{GradientUpdate, UpdateEncoding};
 
 #[derive(Clone, Copy)]
 enum AttackFamily {
@@ -75,6 +75,7 @@ fn make_round_updates(
             sequence: i as u64,
             gradients: benign_update(dim, &mut prng),
             round_id: 1,
+            encoding: UpdateEncoding::Full,
         });
     }
     for i in (n - f)..n {
@@ -83,6 +84,7 @@ fn make_round_updates(
             sequence: i as u64,
             gradients: malicious_update(dim, attack),
             round_id: 1,
+            encoding: UpdateEncoding::Full,
         });
     }
 
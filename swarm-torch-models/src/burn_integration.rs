@@ -1,9 +1,12 @@
 //! Burn framework integration
 //!
-//! This module provides wrappers for using Burn models with SwarmTorch.
+//! This module provides wrappers for using Burn models with SwarmTorch, and
+//! [`model_state_from_burn`] / [`load_burn_from_model_state`] to move a module's float
+//! parameters in and out of a [`ModelState`](crate::ModelState) for transport.
 
-// Placeholder for Burn integration
-// Real implementation would wrap burn::module::Module
+use burn::module::{Module, ModuleMapper, ModuleVisitor, ParamId};
+use burn::tensor::backend::Backend;
+use burn::tensor::{Tensor, TensorData};
 
 /// Marker trait for Burn-compatible models
 pub trait BurnCompatible {}
@@ -26,3 +29,122 @@ impl<M> BurnModelWrapper<M> {
         self.model
     }
 }
+
+/// Flatten every float parameter of `module` into a [`ModelState`](crate::ModelState).
+///
+/// Tensors are taken in the module's visit order (field declaration order, recursively),
+/// which is deterministic for a given module type; `shapes` records each tensor's dims.
+pub fn model_state_from_burn<B, M>(
+    name: impl Into<alloc::string::String>,
+    module: &M,
+) -> crate::ModelState
+where
+    B: Backend,
+    M: Module<B>,
+{
+    let mut flatten = Flatten::default();
+    module.visit(&mut flatten);
+    crate::ModelState::new(name, flatten.parameters).with_shapes(flatten.shapes)
+}
+
+/// Overwrite the float parameters of `module` with `state`, in the order used by
+/// [`model_state_from_burn`].
+///
+/// `state.shapes` must equal the module's tensor shapes (`ShapeMismatch` otherwise) and
+/// `state` must pass [`ModelState::validate`](crate::ModelState::validate). Parameter ids,
+/// devices and `require_grad` flags are kept.
+pub fn load_burn_from_model_state<B, M>(
+    module: M,
+    state: &crate::ModelState,
+) -> Result<M, crate::ModelStateError>
+where
+    B: Backend,
+    M: Module<B>,
+{
+    let mut shapes = ShapeCollector::default();
+    module.visit(&mut shapes);
+    if shapes.shapes != state.shapes {
+        return Err(crate::ModelStateError::ShapeMismatch);
+    }
+    state.validate()?;
+
+    Ok(module.map(&mut Unflatten {
+        parameters: &state.parameters,
+        offset: 0,
+    }))
+}
+
+#[derive(Default)]
+struct Flatten {
+    parameters: alloc::vec::Vec<f32>,
+    shapes: alloc::vec::Vec<alloc::vec::Vec<usize>>,
+}
+
+impl<B: Backend> ModuleVisitor<B> for Flatten {
+    fn visit_float<const D: usize>(&mut self, _id: &ParamId, tensor: &Tensor<B, D>) {
+        self.shapes.push(tensor.shape().dims.to_vec());
+        self.parameters.extend(tensor.to_data().iter::<f32>());
+    }
+}
+
+#[derive(Default)]
+struct ShapeCollector {
+    shapes: alloc::vec::Vec<alloc::vec::Vec<usize>>,
+}
+
+impl<B: Backend> ModuleVisitor<B> for ShapeCollector {
+    fn visit_float<const D: usize>(&mut self, _id: &ParamId, tensor: &Tensor<B, D>) {
+        self.shapes.push(tensor.shape().dims.to_vec());
+    }
+}
+
+struct Unflatten<'a> {
+    parameters: &'a [f32],
+    offset: usize,
+}
+
+impl<B: Backend> ModuleMapper<B> for Unflatten<'_> {
+    fn map_float<const D: usize>(&mut self, _id: &ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        // Shapes were checked against the state, so every slice is in bounds.
+        let dims = tensor.shape().dims;
+        let count: usize = dims.iter().product();
+        let values = self.parameters[self.offset..self.offset + count].to_vec();
+        self.offset += count;
+
+        let data = TensorData::new(values, dims.to_vec()).convert::<B::FloatElem>();
+        Tensor::from_data(data, &tensor.device()).set_require_grad(tensor.is_require_grad())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn::nn::LinearConfig;
+    use burn_ndarray::NdArray;
+
+    type TestBackend = NdArray<f32>;
+
+    #[test]
+    fn linear_layer_round_trips_through_model_state() {
+        let device = Default::default();
+        let linear = LinearConfig::new(3, 2).init::<TestBackend>(&device);
+        let state = model_state_from_burn("linear", &linear);
+        assert_eq!(state.shapes, vec![vec![3, 2], vec![2]]);
+        assert!(state.validate().is_ok());
+
+        let mut shifted = state.clone();
+        shifted.parameters.iter_mut().for_each(|p| *p += 1.0);
+        let fresh = LinearConfig::new(3, 2).init::<TestBackend>(&device);
+        let loaded = load_burn_from_model_state(fresh, &shifted).unwrap();
+        assert_eq!(
+            model_state_from_burn("linear", &loaded).parameters,
+            shifted.parameters
+        );
+
+        let wrong = LinearConfig::new(2, 2).init::<TestBackend>(&device);
+        assert_eq!(
+            load_burn_from_model_state(wrong, &state).unwrap_err(),
+            crate::ModelStateError::ShapeMismatch
+        );
+    }
+}
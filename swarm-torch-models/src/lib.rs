@@ -32,6 +32,45 @@ pub mod prelude {
     pub use crate::burn_integration::*;
 }
 
+/// Default magnitude below which parameter changes are dropped from a [`ModelDelta`].
+pub const DEFAULT_DELTA_EPSILON: f32 = 1e-6;
+
+/// Error type for model state delta encoding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelStateError {
+    /// Model names differ between state and base/delta
+    NameMismatch,
+    /// Parameter shapes differ between state and base/delta
+    ShapeMismatch,
+    /// Flattened parameter counts differ
+    LengthMismatch { expected: usize, actual: usize },
+    /// Delta indices/values are inconsistent, unsorted, or out of bounds
+    MalformedDelta,
+    /// Parameter index exceeds u32 capacity
+    IndexOverflow,
+    /// Quantizing or dequantizing a tensor failed
+    Compression(swarm_torch_core::compression::CompressionError),
+}
+
+impl core::fmt::Display for ModelStateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NameMismatch => write!(f, "model name does not match base"),
+            Self::ShapeMismatch => write!(f, "parameter shapes do not match base"),
+            Self::LengthMismatch { expected, actual } => write!(
+                f,
+                "parameter count mismatch (expected {expected}, got {actual})"
+            ),
+            Self::MalformedDelta => write!(f, "malformed model delta"),
+            Self::IndexOverflow => write!(f, "parameter index exceeds u32 capacity"),
+            Self::Compression(err) => write!(f, "tensor quantization failed: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ModelStateError {}
+
 /// Model state for serialization
 #[cfg(feature = "alloc")]
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -64,6 +103,289 @@ impl ModelState {
         self
     }
 
+    /// Build a consistent state from `(shape, data)` pairs laid out in order.
+    ///
+    /// Fails with `LengthMismatch` if any `data.len()` differs from its shape's element count.
+    pub fn from_tensors(
+        name: impl Into<alloc::string::String>,
+        tensors: &[(&[usize], &[f32])],
+    ) -> Result<Self, ModelStateError> {
+        let mut parameters = alloc::vec::Vec::new();
+        let mut shapes = alloc::vec::Vec::with_capacity(tensors.len());
+        for &(shape, data) in tensors {
+            let expected = element_count(shape);
+            if data.len() != expected {
+                return Err(ModelStateError::LengthMismatch {
+                    expected,
+                    actual: data.len(),
+                });
+            }
+            parameters.extend_from_slice(data);
+            shapes.push(shape.to_vec());
+        }
+        Ok(Self::new(name, parameters).with_shapes(shapes))
+    }
+
+    /// Check that `parameters` holds exactly the elements described by `shapes`.
+    pub fn validate(&self) -> Result<(), ModelStateError> {
+        let expected = self.shapes.iter().fold(0usize, |total, shape| {
+            total.saturating_add(element_count(shape))
+        });
+        if self.parameters.len() != expected {
+            return Err(ModelStateError::LengthMismatch {
+                expected,
+                actual: self.parameters.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Slice the flat parameter buffer into `(shape, data)` per tensor, in order.
+    ///
+    /// Stops at the first shape that does not fit in the remaining buffer; call
+    /// [`ModelState::validate`] first to rule that out.
+    pub fn tensors(&self) -> alloc::vec::Vec<(&[usize], &[f32])> {
+        let mut tensors = alloc::vec::Vec::with_capacity(self.shapes.len());
+        let mut rest = self.parameters.as_slice();
+        for shape in &self.shapes {
+            let count = element_count(shape);
+            if count > rest.len() {
+                break;
+            }
+            let (data, tail) = rest.split_at(count);
+            tensors.push((shape.as_slice(), data));
+            rest = tail;
+        }
+        tensors
+    }
+
+    /// Serialize to bytes
+    pub fn to_bytes(&self) -> Result<alloc::vec::Vec<u8>, postcard::Error> {
+        postcard::to_allocvec(self)
+    }
+
+    /// Deserialize from bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, postcard::Error> {
+        postcard::from_bytes(bytes)
+    }
+
+    /// Sparse delta from `base` to `self`, dropping changes with `|delta| <= DEFAULT_DELTA_EPSILON`.
+    pub fn diff_against(&self, base: &ModelState) -> Result<ModelDelta, ModelStateError> {
+        self.diff_against_with_epsilon(base, DEFAULT_DELTA_EPSILON)
+    }
+
+    /// Sparse delta from `base` to `self`, keeping only entries with `|delta| > epsilon`.
+    ///
+    /// `name` and `shapes` must match `base`. Applying the result to `base` reconstructs
+    /// `self` to within `epsilon` per parameter.
+    pub fn diff_against_with_epsilon(
+        &self,
+        base: &ModelState,
+        epsilon: f32,
+    ) -> Result<ModelDelta, ModelStateError> {
+        self.check_compatible(&base.name, &base.shapes, base.parameters.len())?;
+
+        let mut indices = alloc::vec::Vec::new();
+        let mut values = alloc::vec::Vec::new();
+        for (i, (&target, &prev)) in self.parameters.iter().zip(&base.parameters).enumerate() {
+            let delta = target - prev;
+            // NaN deltas are kept so non-finite parameters are never silently dropped.
+            if delta.is_nan() || delta.abs() > epsilon {
+                indices.push(u32::try_from(i).map_err(|_| ModelStateError::IndexOverflow)?);
+                values.push(delta);
+            }
+        }
+
+        Ok(ModelDelta {
+            name: self.name.clone(),
+            shapes: self.shapes.clone(),
+            num_parameters: self.parameters.len(),
+            indices,
+            values,
+        })
+    }
+
+    /// Apply a delta produced by [`ModelState::diff_against`] in place.
+    ///
+    /// The delta is fully validated before any parameter is modified.
+    pub fn apply_delta(&mut self, delta: &ModelDelta) -> Result<(), ModelStateError> {
+        self.check_compatible(&delta.name, &delta.shapes, delta.num_parameters)?;
+        if delta.indices.len() != delta.values.len() {
+            return Err(ModelStateError::MalformedDelta);
+        }
+        let mut prev: Option<u32> = None;
+        for &index in &delta.indices {
+            if prev.is_some_and(|p| index <= p) || index as usize >= self.parameters.len() {
+                return Err(ModelStateError::MalformedDelta);
+            }
+            prev = Some(index);
+        }
+
+        for (&index, &value) in delta.indices.iter().zip(&delta.values) {
+            self.parameters[index as usize] += value;
+        }
+        Ok(())
+    }
+
+    /// SGD step: `parameters[i] -= lr * grad[i]`.
+    ///
+    /// Fails with `Error::InvalidGradient` (leaving parameters untouched) if `grad` is
+    /// not exactly one value per parameter.
+    pub fn apply_gradient(&mut self, grad: &[f32], lr: f32) -> swarm_torch_core::Result<()> {
+        if grad.len() != self.parameters.len() {
+            return Err(swarm_torch_core::Error::InvalidGradient {
+                index: 0,
+                reason: swarm_torch_core::crypto::GradientValidationError::DimensionMismatch {
+                    expected: self.parameters.len(),
+                    got: grad.len(),
+                },
+            });
+        }
+        for (param, &g) in self.parameters.iter_mut().zip(grad) {
+            *param -= lr * g;
+        }
+        Ok(())
+    }
+
+    /// The flat parameter buffer as a [`GradientUpdate`] payload.
+    ///
+    /// `sender`, `sequence` and `round_id` are zeroed; callers fill them before sending.
+    pub fn to_gradient_update(&self) -> swarm_torch_core::traits::GradientUpdate {
+        swarm_torch_core::traits::GradientUpdate {
+            sender: [0u8; 32],
+            sequence: 0,
+            gradients: self.parameters.clone(),
+            round_id: 0,
+            encoding: swarm_torch_core::traits::UpdateEncoding::Full,
+        }
+    }
+
+    fn check_compatible(
+        &self,
+        name: &str,
+        shapes: &[alloc::vec::Vec<usize>],
+        num_parameters: usize,
+    ) -> Result<(), ModelStateError> {
+        if self.name != name {
+            return Err(ModelStateError::NameMismatch);
+        }
+        if self.shapes != shapes {
+            return Err(ModelStateError::ShapeMismatch);
+        }
+        if self.parameters.len() != num_parameters {
+            return Err(ModelStateError::LengthMismatch {
+                expected: num_parameters,
+                actual: self.parameters.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// [`ModelState`] with each tensor stored as 8-bit codes plus a scale and offset (`Quantizer8`).
+///
+/// Roughly 4x smaller than the `f32` state when serialized, intended for
+/// `MessageType::ModelCheckpoint` payloads over bandwidth-limited links. `name`, `version`
+/// and `shapes` are kept verbatim; a state without `shapes` is quantized as one tensor.
+/// Per-element error is bounded by each tensor's `scale / 2`.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct QuantizedModelState {
+    /// Model name/identifier
+    pub name: alloc::string::String,
+    /// Version of the model format
+    pub version: u32,
+    /// Parameter shapes for reconstruction
+    pub shapes: alloc::vec::Vec<alloc::vec::Vec<usize>>,
+    /// One quantized block per shape (or a single block if `shapes` is empty)
+    pub tensors: alloc::vec::Vec<swarm_torch_core::compression::QuantizedGradient>,
+}
+
+#[cfg(feature = "alloc")]
+impl QuantizedModelState {
+    /// Quantize `state` tensor by tensor.
+    ///
+    /// `state` must pass [`ModelState::validate`] unless it has no shapes; non-finite
+    /// parameters are rejected by the quantizer.
+    pub fn from_model_state(state: &ModelState) -> Result<Self, ModelStateError> {
+        let quantizer = swarm_torch_core::compression::Quantizer8::new();
+        let blocks: alloc::vec::Vec<&[f32]> = if state.shapes.is_empty() {
+            alloc::vec![state.parameters.as_slice()]
+        } else {
+            state.validate()?;
+            state.tensors().into_iter().map(|(_, data)| data).collect()
+        };
+        let tensors = blocks
+            .into_iter()
+            .map(|block| quantizer.quantize(block))
+            .collect::<Result<_, _>>()
+            .map_err(ModelStateError::Compression)?;
+        Ok(Self {
+            name: state.name.clone(),
+            version: state.version,
+            shapes: state.shapes.clone(),
+            tensors,
+        })
+    }
+
+    /// Dequantize back to a [`ModelState`] with the original name, version and shapes.
+    pub fn to_model_state(&self) -> Result<ModelState, ModelStateError> {
+        if !self.shapes.is_empty() && self.tensors.len() != self.shapes.len() {
+            return Err(ModelStateError::ShapeMismatch);
+        }
+        let mut parameters = alloc::vec::Vec::new();
+        for tensor in &self.tensors {
+            parameters.extend(tensor.dequantize().map_err(ModelStateError::Compression)?);
+        }
+        let state = ModelState {
+            name: self.name.clone(),
+            version: self.version,
+            parameters,
+            shapes: self.shapes.clone(),
+        };
+        if !state.shapes.is_empty() {
+            state.validate()?;
+        }
+        Ok(state)
+    }
+
+    /// Serialize to bytes
+    pub fn to_bytes(&self) -> Result<alloc::vec::Vec<u8>, postcard::Error> {
+        postcard::to_allocvec(self)
+    }
+
+    /// Deserialize from bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, postcard::Error> {
+        postcard::from_bytes(bytes)
+    }
+}
+
+/// Elements in a tensor of `shape` (`1` for a scalar `[]`), saturating on overflow.
+#[cfg(feature = "alloc")]
+fn element_count(shape: &[usize]) -> usize {
+    shape
+        .iter()
+        .fold(1usize, |count, &dim| count.saturating_mul(dim))
+}
+
+/// Sparse parameter delta between two compatible [`ModelState`]s.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ModelDelta {
+    /// Model name/identifier (must match the base)
+    pub name: alloc::string::String,
+    /// Parameter shapes (must match the base)
+    pub shapes: alloc::vec::Vec<alloc::vec::Vec<usize>>,
+    /// Total flattened parameter count
+    pub num_parameters: usize,
+    /// Strictly increasing indices of changed parameters
+    pub indices: alloc::vec::Vec<u32>,
+    /// `target - base` for each index
+    pub values: alloc::vec::Vec<f32>,
+}
+
+#[cfg(feature = "alloc")]
+impl ModelDelta {
     /// Serialize to bytes
     pub fn to_bytes(&self) -> Result<alloc::vec::Vec<u8>, postcard::Error> {
         postcard::to_allocvec(self)
@@ -74,3 +396,221 @@ impl ModelState {
         postcard::from_bytes(bytes)
     }
 }
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    fn state(parameters: Vec<f32>) -> ModelState {
+        ModelState::new("mlp", parameters).with_shapes(vec![vec![2, 2], vec![2]])
+    }
+
+    #[test]
+    fn model_delta_postcard_round_trip() {
+        let base = state(vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        let target = state(vec![0.0, 1.5, 2.0, 3.0, -4.0, 5.0]);
+        let delta = target.diff_against(&base).unwrap();
+        assert_eq!(delta.indices, vec![1, 4]);
+
+        let bytes = delta.to_bytes().unwrap();
+        assert_eq!(ModelDelta::from_bytes(&bytes).unwrap(), delta);
+    }
+
+    #[test]
+    fn apply_delta_reconstructs_target_within_epsilon() {
+        let epsilon = 1e-3;
+        let base = state(vec![0.1, -0.2, 0.3, 0.4, 0.5, 0.6]);
+        let target = state(vec![0.1005, -0.9, 0.3, 1.4, 0.4999, 2.6]);
+        let delta = target.diff_against_with_epsilon(&base, epsilon).unwrap();
+        assert_eq!(delta.indices, vec![1, 3, 5], "sub-epsilon changes dropped");
+
+        let mut rebuilt = base.clone();
+        rebuilt.apply_delta(&delta).unwrap();
+        for (r, t) in rebuilt.parameters.iter().zip(&target.parameters) {
+            assert!((r - t).abs() <= epsilon, "{r} vs {t}");
+        }
+    }
+
+    #[test]
+    fn diff_rejects_mismatched_name_or_shapes() {
+        let base = state(vec![0.0; 6]);
+        let renamed = ModelState::new("other", vec![0.0; 6]).with_shapes(base.shapes.clone());
+        assert_eq!(
+            renamed.diff_against(&base).unwrap_err(),
+            ModelStateError::NameMismatch
+        );
+
+        let reshaped = ModelState::new("mlp", vec![0.0; 6]).with_shapes(vec![vec![6]]);
+        assert_eq!(
+            reshaped.diff_against(&base).unwrap_err(),
+            ModelStateError::ShapeMismatch
+        );
+
+        let mut other = state(vec![0.0; 6]);
+        let delta = state(vec![1.0; 6]).diff_against(&base).unwrap();
+        other.name = "other".into();
+        assert_eq!(
+            other.apply_delta(&delta).unwrap_err(),
+            ModelStateError::NameMismatch
+        );
+    }
+
+    #[test]
+    fn validate_rejects_parameter_shape_mismatch() {
+        assert!(state(vec![0.0; 6]).validate().is_ok());
+        assert_eq!(
+            state(vec![0.0; 5]).validate().unwrap_err(),
+            ModelStateError::LengthMismatch {
+                expected: 6,
+                actual: 5
+            }
+        );
+        assert_eq!(
+            state(vec![0.0; 7]).validate().unwrap_err(),
+            ModelStateError::LengthMismatch {
+                expected: 6,
+                actual: 7
+            }
+        );
+        // A short buffer yields only the tensors that fit.
+        assert_eq!(state(vec![0.0; 5]).tensors().len(), 1);
+    }
+
+    #[test]
+    fn from_tensors_round_trips_through_tensors() {
+        let weights = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let bias = [0.5, -0.5];
+        let scale = [2.0];
+        let built =
+            ModelState::from_tensors("mlp", &[(&[2, 3], &weights), (&[2], &bias), (&[], &scale)])
+                .unwrap();
+        assert!(built.validate().is_ok());
+        assert_eq!(built.shapes, vec![vec![2, 3], vec![2], vec![]]);
+        assert_eq!(built.parameters.len(), 9);
+
+        let tensors = built.tensors();
+        assert_eq!(
+            tensors,
+            vec![
+                (&[2usize, 3][..], &weights[..]),
+                (&[2][..], &bias[..]),
+                (&[][..], &scale[..]),
+            ]
+        );
+        let rebuilt = ModelState::from_tensors("mlp", &tensors).unwrap();
+        assert_eq!(rebuilt.parameters, built.parameters);
+        assert_eq!(rebuilt.shapes, built.shapes);
+
+        assert_eq!(
+            ModelState::from_tensors("mlp", &[(&[2, 2], &bias)]).unwrap_err(),
+            ModelStateError::LengthMismatch {
+                expected: 4,
+                actual: 2
+            }
+        );
+    }
+
+    #[test]
+    fn apply_gradient_is_an_sgd_step() {
+        let params = vec![1.0, -2.0, 3.0, 0.5, 0.0, 4.0];
+        let mut model = state(params.clone());
+        model.apply_gradient(&[0.0; 6], 0.1).unwrap();
+        assert_eq!(model.parameters, params);
+
+        let grad = [0.5, 1.0, -1.0, 0.25, 2.0, 4.0];
+        model.apply_gradient(&grad, 1.0).unwrap();
+        let expected: Vec<f32> = params.iter().zip(&grad).map(|(p, g)| p - g).collect();
+        assert_eq!(model.parameters, expected);
+
+        assert!(matches!(
+            model.apply_gradient(&grad[..5], 1.0),
+            Err(swarm_torch_core::Error::InvalidGradient {
+                reason: swarm_torch_core::crypto::GradientValidationError::DimensionMismatch {
+                    expected: 6,
+                    got: 5
+                },
+                ..
+            })
+        ));
+        assert_eq!(model.parameters, expected);
+
+        let update = model.to_gradient_update();
+        assert_eq!(update.gradients, expected);
+        assert_eq!((update.sequence, update.round_id), (0, 0));
+    }
+
+    #[test]
+    fn quantized_state_round_trips_within_int8_bound() {
+        let weights: Vec<f32> = (0..256)
+            .map(|i| ((i * 37) % 101) as f32 / 25.0 - 2.0)
+            .collect();
+        let bias: Vec<f32> = (0..16).map(|i| i as f32 * 0.01).collect();
+        let mut original =
+            ModelState::from_tensors("mlp", &[(&[16, 16], &weights), (&[16], &bias)]).unwrap();
+        original.version = 3;
+
+        let quantized = QuantizedModelState::from_model_state(&original).unwrap();
+        let bytes = quantized.to_bytes().unwrap();
+        let restored = QuantizedModelState::from_bytes(&bytes)
+            .unwrap()
+            .to_model_state()
+            .unwrap();
+        assert_eq!(restored.name, "mlp");
+        assert_eq!(restored.version, 3);
+        assert_eq!(restored.shapes, original.shapes);
+
+        for ((_, before), (_, after)) in original.tensors().into_iter().zip(restored.tensors()) {
+            let lo = before.iter().copied().fold(f32::INFINITY, f32::min);
+            let hi = before.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            let bound = (hi - lo) / 255.0;
+            for (a, b) in before.iter().zip(after) {
+                assert!((a - b).abs() <= bound, "{a} vs {b} (bound {bound})");
+            }
+        }
+
+        let f32_size = original.to_bytes().unwrap().len();
+        assert!(bytes.len() * 3 < f32_size, "{} vs {f32_size}", bytes.len());
+    }
+
+    #[test]
+    fn quantized_state_rejects_inconsistent_input() {
+        assert_eq!(
+            QuantizedModelState::from_model_state(&state(vec![0.0; 5])).unwrap_err(),
+            ModelStateError::LengthMismatch {
+                expected: 6,
+                actual: 5
+            }
+        );
+        let unshaped = ModelState::new("flat", vec![1.0, -1.0, 0.5]);
+        let restored = QuantizedModelState::from_model_state(&unshaped)
+            .unwrap()
+            .to_model_state()
+            .unwrap();
+        assert_eq!(restored.parameters.len(), 3);
+        assert!(restored.shapes.is_empty());
+
+        let mut quantized = QuantizedModelState::from_model_state(&state(vec![1.0; 6])).unwrap();
+        quantized.tensors.pop();
+        assert_eq!(
+            quantized.to_model_state().unwrap_err(),
+            ModelStateError::ShapeMismatch
+        );
+    }
+
+    #[test]
+    fn apply_delta_rejects_malformed_indices_without_mutating() {
+        let mut base = state(vec![0.0; 6]);
+        let delta = ModelDelta {
+            name: "mlp".into(),
+            shapes: base.shapes.clone(),
+            num_parameters: 6,
+            indices: vec![0, 9],
+            values: vec![1.0, 1.0],
+        };
+        assert_eq!(
+            base.apply_delta(&delta).unwrap_err(),
+            ModelStateError::MalformedDelta
+        );
+        assert_eq!(base.parameters, vec![0.0; 6]);
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! These models are useful for testing and examples.
 
+use swarm_torch_core::crypto::GradientValidationError;
 use swarm_torch_core::traits::SwarmModel;
 use swarm_torch_core::Result;
 
@@ -159,7 +160,13 @@ impl<const MAX_PARAMS: usize, const MAX_OUTPUT: usize> SwarmModel
     fn load_parameters(&mut self, params: &[f32]) -> Result<()> {
         let expected = self.weight_count() + self.output_dim;
         if params.len() != expected {
-            return Err(swarm_torch_core::Error::InvalidGradient);
+            return Err(swarm_torch_core::Error::InvalidGradient {
+                index: 0,
+                reason: GradientValidationError::DimensionMismatch {
+                    expected,
+                    got: params.len(),
+                },
+            });
         }
         self.params[..expected].copy_from_slice(params);
         Ok(())
@@ -0,0 +1,203 @@
+//! Multi-transport fallback
+//!
+//! [`FallbackTransport`] implements [`FallbackPolicy::PriorityOrder`] over an ordered list
+//! of transports (e.g. WiFi first, LoRa last): `send` tries each in order until one
+//! succeeds. A transport that fails `failure_threshold` sends in a row is skipped for
+//! `cooldown`, unless every transport is cooling down, in which case all are tried.
+//!
+//! [`FallbackPolicy::PriorityOrder`]: crate::traits::FallbackPolicy::PriorityOrder
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::Poll;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use swarm_torch_core::traits::PeerId;
+
+use crate::traits::{BroadcastStats, SwarmTransport, TransportCapabilities};
+use crate::{Error, Result};
+
+/// Default consecutive send failures before a transport is skipped.
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+/// Default time a failing transport is skipped.
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Health {
+    consecutive_failures: u32,
+    skip_until: Option<Instant>,
+}
+
+/// Ordered multi-transport wrapper with per-transport health tracking.
+pub struct FallbackTransport {
+    transports: Vec<Box<dyn SwarmTransport>>,
+    health: Mutex<Vec<Health>>,
+    failure_threshold: u32,
+    cooldown: Duration,
+}
+
+impl FallbackTransport {
+    /// Wrap `transports`, highest priority first.
+    pub fn new(transports: Vec<Box<dyn SwarmTransport>>) -> Self {
+        let health = alloc::vec![Health::default(); transports.len()];
+        Self {
+            transports,
+            health: Mutex::new(health),
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            cooldown: DEFAULT_COOLDOWN,
+        }
+    }
+
+    /// Skip a transport for `cooldown` after `failure_threshold` consecutive send failures.
+    pub fn with_health_policy(mut self, failure_threshold: u32, cooldown: Duration) -> Self {
+        self.failure_threshold = failure_threshold.max(1);
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Wrapped transports, in priority order.
+    pub fn transports(&self) -> &[Box<dyn SwarmTransport>] {
+        &self.transports
+    }
+
+    /// Whether transport `index` is currently skipped by `send`.
+    pub fn is_skipped(&self, index: usize) -> bool {
+        self.is_skipped_at(index, Instant::now())
+    }
+
+    fn is_skipped_at(&self, index: usize, now: Instant) -> bool {
+        self.health_lock()
+            .get(index)
+            .and_then(|h| h.skip_until)
+            .is_some_and(|until| now < until)
+    }
+
+    fn health_lock(&self) -> std::sync::MutexGuard<'_, Vec<Health>> {
+        self.health
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn record(&self, index: usize, ok: bool) {
+        let mut health = self.health_lock();
+        let h = &mut health[index];
+        if ok {
+            *h = Health::default();
+            return;
+        }
+        h.consecutive_failures = h.consecutive_failures.saturating_add(1);
+        if h.consecutive_failures >= self.failure_threshold {
+            h.skip_until = Some(Instant::now() + self.cooldown);
+        }
+    }
+
+    /// Indices `send` should try, in priority order.
+    fn candidates(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let healthy: Vec<usize> = (0..self.transports.len())
+            .filter(|&i| !self.is_skipped_at(i, now))
+            .collect();
+        if healthy.is_empty() {
+            (0..self.transports.len()).collect()
+        } else {
+            healthy
+        }
+    }
+}
+
+impl core::fmt::Debug for FallbackTransport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FallbackTransport")
+            .field("transports", &self.transports.len())
+            .field("failure_threshold", &self.failure_threshold)
+            .field("cooldown", &self.cooldown)
+            .finish()
+    }
+}
+
+type RecvFuture<'a> = Pin<Box<dyn Future<Output = Result<(PeerId, Vec<u8>)>> + Send + 'a>>;
+
+#[async_trait::async_trait]
+impl SwarmTransport for FallbackTransport {
+    async fn send(&self, peer: PeerId, msg: &[u8]) -> Result<()> {
+        for index in self.candidates() {
+            let ok = self.transports[index].send(peer, msg).await.is_ok();
+            self.record(index, ok);
+            if ok {
+                return Ok(());
+            }
+        }
+        Err(Error::AllTransportsFailed)
+    }
+
+    /// Polls every transport (health does not apply) and returns the first message.
+    ///
+    /// Transports whose `recv` errors drop out; `AllTransportsFailed` once all have.
+    async fn recv(&self) -> Result<(PeerId, Vec<u8>)> {
+        let mut pending: Vec<Option<RecvFuture<'_>>> =
+            self.transports.iter().map(|t| Some(t.recv())).collect();
+        core::future::poll_fn(|cx| {
+            for slot in pending.iter_mut() {
+                let Some(fut) = slot else { continue };
+                match fut.as_mut().poll(cx) {
+                    Poll::Ready(Ok(msg)) => return Poll::Ready(Ok(msg)),
+                    Poll::Ready(Err(_)) => *slot = None,
+                    Poll::Pending => {}
+                }
+            }
+            if pending.iter().all(Option::is_none) {
+                Poll::Ready(Err(Error::AllTransportsFailed))
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    /// Broadcasts on the first candidate transport that succeeds.
+    async fn broadcast(&self, msg: &[u8]) -> Result<BroadcastStats> {
+        for index in self.candidates() {
+            let result = self.transports[index].broadcast(msg).await;
+            self.record(index, result.is_ok());
+            if result.is_ok() {
+                return result;
+            }
+        }
+        Err(Error::AllTransportsFailed)
+    }
+
+    /// Union of peers discovered on every transport.
+    async fn discover(&self) -> Result<Vec<PeerId>> {
+        let mut peers = Vec::new();
+        let mut any_ok = false;
+        for transport in &self.transports {
+            if let Ok(found) = transport.discover().await {
+                any_ok = true;
+                peers.extend(found);
+            }
+        }
+        if !any_ok && !self.transports.is_empty() {
+            return Err(Error::AllTransportsFailed);
+        }
+        peers.sort();
+        peers.dedup();
+        Ok(peers)
+    }
+
+    /// Capabilities of the highest-priority transport `send` would currently use.
+    fn capabilities(&self) -> TransportCapabilities {
+        match self.candidates().first() {
+            Some(&index) => self.transports[index].capabilities(),
+            None => TransportCapabilities {
+                reliability: crate::traits::ReliabilityClass::BestEffort,
+                bandwidth_class: crate::traits::BandwidthClass::UltraLow,
+                max_message_size: 0,
+                supports_multicast: false,
+            },
+        }
+    }
+}
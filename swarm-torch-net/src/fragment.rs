@@ -0,0 +1,137 @@
+//! Fragmentation for constrained links
+//!
+//! Splits a message that exceeds a transport's MTU into fragments that each fit, and
+//! reassembles them on the receiving side. Fragment layout:
+//!
+//! `message_id: u32 BE | index: u16 BE | count: u16 BE | chunk`
+//!
+//! Reassembly is keyed by `message_id` only; use one [`Reassembler`] per peer.
+
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::vec::Vec;
+
+use crate::{Error, Result};
+
+/// Bytes of header in front of every fragment's chunk.
+pub const FRAGMENT_HEADER_LEN: usize = 8;
+
+/// Split `payload` into fragments of at most `mtu` bytes each.
+///
+/// Always yields at least one fragment (an empty payload is one empty chunk). Fails with
+/// `Error::InvalidMessage` if `mtu` leaves no room for data or more than `u16::MAX`
+/// fragments would be needed.
+pub fn fragment(message_id: u32, payload: &[u8], mtu: usize) -> Result<Vec<Vec<u8>>> {
+    let chunk_len = mtu
+        .checked_sub(FRAGMENT_HEADER_LEN)
+        .filter(|&len| len > 0)
+        .ok_or(Error::InvalidMessage)?;
+    let count = payload.len().div_ceil(chunk_len).max(1);
+    let count = u16::try_from(count).map_err(|_| Error::InvalidMessage)?;
+
+    let mut fragments = Vec::with_capacity(usize::from(count));
+    for index in 0..count {
+        let start = usize::from(index) * chunk_len;
+        let chunk = &payload[start..payload.len().min(start + chunk_len)];
+        let mut fragment = Vec::with_capacity(FRAGMENT_HEADER_LEN + chunk.len());
+        fragment.extend_from_slice(&message_id.to_be_bytes());
+        fragment.extend_from_slice(&index.to_be_bytes());
+        fragment.extend_from_slice(&count.to_be_bytes());
+        fragment.extend_from_slice(chunk);
+        fragments.push(fragment);
+    }
+    Ok(fragments)
+}
+
+#[derive(Debug)]
+struct Partial {
+    count: u16,
+    len: usize,
+    chunks: BTreeMap<u16, Vec<u8>>,
+}
+
+/// Reassembles [`fragment`] output, tolerating reordering and duplicates.
+///
+/// Memory is bounded by `max_message_len` per message and `max_pending` incomplete
+/// messages; when a new message would exceed `max_pending`, the oldest is dropped.
+#[derive(Debug)]
+pub struct Reassembler {
+    pending: BTreeMap<u32, Partial>,
+    order: VecDeque<u32>,
+    max_message_len: usize,
+    max_pending: usize,
+}
+
+impl Reassembler {
+    /// Create a reassembler with the given memory bounds.
+    pub fn new(max_message_len: usize, max_pending: usize) -> Self {
+        Self {
+            pending: BTreeMap::new(),
+            order: VecDeque::new(),
+            max_message_len,
+            max_pending: max_pending.max(1),
+        }
+    }
+
+    /// Number of incomplete messages held.
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Accept one fragment; returns the full message once its last fragment arrives.
+    ///
+    /// Malformed fragments, a `count` that disagrees with earlier fragments of the same
+    /// message, or a message growing past `max_message_len` fail with
+    /// `Error::InvalidMessage` (the latter two also drop the partial message).
+    pub fn push(&mut self, fragment: &[u8]) -> Result<Option<Vec<u8>>> {
+        if fragment.len() < FRAGMENT_HEADER_LEN {
+            return Err(Error::InvalidMessage);
+        }
+        let message_id = u32::from_be_bytes([fragment[0], fragment[1], fragment[2], fragment[3]]);
+        let index = u16::from_be_bytes([fragment[4], fragment[5]]);
+        let count = u16::from_be_bytes([fragment[6], fragment[7]]);
+        let chunk = &fragment[FRAGMENT_HEADER_LEN..];
+        if count == 0 || index >= count {
+            return Err(Error::InvalidMessage);
+        }
+
+        if !self.pending.contains_key(&message_id) {
+            if self.pending.len() >= self.max_pending {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.pending.remove(&oldest);
+                }
+            }
+            self.order.push_back(message_id);
+        }
+        let partial = self.pending.entry(message_id).or_insert_with(|| Partial {
+            count,
+            len: 0,
+            chunks: BTreeMap::new(),
+        });
+        if partial.count != count || partial.len + chunk.len() > self.max_message_len {
+            self.remove(message_id);
+            return Err(Error::InvalidMessage);
+        }
+        if partial.chunks.contains_key(&index) {
+            return Ok(None);
+        }
+        partial.len += chunk.len();
+        partial.chunks.insert(index, chunk.to_vec());
+        if partial.chunks.len() < usize::from(partial.count) {
+            return Ok(None);
+        }
+
+        let Some(partial) = self.remove(message_id) else {
+            return Ok(None);
+        };
+        let mut message = Vec::with_capacity(partial.len);
+        for chunk in partial.chunks.values() {
+            message.extend_from_slice(chunk);
+        }
+        Ok(Some(message))
+    }
+
+    fn remove(&mut self, message_id: u32) -> Option<Partial> {
+        self.order.retain(|&id| id != message_id);
+        self.pending.remove(&message_id)
+    }
+}
@@ -5,9 +5,15 @@
 //! This crate provides:
 //! - Unified `SwarmTransport` trait for all transports
 //! - Mock transport/network implementations for integration testing
-//! - Placeholder feature flags for TCP/UDP/BLE/LoRa/WiFi backends (planned)
-//! - Multi-transport policy surface with fallback error semantics
+//! - TCP transport (`tcp-transport`) with length-prefixed framing and connection pooling
+//! - UDP transport (`udp-transport`) with replay filtering and optional gradient acks
+//! - Placeholder feature flags for BLE/LoRa/WiFi backends (planned)
+//! - Multi-transport fallback (`FallbackTransport`) with per-transport health skipping
 //! - Message framing and serialization
+//! - Heartbeat-driven peer liveness tracking (`liveness::LivenessTracker`)
+//! - Bounded discovery peer table with gossip sampling (`peer_table::PeerTable`)
+//! - Per-peer token-bucket rate limiting ahead of signature checks (`rate_limit::RateLimiter`)
+//! - MTU enforcement (`traits::MtuEnforced`) and fragmentation for constrained links
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
@@ -15,16 +21,27 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "alloc")]
+pub mod fragment;
+#[cfg(feature = "alloc")]
+pub mod liveness;
+#[cfg(feature = "alloc")]
+pub mod peer_table;
 pub mod protocol;
+#[cfg(feature = "alloc")]
+pub mod rate_limit;
 pub mod traits;
 
-// TCP transport - placeholder
-// #[cfg(feature = "tcp-transport")]
-// pub mod tcp;
+#[cfg(feature = "std")]
+pub mod fallback;
+#[cfg(feature = "std")]
+pub use fallback::FallbackTransport;
+
+#[cfg(feature = "tcp-transport")]
+pub mod tcp;
 
-// UDP transport - placeholder
-// #[cfg(feature = "udp-transport")]
-// pub mod udp;
+#[cfg(feature = "udp-transport")]
+pub mod udp;
 
 mod mock;
 pub use mock::MockTransport;
@@ -61,10 +78,60 @@ pub enum Error {
     TransportUnavailable,
     /// Invalid message format
     InvalidMessage,
+    /// Core operation failed
+    Core(swarm_torch_core::Error),
+    /// Replay protection rejected the message
+    #[cfg(feature = "alloc")]
+    Replay(swarm_torch_core::replay::ReplayError),
+    /// Message authentication failed
+    #[cfg(feature = "alloc")]
+    Verify(protocol::VerifyError),
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Core(e) => Some(e),
+            Error::Replay(e) => Some(e),
+            Error::Verify(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<postcard::Error> for Error {
+    fn from(_: postcard::Error) -> Self {
+        Error::Serialization
+    }
+}
+
+impl From<swarm_torch_core::Error> for Error {
+    fn from(error: swarm_torch_core::Error) -> Self {
+        match error {
+            swarm_torch_core::Error::Serialization => Error::Serialization,
+            other => Error::Core(other),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<swarm_torch_core::replay::ReplayError> for Error {
+    fn from(error: swarm_torch_core::replay::ReplayError) -> Self {
+        Error::Replay(error)
+    }
+}
+
+/// Replay failures surface as [`Error::Replay`]; everything else as [`Error::Verify`].
+#[cfg(feature = "alloc")]
+impl From<protocol::VerifyError> for Error {
+    fn from(error: protocol::VerifyError) -> Self {
+        match error {
+            protocol::VerifyError::Replay(e) => Error::Replay(e),
+            other => Error::Verify(other),
+        }
+    }
+}
 
 impl core::fmt::Display for Error {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -78,6 +145,11 @@ impl core::fmt::Display for Error {
             Error::Serialization => write!(f, "serialization error"),
             Error::TransportUnavailable => write!(f, "transport unavailable"),
             Error::InvalidMessage => write!(f, "invalid message format"),
+            Error::Core(e) => write!(f, "core error: {}", e),
+            #[cfg(feature = "alloc")]
+            Error::Replay(e) => write!(f, "replay error: {}", e),
+            #[cfg(feature = "alloc")]
+            Error::Verify(e) => write!(f, "verification error: {}", e),
         }
     }
 }
@@ -0,0 +1,104 @@
+//! Peer liveness tracking from heartbeats.
+//!
+//! [`LivenessTracker`] records when each peer last sent a [`HeartbeatMessage`]
+//! and which round it reported, so a coordinator can drop silent peers from a
+//! round's expected set. Time is supplied by the caller (unix seconds), keeping
+//! the tracker runtime-agnostic.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use swarm_torch_core::traits::PeerId;
+
+use crate::protocol::{HeartbeatMessage, MessageEnvelope, MessageType};
+
+/// Last heartbeat seen from one peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerLiveness {
+    /// When the latest heartbeat was recorded (unix seconds)
+    pub last_seen: u64,
+    /// Round the peer reported in that heartbeat
+    pub current_round: u64,
+}
+
+/// Last-heartbeat table keyed by `PeerId`.
+#[derive(Debug, Clone, Default)]
+pub struct LivenessTracker {
+    peers: BTreeMap<PeerId, PeerLiveness>,
+}
+
+impl LivenessTracker {
+    /// Create an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a decoded heartbeat from `peer` received at `now`.
+    ///
+    /// `last_seen` never moves backwards, so a delayed heartbeat cannot make a
+    /// peer look older than it is.
+    pub fn record_heartbeat(&mut self, peer: PeerId, heartbeat: &HeartbeatMessage, now: u64) {
+        let entry = self.peers.entry(peer).or_insert(PeerLiveness {
+            last_seen: now,
+            current_round: heartbeat.current_round,
+        });
+        if now >= entry.last_seen {
+            entry.last_seen = now;
+            entry.current_round = heartbeat.current_round;
+        }
+    }
+
+    /// Record `envelope` if it is a heartbeat; returns whether it was one.
+    ///
+    /// The peer is the envelope's [`MessageEnvelope::sender_peer_id`]. Verify the
+    /// envelope first: this only decodes it.
+    pub fn record_envelope(&mut self, envelope: &MessageEnvelope, now: u64) -> crate::Result<bool> {
+        if envelope.message_type != MessageType::Heartbeat {
+            return Ok(false);
+        }
+        let peer = envelope
+            .sender_peer_id()
+            .map_err(|_| crate::Error::InvalidMessage)?;
+        let heartbeat: HeartbeatMessage = postcard::from_bytes(&envelope.payload)?;
+        self.record_heartbeat(peer, &heartbeat, now);
+        Ok(true)
+    }
+
+    /// Last heartbeat recorded for `peer`.
+    pub fn get(&self, peer: &PeerId) -> Option<&PeerLiveness> {
+        self.peers.get(peer)
+    }
+
+    /// Peers heard from within `timeout` seconds of `now`, in `PeerId` order.
+    pub fn alive_peers(&self, now: u64, timeout: u64) -> Vec<PeerId> {
+        self.peers
+            .iter()
+            .filter(|(_, liveness)| now.saturating_sub(liveness.last_seen) <= timeout)
+            .map(|(peer, _)| *peer)
+            .collect()
+    }
+
+    /// Peers silent for more than `timeout` seconds at `now`, in `PeerId` order.
+    pub fn stale_peers(&self, now: u64, timeout: u64) -> Vec<PeerId> {
+        self.peers
+            .iter()
+            .filter(|(_, liveness)| now.saturating_sub(liveness.last_seen) > timeout)
+            .map(|(peer, _)| *peer)
+            .collect()
+    }
+
+    /// Forget `peer`; returns its last entry.
+    pub fn remove(&mut self, peer: &PeerId) -> Option<PeerLiveness> {
+        self.peers.remove(peer)
+    }
+
+    /// Number of tracked peers.
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Whether no peers are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+}
@@ -8,10 +8,18 @@ use alloc::collections::VecDeque;
 use alloc::vec::Vec;
 
 use crate::traits::{BandwidthClass, ReliabilityClass, TransportCapabilities};
+#[cfg(feature = "std")]
+use crate::traits::{BroadcastStats, SwarmTransport};
 #[cfg(feature = "alloc")]
 use swarm_torch_core::traits::PeerId;
 
 /// Mock transport for testing without real networking
+///
+/// With `std`, this implements [`SwarmTransport`]: sends are recorded instead of
+/// delivered, and `recv` waits for messages queued with [`MockTransport::push_incoming`]
+/// (or fails with `ReceiveFailed` when disconnected).
+/// Failures are deterministic: `failure_rate` is accumulated per send and a send fails
+/// each time the accumulator reaches 1 (so `1.0` always fails, `0.5` every other send).
 #[derive(Debug, Default)]
 pub struct MockTransport {
     /// Simulated failure rate (0.0 to 1.0)
@@ -20,6 +28,20 @@ pub struct MockTransport {
     pub latency_ms: u32,
     /// Whether the transport is connected
     pub connected: bool,
+    max_message_size: Option<usize>,
+    #[cfg(feature = "std")]
+    state: std::sync::Mutex<MockTransportState>,
+    #[cfg(feature = "std")]
+    incoming_ready: tokio::sync::Notify,
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+struct MockTransportState {
+    failure_acc: f32,
+    send_attempts: usize,
+    sent: Vec<(PeerId, Vec<u8>)>,
+    incoming: VecDeque<(PeerId, Vec<u8>)>,
 }
 
 impl MockTransport {
@@ -29,6 +51,11 @@ impl MockTransport {
             failure_rate: 0.0,
             latency_ms: 0,
             connected: true,
+            max_message_size: None,
+            #[cfg(feature = "std")]
+            state: Default::default(),
+            #[cfg(feature = "std")]
+            incoming_ready: Default::default(),
         }
     }
 
@@ -44,15 +71,97 @@ impl MockTransport {
         self
     }
 
+    /// Set the simulated MTU (default 65536); larger sends fail with `InvalidMessage`
+    pub fn with_max_message_size(mut self, bytes: usize) -> Self {
+        self.max_message_size = Some(bytes);
+        self
+    }
+
     /// Get transport capabilities
     pub fn capabilities(&self) -> TransportCapabilities {
         TransportCapabilities {
             reliability: ReliabilityClass::Reliable,
             bandwidth_class: BandwidthClass::High,
-            max_message_size: 65536,
+            max_message_size: self.max_message_size.unwrap_or(65536),
             supports_multicast: true,
         }
     }
+
+    /// Queue a message to be returned by `recv`.
+    #[cfg(feature = "std")]
+    pub fn push_incoming(&self, from: PeerId, msg: Vec<u8>) {
+        self.state().incoming.push_back((from, msg));
+        self.incoming_ready.notify_waiters();
+    }
+
+    /// Messages accepted by `send`, in order.
+    #[cfg(feature = "std")]
+    pub fn sent(&self) -> Vec<(PeerId, Vec<u8>)> {
+        self.state().sent.clone()
+    }
+
+    /// Number of `send` calls, including failed ones.
+    #[cfg(feature = "std")]
+    pub fn send_attempts(&self) -> usize {
+        self.state().send_attempts
+    }
+
+    #[cfg(feature = "std")]
+    fn state(&self) -> std::sync::MutexGuard<'_, MockTransportState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(feature = "std")]
+#[async_trait::async_trait]
+impl SwarmTransport for MockTransport {
+    async fn send(&self, peer: PeerId, msg: &[u8]) -> crate::Result<()> {
+        if self.latency_ms > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(u64::from(self.latency_ms))).await;
+        }
+        let mut state = self.state();
+        state.send_attempts += 1;
+        if !self.connected {
+            return Err(crate::Error::ConnectionFailed);
+        }
+        MockTransport::capabilities(self).check_payload_len(msg.len())?;
+        state.failure_acc += self.failure_rate;
+        if state.failure_acc >= 1.0 {
+            state.failure_acc -= 1.0;
+            return Err(crate::Error::SendFailed);
+        }
+        state.sent.push((peer, msg.to_vec()));
+        Ok(())
+    }
+
+    async fn recv(&self) -> crate::Result<(PeerId, Vec<u8>)> {
+        loop {
+            if !self.connected {
+                return Err(crate::Error::ReceiveFailed);
+            }
+            // Registered before the check so a concurrent push is not missed.
+            let ready = self.incoming_ready.notified();
+            if let Some(msg) = self.state().incoming.pop_front() {
+                return Ok(msg);
+            }
+            ready.await;
+        }
+    }
+
+    async fn broadcast(&self, _msg: &[u8]) -> crate::Result<BroadcastStats> {
+        // A standalone mock has no peers to reach.
+        Ok(BroadcastStats::default())
+    }
+
+    async fn discover(&self) -> crate::Result<Vec<PeerId>> {
+        Ok(Vec::new())
+    }
+
+    fn capabilities(&self) -> TransportCapabilities {
+        MockTransport::capabilities(self)
+    }
 }
 
 /// A network of interconnected mock transports for testing
@@ -0,0 +1,124 @@
+//! Bounded peer table fed by discovery responses.
+//!
+//! [`PeerTable`] merges the `peers` of a [`PeerDiscoveryMessage`], deduplicating
+//! and skipping the local peer. When full, the least recently merged peer is
+//! evicted, so peers that keep being advertised stay resident. [`PeerTable::sample`]
+//! draws gossip targets with the same seeded partial Fisher-Yates as
+//! `Topology::Gossip`.
+//!
+//! [`PeerDiscoveryMessage`]: crate::protocol::PeerDiscoveryMessage
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use swarm_torch_core::algorithms::Topology;
+use swarm_torch_core::traits::PeerId;
+
+/// Default [`PeerTable`] capacity.
+pub const DEFAULT_PEER_TABLE_CAPACITY: usize = 256;
+
+/// Deduplicated, capacity-bounded set of known peers with LRU eviction.
+#[derive(Debug, Clone)]
+pub struct PeerTable {
+    local: PeerId,
+    capacity: usize,
+    /// Peer -> tick of its latest merge.
+    peers: BTreeMap<PeerId, u64>,
+    tick: u64,
+}
+
+impl PeerTable {
+    /// Empty table for `local` holding at most `capacity` peers (minimum 1).
+    pub fn new(local: PeerId, capacity: usize) -> Self {
+        Self {
+            local,
+            capacity: capacity.max(1),
+            peers: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Empty table with [`DEFAULT_PEER_TABLE_CAPACITY`].
+    pub fn with_default_capacity(local: PeerId) -> Self {
+        Self::new(local, DEFAULT_PEER_TABLE_CAPACITY)
+    }
+
+    /// Merge advertised peers; returns how many were new.
+    ///
+    /// Known peers are refreshed, the local peer is skipped, and the least
+    /// recently merged peer is evicted whenever the table is full.
+    pub fn merge(&mut self, peers: &[[u8; 32]]) -> usize {
+        let mut added = 0;
+        for bytes in peers {
+            let peer = PeerId::new(*bytes);
+            if peer == self.local {
+                continue;
+            }
+            self.tick += 1;
+            if self.peers.insert(peer, self.tick).is_none() {
+                added += 1;
+                if self.peers.len() > self.capacity {
+                    self.evict_oldest();
+                }
+            }
+        }
+        added
+    }
+
+    /// Up to `fanout` distinct peers for gossip (seed `0`).
+    pub fn sample(&self, fanout: usize) -> Vec<PeerId> {
+        self.sample_seeded(fanout, 0)
+    }
+
+    /// [`PeerTable::sample`] with an explicit seed (e.g. the round number).
+    pub fn sample_seeded(&self, fanout: usize, seed: u64) -> Vec<PeerId> {
+        let known: Vec<PeerId> = self.peers.keys().copied().collect();
+        // The local node is the virtual last index, so draws cover every entry.
+        Topology::Gossip { fanout }
+            .neighbors_seeded(known.len(), known.len() + 1, seed)
+            .into_iter()
+            .map(|index| known[index])
+            .collect()
+    }
+
+    /// Whether `peer` is in the table.
+    pub fn contains(&self, peer: &PeerId) -> bool {
+        self.peers.contains_key(peer)
+    }
+
+    /// Known peers in `PeerId` order.
+    pub fn peers(&self) -> impl Iterator<Item = &PeerId> + '_ {
+        self.peers.keys()
+    }
+
+    /// Drop `peer` (e.g. after it went stale); returns whether it was present.
+    pub fn remove(&mut self, peer: &PeerId) -> bool {
+        self.peers.remove(peer).is_some()
+    }
+
+    /// Maximum number of peers held.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of known peers.
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Whether no peers are known.
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+
+    fn evict_oldest(&mut self) {
+        if let Some(oldest) = self
+            .peers
+            .iter()
+            .min_by_key(|(_, tick)| **tick)
+            .map(|(peer, _)| *peer)
+        {
+            self.peers.remove(&oldest);
+        }
+    }
+}
@@ -1,6 +1,9 @@
 //! Message protocol and framing
 //!
 //! This module defines the wire format for swarm messages.
+//!
+//! Stream transports carry serialized envelopes as length-prefixed frames
+//! (`u32` big-endian length, then the bytes); see [`encode_frame`] and [`FrameDecoder`].
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
@@ -10,9 +13,14 @@ use alloc::vec::Vec;
 
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "alloc")]
+use swarm_torch_core::crypto::KeyRing;
+#[cfg(feature = "alloc")]
 use swarm_torch_core::replay::ReplayProtection;
 use swarm_torch_core::traits::PeerId;
 
+#[cfg(feature = "alloc")]
+use crate::rate_limit::RateLimiter;
+
 /// Message envelope for all swarm communications
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageEnvelope {
@@ -32,13 +40,133 @@ pub struct MessageEnvelope {
     /// Optional cryptographic signature (64 bytes for Ed25519)
     #[cfg(feature = "alloc")]
     pub signature: Option<alloc::vec::Vec<u8>>,
+    /// Rotated signing key id (protocol 0.2+); `None` means the `sender` identity key.
+    ///
+    /// Not encoded in the 0.1 wire format.
+    pub key_id: Option<u16>,
+    /// Payload flags (protocol 0.3+), e.g. [`ENVELOPE_FLAG_COMPRESSED`].
+    ///
+    /// Bound into the signature. Not encoded in the 0.1/0.2 wire formats.
+    pub flags: u8,
+}
+
+/// [`MessageEnvelope::flags`] bit: the payload is deflate-compressed.
+pub const ENVELOPE_FLAG_COMPRESSED: u8 = 0x01;
+
+/// Protocol 0.1 wire layout (no `key_id`), kept for mixed-version fleets.
+#[derive(Serialize, Deserialize)]
+struct MessageEnvelopeV0_1 {
+    version: (u8, u8),
+    message_type: MessageType,
+    sender: [u8; 32],
+    sequence: u64,
+    timestamp: u32,
+    #[cfg(feature = "alloc")]
+    payload: Vec<u8>,
+    #[cfg(feature = "alloc")]
+    signature: Option<alloc::vec::Vec<u8>>,
+}
+
+impl From<MessageEnvelopeV0_1> for MessageEnvelope {
+    fn from(legacy: MessageEnvelopeV0_1) -> Self {
+        Self {
+            version: legacy.version,
+            message_type: legacy.message_type,
+            sender: legacy.sender,
+            sequence: legacy.sequence,
+            timestamp: legacy.timestamp,
+            #[cfg(feature = "alloc")]
+            payload: legacy.payload,
+            #[cfg(feature = "alloc")]
+            signature: legacy.signature,
+            key_id: None,
+            flags: 0,
+        }
+    }
+}
+
+/// Protocol 0.2 wire layout (no `flags`).
+#[derive(Serialize, Deserialize)]
+struct MessageEnvelopeV0_2 {
+    version: (u8, u8),
+    message_type: MessageType,
+    sender: [u8; 32],
+    sequence: u64,
+    timestamp: u32,
+    #[cfg(feature = "alloc")]
+    payload: Vec<u8>,
+    #[cfg(feature = "alloc")]
+    signature: Option<alloc::vec::Vec<u8>>,
+    key_id: Option<u16>,
+}
+
+impl From<MessageEnvelopeV0_2> for MessageEnvelope {
+    fn from(legacy: MessageEnvelopeV0_2) -> Self {
+        Self {
+            version: legacy.version,
+            message_type: legacy.message_type,
+            sender: legacy.sender,
+            sequence: legacy.sequence,
+            timestamp: legacy.timestamp,
+            #[cfg(feature = "alloc")]
+            payload: legacy.payload,
+            #[cfg(feature = "alloc")]
+            signature: legacy.signature,
+            key_id: legacy.key_id,
+            flags: 0,
+        }
+    }
+}
+
+/// Length of an Ed25519 envelope signature.
+pub const SIGNATURE_LEN: usize = 64;
+
+/// Default [`EnvelopeLimits::max_payload_len`] (matches the TCP frame cap).
+pub const DEFAULT_MAX_PAYLOAD_LEN: usize = 16 * 1024 * 1024;
+
+/// Decode limits for untrusted envelope bytes (see [`MessageEnvelope::deserialize_bounded`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnvelopeLimits {
+    /// Maximum declared `payload` length in bytes.
+    pub max_payload_len: usize,
+    /// Maximum declared `signature` length in bytes. Present signatures must also be
+    /// exactly [`SIGNATURE_LEN`].
+    pub max_signature_len: usize,
+}
+
+impl Default for EnvelopeLimits {
+    fn default() -> Self {
+        Self {
+            max_payload_len: DEFAULT_MAX_PAYLOAD_LEN,
+            max_signature_len: SIGNATURE_LEN,
+        }
+    }
+}
+
+/// Fixed-size envelope prefix shared by every wire version.
+#[cfg(feature = "alloc")]
+#[derive(Deserialize)]
+struct EnvelopeHeader {
+    _version: (u8, u8),
+    _message_type: MessageType,
+    _sender: [u8; 32],
+    _sequence: u64,
+    _timestamp: u32,
 }
 
 impl MessageEnvelope {
     /// Current protocol version
-    pub const CURRENT_VERSION: (u8, u8) = (0, 1);
+    pub const CURRENT_VERSION: (u8, u8) = (0, 3);
+    /// Protocol 0.1: no `key_id` on the wire; always verified against `sender`.
+    pub const LEGACY_VERSION_V0_1: (u8, u8) = (0, 1);
+    /// Protocol 0.2: `key_id` but no `flags` on the wire.
+    pub const LEGACY_VERSION_V0_2: (u8, u8) = (0, 2);
     /// Supported protocol versions
-    pub const SUPPORTED_VERSIONS: &'static [(u8, u8)] = &[Self::CURRENT_VERSION];
+    pub const SUPPORTED_VERSIONS: &'static [(u8, u8)] = &[
+        Self::LEGACY_VERSION_V0_1,
+        Self::LEGACY_VERSION_V0_2,
+        Self::CURRENT_VERSION,
+    ];
 
     /// Create a new message envelope with explicit public key bytes.
     #[cfg(feature = "alloc")]
@@ -55,9 +183,39 @@ impl MessageEnvelope {
             timestamp: 0,
             payload,
             signature: None,
+            key_id: None,
+            flags: 0,
         }
     }
 
+    /// Build and sign an envelope in one step.
+    ///
+    /// `sender` is `auth`'s public key and the signature covers exactly the fields
+    /// stored in the envelope, so the two cannot drift apart. Mutating any signed field
+    /// afterwards (or calling [`MessageEnvelope::encrypt_payload`]) invalidates it.
+    #[cfg(feature = "alloc")]
+    pub fn signed(
+        auth: &swarm_torch_core::crypto::MessageAuth,
+        message_type: MessageType,
+        payload: Vec<u8>,
+        sequence: u64,
+        timestamp: u32,
+    ) -> Self {
+        let envelope =
+            Self::new_with_public_key(*auth.key_pair().public_key(), message_type, payload)
+                .with_sequence(sequence)
+                .with_timestamp(timestamp);
+        let signature = auth.sign_with_flags(
+            envelope.version,
+            envelope.message_type as u8,
+            envelope.flags,
+            envelope.sequence,
+            envelope.timestamp,
+            &envelope.payload,
+        );
+        envelope.with_signature(signature.as_bytes().to_vec())
+    }
+
     /// Returns true when this envelope version is supported.
     pub fn is_version_supported(&self) -> bool {
         Self::SUPPORTED_VERSIONS.contains(&self.version)
@@ -112,17 +270,84 @@ impl MessageEnvelope {
         self
     }
 
+    /// Set the rotated signing key id
+    pub fn with_key_id(mut self, key_id: u16) -> Self {
+        self.key_id = Some(key_id);
+        self
+    }
+
     /// Serialize the envelope to bytes
+    ///
+    /// Envelopes stamped with protocol 0.1 use the 0.1 layout (no `key_id`), and
+    /// 0.2 envelopes the 0.2 layout (no `flags`).
     #[cfg(feature = "alloc")]
     pub fn serialize(&self) -> Result<Vec<u8>, postcard::Error> {
+        if self.version == Self::LEGACY_VERSION_V0_1 {
+            return postcard::to_allocvec(&MessageEnvelopeV0_1 {
+                version: self.version,
+                message_type: self.message_type,
+                sender: self.sender,
+                sequence: self.sequence,
+                timestamp: self.timestamp,
+                payload: self.payload.clone(),
+                signature: self.signature.clone(),
+            });
+        }
+        if self.version == Self::LEGACY_VERSION_V0_2 {
+            return postcard::to_allocvec(&MessageEnvelopeV0_2 {
+                version: self.version,
+                message_type: self.message_type,
+                sender: self.sender,
+                sequence: self.sequence,
+                timestamp: self.timestamp,
+                payload: self.payload.clone(),
+                signature: self.signature.clone(),
+                key_id: self.key_id,
+            });
+        }
         postcard::to_allocvec(self)
     }
 
     /// Deserialize from bytes
+    ///
+    /// Dispatches on the leading version: 0.1 bytes decode with `key_id = None`.
     pub fn deserialize(bytes: &[u8]) -> Result<Self, postcard::Error> {
+        let (version, _) = postcard::take_from_bytes::<(u8, u8)>(bytes)?;
+        if version == Self::LEGACY_VERSION_V0_1 {
+            return postcard::from_bytes::<MessageEnvelopeV0_1>(bytes).map(Self::from);
+        }
+        if version == Self::LEGACY_VERSION_V0_2 {
+            return postcard::from_bytes::<MessageEnvelopeV0_2>(bytes).map(Self::from);
+        }
         postcard::from_bytes(bytes)
     }
 
+    /// Deserialize untrusted bytes, enforcing `limits` before decoding.
+    ///
+    /// The declared `payload` and `signature` lengths are read from the wire and
+    /// checked (against `limits` and the bytes actually present) before any field is
+    /// materialized. A present signature must be exactly [`SIGNATURE_LEN`] bytes.
+    /// Every violation, like any decode failure, is `Error::InvalidMessage`.
+    #[cfg(feature = "alloc")]
+    pub fn deserialize_bounded(bytes: &[u8], limits: EnvelopeLimits) -> crate::Result<Self> {
+        let invalid = |_| crate::Error::InvalidMessage;
+        let (_, rest) = postcard::take_from_bytes::<EnvelopeHeader>(bytes).map_err(invalid)?;
+        let rest = skip_bounded_bytes(rest, limits.max_payload_len)?;
+        let (has_signature, rest) = postcard::take_from_bytes::<u8>(rest).map_err(invalid)?;
+        match has_signature {
+            0 => {}
+            1 => {
+                let (len, _) = postcard::take_from_bytes::<u64>(rest).map_err(invalid)?;
+                if len != SIGNATURE_LEN as u64 {
+                    return Err(crate::Error::InvalidMessage);
+                }
+                skip_bounded_bytes(rest, limits.max_signature_len)?;
+            }
+            _ => return Err(crate::Error::InvalidMessage),
+        }
+        Self::deserialize(bytes).map_err(invalid)
+    }
+
     /// Verify signature and replay protection
     ///
     /// This method performs a three-stage validation:
@@ -139,29 +364,76 @@ impl MessageEnvelope {
     ///
     /// Returns an error if:
     /// - Signature is missing or invalid
-    /// - Timestamp is outside acceptable window
+    /// - Timestamp is outside the window for this message type
+    ///   ([`ReplayProtection::with_message_type_skew`])
     /// - Sequence number is duplicate or retrograde
+    ///
+    /// Envelopes carrying a `key_id` need the sender's key ring; use
+    /// [`MessageEnvelope::verify_authenticated_with_keyring`].
     #[cfg(feature = "alloc")]
     pub fn verify_authenticated(
         &self,
         replay_guard: &mut ReplayProtection,
         current_time: u32,
     ) -> Result<(), VerifyError> {
-        use swarm_torch_core::crypto::MessageAuth;
+        self.check_version()?;
+        if let Some(key_id) = self.key_id {
+            return Err(VerifyError::UnknownKeyId { key_id });
+        }
+        self.verify_with_key(&self.sender, replay_guard, current_time)
+    }
 
-        // 0. VERSION CHECK (before any state mutation)
+    /// Verify against the sender's key ring, selecting the signing key by `key_id`.
+    ///
+    /// Envelopes without a `key_id` (including all 0.1 envelopes) verify against
+    /// the ring's identity key, which must equal `sender`. Replay state stays keyed
+    /// on the `sender` identity across rotations.
+    #[cfg(feature = "alloc")]
+    pub fn verify_authenticated_with_keyring(
+        &self,
+        replay_guard: &mut ReplayProtection,
+        current_time: u32,
+        keyring: &KeyRing,
+    ) -> Result<(), VerifyError> {
+        self.check_version()?;
+        if keyring.default_key() != &self.sender {
+            return Err(VerifyError::KeyRingMismatch);
+        }
+        let key = keyring
+            .resolve(self.key_id)
+            .ok_or(VerifyError::UnknownKeyId {
+                key_id: self.key_id.unwrap_or_default(),
+            })?;
+        self.verify_with_key(key, replay_guard, current_time)
+    }
+
+    /// Version check (before any state mutation).
+    #[cfg(feature = "alloc")]
+    fn check_version(&self) -> Result<(), VerifyError> {
         if !self.is_version_supported() {
             return Err(VerifyError::UnsupportedVersion {
                 major: self.version.0,
                 minor: self.version.1,
             });
         }
+        Ok(())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn verify_with_key(
+        &self,
+        signing_key: &[u8; 32],
+        replay_guard: &mut ReplayProtection,
+        current_time: u32,
+    ) -> Result<(), VerifyError> {
+        use swarm_torch_core::crypto::MessageAuth;
 
         // OPTIMIZATION: Fail-fast checks before expensive crypto
 
-        // 1. CHEAP: Timestamp expiry (no state mutation)
+        // 1. CHEAP: Timestamp expiry (no state mutation). Sealed envelopes use
+        // the `Encrypted` window; the inner type is not known until decryption.
         replay_guard
-            .check_timestamp_only(self.timestamp, current_time)
+            .check_timestamp_for_type(self.timestamp, current_time, self.message_type as u8)
             .map_err(VerifyError::Replay)?;
 
         // 2. CHEAP: validate sender key before expensive signature verification.
@@ -188,11 +460,12 @@ impl MessageEnvelope {
         sig_array.copy_from_slice(sig_bytes);
         let signature = swarm_torch_core::crypto::Signature::from_bytes(sig_array);
 
-        // Verify signature
-        MessageAuth::verify(
-            &self.sender,
+        // Verify signature (flags are bound, so they cannot be toggled in transit)
+        MessageAuth::verify_with_flags(
+            signing_key,
             self.version,
             self.message_type as u8,
+            self.flags,
             self.sequence,
             self.timestamp,
             &self.payload,
@@ -207,15 +480,226 @@ impl MessageEnvelope {
 
         Ok(())
     }
+
+    /// Whether the payload carries [`ENVELOPE_FLAG_COMPRESSED`].
+    pub fn is_compressed(&self) -> bool {
+        self.flags & ENVELOPE_FLAG_COMPRESSED != 0
+    }
+
+    /// Deflate-compress the payload in place and set [`ENVELOPE_FLAG_COMPRESSED`].
+    ///
+    /// Order on send is compress, then encrypt, then sign (the signature covers
+    /// the compressed bytes and the flag). Needs protocol 0.3+, since older
+    /// wire formats cannot carry the flag.
+    #[cfg(feature = "compression")]
+    pub fn compress_payload(&mut self) -> Result<(), VerifyError> {
+        use std::io::Write;
+
+        if self.version != Self::CURRENT_VERSION {
+            return Err(VerifyError::UnsupportedVersion {
+                major: self.version.0,
+                minor: self.version.1,
+            });
+        }
+        if self.is_compressed() {
+            return Err(VerifyError::AlreadyCompressed);
+        }
+        if self.message_type == MessageType::Encrypted {
+            return Err(VerifyError::AlreadyEncrypted);
+        }
+        let mut encoder =
+            flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder
+            .write_all(&self.payload)
+            .and_then(|()| encoder.finish())
+            .map(|compressed| self.payload = compressed)
+            .map_err(|_| VerifyError::CompressionFailed)?;
+        self.flags |= ENVELOPE_FLAG_COMPRESSED;
+        Ok(())
+    }
+
+    /// Inflate a compressed payload in place and clear [`ENVELOPE_FLAG_COMPRESSED`].
+    ///
+    /// Run after verification (and decryption). Output larger than
+    /// `limits.max_payload_len` is rejected as `DecompressionFailed`, so a small
+    /// envelope cannot expand into an unbounded allocation. On failure the
+    /// envelope is left unchanged.
+    #[cfg(feature = "compression")]
+    pub fn decompress_payload(&mut self, limits: EnvelopeLimits) -> Result<(), VerifyError> {
+        use std::io::Read;
+
+        if !self.is_compressed() {
+            return Err(VerifyError::NotCompressed);
+        }
+        if self.message_type == MessageType::Encrypted {
+            return Err(VerifyError::AlreadyEncrypted);
+        }
+        let mut payload = Vec::new();
+        flate2::read::DeflateDecoder::new(self.payload.as_slice())
+            .take(limits.max_payload_len as u64 + 1)
+            .read_to_end(&mut payload)
+            .map_err(|_| VerifyError::DecompressionFailed)?;
+        if payload.len() > limits.max_payload_len {
+            return Err(VerifyError::DecompressionFailed);
+        }
+        self.payload = payload;
+        self.flags &= !ENVELOPE_FLAG_COMPRESSED;
+        Ok(())
+    }
+
+    /// Encrypt the payload in place with ChaCha20-Poly1305.
+    ///
+    /// The payload becomes `inner_type || nonce || ciphertext+tag` and
+    /// `message_type` becomes [`MessageType::Encrypted`]. `version`, the inner
+    /// message type, `sender`, `sequence`, and `timestamp` are bound as associated
+    /// data, so set them before encrypting and sign afterwards (encrypt-then-sign).
+    ///
+    /// `nonce` must never repeat under the same `key`.
+    #[cfg(feature = "alloc")]
+    pub fn encrypt_payload(&mut self, key: &[u8; 32], nonce: [u8; 12]) -> Result<(), VerifyError> {
+        use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+        use chacha20poly1305::ChaCha20Poly1305;
+
+        if self.message_type == MessageType::Encrypted {
+            return Err(VerifyError::AlreadyEncrypted);
+        }
+        let inner_type = self.message_type;
+        let aad = self.payload_aad(inner_type);
+        let ciphertext = ChaCha20Poly1305::new(key.into())
+            .encrypt(
+                (&nonce).into(),
+                Payload {
+                    msg: &self.payload,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| VerifyError::MalformedCiphertext)?;
+
+        let mut framed = Vec::with_capacity(ENCRYPTED_HEADER_LEN + ciphertext.len());
+        framed.push(inner_type as u8);
+        framed.extend_from_slice(&nonce);
+        framed.extend_from_slice(&ciphertext);
+        self.payload = framed;
+        self.message_type = MessageType::Encrypted;
+        Ok(())
+    }
+
+    /// Decrypt an [`MessageType::Encrypted`] payload in place.
+    ///
+    /// On success restores the inner message type and plaintext payload; on
+    /// failure the envelope is left unchanged. Any change to the bound header
+    /// fields since encryption yields `VerifyError::DecryptionFailed`.
+    #[cfg(feature = "alloc")]
+    pub fn decrypt_payload(&mut self, key: &[u8; 32]) -> Result<(), VerifyError> {
+        use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+        use chacha20poly1305::ChaCha20Poly1305;
+
+        if self.message_type != MessageType::Encrypted {
+            return Err(VerifyError::NotEncrypted);
+        }
+        if self.payload.len() < ENCRYPTED_HEADER_LEN + AEAD_TAG_LEN {
+            return Err(VerifyError::MalformedCiphertext);
+        }
+        let inner_type = MessageType::from_u8(self.payload[0])
+            .filter(|t| *t != MessageType::Encrypted)
+            .ok_or(VerifyError::MalformedCiphertext)?;
+        let (nonce, ciphertext) = self.payload[1..].split_at(12);
+        let aad = self.payload_aad(inner_type);
+        let plaintext = ChaCha20Poly1305::new(key.into())
+            .decrypt(
+                nonce.into(),
+                Payload {
+                    msg: ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| VerifyError::DecryptionFailed)?;
+
+        self.payload = plaintext;
+        self.message_type = inner_type;
+        Ok(())
+    }
+
+    /// Verify signature and replay state, then decrypt an encrypted payload.
+    ///
+    /// Decryption runs only after `verify_authenticated` succeeds. Plaintext
+    /// envelopes pass through unchanged, so callers that require encryption must
+    /// check `message_type` themselves before verifying. Envelopes carrying a
+    /// `key_id` need [`MessageEnvelope::verify_and_decrypt_with_keyring`].
+    #[cfg(feature = "alloc")]
+    pub fn verify_and_decrypt(
+        &mut self,
+        replay_guard: &mut ReplayProtection,
+        current_time: u32,
+        key: &[u8; 32],
+    ) -> Result<(), VerifyError> {
+        self.verify_authenticated(replay_guard, current_time)?;
+        self.decrypt_if_encrypted(key)
+    }
+
+    /// Like [`MessageEnvelope::verify_and_decrypt`], but verifies against the
+    /// sender's key ring ([`MessageEnvelope::verify_authenticated_with_keyring`]),
+    /// so envelopes signed with a rotated key can be decrypted.
+    #[cfg(feature = "alloc")]
+    pub fn verify_and_decrypt_with_keyring(
+        &mut self,
+        replay_guard: &mut ReplayProtection,
+        current_time: u32,
+        keyring: &KeyRing,
+        key: &[u8; 32],
+    ) -> Result<(), VerifyError> {
+        self.verify_authenticated_with_keyring(replay_guard, current_time, keyring)?;
+        self.decrypt_if_encrypted(key)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn decrypt_if_encrypted(&mut self, key: &[u8; 32]) -> Result<(), VerifyError> {
+        if self.message_type == MessageType::Encrypted {
+            self.decrypt_payload(key)?;
+        }
+        Ok(())
+    }
+
+    /// Associated data for payload AEAD: domain tag + header fields.
+    #[cfg(feature = "alloc")]
+    fn payload_aad(&self, inner_type: MessageType) -> [u8; PAYLOAD_AAD_LEN] {
+        let mut aad = [0u8; PAYLOAD_AAD_LEN];
+        let tag = PAYLOAD_AAD_TAG;
+        aad[..tag.len()].copy_from_slice(tag);
+        let mut at = tag.len();
+        aad[at] = self.version.0;
+        aad[at + 1] = self.version.1;
+        aad[at + 2] = inner_type as u8;
+        at += 3;
+        aad[at..at + 32].copy_from_slice(&self.sender);
+        at += 32;
+        aad[at..at + 8].copy_from_slice(&self.sequence.to_le_bytes());
+        at += 8;
+        aad[at..at + 4].copy_from_slice(&self.timestamp.to_le_bytes());
+        aad
+    }
 }
 
+/// Domain separation tag for envelope payload AEAD associated data.
+#[cfg(feature = "alloc")]
+const PAYLOAD_AAD_TAG: &[u8] = b"swarmtorch.envelope.aead.v0";
+/// Tag + version (2) + inner type (1) + sender (32) + sequence (8) + timestamp (4).
+#[cfg(feature = "alloc")]
+const PAYLOAD_AAD_LEN: usize = PAYLOAD_AAD_TAG.len() + 2 + 1 + 32 + 8 + 4;
+/// Inner type byte + 96-bit nonce.
+#[cfg(feature = "alloc")]
+const ENCRYPTED_HEADER_LEN: usize = 1 + 12;
+/// Poly1305 tag length.
+#[cfg(feature = "alloc")]
+const AEAD_TAG_LEN: usize = 16;
+
 /// Message type discriminator
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum MessageType {
     /// Gradient update from training
     GradientUpdate = 0x01,
-    /// Full model checkpoint
+    /// Full model checkpoint (`swarm_torch_models::QuantizedModelState` is the compact payload)
     ModelCheckpoint = 0x02,
     /// Consensus vote
     ConsensusVote = 0x03,
@@ -233,6 +717,30 @@ pub enum MessageType {
     RoundComplete = 0x09,
     /// Error/rejection notification
     Error = 0xFF,
+    /// AEAD-sealed payload; the inner message type is the first payload byte.
+    ///
+    /// Declared last so existing variants keep their postcard indices.
+    Encrypted = 0x0A,
+}
+
+impl MessageType {
+    /// Parse a wire discriminant (`message_type as u8`).
+    pub const fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0x01 => Some(Self::GradientUpdate),
+            0x02 => Some(Self::ModelCheckpoint),
+            0x03 => Some(Self::ConsensusVote),
+            0x04 => Some(Self::Heartbeat),
+            0x05 => Some(Self::PeerDiscovery),
+            0x06 => Some(Self::TopologyChange),
+            0x07 => Some(Self::AggregationResult),
+            0x08 => Some(Self::RoundStart),
+            0x09 => Some(Self::RoundComplete),
+            0x0A => Some(Self::Encrypted),
+            0xFF => Some(Self::Error),
+            _ => None,
+        }
+    }
 }
 
 /// Heartbeat message
@@ -271,6 +779,18 @@ pub struct RoundStartMessage {
     pub aggregation_method: u8,
 }
 
+/// Skip one length-prefixed byte string, rejecting lengths above `max` or past the input.
+#[cfg(feature = "alloc")]
+fn skip_bounded_bytes(bytes: &[u8], max: usize) -> crate::Result<&[u8]> {
+    let (len, rest) =
+        postcard::take_from_bytes::<u64>(bytes).map_err(|_| crate::Error::InvalidMessage)?;
+    let len = usize::try_from(len).map_err(|_| crate::Error::InvalidMessage)?;
+    if len > max || len > rest.len() {
+        return Err(crate::Error::InvalidMessage);
+    }
+    Ok(&rest[len..])
+}
+
 /// Verification errors for authenticated messages
 #[cfg(feature = "alloc")]
 #[derive(Debug)]
@@ -299,6 +819,34 @@ pub enum VerifyError {
     InvalidSenderKey,
     /// System time lookup failed
     Time(TimeError),
+    /// Encrypted payload failed AEAD authentication (wrong key or tampered header/payload)
+    DecryptionFailed,
+    /// Encrypted payload framing is malformed (too short or unknown inner type)
+    MalformedCiphertext,
+    /// `encrypt_payload` called on an already-encrypted envelope
+    AlreadyEncrypted,
+    /// `decrypt_payload` called on a plaintext envelope
+    NotEncrypted,
+    /// `key_id` is not (or no longer) in the sender's key ring
+    UnknownKeyId {
+        /// Requested key id
+        key_id: u16,
+    },
+    /// Key ring identity does not match the envelope `sender`
+    KeyRingMismatch,
+    /// `compress_payload` called on an already-compressed envelope
+    AlreadyCompressed,
+    /// `decompress_payload` called on an uncompressed envelope
+    NotCompressed,
+    /// Deflate encoder failed while compressing the payload
+    CompressionFailed,
+    /// Compressed payload is corrupt or inflates beyond the payload limit
+    DecompressionFailed,
+    /// Sender exceeded its rate limit; the envelope was not verified
+    RateLimited {
+        /// Seconds until the sender's bucket has a token again
+        retry_after_secs: u32,
+    },
 }
 
 #[cfg(feature = "alloc")]
@@ -320,6 +868,19 @@ impl core::fmt::Display for VerifyError {
             }
             VerifyError::InvalidSenderKey => write!(f, "invalid sender key"),
             VerifyError::Time(e) => write!(f, "time error: {}", e),
+            VerifyError::DecryptionFailed => write!(f, "payload decryption failed"),
+            VerifyError::MalformedCiphertext => write!(f, "malformed encrypted payload"),
+            VerifyError::AlreadyEncrypted => write!(f, "payload is already encrypted"),
+            VerifyError::NotEncrypted => write!(f, "payload is not encrypted"),
+            VerifyError::UnknownKeyId { key_id } => write!(f, "unknown signing key id {}", key_id),
+            VerifyError::KeyRingMismatch => write!(f, "key ring does not match sender"),
+            VerifyError::AlreadyCompressed => write!(f, "payload is already compressed"),
+            VerifyError::NotCompressed => write!(f, "payload is not compressed"),
+            VerifyError::CompressionFailed => write!(f, "payload compression failed"),
+            VerifyError::DecompressionFailed => write!(f, "payload decompression failed"),
+            VerifyError::RateLimited { retry_after_secs } => {
+                write!(f, "rate limited; retry after {}s", retry_after_secs)
+            }
         }
     }
 }
@@ -358,23 +919,35 @@ impl core::fmt::Display for TimeError {
 impl std::error::Error for TimeError {}
 
 /// Replay+signature enforcement wrapper for incoming envelopes.
+///
+/// With a [`RateLimiter`] attached, each envelope first takes a token from its
+/// sender's bucket (whether or not it then verifies), so an abusive peer is
+/// cut off before the signature check.
 #[cfg(feature = "alloc")]
 pub struct AuthenticatedEnvelopeVerifier {
     replay_guard: ReplayProtection,
+    rate_limiter: Option<RateLimiter>,
 }
 
 #[cfg(feature = "alloc")]
 impl AuthenticatedEnvelopeVerifier {
     /// Create a verifier with default replay protection configuration.
     pub fn new() -> Self {
-        Self {
-            replay_guard: ReplayProtection::new(),
-        }
+        Self::with_replay_guard(ReplayProtection::new())
     }
 
     /// Create a verifier with caller-provided replay protection state.
     pub fn with_replay_guard(replay_guard: ReplayProtection) -> Self {
-        Self { replay_guard }
+        Self {
+            replay_guard,
+            rate_limiter: None,
+        }
+    }
+
+    /// Enforce per-peer rate limits before signature verification.
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
     }
 
     /// Verify and return the envelope using current wall clock time.
@@ -384,8 +957,7 @@ impl AuthenticatedEnvelopeVerifier {
         envelope: MessageEnvelope,
     ) -> Result<MessageEnvelope, VerifyError> {
         let now = MessageEnvelope::current_unix_secs().map_err(VerifyError::Time)?;
-        envelope.verify_authenticated(&mut self.replay_guard, now)?;
-        Ok(envelope)
+        self.verify_and_unwrap_with_time(envelope, now)
     }
 
     /// Verify and return the envelope with injected current time.
@@ -394,6 +966,14 @@ impl AuthenticatedEnvelopeVerifier {
         envelope: MessageEnvelope,
         current_time_secs: u32,
     ) -> Result<MessageEnvelope, VerifyError> {
+        if let Some(limiter) = &mut self.rate_limiter {
+            let sender = envelope
+                .sender_peer_id()
+                .map_err(|_| VerifyError::InvalidSenderKey)?;
+            limiter
+                .check(&sender, current_time_secs)
+                .map_err(|retry_after_secs| VerifyError::RateLimited { retry_after_secs })?;
+        }
         envelope.verify_authenticated(&mut self.replay_guard, current_time_secs)?;
         Ok(envelope)
     }
@@ -407,6 +987,11 @@ impl AuthenticatedEnvelopeVerifier {
     pub fn replay_guard_mut(&mut self) -> &mut ReplayProtection {
         &mut self.replay_guard
     }
+
+    /// Get mutable access to the rate limiter, if one is attached.
+    pub fn rate_limiter_mut(&mut self) -> Option<&mut RateLimiter> {
+        self.rate_limiter.as_mut()
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -415,3 +1000,85 @@ impl Default for AuthenticatedEnvelopeVerifier {
         Self::new()
     }
 }
+
+/// Size of the length prefix in front of every frame.
+pub const FRAME_HEADER_LEN: usize = 4;
+
+/// Prefix `payload` (typically serialized envelope bytes) with its `u32` big-endian length.
+///
+/// Fails with `Error::InvalidMessage` if the payload does not fit a `u32` length.
+#[cfg(feature = "alloc")]
+pub fn encode_frame(payload: &[u8]) -> crate::Result<Vec<u8>> {
+    let len = u32::try_from(payload.len()).map_err(|_| crate::Error::InvalidMessage)?;
+    let mut frame = Vec::with_capacity(FRAME_HEADER_LEN + payload.len());
+    frame.extend_from_slice(&len.to_be_bytes());
+    frame.extend_from_slice(payload);
+    Ok(frame)
+}
+
+/// Incremental decoder for [`encode_frame`] output.
+///
+/// Feed arbitrary chunks with [`FrameDecoder::extend`] and drain complete frames with
+/// [`FrameDecoder::next_frame`]. A declared length above `max_frame_len` is rejected as
+/// soon as the header is seen, before any buffer is sized for it; the decoder then stays
+/// failed, since the stream can no longer be resynchronized.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct FrameDecoder {
+    buf: Vec<u8>,
+    max_frame_len: usize,
+    failed: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl FrameDecoder {
+    /// Create a decoder accepting frames of at most `max_frame_len` payload bytes.
+    pub fn new(max_frame_len: usize) -> Self {
+        Self {
+            buf: Vec::new(),
+            max_frame_len,
+            failed: false,
+        }
+    }
+
+    /// Largest accepted payload length.
+    pub fn max_frame_len(&self) -> usize {
+        self.max_frame_len
+    }
+
+    /// Bytes buffered but not yet returned as a frame.
+    pub fn buffered_len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Append received bytes.
+    pub fn extend(&mut self, bytes: &[u8]) {
+        if !self.failed {
+            self.buf.extend_from_slice(bytes);
+        }
+    }
+
+    /// Pop the next complete frame, `Ok(None)` if more bytes are needed.
+    pub fn next_frame(&mut self) -> crate::Result<Option<Vec<u8>>> {
+        if self.failed {
+            return Err(crate::Error::InvalidMessage);
+        }
+        if self.buf.len() < FRAME_HEADER_LEN {
+            return Ok(None);
+        }
+        let mut header = [0u8; FRAME_HEADER_LEN];
+        header.copy_from_slice(&self.buf[..FRAME_HEADER_LEN]);
+        let len = u32::from_be_bytes(header) as usize;
+        if len > self.max_frame_len {
+            self.failed = true;
+            self.buf = Vec::new();
+            return Err(crate::Error::InvalidMessage);
+        }
+        if self.buf.len() - FRAME_HEADER_LEN < len {
+            return Ok(None);
+        }
+        let frame = self.buf[FRAME_HEADER_LEN..FRAME_HEADER_LEN + len].to_vec();
+        self.buf.drain(..FRAME_HEADER_LEN + len);
+        Ok(Some(frame))
+    }
+}
@@ -0,0 +1,190 @@
+//! Per-peer token-bucket rate limiting.
+//!
+//! [`RateLimiter`] caps how many envelopes each peer may submit for
+//! verification, so a peer flooding fresh sequence numbers cannot make the
+//! node spend unbounded CPU on signature checks. Attach it to an
+//! [`AuthenticatedEnvelopeVerifier`](crate::protocol::AuthenticatedEnvelopeVerifier),
+//! which consults it before the signature step. Time is supplied by the caller
+//! (unix seconds), keeping the limiter runtime-agnostic.
+//!
+//! Buckets are keyed by the envelope's claimed sender, which is not yet
+//! authenticated when the limiter runs. The table is therefore bounded: only
+//! fully refilled buckets (indistinguishable from fresh ones) are dropped to make
+//! room, and while every tracked bucket is still limiting, new senders are
+//! rejected until one refills. A flood of forged senders cannot grow memory or
+//! reset a throttled peer's bucket.
+//!
+//! **Limitation:** because the key is the *claimed* sender, anyone can forge
+//! envelopes naming an honest peer and drain that peer's bucket, delaying its
+//! genuine traffic. The limiter bounds verification CPU; it does not protect a
+//! peer from impersonation. Callers with an authenticated transport identity
+//! can call [`RateLimiter::check`] with that identity instead.
+
+use alloc::collections::BTreeMap;
+
+use swarm_torch_core::traits::PeerId;
+
+/// Default bucket-table capacity (peers).
+pub const DEFAULT_RATE_LIMITER_CAPACITY: usize = 1024;
+
+/// Token-bucket parameters for one peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    /// Tokens added per second
+    pub rate_per_sec: u32,
+    /// Bucket size: the largest burst accepted after an idle period
+    pub burst: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            rate_per_sec: 50,
+            burst: 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: u32,
+    last_refill: u32,
+}
+
+impl Bucket {
+    fn refill(&mut self, config: RateLimitConfig, now: u32) {
+        let elapsed = now.saturating_sub(self.last_refill);
+        let added = u64::from(elapsed) * u64::from(config.rate_per_sec);
+        self.tokens = (u64::from(self.tokens) + added).min(u64::from(config.burst)) as u32;
+        self.last_refill = self.last_refill.max(now);
+    }
+}
+
+/// Per-peer token buckets with a default configuration and per-peer overrides.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    default_config: RateLimitConfig,
+    overrides: BTreeMap<PeerId, RateLimitConfig>,
+    buckets: BTreeMap<PeerId, Bucket>,
+    capacity: usize,
+}
+
+impl RateLimiter {
+    /// Limiter applying `default_config` to every peer, tracking at most
+    /// [`DEFAULT_RATE_LIMITER_CAPACITY`] buckets.
+    pub fn new(default_config: RateLimitConfig) -> Self {
+        Self::with_capacity(default_config, DEFAULT_RATE_LIMITER_CAPACITY)
+    }
+
+    /// Like [`Self::new`], tracking at most `capacity` buckets (minimum 1).
+    pub fn with_capacity(default_config: RateLimitConfig, capacity: usize) -> Self {
+        Self {
+            default_config,
+            overrides: BTreeMap::new(),
+            buckets: BTreeMap::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Use `config` for `peer` instead of the default. Takes effect on the
+    /// peer's next [`Self::check`]; an existing bucket keeps its tokens (capped
+    /// to the new burst).
+    pub fn set_peer_limit(&mut self, peer: PeerId, config: RateLimitConfig) {
+        self.overrides.insert(peer, config);
+    }
+
+    /// Revert `peer` to the default configuration.
+    pub fn clear_peer_limit(&mut self, peer: &PeerId) {
+        self.overrides.remove(peer);
+    }
+
+    /// Configuration applied to `peer`.
+    pub fn config_for(&self, peer: &PeerId) -> RateLimitConfig {
+        self.overrides
+            .get(peer)
+            .copied()
+            .unwrap_or(self.default_config)
+    }
+
+    /// Take one token for `peer` at `now` (unix seconds).
+    ///
+    /// On an empty bucket returns `Err(retry_after_secs)`: the wait until a
+    /// token is available, or `u32::MAX` if the peer's rate is zero. A peer
+    /// without a bucket is also rejected while the table is full of buckets
+    /// that are still refilling, with the wait until one of them is full.
+    pub fn check(&mut self, peer: &PeerId, now: u32) -> Result<(), u32> {
+        let config = self.config_for(peer);
+        if !self.buckets.contains_key(peer) {
+            self.make_room(now)?;
+        }
+        let bucket = self.buckets.entry(*peer).or_insert(Bucket {
+            tokens: config.burst,
+            last_refill: now,
+        });
+        bucket.refill(config, now);
+        if bucket.tokens == 0 {
+            return Err(if config.rate_per_sec == 0 {
+                u32::MAX
+            } else {
+                1
+            });
+        }
+        bucket.tokens -= 1;
+        Ok(())
+    }
+
+    /// Tokens `peer` has left at `now`, without taking one.
+    pub fn available(&self, peer: &PeerId, now: u32) -> u32 {
+        let config = self.config_for(peer);
+        match self.buckets.get(peer) {
+            Some(bucket) => {
+                let mut bucket = *bucket;
+                bucket.refill(config, now);
+                bucket.tokens
+            }
+            None => config.burst,
+        }
+    }
+
+    /// Number of peers with a tracked bucket.
+    pub fn len(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Whether no bucket is tracked.
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+
+    /// Free a slot for a new bucket by dropping buckets that have fully
+    /// refilled (a fresh bucket is identical). A bucket that is still limiting
+    /// is never evicted; if none can be dropped, returns `Err` with the seconds
+    /// until the first one is full (`u32::MAX` if none ever refills).
+    fn make_room(&mut self, now: u32) -> Result<(), u32> {
+        if self.buckets.len() < self.capacity {
+            return Ok(());
+        }
+        let (default_config, overrides) = (self.default_config, &self.overrides);
+        let mut retry_after = u32::MAX;
+        self.buckets.retain(|peer, bucket| {
+            let config = overrides.get(peer).copied().unwrap_or(default_config);
+            let mut bucket = *bucket;
+            bucket.refill(config, now);
+            let missing = config.burst - bucket.tokens;
+            if missing > 0 && config.rate_per_sec > 0 {
+                retry_after = retry_after.min(missing.div_ceil(config.rate_per_sec));
+            }
+            missing > 0
+        });
+        if self.buckets.len() >= self.capacity {
+            return Err(retry_after);
+        }
+        Ok(())
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(RateLimitConfig::default())
+    }
+}
@@ -0,0 +1,317 @@
+//! TCP transport (std, tokio)
+//!
+//! Wire format per connection:
+//! - handshake: the dialing side's 32-byte `PeerId`
+//! - frames: [`encode_frame`] output carrying a serialized `MessageEnvelope`
+//!
+//! Outbound connections are pooled per `PeerId` and reused across sends; a send on a
+//! broken pooled connection reconnects once before failing.
+//!
+//! Inbound connections must complete the handshake within the IO timeout. At most
+//! [`INBOX_CAPACITY`] received frames wait for `recv`; beyond that, connection readers
+//! stop reading and TCP flow control pushes back on the senders. Reader tasks are
+//! aborted when the transport is dropped.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use alloc::vec::Vec;
+use swarm_torch_core::traits::PeerId;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::{AbortHandle, JoinHandle};
+
+use crate::protocol::{encode_frame, FrameDecoder};
+use crate::traits::{
+    BandwidthClass, BroadcastStats, ReliabilityClass, SwarmTransport, TransportCapabilities,
+};
+use crate::{Error, Result};
+
+/// Default upper bound for a single frame (16 MiB).
+pub const DEFAULT_MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// Default connect/handshake/write timeout.
+pub const DEFAULT_IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Received frames buffered for `recv` before readers apply backpressure.
+pub const INBOX_CAPACITY: usize = 1024;
+
+type Inbox = mpsc::Receiver<(PeerId, Vec<u8>)>;
+
+/// Abort handles of live inbound reader tasks, keyed by connection number.
+type Readers = Arc<StdMutex<HashMap<u64, AbortHandle>>>;
+
+/// TCP implementation of [`SwarmTransport`].
+#[derive(Debug)]
+pub struct TcpTransport {
+    local_peer: PeerId,
+    local_addr: SocketAddr,
+    peers: StdMutex<HashMap<PeerId, SocketAddr>>,
+    connections: Mutex<HashMap<PeerId, Arc<Mutex<OwnedWriteHalf>>>>,
+    inbox: Mutex<Inbox>,
+    accept_task: JoinHandle<()>,
+    readers: Readers,
+    max_frame_len: usize,
+    io_timeout: Duration,
+}
+
+impl TcpTransport {
+    /// Listen on `addr` as `local_peer`.
+    pub async fn bind(local_peer: PeerId, addr: SocketAddr) -> Result<Self> {
+        Self::bind_with_limits(local_peer, addr, DEFAULT_MAX_FRAME_LEN, DEFAULT_IO_TIMEOUT).await
+    }
+
+    /// Like [`Self::bind`], with explicit frame-size and IO-timeout limits.
+    pub async fn bind_with_limits(
+        local_peer: PeerId,
+        addr: SocketAddr,
+        max_frame_len: usize,
+        io_timeout: Duration,
+    ) -> Result<Self> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|_| Error::ConnectionFailed)?;
+        let local_addr = listener.local_addr().map_err(|_| Error::ConnectionFailed)?;
+        let (inbox_tx, inbox_rx) = mpsc::channel(INBOX_CAPACITY);
+        let readers = Readers::default();
+        let accept_task = tokio::spawn(accept_loop(
+            listener,
+            inbox_tx,
+            readers.clone(),
+            max_frame_len,
+            io_timeout,
+        ));
+        Ok(Self {
+            local_peer,
+            local_addr,
+            peers: StdMutex::new(HashMap::new()),
+            connections: Mutex::new(HashMap::new()),
+            inbox: Mutex::new(inbox_rx),
+            accept_task,
+            readers,
+            max_frame_len,
+            io_timeout,
+        })
+    }
+
+    /// Bind `num_nodes` transports on loopback, each knowing every other node.
+    pub async fn local_cluster(num_nodes: usize) -> Result<Vec<Self>> {
+        let loopback = SocketAddr::from(([127, 0, 0, 1], 0));
+        let mut nodes = Vec::with_capacity(num_nodes);
+        for i in 0..num_nodes {
+            nodes.push(Self::bind(local_cluster_peer_id(i), loopback).await?);
+        }
+        let directory: Vec<(PeerId, SocketAddr)> = nodes
+            .iter()
+            .map(|node| (node.local_peer, node.local_addr))
+            .collect();
+        for node in &nodes {
+            for (peer, addr) in &directory {
+                if *peer != node.local_peer {
+                    node.add_peer(*peer, *addr);
+                }
+            }
+        }
+        Ok(nodes)
+    }
+
+    /// This node's peer id (sent as the connection handshake).
+    pub fn local_peer(&self) -> PeerId {
+        self.local_peer
+    }
+
+    /// Address the listener is bound to.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Register (or move) a peer's listening address.
+    pub fn add_peer(&self, peer: PeerId, addr: SocketAddr) {
+        self.peers_lock().insert(peer, addr);
+    }
+
+    fn peers_lock(&self) -> std::sync::MutexGuard<'_, HashMap<PeerId, SocketAddr>> {
+        self.peers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    async fn connection(&self, peer: PeerId) -> Result<Arc<Mutex<OwnedWriteHalf>>> {
+        if let Some(conn) = self.connections.lock().await.get(&peer) {
+            return Ok(conn.clone());
+        }
+        let addr = *self.peers_lock().get(&peer).ok_or(Error::PeerNotFound)?;
+        let stream = tokio::time::timeout(self.io_timeout, TcpStream::connect(addr))
+            .await
+            .map_err(|_| Error::Timeout)?
+            .map_err(|_| Error::ConnectionFailed)?;
+        let _ = stream.set_nodelay(true);
+        // Inbound half is unused: peers answer over their own outbound connection.
+        let (_, mut writer) = stream.into_split();
+        self.with_timeout(writer.write_all(self.local_peer.as_bytes()))
+            .await
+            .map_err(|_| Error::ConnectionFailed)?;
+        let conn = Arc::new(Mutex::new(writer));
+        self.connections.lock().await.insert(peer, conn.clone());
+        Ok(conn)
+    }
+
+    async fn write_frame(&self, conn: &Mutex<OwnedWriteHalf>, msg: &[u8]) -> Result<()> {
+        let frame = encode_frame(msg)?;
+        let mut writer = conn.lock().await;
+        self.with_timeout(async {
+            writer.write_all(&frame).await?;
+            writer.flush().await
+        })
+        .await
+    }
+
+    async fn with_timeout<F>(&self, io: F) -> Result<()>
+    where
+        F: core::future::Future<Output = std::io::Result<()>>,
+    {
+        match tokio::time::timeout(self.io_timeout, io).await {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(_)) => Err(Error::SendFailed),
+            Err(_) => Err(Error::Timeout),
+        }
+    }
+}
+
+impl Drop for TcpTransport {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+        for (_, reader) in lock_readers(&self.readers).drain() {
+            reader.abort();
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SwarmTransport for TcpTransport {
+    async fn send(&self, peer: PeerId, msg: &[u8]) -> Result<()> {
+        self.capabilities().check_payload_len(msg.len())?;
+        let conn = self.connection(peer).await?;
+        if self.write_frame(&conn, msg).await.is_ok() {
+            return Ok(());
+        }
+        // The pooled connection may be stale (peer restarted): reconnect once.
+        self.connections.lock().await.remove(&peer);
+        let conn = self.connection(peer).await?;
+        let result = self.write_frame(&conn, msg).await;
+        if result.is_err() {
+            self.connections.lock().await.remove(&peer);
+        }
+        result
+    }
+
+    async fn recv(&self) -> Result<(PeerId, Vec<u8>)> {
+        self.inbox
+            .lock()
+            .await
+            .recv()
+            .await
+            .ok_or(Error::ReceiveFailed)
+    }
+
+    async fn broadcast(&self, msg: &[u8]) -> Result<BroadcastStats> {
+        let peers: Vec<PeerId> = self.peers_lock().keys().copied().collect();
+        let mut stats = BroadcastStats::default();
+        for peer in peers {
+            stats.peers_sent += 1;
+            match self.send(peer, msg).await {
+                // TCP acknowledges delivery to the peer's socket.
+                Ok(()) => stats.confirmed += 1,
+                Err(_) => stats.failed += 1,
+            }
+        }
+        Ok(stats)
+    }
+
+    async fn discover(&self) -> Result<Vec<PeerId>> {
+        let mut peers: Vec<PeerId> = self.peers_lock().keys().copied().collect();
+        peers.sort();
+        Ok(peers)
+    }
+
+    fn capabilities(&self) -> TransportCapabilities {
+        TransportCapabilities {
+            reliability: ReliabilityClass::Reliable,
+            bandwidth_class: BandwidthClass::High,
+            max_message_size: self.max_frame_len,
+            supports_multicast: false,
+        }
+    }
+}
+
+/// Deterministic peer ids for [`TcpTransport::local_cluster`].
+fn local_cluster_peer_id(index: usize) -> PeerId {
+    let mut seed = [0u8; 40];
+    seed[..12].copy_from_slice(b"tcp-peer-v1:");
+    seed[12..20].copy_from_slice(&(index as u64).to_le_bytes());
+    PeerId::from_public_key(&seed)
+}
+
+fn lock_readers(readers: &Readers) -> std::sync::MutexGuard<'_, HashMap<u64, AbortHandle>> {
+    readers
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+async fn accept_loop(
+    listener: TcpListener,
+    inbox: mpsc::Sender<(PeerId, Vec<u8>)>,
+    readers: Readers,
+    max_frame_len: usize,
+    io_timeout: Duration,
+) {
+    let next_id = AtomicU64::new(0);
+    while let Ok((stream, _)) = listener.accept().await {
+        let inbox = inbox.clone();
+        let id = next_id.fetch_add(1, Ordering::Relaxed);
+        let task_readers = readers.clone();
+        // Hold the lock across spawn so the task cannot deregister before it is registered.
+        let mut live = lock_readers(&readers);
+        let task = tokio::spawn(async move {
+            // Any read error, handshake timeout, or oversized frame closes the connection.
+            let _ = read_connection(stream, inbox, max_frame_len, io_timeout).await;
+            lock_readers(&task_readers).remove(&id);
+        });
+        live.insert(id, task.abort_handle());
+    }
+}
+
+async fn read_connection(
+    mut stream: TcpStream,
+    inbox: mpsc::Sender<(PeerId, Vec<u8>)>,
+    max_frame_len: usize,
+    io_timeout: Duration,
+) -> std::io::Result<()> {
+    let mut peer = [0u8; 32];
+    tokio::time::timeout(io_timeout, stream.read_exact(&mut peer))
+        .await
+        .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))??;
+    let peer = PeerId::new(peer);
+    let mut decoder = FrameDecoder::new(max_frame_len);
+    let mut chunk = [0u8; 8 * 1024];
+    loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        decoder.extend(&chunk[..n]);
+        while let Some(frame) = decoder
+            .next_frame()
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::InvalidData))?
+        {
+            if inbox.send((peer, frame)).await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
@@ -45,6 +45,24 @@ pub struct TransportCapabilities {
     pub supports_multicast: bool,
 }
 
+impl TransportCapabilities {
+    /// Largest serialized envelope the transport carries in one `send` (its MTU).
+    pub fn max_payload_bytes(&self) -> usize {
+        self.max_message_size
+    }
+
+    /// Reject `len` bytes with `Error::InvalidMessage` if it exceeds the MTU.
+    ///
+    /// Oversized messages must be split with [`crate::fragment::fragment`] instead of
+    /// being handed to a link that would truncate them.
+    pub fn check_payload_len(&self, len: usize) -> Result<()> {
+        if len > self.max_payload_bytes() {
+            return Err(crate::Error::InvalidMessage);
+        }
+        Ok(())
+    }
+}
+
 /// Statistics from a broadcast operation
 #[derive(Debug, Clone, Default)]
 pub struct BroadcastStats {
@@ -76,6 +94,83 @@ pub trait SwarmTransport: Send + Sync {
     fn capabilities(&self) -> TransportCapabilities;
 }
 
+/// Wrapper enforcing the inner transport's MTU on `send` and `broadcast`.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct MtuEnforced<T> {
+    inner: T,
+}
+
+#[cfg(feature = "std")]
+impl<T: SwarmTransport> MtuEnforced<T> {
+    /// Wrap `inner`.
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    /// The wrapped transport.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Unwrap the transport.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+#[async_trait::async_trait]
+impl<T: SwarmTransport> SwarmTransport for MtuEnforced<T> {
+    async fn send(&self, peer: PeerId, msg: &[u8]) -> Result<()> {
+        self.inner.capabilities().check_payload_len(msg.len())?;
+        self.inner.send(peer, msg).await
+    }
+
+    async fn recv(&self) -> Result<(PeerId, Vec<u8>)> {
+        self.inner.recv().await
+    }
+
+    async fn broadcast(&self, msg: &[u8]) -> Result<BroadcastStats> {
+        self.inner.capabilities().check_payload_len(msg.len())?;
+        self.inner.broadcast(msg).await
+    }
+
+    async fn discover(&self) -> Result<Vec<PeerId>> {
+        self.inner.discover().await
+    }
+
+    fn capabilities(&self) -> TransportCapabilities {
+        self.inner.capabilities()
+    }
+}
+
+/// Shared transports (e.g. one handle held by a [`FallbackTransport`](crate::FallbackTransport)
+/// and one by the caller).
+#[cfg(feature = "std")]
+#[async_trait::async_trait]
+impl<T: SwarmTransport + ?Sized> SwarmTransport for std::sync::Arc<T> {
+    async fn send(&self, peer: PeerId, msg: &[u8]) -> Result<()> {
+        (**self).send(peer, msg).await
+    }
+
+    async fn recv(&self) -> Result<(PeerId, Vec<u8>)> {
+        (**self).recv().await
+    }
+
+    async fn broadcast(&self, msg: &[u8]) -> Result<BroadcastStats> {
+        (**self).broadcast(msg).await
+    }
+
+    async fn discover(&self) -> Result<Vec<PeerId>> {
+        (**self).discover().await
+    }
+
+    fn capabilities(&self) -> TransportCapabilities {
+        (**self).capabilities()
+    }
+}
+
 /// Synchronous transport trait for no_std environments
 #[cfg(not(feature = "std"))]
 pub trait SwarmTransport: Send + Sync {
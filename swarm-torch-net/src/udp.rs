@@ -0,0 +1,458 @@
+//! UDP transport (std, tokio)
+//!
+//! Datagram layout: `kind: u8 | sender PeerId (32) | [ack_id: u32 BE] | envelope`.
+//! The header sender is informational only and is never trusted on receive.
+//! `ack_id` is present for reliable data and acks only.
+//!
+//! UDP may drop, duplicate, and reorder, so every received datagram must carry a signed
+//! `MessageEnvelope`; it is verified with [`ReplayProtection`] before `recv` sees it, and
+//! `recv` reports the peer id derived from the envelope's signing key, not the header.
+//! Duplicates (including retransmits whose ack was lost) and unverifiable envelopes are
+//! dropped and counted. With [`RetransmitConfig`], `MessageType::GradientUpdate` sends
+//! wait for an ack and retransmit; other message types stay fire-and-forget. Acks carry
+//! a random id, are sent only once the envelope verified and was queued (or is a signed
+//! duplicate), and are accepted only from the address the data was sent to.
+//!
+//! At most [`INBOX_CAPACITY`] verified messages wait for `recv`; further datagrams are
+//! dropped unacked (and counted) until the inbox drains.
+//!
+//! Each envelope must fit one datagram; split larger ones with [`crate::fragment`].
+
+use std::collections::hash_map::{Entry, RandomState};
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use alloc::vec::Vec;
+use swarm_torch_core::replay::{ReplayError, ReplayProtection};
+use swarm_torch_core::traits::PeerId;
+use tokio::net::UdpSocket;
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::task::JoinHandle;
+
+use crate::protocol::{
+    AuthenticatedEnvelopeVerifier, EnvelopeLimits, MessageEnvelope, MessageType, VerifyError,
+};
+use crate::traits::{
+    BandwidthClass, BroadcastStats, ReliabilityClass, SwarmTransport, TransportCapabilities,
+};
+use crate::{Error, Result};
+
+/// Default datagram budget, conservative for typical path MTUs.
+pub const DEFAULT_MAX_DATAGRAM_LEN: usize = 1200;
+
+/// Verified messages buffered for `recv` before further datagrams are dropped.
+pub const INBOX_CAPACITY: usize = 1024;
+
+const KIND_DATA: u8 = 0;
+const KIND_RELIABLE: u8 = 1;
+const KIND_ACK: u8 = 2;
+const SENDER_LEN: usize = 32;
+const DATA_HEADER_LEN: usize = 1 + SENDER_LEN;
+const RELIABLE_HEADER_LEN: usize = DATA_HEADER_LEN + 4;
+
+/// Ack/retransmit settings for `GradientUpdate` envelopes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetransmitConfig {
+    /// Time to wait for an ack before retransmitting.
+    pub ack_timeout: Duration,
+    /// Retransmissions after the first attempt.
+    pub max_retries: u32,
+}
+
+impl Default for RetransmitConfig {
+    fn default() -> Self {
+        Self {
+            ack_timeout: Duration::from_millis(200),
+            max_retries: 3,
+        }
+    }
+}
+
+/// Ack waiters keyed by the destination address and ack id of the reliable send.
+type PendingAcks = Arc<StdMutex<HashMap<(SocketAddr, u32), oneshot::Sender<()>>>>;
+
+#[derive(Debug, Default)]
+struct RecvCounters {
+    rejected_replays: AtomicU64,
+    rejected_invalid: AtomicU64,
+    dropped_inbox_full: AtomicU64,
+}
+
+/// UDP implementation of [`SwarmTransport`].
+#[derive(Debug)]
+pub struct UdpTransport {
+    local_peer: PeerId,
+    local_addr: SocketAddr,
+    socket: Arc<UdpSocket>,
+    peers: StdMutex<HashMap<PeerId, SocketAddr>>,
+    inbox: Mutex<mpsc::Receiver<(PeerId, Vec<u8>)>>,
+    pending_acks: PendingAcks,
+    ack_id_keys: RandomState,
+    ack_id_counter: AtomicU64,
+    counters: Arc<RecvCounters>,
+    recv_task: JoinHandle<()>,
+    max_datagram_len: usize,
+    retransmit: Option<RetransmitConfig>,
+}
+
+impl UdpTransport {
+    /// Bind `addr` as `local_peer` with default replay protection and no retransmits.
+    pub async fn bind(local_peer: PeerId, addr: SocketAddr) -> Result<Self> {
+        Self::bind_with_replay_guard(local_peer, addr, ReplayProtection::new()).await
+    }
+
+    /// Like [`Self::bind`], verifying received envelopes against `replay_guard`.
+    pub async fn bind_with_replay_guard(
+        local_peer: PeerId,
+        addr: SocketAddr,
+        replay_guard: ReplayProtection,
+    ) -> Result<Self> {
+        let socket = Arc::new(
+            UdpSocket::bind(addr)
+                .await
+                .map_err(|_| Error::ConnectionFailed)?,
+        );
+        let local_addr = socket.local_addr().map_err(|_| Error::ConnectionFailed)?;
+        let (inbox_tx, inbox_rx) = mpsc::channel(INBOX_CAPACITY);
+        let pending_acks = PendingAcks::default();
+        let counters = Arc::new(RecvCounters::default());
+        let recv_task = tokio::spawn(recv_loop(
+            socket.clone(),
+            local_peer,
+            AuthenticatedEnvelopeVerifier::with_replay_guard(replay_guard),
+            inbox_tx,
+            pending_acks.clone(),
+            counters.clone(),
+        ));
+        Ok(Self {
+            local_peer,
+            local_addr,
+            socket,
+            peers: StdMutex::new(HashMap::new()),
+            inbox: Mutex::new(inbox_rx),
+            pending_acks,
+            ack_id_keys: RandomState::new(),
+            ack_id_counter: AtomicU64::new(0),
+            counters,
+            recv_task,
+            max_datagram_len: DEFAULT_MAX_DATAGRAM_LEN,
+            retransmit: None,
+        })
+    }
+
+    /// Bind `num_nodes` transports on loopback, each knowing every other node.
+    pub async fn local_cluster(num_nodes: usize) -> Result<Vec<Self>> {
+        let loopback = SocketAddr::from(([127, 0, 0, 1], 0));
+        let mut nodes = Vec::with_capacity(num_nodes);
+        for i in 0..num_nodes {
+            nodes.push(Self::bind(local_cluster_peer_id(i), loopback).await?);
+        }
+        let directory: Vec<(PeerId, SocketAddr)> = nodes
+            .iter()
+            .map(|node| (node.local_peer, node.local_addr))
+            .collect();
+        for node in &nodes {
+            for (peer, addr) in &directory {
+                if *peer != node.local_peer {
+                    node.add_peer(*peer, *addr);
+                }
+            }
+        }
+        Ok(nodes)
+    }
+
+    /// Ack and retransmit `GradientUpdate` sends.
+    pub fn with_retransmit(mut self, config: RetransmitConfig) -> Self {
+        self.retransmit = Some(config);
+        self
+    }
+
+    /// Override the datagram budget (header included).
+    pub fn with_max_datagram_len(mut self, bytes: usize) -> Self {
+        self.max_datagram_len = bytes.max(RELIABLE_HEADER_LEN + 1);
+        self
+    }
+
+    /// This node's peer id (carried in every datagram).
+    pub fn local_peer(&self) -> PeerId {
+        self.local_peer
+    }
+
+    /// Address the socket is bound to.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Register (or move) a peer's address.
+    pub fn add_peer(&self, peer: PeerId, addr: SocketAddr) {
+        self.peers_lock().insert(peer, addr);
+    }
+
+    /// Received envelopes dropped as duplicate or retrograde sequence numbers.
+    pub fn rejected_replays(&self) -> u64 {
+        self.counters.rejected_replays.load(Ordering::Relaxed)
+    }
+
+    /// Received datagrams dropped as malformed or failing verification (other than replay).
+    pub fn rejected_invalid(&self) -> u64 {
+        self.counters.rejected_invalid.load(Ordering::Relaxed)
+    }
+
+    /// Verified envelopes dropped (and left unacked) because the inbox was full.
+    pub fn dropped_inbox_full(&self) -> u64 {
+        self.counters.dropped_inbox_full.load(Ordering::Relaxed)
+    }
+
+    fn peers_lock(&self) -> std::sync::MutexGuard<'_, HashMap<PeerId, SocketAddr>> {
+        self.peers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn acks_lock(
+        &self,
+    ) -> std::sync::MutexGuard<'_, HashMap<(SocketAddr, u32), oneshot::Sender<()>>> {
+        self.pending_acks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    async fn send_datagram(&self, datagram: &[u8], addr: SocketAddr) -> Result<()> {
+        self.socket
+            .send_to(datagram, addr)
+            .await
+            .map(|_| ())
+            .map_err(|_| Error::SendFailed)
+    }
+
+    async fn send_reliable(
+        &self,
+        msg: &[u8],
+        addr: SocketAddr,
+        config: RetransmitConfig,
+    ) -> Result<()> {
+        let (ack_tx, mut ack_rx) = oneshot::channel();
+        let ack_id = self.register_ack_waiter(addr, ack_tx);
+        let datagram = encode_datagram(KIND_RELIABLE, &self.local_peer, Some(ack_id), msg);
+
+        let mut result = Err(Error::Timeout);
+        for _ in 0..=config.max_retries {
+            if let Err(err) = self.send_datagram(&datagram, addr).await {
+                result = Err(err);
+                break;
+            }
+            if tokio::time::timeout(config.ack_timeout, &mut ack_rx)
+                .await
+                .is_ok()
+            {
+                result = Ok(());
+                break;
+            }
+        }
+        self.acks_lock().remove(&(addr, ack_id));
+        result
+    }
+
+    /// Register `waiter` under a fresh, unpredictable ack id for `addr`.
+    ///
+    /// Ids are a keyed hash of a counter so an off-path sender cannot guess a
+    /// pending id to forge an ack.
+    fn register_ack_waiter(&self, addr: SocketAddr, waiter: oneshot::Sender<()>) -> u32 {
+        let mut acks = self.acks_lock();
+        loop {
+            let counter = self.ack_id_counter.fetch_add(1, Ordering::Relaxed);
+            let ack_id = self.ack_id_keys.hash_one(counter) as u32;
+            if let Entry::Vacant(slot) = acks.entry((addr, ack_id)) {
+                slot.insert(waiter);
+                return ack_id;
+            }
+        }
+    }
+}
+
+impl Drop for UdpTransport {
+    fn drop(&mut self) {
+        self.recv_task.abort();
+    }
+}
+
+#[async_trait::async_trait]
+impl SwarmTransport for UdpTransport {
+    async fn send(&self, peer: PeerId, msg: &[u8]) -> Result<()> {
+        self.capabilities().check_payload_len(msg.len())?;
+        let addr = *self.peers_lock().get(&peer).ok_or(Error::PeerNotFound)?;
+        match self.retransmit {
+            Some(config) if is_gradient_update(msg) => self.send_reliable(msg, addr, config).await,
+            _ => {
+                let datagram = encode_datagram(KIND_DATA, &self.local_peer, None, msg);
+                self.send_datagram(&datagram, addr).await
+            }
+        }
+    }
+
+    async fn recv(&self) -> Result<(PeerId, Vec<u8>)> {
+        self.inbox
+            .lock()
+            .await
+            .recv()
+            .await
+            .ok_or(Error::ReceiveFailed)
+    }
+
+    async fn broadcast(&self, msg: &[u8]) -> Result<BroadcastStats> {
+        let peers: Vec<PeerId> = self.peers_lock().keys().copied().collect();
+        let reliable = self.retransmit.is_some() && is_gradient_update(msg);
+        let mut stats = BroadcastStats::default();
+        for peer in peers {
+            stats.peers_sent += 1;
+            match self.send(peer, msg).await {
+                // Only acked sends are confirmed deliveries.
+                Ok(()) if reliable => stats.confirmed += 1,
+                Ok(()) => {}
+                Err(_) => stats.failed += 1,
+            }
+        }
+        Ok(stats)
+    }
+
+    async fn discover(&self) -> Result<Vec<PeerId>> {
+        let mut peers: Vec<PeerId> = self.peers_lock().keys().copied().collect();
+        peers.sort();
+        Ok(peers)
+    }
+
+    fn capabilities(&self) -> TransportCapabilities {
+        TransportCapabilities {
+            reliability: if self.retransmit.is_some() {
+                ReliabilityClass::AtLeastOnce
+            } else {
+                ReliabilityClass::BestEffort
+            },
+            bandwidth_class: BandwidthClass::Medium,
+            max_message_size: self.max_datagram_len - RELIABLE_HEADER_LEN,
+            supports_multicast: false,
+        }
+    }
+}
+
+/// Deterministic peer ids for [`UdpTransport::local_cluster`].
+fn local_cluster_peer_id(index: usize) -> PeerId {
+    let mut seed = [0u8; 40];
+    seed[..12].copy_from_slice(b"udp-peer-v1:");
+    seed[12..20].copy_from_slice(&(index as u64).to_le_bytes());
+    PeerId::from_public_key(&seed)
+}
+
+fn is_gradient_update(msg: &[u8]) -> bool {
+    MessageEnvelope::deserialize_bounded(msg, EnvelopeLimits::default())
+        .is_ok_and(|envelope| envelope.message_type == MessageType::GradientUpdate)
+}
+
+fn encode_datagram(kind: u8, sender: &PeerId, ack_id: Option<u32>, body: &[u8]) -> Vec<u8> {
+    let mut datagram = Vec::with_capacity(RELIABLE_HEADER_LEN + body.len());
+    datagram.push(kind);
+    datagram.extend_from_slice(sender.as_bytes());
+    if let Some(ack_id) = ack_id {
+        datagram.extend_from_slice(&ack_id.to_be_bytes());
+    }
+    datagram.extend_from_slice(body);
+    datagram
+}
+
+fn decode_ack_id(datagram: &[u8]) -> Option<u32> {
+    let bytes = datagram.get(DATA_HEADER_LEN..RELIABLE_HEADER_LEN)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+async fn recv_loop(
+    socket: Arc<UdpSocket>,
+    local_peer: PeerId,
+    mut verifier: AuthenticatedEnvelopeVerifier,
+    inbox: mpsc::Sender<(PeerId, Vec<u8>)>,
+    pending_acks: PendingAcks,
+    counters: Arc<RecvCounters>,
+) {
+    let mut buf = alloc::vec![0u8; 64 * 1024];
+    loop {
+        // ICMP errors from earlier sends surface here on some platforms; keep reading.
+        let Ok((n, from)) = socket.recv_from(&mut buf).await else {
+            continue;
+        };
+        let datagram = &buf[..n];
+        if datagram.len() < DATA_HEADER_LEN {
+            counters.rejected_invalid.fetch_add(1, Ordering::Relaxed);
+            continue;
+        }
+        // The header sender is advisory; `recv` reports the verified signer.
+
+        let (body, ack_id) = match datagram[0] {
+            KIND_DATA => (&datagram[DATA_HEADER_LEN..], None),
+            KIND_RELIABLE => {
+                let Some(ack_id) = decode_ack_id(datagram) else {
+                    counters.rejected_invalid.fetch_add(1, Ordering::Relaxed);
+                    continue;
+                };
+                (&datagram[RELIABLE_HEADER_LEN..], Some(ack_id))
+            }
+            KIND_ACK => {
+                // Only the address a reliable send went to can complete it.
+                if let Some(ack_id) = decode_ack_id(datagram) {
+                    let waiter = pending_acks
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .remove(&(from, ack_id));
+                    if let Some(waiter) = waiter {
+                        let _ = waiter.send(());
+                    }
+                }
+                continue;
+            }
+            _ => {
+                counters.rejected_invalid.fetch_add(1, Ordering::Relaxed);
+                continue;
+            }
+        };
+
+        let verified = MessageEnvelope::deserialize_bounded(body, EnvelopeLimits::default())
+            .map_err(|_| None)
+            .and_then(|envelope| verifier.verify_and_unwrap(envelope).map_err(Some));
+        let deliver_ack = match verified {
+            Ok(envelope) => {
+                // Verification already rejected keys that do not derive a peer id.
+                let Ok(sender) = envelope.sender_peer_id() else {
+                    counters.rejected_invalid.fetch_add(1, Ordering::Relaxed);
+                    continue;
+                };
+                match inbox.try_send((sender, body.to_vec())) {
+                    Ok(()) => true,
+                    Err(mpsc::error::TrySendError::Full(_)) => {
+                        // Leave it unacked so a reliable sender retransmits later.
+                        counters.dropped_inbox_full.fetch_add(1, Ordering::Relaxed);
+                        false
+                    }
+                    Err(mpsc::error::TrySendError::Closed(_)) => return,
+                }
+            }
+            // A signed duplicate (typically a retransmit whose first ack was lost):
+            // the signature checked out, so ack it again to stop the retries.
+            Err(Some(VerifyError::Replay(
+                ReplayError::Replay { .. } | ReplayError::TooOld { .. },
+            ))) => {
+                counters.rejected_replays.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+            Err(_) => {
+                counters.rejected_invalid.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+        };
+        if let (true, Some(ack_id)) = (deliver_ack, ack_id) {
+            let ack = encode_datagram(KIND_ACK, &local_peer, Some(ack_id), &[]);
+            let _ = socket.send_to(&ack, from).await;
+        }
+    }
+}
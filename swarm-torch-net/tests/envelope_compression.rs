@@ -0,0 +1,130 @@
+//! Integration tests for the signed envelope compression flag.
+#![cfg(feature = "compression")]
+
+use swarm_torch_core::crypto::{KeyPair, MessageAuth};
+use swarm_torch_core::replay::ReplayProtection;
+use swarm_torch_net::protocol::{
+    EnvelopeLimits, MessageEnvelope, MessageType, VerifyError, ENVELOPE_FLAG_COMPRESSED,
+};
+
+fn sign(auth: &MessageAuth, envelope: MessageEnvelope) -> MessageEnvelope {
+    let sig = auth.sign_with_flags(
+        envelope.version,
+        envelope.message_type as u8,
+        envelope.flags,
+        envelope.sequence,
+        envelope.timestamp,
+        &envelope.payload,
+    );
+    envelope.with_signature(sig.as_bytes().to_vec())
+}
+
+fn compressed_heartbeat(keypair: &KeyPair, payload: &[u8]) -> MessageEnvelope {
+    let mut envelope = MessageEnvelope::new_with_public_key(
+        *keypair.public_key(),
+        MessageType::Heartbeat,
+        payload.to_vec(),
+    )
+    .with_sequence(1)
+    .with_timestamp(1000);
+    envelope
+        .compress_payload()
+        .expect("plain current-version envelope");
+    envelope
+}
+
+#[test]
+fn compressed_envelope_verifies_and_decompresses() {
+    let keypair = KeyPair::from_seed([1u8; 32]).expect("non-zero seed");
+    let auth = MessageAuth::new(keypair.clone());
+    let plaintext = vec![0xABu8; 4096];
+
+    let envelope = compressed_heartbeat(&keypair, &plaintext);
+    assert!(envelope.is_compressed());
+    assert!(envelope.payload.len() < plaintext.len());
+
+    let bytes = sign(&auth, envelope).serialize().unwrap();
+    let mut received = MessageEnvelope::deserialize(&bytes).unwrap();
+    assert_eq!(received.flags, ENVELOPE_FLAG_COMPRESSED);
+
+    let mut replay_guard = ReplayProtection::new();
+    received
+        .verify_authenticated(&mut replay_guard, 1000)
+        .expect("valid signature");
+    received
+        .decompress_payload(EnvelopeLimits::default())
+        .expect("within limits");
+    assert!(!received.is_compressed());
+    assert_eq!(received.payload, plaintext);
+    assert!(matches!(
+        received.decompress_payload(EnvelopeLimits::default()),
+        Err(VerifyError::NotCompressed)
+    ));
+}
+
+#[test]
+fn toggling_flags_after_signing_fails_verification() {
+    let keypair = KeyPair::from_seed([2u8; 32]).expect("non-zero seed");
+    let auth = MessageAuth::new(keypair.clone());
+
+    let mut stripped = sign(&auth, compressed_heartbeat(&keypair, b"payload payload"));
+    stripped.flags = 0;
+    let mut replay_guard = ReplayProtection::new();
+    assert!(matches!(
+        stripped.verify_authenticated(&mut replay_guard, 1000),
+        Err(VerifyError::Crypto(_))
+    ));
+
+    let mut added =
+        MessageEnvelope::signed(&auth, MessageType::Heartbeat, b"raw".to_vec(), 2, 1000);
+    added.flags = ENVELOPE_FLAG_COMPRESSED;
+    assert!(matches!(
+        added.verify_authenticated(&mut replay_guard, 1000),
+        Err(VerifyError::Crypto(_))
+    ));
+}
+
+#[test]
+fn legacy_v0_2_envelopes_round_trip_without_flags() {
+    let keypair = KeyPair::from_seed([3u8; 32]).expect("non-zero seed");
+    let auth = MessageAuth::new(keypair.clone());
+
+    let mut envelope = MessageEnvelope::new_with_public_key(
+        *keypair.public_key(),
+        MessageType::Heartbeat,
+        vec![1],
+    )
+    .with_sequence(1)
+    .with_timestamp(1000)
+    .with_key_id(7);
+    envelope.version = MessageEnvelope::LEGACY_VERSION_V0_2;
+    assert!(matches!(
+        envelope.clone().compress_payload(),
+        Err(VerifyError::UnsupportedVersion { major: 0, minor: 2 })
+    ));
+
+    let bytes = sign(&auth, envelope).serialize().unwrap();
+    let received = MessageEnvelope::deserialize(&bytes).unwrap();
+    assert_eq!(received.version, MessageEnvelope::LEGACY_VERSION_V0_2);
+    assert_eq!(received.key_id, Some(7));
+    assert_eq!(received.flags, 0);
+}
+
+#[test]
+fn decompression_is_bounded_by_payload_limit() {
+    let keypair = KeyPair::from_seed([4u8; 32]).expect("non-zero seed");
+    let mut envelope = compressed_heartbeat(&keypair, &[0u8; 64 * 1024]);
+    let compressed = envelope.payload.clone();
+
+    let limits = EnvelopeLimits {
+        max_payload_len: 1024,
+        ..EnvelopeLimits::default()
+    };
+    assert!(matches!(
+        envelope.decompress_payload(limits),
+        Err(VerifyError::DecompressionFailed)
+    ));
+    // Left unchanged on failure.
+    assert!(envelope.is_compressed());
+    assert_eq!(envelope.payload, compressed);
+}
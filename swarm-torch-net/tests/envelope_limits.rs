@@ -0,0 +1,69 @@
+//! Tests for bounded decoding of untrusted envelope bytes.
+
+use swarm_torch_core::crypto::{KeyPair, MessageAuth};
+use swarm_torch_net::protocol::{EnvelopeLimits, MessageEnvelope, MessageType, SIGNATURE_LEN};
+use swarm_torch_net::Error;
+
+fn signed_envelope(payload: &[u8]) -> MessageEnvelope {
+    let auth = MessageAuth::new(KeyPair::from_seed([3u8; 32]).expect("non-zero seed"));
+    MessageEnvelope::signed(&auth, MessageType::Heartbeat, payload.to_vec(), 1, 1000)
+}
+
+#[test]
+fn valid_envelope_decodes_within_limits() {
+    let envelope = signed_envelope(b"ping");
+    let bytes = envelope.serialize().unwrap();
+    let decoded = MessageEnvelope::deserialize_bounded(&bytes, EnvelopeLimits::default()).unwrap();
+    assert_eq!(decoded.payload, b"ping");
+    assert_eq!(decoded.signature, envelope.signature);
+
+    let unsigned = MessageEnvelope::new_with_public_key([9u8; 32], MessageType::Heartbeat, vec![]);
+    let bytes = unsigned.serialize().unwrap();
+    assert!(MessageEnvelope::deserialize_bounded(&bytes, EnvelopeLimits::default()).is_ok());
+}
+
+#[test]
+fn oversized_payload_is_rejected() {
+    let bytes = signed_envelope(&[0u8; 100]).serialize().unwrap();
+    let limits = EnvelopeLimits {
+        max_payload_len: 64,
+        ..EnvelopeLimits::default()
+    };
+    assert!(matches!(
+        MessageEnvelope::deserialize_bounded(&bytes, limits),
+        Err(Error::InvalidMessage)
+    ));
+
+    // A length prefix claiming ~1 TiB with nothing behind it.
+    let empty = MessageEnvelope::new_with_public_key([9u8; 32], MessageType::Heartbeat, vec![])
+        .serialize()
+        .unwrap();
+    // Trailer is payload len (0), signature tag (None), key_id tag (None).
+    let header = &empty[..empty.len() - 3];
+    let mut crafted = header.to_vec();
+    crafted.extend_from_slice(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x20]);
+    crafted.extend_from_slice(&[0, 0]);
+    assert!(matches!(
+        MessageEnvelope::deserialize_bounded(&crafted, EnvelopeLimits::default()),
+        Err(Error::InvalidMessage)
+    ));
+}
+
+#[test]
+fn signature_must_be_exactly_64_bytes() {
+    let mut envelope = signed_envelope(b"ping");
+    envelope.signature = Some(vec![1u8; SIGNATURE_LEN - 1]);
+    let bytes = envelope.serialize().unwrap();
+    assert!(MessageEnvelope::deserialize(&bytes).is_ok());
+    assert!(matches!(
+        MessageEnvelope::deserialize_bounded(&bytes, EnvelopeLimits::default()),
+        Err(Error::InvalidMessage)
+    ));
+
+    envelope.signature = Some(vec![1u8; 4096]);
+    let bytes = envelope.serialize().unwrap();
+    assert!(matches!(
+        MessageEnvelope::deserialize_bounded(&bytes, EnvelopeLimits::default()),
+        Err(Error::InvalidMessage)
+    ));
+}
@@ -0,0 +1,47 @@
+//! `?`-friendly conversions into `swarm_torch_net::Error`.
+
+use swarm_torch_core::replay::ReplayError;
+use swarm_torch_net::protocol::VerifyError;
+use swarm_torch_net::Error;
+
+#[test]
+fn postcard_failure_converts_to_serialization() {
+    fn decode(bytes: &[u8]) -> swarm_torch_net::Result<u64> {
+        Ok(postcard::from_bytes(bytes)?)
+    }
+    assert!(matches!(decode(&[]), Err(Error::Serialization)));
+
+    fn decode_core(bytes: &[u8]) -> swarm_torch_core::Result<u64> {
+        Ok(postcard::from_bytes(bytes)?)
+    }
+    assert!(matches!(
+        decode_core(&[]),
+        Err(swarm_torch_core::Error::Serialization)
+    ));
+    assert!(matches!(
+        Error::from(swarm_torch_core::Error::Serialization),
+        Error::Serialization
+    ));
+}
+
+#[test]
+fn replay_error_maps_to_distinct_variant() {
+    let replay = ReplayError::Replay {
+        peer: swarm_torch_core::traits::PeerId::new([7u8; 32]),
+        seq: 3,
+    };
+    assert!(matches!(Error::from(replay), Error::Replay(e) if e == replay));
+    // Replay failures keep their own variant even when wrapped in `VerifyError`.
+    assert!(matches!(
+        Error::from(VerifyError::Replay(replay)),
+        Error::Replay(_)
+    ));
+    assert!(matches!(
+        Error::from(VerifyError::MissingSignature),
+        Error::Verify(VerifyError::MissingSignature)
+    ));
+    assert!(matches!(
+        Error::from(swarm_torch_core::Error::VerificationFailed),
+        Error::Core(swarm_torch_core::Error::VerificationFailed)
+    ));
+}
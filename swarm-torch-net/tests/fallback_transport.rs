@@ -0,0 +1,109 @@
+//! Tests for the multi-transport fallback wrapper.
+#![cfg(feature = "std")]
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use swarm_torch_core::traits::PeerId;
+use swarm_torch_net::traits::SwarmTransport;
+use swarm_torch_net::{Error, FallbackTransport, MockTransport};
+
+fn peer(byte: u8) -> PeerId {
+    PeerId::new([byte; 32])
+}
+
+fn fallback(
+    primary: &Arc<MockTransport>,
+    secondary: &Arc<MockTransport>,
+    threshold: u32,
+    cooldown: Duration,
+) -> FallbackTransport {
+    FallbackTransport::new(vec![Box::new(primary.clone()), Box::new(secondary.clone())])
+        .with_health_policy(threshold, cooldown)
+}
+
+#[tokio::test]
+async fn send_falls_back_to_second_transport() {
+    let primary = Arc::new(MockTransport::new().with_failure_rate(1.0));
+    let secondary = Arc::new(MockTransport::new());
+    let transport = fallback(&primary, &secondary, 3, Duration::from_secs(60));
+
+    transport.send(peer(1), b"grad").await.unwrap();
+    assert_eq!(primary.send_attempts(), 1);
+    assert!(primary.sent().is_empty());
+    assert_eq!(secondary.sent(), vec![(peer(1), b"grad".to_vec())]);
+}
+
+#[tokio::test]
+async fn repeatedly_failing_transport_is_skipped() {
+    let primary = Arc::new(MockTransport::new().with_failure_rate(1.0));
+    let secondary = Arc::new(MockTransport::new());
+    let transport = fallback(&primary, &secondary, 2, Duration::from_secs(60));
+
+    for _ in 0..2 {
+        transport.send(peer(1), b"x").await.unwrap();
+    }
+    assert!(transport.is_skipped(0));
+    assert!(!transport.is_skipped(1));
+
+    transport.send(peer(1), b"x").await.unwrap();
+    assert_eq!(primary.send_attempts(), 2, "skipped while cooling down");
+    assert_eq!(secondary.sent().len(), 3);
+}
+
+#[tokio::test]
+async fn skipped_transport_is_retried_after_cooldown() {
+    let primary = Arc::new(MockTransport::new().with_failure_rate(1.0));
+    let secondary = Arc::new(MockTransport::new());
+    let transport = fallback(&primary, &secondary, 1, Duration::from_millis(20));
+
+    transport.send(peer(1), b"x").await.unwrap();
+    assert!(transport.is_skipped(0));
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    assert!(!transport.is_skipped(0));
+
+    transport.send(peer(1), b"x").await.unwrap();
+    assert_eq!(primary.send_attempts(), 2);
+}
+
+#[tokio::test]
+async fn all_transports_failing_is_reported() {
+    let primary = Arc::new(MockTransport::new().with_failure_rate(1.0));
+    let mut down = MockTransport::new();
+    down.connected = false;
+    let secondary = Arc::new(down);
+    let transport = fallback(&primary, &secondary, 1, Duration::from_secs(60));
+
+    assert!(matches!(
+        transport.send(peer(1), b"x").await,
+        Err(Error::AllTransportsFailed)
+    ));
+    // Both are cooling down, so both are tried again rather than none.
+    assert!(matches!(
+        transport.send(peer(1), b"x").await,
+        Err(Error::AllTransportsFailed)
+    ));
+    assert_eq!(primary.send_attempts(), 2);
+    assert_eq!(secondary.send_attempts(), 2);
+}
+
+#[tokio::test]
+async fn recv_returns_message_from_any_transport() {
+    let primary = Arc::new(MockTransport::new());
+    let secondary = Arc::new(MockTransport::new());
+    let transport = fallback(&primary, &secondary, 3, Duration::from_secs(60));
+
+    secondary.push_incoming(peer(2), b"from lora".to_vec());
+    assert_eq!(
+        transport.recv().await.unwrap(),
+        (peer(2), b"from lora".to_vec())
+    );
+
+    // A message arriving while recv is pending wakes it.
+    let pusher = primary.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        pusher.push_incoming(peer(3), b"late".to_vec());
+    });
+    assert_eq!(transport.recv().await.unwrap(), (peer(3), b"late".to_vec()));
+}
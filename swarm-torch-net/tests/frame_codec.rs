@@ -0,0 +1,67 @@
+//! Tests for the length-prefixed frame codec.
+
+use swarm_torch_net::protocol::{encode_frame, FrameDecoder, FRAME_HEADER_LEN};
+use swarm_torch_net::Error;
+
+#[test]
+fn encode_frame_prepends_big_endian_length() {
+    let frame = encode_frame(b"abc").unwrap();
+    assert_eq!(frame, [0, 0, 0, 3, b'a', b'b', b'c']);
+}
+
+#[test]
+fn partial_chunks_reassemble_across_boundaries() {
+    let first = vec![7u8; 300];
+    let second = b"tail".to_vec();
+    let mut stream = encode_frame(&first).unwrap();
+    stream.extend(encode_frame(&second).unwrap());
+
+    // Every chunk size splits headers and payloads at different offsets.
+    for chunk_size in 1..=stream.len() {
+        let mut decoder = FrameDecoder::new(1024);
+        let mut frames = Vec::new();
+        for chunk in stream.chunks(chunk_size) {
+            decoder.extend(chunk);
+            while let Some(frame) = decoder.next_frame().unwrap() {
+                frames.push(frame);
+            }
+        }
+        assert_eq!(
+            frames,
+            vec![first.clone(), second.clone()],
+            "chunk {chunk_size}"
+        );
+        assert_eq!(decoder.buffered_len(), 0);
+    }
+}
+
+#[test]
+fn zero_length_frame_is_yielded() {
+    let mut decoder = FrameDecoder::new(16);
+    decoder.extend(&encode_frame(&[]).unwrap());
+    decoder.extend(&encode_frame(b"x").unwrap());
+    assert_eq!(decoder.next_frame().unwrap(), Some(Vec::new()));
+    assert_eq!(decoder.next_frame().unwrap(), Some(b"x".to_vec()));
+    assert_eq!(decoder.next_frame().unwrap(), None);
+}
+
+#[test]
+fn oversized_length_is_rejected_from_header_alone() {
+    let mut decoder = FrameDecoder::new(1024);
+    // Declares ~4 GiB; only the header is ever buffered.
+    decoder.extend(&u32::MAX.to_be_bytes());
+    assert_eq!(decoder.buffered_len(), FRAME_HEADER_LEN);
+    assert!(matches!(decoder.next_frame(), Err(Error::InvalidMessage)));
+    assert_eq!(decoder.buffered_len(), 0);
+
+    // The stream cannot be resynchronized: the decoder stays failed.
+    decoder.extend(&encode_frame(b"ok").unwrap());
+    assert!(matches!(decoder.next_frame(), Err(Error::InvalidMessage)));
+}
+
+#[test]
+fn frame_at_limit_is_accepted() {
+    let mut decoder = FrameDecoder::new(4);
+    decoder.extend(&encode_frame(b"four").unwrap());
+    assert_eq!(decoder.next_frame().unwrap(), Some(b"four".to_vec()));
+}
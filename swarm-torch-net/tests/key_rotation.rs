@@ -0,0 +1,164 @@
+//! Integration tests for signing-key rotation via envelope `key_id`.
+
+use swarm_torch_core::crypto::{KeyPair, KeyRing, MessageAuth};
+use swarm_torch_core::replay::ReplayProtection;
+use swarm_torch_net::protocol::{MessageEnvelope, MessageType, VerifyError};
+
+fn signed(
+    identity: &KeyPair,
+    signer: &KeyPair,
+    key_id: Option<u16>,
+    version: (u8, u8),
+    sequence: u64,
+) -> MessageEnvelope {
+    let mut envelope = MessageEnvelope::new_with_public_key(
+        *identity.public_key(),
+        MessageType::Heartbeat,
+        b"rotation".to_vec(),
+    )
+    .with_sequence(sequence)
+    .with_timestamp(1000);
+    envelope.version = version;
+    if let Some(id) = key_id {
+        envelope = envelope.with_key_id(id);
+    }
+    let sig = MessageAuth::new(signer.clone()).sign(
+        envelope.version,
+        envelope.message_type as u8,
+        envelope.sequence,
+        envelope.timestamp,
+        &envelope.payload,
+    );
+    envelope.with_signature(sig.as_bytes().to_vec())
+}
+
+fn rotated_ring(identity: &KeyPair, signers: &[KeyPair]) -> KeyRing {
+    let mut ring = KeyRing::with_retention(*identity.public_key(), 1).unwrap();
+    for signer in signers {
+        ring.rotate(*signer.public_key()).unwrap();
+    }
+    ring
+}
+
+#[test]
+fn envelope_under_retained_older_key_verifies() {
+    let identity = KeyPair::from_seed([1u8; 32]).unwrap();
+    let signers: Vec<KeyPair> = (2u8..=4)
+        .map(|i| KeyPair::from_seed([i; 32]).unwrap())
+        .collect();
+    // Ids 0, 1, 2; retention 1 keeps ids 1 and 2.
+    let ring = rotated_ring(&identity, &signers);
+    let mut replay_guard = ReplayProtection::new();
+
+    let current = signed(
+        &identity,
+        &signers[2],
+        Some(2),
+        MessageEnvelope::CURRENT_VERSION,
+        1,
+    );
+    let previous = signed(
+        &identity,
+        &signers[1],
+        Some(1),
+        MessageEnvelope::CURRENT_VERSION,
+        2,
+    );
+    for envelope in [current, previous] {
+        let bytes = envelope.serialize().unwrap();
+        let received = MessageEnvelope::deserialize(&bytes).unwrap();
+        assert!(received
+            .verify_authenticated_with_keyring(&mut replay_guard, 1000, &ring)
+            .is_ok());
+    }
+}
+
+#[test]
+fn envelope_under_evicted_key_fails() {
+    let identity = KeyPair::from_seed([1u8; 32]).unwrap();
+    let signers: Vec<KeyPair> = (2u8..=4)
+        .map(|i| KeyPair::from_seed([i; 32]).unwrap())
+        .collect();
+    let ring = rotated_ring(&identity, &signers);
+    let mut replay_guard = ReplayProtection::new();
+
+    let evicted = signed(
+        &identity,
+        &signers[0],
+        Some(0),
+        MessageEnvelope::CURRENT_VERSION,
+        1,
+    );
+    assert!(matches!(
+        evicted.verify_authenticated_with_keyring(&mut replay_guard, 1000, &ring),
+        Err(VerifyError::UnknownKeyId { key_id: 0 })
+    ));
+
+    // Claiming a retained id with the wrong key fails signature verification.
+    let mislabeled = signed(
+        &identity,
+        &signers[0],
+        Some(2),
+        MessageEnvelope::CURRENT_VERSION,
+        1,
+    );
+    assert!(matches!(
+        mislabeled.verify_authenticated_with_keyring(&mut replay_guard, 1000, &ring),
+        Err(VerifyError::Crypto(_))
+    ));
+    assert_eq!(replay_guard.cache_size(), 0);
+}
+
+#[test]
+fn legacy_v0_1_envelope_verifies_against_default_key() {
+    let identity = KeyPair::from_seed([5u8; 32]).unwrap();
+    let rotated = KeyPair::from_seed([6u8; 32]).unwrap();
+    let ring = rotated_ring(&identity, &[rotated]);
+
+    let legacy = signed(
+        &identity,
+        &identity,
+        None,
+        MessageEnvelope::LEGACY_VERSION_V0_1,
+        1,
+    );
+    let bytes = legacy.serialize().unwrap();
+    let received = MessageEnvelope::deserialize(&bytes).unwrap();
+    assert_eq!(received.version, MessageEnvelope::LEGACY_VERSION_V0_1);
+    assert_eq!(received.key_id, None);
+
+    let mut replay_guard = ReplayProtection::new();
+    assert!(received
+        .verify_authenticated_with_keyring(&mut replay_guard, 1000, &ring)
+        .is_ok());
+    let mut replay_guard = ReplayProtection::new();
+    assert!(received
+        .verify_authenticated(&mut replay_guard, 1000)
+        .is_ok());
+}
+
+#[test]
+fn key_id_requires_matching_keyring() {
+    let identity = KeyPair::from_seed([7u8; 32]).unwrap();
+    let rotated = KeyPair::from_seed([8u8; 32]).unwrap();
+    let envelope = signed(
+        &identity,
+        &rotated,
+        Some(0),
+        MessageEnvelope::CURRENT_VERSION,
+        1,
+    );
+    let mut replay_guard = ReplayProtection::new();
+
+    assert!(matches!(
+        envelope.verify_authenticated(&mut replay_guard, 1000),
+        Err(VerifyError::UnknownKeyId { key_id: 0 })
+    ));
+
+    let other = KeyPair::from_seed([9u8; 32]).unwrap();
+    let foreign_ring = rotated_ring(&other, &[rotated]);
+    assert!(matches!(
+        envelope.verify_authenticated_with_keyring(&mut replay_guard, 1000, &foreign_ring),
+        Err(VerifyError::KeyRingMismatch)
+    ));
+}
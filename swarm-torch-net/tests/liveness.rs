@@ -0,0 +1,56 @@
+//! Heartbeat-driven peer liveness.
+
+use swarm_torch_core::traits::PeerId;
+use swarm_torch_net::liveness::LivenessTracker;
+use swarm_torch_net::protocol::{HeartbeatMessage, MessageEnvelope, MessageType};
+
+fn heartbeat(current_round: u64) -> HeartbeatMessage {
+    HeartbeatMessage {
+        current_round,
+        role: 0,
+        known_peers: 2,
+        load: 0,
+    }
+}
+
+#[test]
+fn silent_peer_goes_stale_and_fresh_heartbeat_revives_it() {
+    let a = PeerId::new([1u8; 32]);
+    let b = PeerId::new([2u8; 32]);
+    let mut tracker = LivenessTracker::new();
+    tracker.record_heartbeat(a, &heartbeat(1), 100);
+    tracker.record_heartbeat(b, &heartbeat(1), 100);
+
+    tracker.record_heartbeat(a, &heartbeat(2), 125);
+    assert_eq!(tracker.alive_peers(135, 30), vec![a]);
+    assert_eq!(tracker.stale_peers(135, 30), vec![b]);
+
+    tracker.record_heartbeat(b, &heartbeat(3), 140);
+    assert_eq!(tracker.alive_peers(140, 30), vec![a, b]);
+    assert!(tracker.stale_peers(140, 30).is_empty());
+    assert_eq!(tracker.get(&b).unwrap().current_round, 3);
+
+    // A delayed heartbeat does not roll liveness back.
+    tracker.record_heartbeat(b, &heartbeat(2), 110);
+    let b_state = tracker.get(&b).unwrap();
+    assert_eq!((b_state.last_seen, b_state.current_round), (140, 3));
+}
+
+#[test]
+fn records_heartbeat_envelopes_and_ignores_other_types() {
+    let sender = [9u8; 32];
+    let payload = postcard::to_allocvec(&heartbeat(4)).unwrap();
+    let envelope = MessageEnvelope::new_with_public_key(sender, MessageType::Heartbeat, payload);
+    let mut tracker = LivenessTracker::new();
+    assert!(tracker.record_envelope(&envelope, 50).unwrap());
+
+    let peer = envelope.sender_peer_id().unwrap();
+    assert_eq!(tracker.get(&peer).unwrap().current_round, 4);
+
+    let other = MessageEnvelope::new_with_public_key(sender, MessageType::RoundStart, Vec::new());
+    assert!(!tracker.record_envelope(&other, 60).unwrap());
+    assert_eq!(tracker.get(&peer).unwrap().last_seen, 50);
+
+    let garbled = MessageEnvelope::new_with_public_key(sender, MessageType::Heartbeat, Vec::new());
+    assert!(tracker.record_envelope(&garbled, 70).is_err());
+}
@@ -0,0 +1,185 @@
+//! Integration tests for AEAD payload encryption on message envelopes.
+
+use swarm_torch_core::crypto::x25519::{derive_session_key, EphemeralKeyPair};
+use swarm_torch_core::crypto::{KeyPair, KeyRing, MessageAuth};
+use swarm_torch_core::replay::ReplayProtection;
+use swarm_torch_net::protocol::{MessageEnvelope, MessageType, VerifyError};
+
+const NONCE: [u8; 12] = [7u8; 12];
+
+fn session_key() -> [u8; 32] {
+    let a = EphemeralKeyPair::from_seed([11u8; 32]).expect("non-zero seed");
+    let b = EphemeralKeyPair::from_seed([12u8; 32]).expect("non-zero seed");
+    let shared = a.diffie_hellman(b.public_key()).expect("contributory");
+    derive_session_key(&shared, b"swarmtorch.payload.test")
+}
+
+fn encrypted_heartbeat(keypair: &KeyPair, key: &[u8; 32], payload: &[u8]) -> MessageEnvelope {
+    let mut envelope = MessageEnvelope::new_with_public_key(
+        *keypair.public_key(),
+        MessageType::Heartbeat,
+        payload.to_vec(),
+    )
+    .with_sequence(5)
+    .with_timestamp(1000);
+    envelope
+        .encrypt_payload(key, NONCE)
+        .expect("plaintext envelope");
+    envelope
+}
+
+fn sign(auth: &MessageAuth, envelope: MessageEnvelope) -> MessageEnvelope {
+    let sig = auth.sign(
+        envelope.version,
+        envelope.message_type as u8,
+        envelope.sequence,
+        envelope.timestamp,
+        &envelope.payload,
+    );
+    envelope.with_signature(sig.as_bytes().to_vec())
+}
+
+#[test]
+fn encrypted_payload_round_trips_through_verify_and_decrypt() {
+    let keypair = KeyPair::from_seed([1u8; 32]).expect("non-zero seed");
+    let auth = MessageAuth::new(keypair.clone());
+    let key = session_key();
+    let plaintext = b"gradient bytes".to_vec();
+
+    let envelope = encrypted_heartbeat(&keypair, &key, &plaintext);
+    assert_eq!(envelope.message_type, MessageType::Encrypted);
+    assert_ne!(envelope.payload, plaintext);
+
+    // Survives the wire.
+    let bytes = sign(&auth, envelope).serialize().unwrap();
+    let mut received = MessageEnvelope::deserialize(&bytes).unwrap();
+
+    let mut replay_guard = ReplayProtection::new();
+    received
+        .verify_and_decrypt(&mut replay_guard, 1000, &key)
+        .expect("valid encrypted envelope");
+    assert_eq!(received.message_type, MessageType::Heartbeat);
+    assert_eq!(received.payload, plaintext);
+}
+
+#[test]
+fn encrypted_payload_signed_with_rotated_key_decrypts_through_keyring() {
+    let identity = KeyPair::from_seed([3u8; 32]).expect("non-zero seed");
+    let rotated = KeyPair::from_seed([4u8; 32]).expect("non-zero seed");
+    let mut ring = KeyRing::with_retention(*identity.public_key(), 1).unwrap();
+    let key_id = ring.rotate(*rotated.public_key()).unwrap();
+    let key = session_key();
+    let plaintext = b"rotated gradient bytes".to_vec();
+
+    let envelope = encrypted_heartbeat(&identity, &key, &plaintext).with_key_id(key_id);
+    let bytes = sign(&MessageAuth::new(rotated), envelope)
+        .serialize()
+        .unwrap();
+
+    // The key-id-unaware path cannot resolve the signing key.
+    let mut received = MessageEnvelope::deserialize(&bytes).unwrap();
+    assert!(matches!(
+        received.verify_and_decrypt(&mut ReplayProtection::new(), 1000, &key),
+        Err(VerifyError::UnknownKeyId { key_id: id }) if id == key_id
+    ));
+
+    let mut received = MessageEnvelope::deserialize(&bytes).unwrap();
+    received
+        .verify_and_decrypt_with_keyring(&mut ReplayProtection::new(), 1000, &ring, &key)
+        .expect("rotated-key encrypted envelope");
+    assert_eq!(received.message_type, MessageType::Heartbeat);
+    assert_eq!(received.payload, plaintext);
+}
+
+#[test]
+fn decrypt_rejects_tampered_associated_data() {
+    let keypair = KeyPair::from_seed([2u8; 32]).expect("non-zero seed");
+    let key = session_key();
+    let envelope = encrypted_heartbeat(&keypair, &key, b"payload");
+
+    let tampers: [fn(&mut MessageEnvelope); 5] = [
+        |e| e.version.1 ^= 1,
+        |e| e.sequence += 1,
+        |e| e.timestamp += 1,
+        |e| e.sender[0] ^= 1,
+        // Swap the inner message type.
+        |e| e.payload[0] = MessageType::GradientUpdate as u8,
+    ];
+    for tamper in tampers {
+        let mut tampered = envelope.clone();
+        tamper(&mut tampered);
+        let before = tampered.payload.clone();
+        assert!(matches!(
+            tampered.decrypt_payload(&key),
+            Err(VerifyError::DecryptionFailed)
+        ));
+        // Failed decryption leaves the envelope untouched.
+        assert_eq!(tampered.message_type, MessageType::Encrypted);
+        assert_eq!(tampered.payload, before);
+    }
+
+    let mut clean = envelope.clone();
+    assert!(clean.decrypt_payload(&key).is_ok());
+}
+
+#[test]
+fn decrypt_rejects_wrong_key_and_bad_framing() {
+    let keypair = KeyPair::from_seed([3u8; 32]).expect("non-zero seed");
+    let key = session_key();
+    let envelope = encrypted_heartbeat(&keypair, &key, b"payload");
+
+    let mut wrong_key = envelope.clone();
+    assert!(matches!(
+        wrong_key.decrypt_payload(&[9u8; 32]),
+        Err(VerifyError::DecryptionFailed)
+    ));
+
+    let mut truncated = envelope.clone();
+    truncated.payload.truncate(20);
+    assert!(matches!(
+        truncated.decrypt_payload(&key),
+        Err(VerifyError::MalformedCiphertext)
+    ));
+
+    let mut unknown_inner = envelope.clone();
+    unknown_inner.payload[0] = 0x42;
+    assert!(matches!(
+        unknown_inner.decrypt_payload(&key),
+        Err(VerifyError::MalformedCiphertext)
+    ));
+
+    let mut twice = envelope.clone();
+    assert!(matches!(
+        twice.encrypt_payload(&key, NONCE),
+        Err(VerifyError::AlreadyEncrypted)
+    ));
+    let mut plain = MessageEnvelope::new_with_public_key(
+        *keypair.public_key(),
+        MessageType::Heartbeat,
+        b"x".to_vec(),
+    );
+    assert!(matches!(
+        plain.decrypt_payload(&key),
+        Err(VerifyError::NotEncrypted)
+    ));
+}
+
+#[test]
+fn verify_and_decrypt_checks_signature_before_decrypting() {
+    let keypair = KeyPair::from_seed([4u8; 32]).expect("non-zero seed");
+    let auth = MessageAuth::new(keypair.clone());
+    let key = session_key();
+
+    let mut envelope = sign(&auth, encrypted_heartbeat(&keypair, &key, b"payload"));
+    // Flip a ciphertext byte: the signature (over ciphertext) fails first.
+    let last = envelope.payload.len() - 1;
+    envelope.payload[last] ^= 1;
+
+    let mut replay_guard = ReplayProtection::new();
+    assert!(matches!(
+        envelope.verify_and_decrypt(&mut replay_guard, 1000, &key),
+        Err(VerifyError::Crypto(_))
+    ));
+    assert_eq!(envelope.message_type, MessageType::Encrypted);
+    assert_eq!(replay_guard.cache_size(), 0);
+}
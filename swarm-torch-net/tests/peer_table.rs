@@ -0,0 +1,60 @@
+//! Discovery-fed peer table: dedup, capacity, and gossip sampling.
+
+use swarm_torch_core::traits::PeerId;
+use swarm_torch_net::peer_table::PeerTable;
+
+const LOCAL: [u8; 32] = [0u8; 32];
+
+fn peer(byte: u8) -> [u8; 32] {
+    [byte; 32]
+}
+
+#[test]
+fn merging_overlapping_sets_dedups_and_skips_local() {
+    let mut table = PeerTable::new(PeerId::new(LOCAL), 16);
+    assert_eq!(table.merge(&[peer(1), peer(2), peer(3), LOCAL]), 3);
+    assert_eq!(table.merge(&[peer(2), peer(3), peer(4), peer(4)]), 1);
+
+    assert_eq!(table.len(), 4);
+    assert!(!table.contains(&PeerId::new(LOCAL)));
+}
+
+#[test]
+fn table_respects_capacity_and_evicts_least_recently_merged() {
+    let mut table = PeerTable::new(PeerId::new(LOCAL), 3);
+    table.merge(&[peer(1), peer(2), peer(3)]);
+    // Re-advertising peer 1 makes peer 2 the eviction candidate.
+    table.merge(&[peer(1)]);
+    table.merge(&[peer(4), peer(5)]);
+
+    assert_eq!(table.len(), 3);
+    let kept: Vec<PeerId> = table.peers().copied().collect();
+    assert_eq!(
+        kept,
+        vec![
+            PeerId::new(peer(1)),
+            PeerId::new(peer(4)),
+            PeerId::new(peer(5))
+        ]
+    );
+}
+
+#[test]
+fn sample_returns_requested_count_without_local_peer() {
+    let mut table = PeerTable::new(PeerId::new(LOCAL), 32);
+    let advertised: Vec<[u8; 32]> = (0..10).map(peer).collect();
+    table.merge(&advertised);
+    assert_eq!(table.len(), 9);
+
+    let picks = table.sample(4);
+    assert_eq!(picks.len(), 4);
+    assert!(picks.iter().all(|p| table.contains(p)));
+    assert!(!picks.contains(&PeerId::new(LOCAL)));
+    let mut unique = picks.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), 4);
+
+    assert_eq!(table.sample_seeded(4, 7), table.sample_seeded(4, 7));
+    assert_eq!(table.sample(50).len(), 9);
+}
@@ -0,0 +1,117 @@
+//! Per-peer rate limiting, standalone and inside envelope verification.
+
+use swarm_torch_core::crypto::{KeyPair, MessageAuth};
+use swarm_torch_core::traits::PeerId;
+use swarm_torch_net::protocol::{
+    AuthenticatedEnvelopeVerifier, MessageEnvelope, MessageType, VerifyError,
+};
+use swarm_torch_net::rate_limit::{RateLimitConfig, RateLimiter};
+
+const CONFIG: RateLimitConfig = RateLimitConfig {
+    rate_per_sec: 2,
+    burst: 3,
+};
+
+fn signed(auth: &MessageAuth, sequence: u64, timestamp: u32) -> MessageEnvelope {
+    MessageEnvelope::signed(auth, MessageType::Heartbeat, vec![], sequence, timestamp)
+}
+
+#[test]
+fn burst_passes_then_limits_then_replenishes() {
+    let mut limiter = RateLimiter::new(CONFIG);
+    let peer = PeerId::from_name("flooder");
+    let now = 1_000;
+
+    for _ in 0..3 {
+        assert_eq!(limiter.check(&peer, now), Ok(()));
+    }
+    assert_eq!(limiter.check(&peer, now), Err(1));
+    assert_eq!(limiter.available(&peer, now), 0);
+
+    // One second refills `rate_per_sec` tokens, capped at `burst`.
+    assert_eq!(limiter.available(&peer, now + 1), 2);
+    assert_eq!(limiter.available(&peer, now + 60), 3);
+    assert_eq!(limiter.check(&peer, now + 1), Ok(()));
+    assert_eq!(limiter.check(&peer, now + 1), Ok(()));
+    assert_eq!(limiter.check(&peer, now + 1), Err(1));
+
+    // Other peers have their own bucket.
+    assert_eq!(limiter.check(&PeerId::from_name("quiet"), now), Ok(()));
+}
+
+#[test]
+fn per_peer_override_and_zero_rate() {
+    let mut limiter = RateLimiter::new(CONFIG);
+    let banned = PeerId::from_name("banned");
+    limiter.set_peer_limit(
+        banned,
+        RateLimitConfig {
+            rate_per_sec: 0,
+            burst: 1,
+        },
+    );
+    assert_eq!(limiter.check(&banned, 0), Ok(()));
+    assert_eq!(limiter.check(&banned, 100), Err(u32::MAX));
+
+    limiter.clear_peer_limit(&banned);
+    assert_eq!(limiter.config_for(&banned), CONFIG);
+    assert_eq!(limiter.check(&banned, 101), Ok(()));
+}
+
+#[test]
+fn bucket_table_stays_bounded() {
+    let mut limiter = RateLimiter::with_capacity(CONFIG, 4);
+    for n in 0..64u32 {
+        let peer = PeerId::from_name(&format!("forged-{n}"));
+        assert_eq!(limiter.check(&peer, n), Ok(()));
+        assert!(limiter.len() <= 4);
+    }
+}
+
+#[test]
+fn full_table_rejects_new_senders_instead_of_evicting_throttled_ones() {
+    let mut limiter = RateLimiter::with_capacity(CONFIG, 2);
+    let (a, b) = (PeerId::from_name("a"), PeerId::from_name("b"));
+    for _ in 0..3 {
+        assert_eq!(limiter.check(&a, 0), Ok(()));
+    }
+    assert_eq!(limiter.check(&b, 0), Ok(()));
+
+    // Both buckets are still refilling: a forged newcomer is turned away and
+    // `a` stays throttled rather than being reset by eviction.
+    let forged = PeerId::from_name("forged");
+    assert_eq!(limiter.check(&forged, 0), Err(1));
+    assert_eq!(limiter.check(&a, 0), Err(1));
+    assert_eq!(limiter.len(), 2);
+
+    // Once `b` has refilled it can be dropped to make room.
+    assert_eq!(limiter.check(&forged, 1), Ok(()));
+    assert_eq!(limiter.available(&a, 1), 2);
+}
+
+#[test]
+fn verifier_rate_limits_before_signature_check() {
+    let auth = MessageAuth::new(KeyPair::from_seed([41u8; 32]).expect("non-zero seed"));
+    let mut verifier =
+        AuthenticatedEnvelopeVerifier::new().with_rate_limiter(RateLimiter::new(CONFIG));
+    let now = 5_000;
+
+    for seq in 1..=3 {
+        assert!(verifier
+            .verify_and_unwrap_with_time(signed(&auth, seq, now), now)
+            .is_ok());
+    }
+    // A forged signature is rejected without reaching signature verification.
+    let forged = signed(&auth, 4, now).with_signature(vec![0u8; 64]);
+    assert!(matches!(
+        verifier.verify_and_unwrap_with_time(forged, now),
+        Err(VerifyError::RateLimited {
+            retry_after_secs: 1
+        })
+    ));
+    assert_eq!(verifier.replay_guard().stats().total_accepted, 3);
+
+    assert!(verifier
+        .verify_and_unwrap_with_time(signed(&auth, 4, now + 1), now + 1)
+        .is_ok());
+}
@@ -14,13 +14,28 @@ fn signed_heartbeat(
     timestamp: u32,
     payload: Vec<u8>,
 ) -> MessageEnvelope {
-    let mut envelope = MessageEnvelope::new_with_public_key(
-        *keypair.public_key(),
+    signed_envelope(
+        keypair,
+        auth,
         MessageType::Heartbeat,
+        sequence,
+        timestamp,
         payload,
     )
-    .with_sequence(sequence)
-    .with_timestamp(timestamp);
+}
+
+fn signed_envelope(
+    keypair: &KeyPair,
+    auth: &MessageAuth,
+    message_type: MessageType,
+    sequence: u64,
+    timestamp: u32,
+    payload: Vec<u8>,
+) -> MessageEnvelope {
+    let mut envelope =
+        MessageEnvelope::new_with_public_key(*keypair.public_key(), message_type, payload)
+            .with_sequence(sequence)
+            .with_timestamp(timestamp);
 
     let sig = auth.sign(
         envelope.version,
@@ -78,6 +93,49 @@ fn envelope_verify_authenticated_rejects_expired() {
     ));
 }
 
+#[test]
+fn envelope_verify_authenticated_uses_per_message_type_skew() {
+    let keypair = KeyPair::from_seed([30u8; 32]).expect("non-zero seed");
+    let auth = MessageAuth::new(keypair.clone());
+    let mut replay_guard = ReplayProtection::new()
+        .with_message_type_skew(MessageType::Heartbeat as u8, 10)
+        .with_message_type_skew(MessageType::ModelCheckpoint as u8, 300);
+    let now = 10_000;
+    let skewed = now - 120;
+
+    let heartbeat = signed_heartbeat(&keypair, &auth, 1, now - 11, Vec::new());
+    assert!(matches!(
+        heartbeat.verify_authenticated(&mut replay_guard, now),
+        Err(VerifyError::Replay(ReplayError::Expired { window: 10, .. }))
+    ));
+
+    let checkpoint = signed_envelope(
+        &keypair,
+        &auth,
+        MessageType::ModelCheckpoint,
+        2,
+        skewed,
+        b"weights".to_vec(),
+    );
+    assert!(checkpoint
+        .verify_authenticated(&mut replay_guard, now)
+        .is_ok());
+
+    // Types without an override keep the global 60s window.
+    let vote = signed_envelope(
+        &keypair,
+        &auth,
+        MessageType::ConsensusVote,
+        3,
+        skewed,
+        Vec::new(),
+    );
+    assert!(matches!(
+        vote.verify_authenticated(&mut replay_guard, now),
+        Err(VerifyError::Replay(ReplayError::Expired { window: 60, .. }))
+    ));
+}
+
 #[test]
 fn envelope_verify_authenticated_signature_before_replay() {
     let keypair = KeyPair::from_seed([4u8; 32]).expect("non-zero seed");
@@ -464,3 +522,57 @@ fn h02_verify_path_uses_canonical_peer_id_for_replay() {
         "sender_peer_id() must match KeyPair::peer_id() (canonical hash)"
     );
 }
+
+#[test]
+fn signed_constructor_produces_verifiable_envelope() {
+    let keypair = KeyPair::from_seed([21u8; 32]).expect("non-zero seed");
+    let auth = MessageAuth::new(keypair.clone());
+    let envelope = MessageEnvelope::signed(
+        &auth,
+        MessageType::GradientUpdate,
+        b"grad".to_vec(),
+        7,
+        1000,
+    );
+
+    assert_eq!(envelope.sender_public_key(), keypair.public_key());
+    assert_eq!(envelope.message_type, MessageType::GradientUpdate);
+    assert_eq!(envelope.sequence, 7);
+    assert_eq!(envelope.timestamp, 1000);
+    assert_eq!(envelope.payload, b"grad");
+
+    let bytes = envelope.serialize().unwrap();
+    let received = MessageEnvelope::deserialize(&bytes).unwrap();
+    let mut replay_guard = ReplayProtection::new();
+    received
+        .verify_authenticated(&mut replay_guard, 1000)
+        .expect("signed envelope verifies");
+}
+
+#[test]
+fn signed_envelope_fails_after_any_field_mutation() {
+    let keypair = KeyPair::from_seed([22u8; 32]).expect("non-zero seed");
+    let auth = MessageAuth::new(keypair);
+    let envelope =
+        MessageEnvelope::signed(&auth, MessageType::Heartbeat, b"payload".to_vec(), 3, 1000);
+
+    let mutations: [fn(&mut MessageEnvelope); 6] = [
+        |e| e.version = MessageEnvelope::LEGACY_VERSION_V0_1,
+        |e| e.message_type = MessageType::GradientUpdate,
+        |e| e.sender[0] ^= 1,
+        |e| e.sequence += 1,
+        |e| e.timestamp += 1,
+        |e| e.payload.push(0),
+    ];
+    for (i, mutate) in mutations.into_iter().enumerate() {
+        let mut tampered = envelope.clone();
+        mutate(&mut tampered);
+        let mut replay_guard = ReplayProtection::new();
+        assert!(
+            tampered
+                .verify_authenticated(&mut replay_guard, 1000)
+                .is_err(),
+            "mutation {i} should invalidate the signature"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! Integration tests for the TCP transport over loopback.
+#![cfg(feature = "tcp-transport")]
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use swarm_torch_core::crypto::{KeyPair, MessageAuth};
+use swarm_torch_core::traits::PeerId;
+use swarm_torch_net::protocol::{MessageEnvelope, MessageType};
+use swarm_torch_net::tcp::{TcpTransport, DEFAULT_MAX_FRAME_LEN};
+use swarm_torch_net::traits::SwarmTransport;
+use swarm_torch_net::Error;
+
+fn signed_heartbeat(keypair: &KeyPair, payload: &[u8]) -> MessageEnvelope {
+    let auth = MessageAuth::new(keypair.clone());
+    MessageEnvelope::signed(&auth, MessageType::Heartbeat, payload.to_vec(), 3, 1000)
+}
+
+#[tokio::test]
+async fn signed_envelope_crosses_loopback_intact() {
+    let nodes = TcpTransport::local_cluster(2).await.unwrap();
+    let (a, b) = (&nodes[0], &nodes[1]);
+    assert_eq!(a.discover().await.unwrap(), vec![b.local_peer()]);
+
+    let keypair = KeyPair::from_seed([9u8; 32]).expect("non-zero seed");
+    let envelope = signed_heartbeat(&keypair, b"gradient bytes");
+    let bytes = envelope.serialize().unwrap();
+
+    // Two sends reuse the pooled connection and arrive in order.
+    a.send(b.local_peer(), &bytes).await.unwrap();
+    a.send(b.local_peer(), b"second").await.unwrap();
+
+    let (from, received) = b.recv().await.unwrap();
+    assert_eq!(from, a.local_peer());
+    assert_eq!(received, bytes);
+    let decoded = MessageEnvelope::deserialize(&received).unwrap();
+    assert_eq!(decoded.message_type, MessageType::Heartbeat);
+    assert_eq!(decoded.sequence, envelope.sequence);
+    assert_eq!(decoded.timestamp, envelope.timestamp);
+    assert_eq!(decoded.sender, envelope.sender);
+    assert_eq!(decoded.payload, envelope.payload);
+    assert_eq!(decoded.signature, envelope.signature);
+
+    assert_eq!(
+        b.recv().await.unwrap(),
+        (a.local_peer(), b"second".to_vec())
+    );
+}
+
+#[tokio::test]
+async fn broadcast_reaches_every_peer() {
+    let nodes = TcpTransport::local_cluster(3).await.unwrap();
+    let stats = nodes[0].broadcast(b"hello").await.unwrap();
+    assert_eq!((stats.peers_sent, stats.confirmed, stats.failed), (2, 2, 0));
+    for node in &nodes[1..] {
+        assert_eq!(
+            node.recv().await.unwrap(),
+            (nodes[0].local_peer(), b"hello".to_vec())
+        );
+    }
+}
+
+#[tokio::test]
+async fn send_to_unknown_peer_is_peer_not_found() {
+    let nodes = TcpTransport::local_cluster(1).await.unwrap();
+    let unknown = PeerId::new([0xAB; 32]);
+    assert!(matches!(
+        nodes[0].send(unknown, b"x").await,
+        Err(Error::PeerNotFound)
+    ));
+}
+
+#[tokio::test]
+async fn send_to_closed_listener_is_connection_failed() {
+    let mut nodes = TcpTransport::local_cluster(2).await.unwrap();
+    let gone = nodes.pop().unwrap();
+    let gone_peer = gone.local_peer();
+    drop(gone);
+    // Let the aborted accept task release the listening socket.
+    tokio::task::yield_now().await;
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    assert!(matches!(
+        nodes[0].send(gone_peer, b"x").await,
+        Err(Error::ConnectionFailed)
+    ));
+}
+
+/// Read from `stream` until EOF or error; `true` if the peer closed within `limit`.
+async fn closed_within(stream: &mut tokio::net::TcpStream, limit: Duration) -> bool {
+    use tokio::io::AsyncReadExt;
+    let mut buf = [0u8; 16];
+    matches!(
+        tokio::time::timeout(limit, stream.read(&mut buf)).await,
+        Ok(Ok(0) | Err(_))
+    )
+}
+
+#[tokio::test]
+async fn silent_inbound_connection_is_closed_after_io_timeout() {
+    let loopback = SocketAddr::from(([127, 0, 0, 1], 0));
+    let node = TcpTransport::bind_with_limits(
+        PeerId::new([1; 32]),
+        loopback,
+        DEFAULT_MAX_FRAME_LEN,
+        Duration::from_millis(100),
+    )
+    .await
+    .unwrap();
+
+    // Connect but never send the handshake.
+    let mut stream = tokio::net::TcpStream::connect(node.local_addr())
+        .await
+        .unwrap();
+    assert!(closed_within(&mut stream, Duration::from_secs(2)).await);
+}
+
+#[tokio::test]
+async fn dropping_transport_closes_inbound_connections() {
+    use tokio::io::AsyncWriteExt;
+
+    let loopback = SocketAddr::from(([127, 0, 0, 1], 0));
+    let node = TcpTransport::bind(PeerId::new([1; 32]), loopback)
+        .await
+        .unwrap();
+    let mut stream = tokio::net::TcpStream::connect(node.local_addr())
+        .await
+        .unwrap();
+    stream.write_all(&[7u8; 32]).await.unwrap();
+    // Give the accept loop time to spawn the reader.
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!closed_within(&mut stream, Duration::from_millis(50)).await);
+
+    drop(node);
+    assert!(closed_within(&mut stream, Duration::from_secs(2)).await);
+}
@@ -4,7 +4,8 @@
 //!
 //! This crate provides a unified interface for async operations across:
 //! - **Tokio**: Implemented for server/edge deployments (std)
-//! - **Embassy**: Placeholder adapter for embedded microcontrollers (no_std path is partial)
+//! - **Embassy**: Adapter for embedded microcontrollers (`embassy-time` clock/timers;
+//!   task spawning via static `SpawnToken`s)
 //!
 //! Current conformance posture:
 //! - Tokio path is validated on Rust 1.75.
@@ -13,7 +14,7 @@
 //! ## Feature Flags
 //!
 //! - `tokio` (default): Use Tokio runtime
-//! - `embassy`: Enable embassy placeholder runtime adapter (experimental)
+//! - `embassy`: Enable the Embassy runtime adapter (experimental)
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
@@ -108,77 +109,189 @@ pub mod tokio_runtime {
 #[cfg(feature = "embassy")]
 pub mod embassy_runtime {
     //! Embassy-based runtime implementation for embedded
+    //!
+    //! - `now()` reads `embassy_time::Instant::now()` (milliseconds since boot); the
+    //!   target must link an `embassy-time` driver.
+    //! - `sleep()` awaits `embassy_time::Timer::after`.
+    //! - Embassy tasks are statically allocated (`#[embassy_executor::task]`), so an
+    //!   arbitrary `Future` cannot be spawned at runtime. Spawn through
+    //!   [`EmbassyRuntime::spawn_token`] with the `SpawnToken` returned by a task function;
+    //!   the task's `pool_size` bounds how many copies may run at once.
+    //!   [`SwarmRuntime::spawn`] has no static task to run the future in, so it drops the
+    //!   future (like the no-`std` mock runtime) and counts it in
+    //!   [`EmbassyRuntime::dropped_spawns`].
 
     use super::*;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use embassy_executor::{SendSpawner, SpawnError, SpawnToken};
+    use embassy_time::{Instant, Timer};
+
+    /// Futures dropped by [`SwarmRuntime::spawn`] since boot.
+    static DROPPED_SPAWNS: AtomicUsize = AtomicUsize::new(0);
 
     /// Embassy runtime wrapper
+    #[derive(Clone, Copy, Default)]
     pub struct EmbassyRuntime {
-        // Embassy spawner would go here
-        _private: (),
-    }
-
-    impl Default for EmbassyRuntime {
-        fn default() -> Self {
-            Self::new()
-        }
+        spawner: Option<SendSpawner>,
     }
 
     impl EmbassyRuntime {
-        /// Create a new Embassy runtime wrapper
+        /// Create a runtime without a spawner (time only; [`Self::spawn_token`] fails).
         pub fn new() -> Self {
-            Self { _private: () }
+            Self { spawner: None }
+        }
+
+        /// Create a runtime that spawns onto `spawner`'s executor.
+        ///
+        /// Obtain one with `Spawner::make_send()` or `SendSpawner::for_current_executor()`.
+        pub fn with_spawner(spawner: SendSpawner) -> Self {
+            Self {
+                spawner: Some(spawner),
+            }
         }
+
+        /// Spawn a task from its `SpawnToken`.
+        ///
+        /// Returns `SpawnError::Busy` if the task's pool is exhausted or no spawner was
+        /// supplied.
+        pub fn spawn_token<S: Send>(&self, token: SpawnToken<S>) -> Result<(), SpawnError> {
+            match self.spawner {
+                Some(spawner) => spawner.spawn(token),
+                None => {
+                    drop(token);
+                    Err(SpawnError::Busy)
+                }
+            }
+        }
+
+        /// Futures passed to [`SwarmRuntime::spawn`] and dropped unrun, across all
+        /// `EmbassyRuntime` values.
+        ///
+        /// Non-zero means some code path needs a static task and
+        /// [`Self::spawn_token`] instead. Updated with plain load/store so targets
+        /// without atomic read-modify-write still build; concurrent drops may undercount.
+        pub fn dropped_spawns() -> usize {
+            DROPPED_SPAWNS.load(Ordering::Relaxed)
+        }
+    }
+
+    /// Milliseconds since boot for an `embassy-time` tick count.
+    pub(crate) fn millis_since_boot(ticks: u64) -> u64 {
+        Instant::from_ticks(ticks).as_millis()
     }
 
     impl SwarmRuntime for EmbassyRuntime {
+        /// Milliseconds since boot from the `embassy-time` driver (monotonic by contract).
         fn now(&self) -> u64 {
-            // Embassy clock integration is not yet wired — returning zero
-            // would silently falsify every timestamp in the protocol layer.
-            // Fail-fast until a real embassy_time::Instant driver is available.
-            unimplemented!(
-                "EmbassyRuntime::now() is a placeholder; \
-                 wire embassy_time::Instant before using this runtime"
-            )
+            millis_since_boot(Instant::now().as_ticks())
         }
 
         async fn sleep(&self, duration: Duration) {
-            // Would use embassy_time::Timer::after(duration).await
-            let _ = duration;
+            let micros = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+            Timer::after(embassy_time::Duration::from_micros(micros)).await;
         }
 
-        fn spawn<F>(&self, _future: F)
+        /// Drops `future` without running it and bumps [`EmbassyRuntime::dropped_spawns`].
+        ///
+        /// Embassy cannot run an arbitrary future without a static task; declare an
+        /// `#[embassy_executor::task]` and use [`EmbassyRuntime::spawn_token`].
+        fn spawn<F>(&self, future: F)
         where
             F: Future<Output = ()> + Send + 'static,
         {
-            // Embassy requires static tasks - this is a simplified placeholder
-            // Real implementation would use spawner.spawn()
+            drop(future);
+            let dropped = DROPPED_SPAWNS.load(Ordering::Relaxed);
+            DROPPED_SPAWNS.store(dropped.saturating_add(1), Ordering::Relaxed);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn millis_since_boot_is_monotonic() {
+            let hz = embassy_time::TICK_HZ;
+            let mut previous = 0;
+            for ticks in (0..hz * 5).step_by((hz / 997).max(1) as usize) {
+                let millis = millis_since_boot(ticks);
+                assert!(millis >= previous, "{millis} < {previous} at tick {ticks}");
+                previous = millis;
+            }
+            assert_eq!(millis_since_boot(hz), 1_000);
+        }
+
+        #[test]
+        fn spawn_drops_future_without_panicking() {
+            let before = EmbassyRuntime::dropped_spawns();
+            EmbassyRuntime::new().spawn(async {});
+            assert!(EmbassyRuntime::dropped_spawns() > before);
         }
     }
 }
 
-/// Mock runtime for testing
 pub mod mock_runtime {
+    //! Mock runtime for testing: deterministic and single-threaded.
+    //!
+    //! With `std`, spawned futures are queued and only run when the test drives them via
+    //! [`MockRuntime::poll_once`] / [`MockRuntime::run_until_idle`], and `sleep` completes
+    //! once [`MockRuntime::advance`] moves the clock past its deadline. Without `std`
+    //! there is no queue: `spawn` drops the future and `sleep` returns immediately.
+
     use super::*;
 
+    #[cfg(feature = "std")]
+    use executor::MockExecutor;
+
     /// Mock runtime for testing without real async
     #[derive(Debug, Default)]
     pub struct MockRuntime {
         current_time_ms: core::sync::atomic::AtomicU64,
+        #[cfg(feature = "std")]
+        executor: MockExecutor,
     }
 
     impl MockRuntime {
         /// Create a new mock runtime
         pub fn new() -> Self {
-            Self {
-                current_time_ms: core::sync::atomic::AtomicU64::new(0),
-            }
+            Self::default()
         }
 
-        /// Advance the mock clock
+        /// Advance the mock clock, waking every `sleep` whose deadline has passed.
         pub fn advance(&self, duration: Duration) {
             use core::sync::atomic::Ordering;
             self.current_time_ms
                 .fetch_add(duration.as_millis() as u64, Ordering::SeqCst);
+            #[cfg(feature = "std")]
+            self.executor.wake_timers(self.now());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl MockRuntime {
+        /// Poll every currently-ready task once; returns how many were polled.
+        ///
+        /// Tasks woken during this pass are polled on the next call.
+        pub fn poll_once(&self) -> usize {
+            self.executor.poll_ready()
+        }
+
+        /// Poll until no task is ready; returns the total number of polls.
+        ///
+        /// Tasks blocked on `sleep` stay pending until [`Self::advance`] wakes them.
+        pub fn run_until_idle(&self) -> usize {
+            let mut polls = 0;
+            loop {
+                match self.poll_once() {
+                    0 => return polls,
+                    n => polls += n,
+                }
+            }
+        }
+
+        /// Spawned tasks that have not completed yet.
+        pub fn pending_tasks(&self) -> usize {
+            self.executor.live_tasks()
         }
     }
 
@@ -188,15 +301,187 @@ pub mod mock_runtime {
             self.current_time_ms.load(Ordering::SeqCst)
         }
 
-        async fn sleep(&self, _duration: Duration) {
-            // Mock sleep does nothing
+        async fn sleep(&self, duration: Duration) {
+            #[cfg(feature = "std")]
+            {
+                let deadline = self.now().saturating_add(duration.as_millis() as u64);
+                core::future::poll_fn(|cx| {
+                    if self.now() >= deadline {
+                        core::task::Poll::Ready(())
+                    } else {
+                        self.executor.register_timer(deadline, cx.waker().clone());
+                        core::task::Poll::Pending
+                    }
+                })
+                .await;
+            }
+            #[cfg(not(feature = "std"))]
+            let _ = duration;
         }
 
-        fn spawn<F>(&self, _future: F)
+        fn spawn<F>(&self, future: F)
         where
             F: Future<Output = ()> + Send + 'static,
         {
-            // Mock spawn does nothing
+            #[cfg(feature = "std")]
+            self.executor.spawn(future);
+            #[cfg(not(feature = "std"))]
+            drop(future);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod executor {
+        use core::future::Future;
+        use core::pin::Pin;
+        use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+        use core::task::{Context, Waker};
+        use std::collections::VecDeque;
+        use std::sync::{Arc, Mutex};
+        use std::task::Wake;
+
+        type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+        type ReadyQueue = Arc<Mutex<VecDeque<Arc<MockTask>>>>;
+
+        struct MockTask {
+            future: Mutex<Option<BoxFuture>>,
+            queued: AtomicBool,
+            ready: ReadyQueue,
+        }
+
+        impl Wake for MockTask {
+            fn wake(self: Arc<Self>) {
+                self.wake_by_ref();
+            }
+
+            fn wake_by_ref(self: &Arc<Self>) {
+                if !self.queued.swap(true, Ordering::SeqCst) {
+                    self.ready.lock().unwrap().push_back(Arc::clone(self));
+                }
+            }
+        }
+
+        /// Ready queue, live-task count and pending `sleep` deadlines.
+        #[derive(Default)]
+        pub(super) struct MockExecutor {
+            ready: ReadyQueue,
+            live: Arc<AtomicUsize>,
+            timers: Mutex<Vec<(u64, Waker)>>,
+        }
+
+        impl core::fmt::Debug for MockExecutor {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct("MockExecutor")
+                    .field("ready", &self.ready.lock().unwrap().len())
+                    .field("live", &self.live.load(Ordering::SeqCst))
+                    .field("timers", &self.timers.lock().unwrap().len())
+                    .finish()
+            }
+        }
+
+        impl MockExecutor {
+            pub(super) fn spawn<F>(&self, future: F)
+            where
+                F: Future<Output = ()> + Send + 'static,
+            {
+                self.live.fetch_add(1, Ordering::SeqCst);
+                let task = Arc::new(MockTask {
+                    future: Mutex::new(Some(Box::pin(future))),
+                    queued: AtomicBool::new(true),
+                    ready: Arc::clone(&self.ready),
+                });
+                self.ready.lock().unwrap().push_back(task);
+            }
+
+            pub(super) fn poll_ready(&self) -> usize {
+                let batch: Vec<Arc<MockTask>> = self.ready.lock().unwrap().drain(..).collect();
+                for task in &batch {
+                    task.queued.store(false, Ordering::SeqCst);
+                    let waker = Waker::from(Arc::clone(task));
+                    let mut slot = task.future.lock().unwrap();
+                    if let Some(future) = slot.as_mut() {
+                        if future
+                            .as_mut()
+                            .poll(&mut Context::from_waker(&waker))
+                            .is_ready()
+                        {
+                            *slot = None;
+                            self.live.fetch_sub(1, Ordering::SeqCst);
+                        }
+                    }
+                }
+                batch.len()
+            }
+
+            pub(super) fn live_tasks(&self) -> usize {
+                self.live.load(Ordering::SeqCst)
+            }
+
+            pub(super) fn register_timer(&self, deadline: u64, waker: Waker) {
+                self.timers.lock().unwrap().push((deadline, waker));
+            }
+
+            pub(super) fn wake_timers(&self, now: u64) {
+                let due: Vec<Waker> = {
+                    let mut timers = self.timers.lock().unwrap();
+                    let (due, pending) =
+                        timers.drain(..).partition(|(deadline, _)| *deadline <= now);
+                    *timers = pending;
+                    due.into_iter().map(|(_, waker)| waker).collect()
+                };
+                due.into_iter().for_each(Waker::wake);
+            }
+        }
+    }
+
+    #[cfg(all(test, feature = "std"))]
+    mod tests {
+        use super::*;
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        #[test]
+        fn spawned_task_runs_on_run_until_idle() {
+            let rt = MockRuntime::new();
+            let counter = Arc::new(AtomicUsize::new(0));
+            for _ in 0..3 {
+                let counter = Arc::clone(&counter);
+                rt.spawn(async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                });
+            }
+            assert_eq!(counter.load(Ordering::SeqCst), 0);
+            assert_eq!(rt.pending_tasks(), 3);
+
+            assert_eq!(rt.run_until_idle(), 3);
+            assert_eq!(counter.load(Ordering::SeqCst), 3);
+            assert_eq!(rt.pending_tasks(), 0);
+            assert_eq!(rt.poll_once(), 0);
+        }
+
+        #[test]
+        fn sleeping_task_fires_only_after_advance() {
+            let rt = Arc::new(MockRuntime::new());
+            let fired = Arc::new(AtomicBool::new(false));
+            {
+                let (task_rt, fired) = (Arc::clone(&rt), Arc::clone(&fired));
+                rt.spawn(async move {
+                    task_rt.sleep(Duration::from_millis(100)).await;
+                    fired.store(true, Ordering::SeqCst);
+                });
+            }
+
+            rt.run_until_idle();
+            assert!(!fired.load(Ordering::SeqCst));
+
+            rt.advance(Duration::from_millis(99));
+            rt.run_until_idle();
+            assert!(!fired.load(Ordering::SeqCst));
+
+            rt.advance(Duration::from_millis(1));
+            rt.run_until_idle();
+            assert!(fired.load(Ordering::SeqCst));
+            assert_eq!(rt.pending_tasks(), 0);
         }
     }
 }
@@ -1,31 +1,93 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::{self, File};
-use std::io;
+use std::io::{self, BufRead};
 use std::path::{Component, Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use swarm_torch_core::crypto::{ct_eq, MessageAuth, Signature};
 use swarm_torch_core::dataops::{
-    DatasetEntryV1, DatasetLineageV1, DatasetRegistryV1, LineageEdgeV1, MaterializationRecordV1,
-    MaterializationRecordV2,
+    DatasetEntryV1, DatasetLineageV1, DatasetRegistryV1, HashAlgo, LineageEdgeV1,
+    MaterializationRecordCompat, MaterializationRecordV1, MaterializationRecordV2,
 };
 use swarm_torch_core::observe::{
     validate_event_record, validate_metric_record, validate_span_record, EventRecord, MetricRecord,
-    RunId, SpanRecord,
+    RunId, SpanRecord, TraceId,
+};
+use swarm_torch_core::run_graph::{
+    node_id_from_key, validate_graph_v1, validate_node_v1, GraphV1, NodeId,
 };
-use swarm_torch_core::run_graph::{validate_graph_v1, validate_node_v1, GraphV1};
 
 use super::io::{
-    append_ndjson, collect_files_recursive, ensure_file, hex_lower, read_json, rel_path_string,
-    sha256_file, write_json_pretty_atomic,
+    append_ndjson, atomic_write, collect_files_recursive, ensure_file, hash_file, hex_lower,
+    open_ndjson, read_json, rel_path_string, sha256_file, write_json_canonical_atomic,
+    write_json_pretty_atomic,
 };
 use super::record_validation_error_to_io;
 
 const SCHEMA_VERSION_V1: u32 = 1;
+const MANIFEST_SIGNATURE_DOMAIN: &[u8] = b"swarmtorch.manifest.v1";
+const SNAPSHOT_PAIR_SCHEMA_V1: u32 = 1;
+
+/// `datasets/snapshot_pair_commit.json`: SHA-256 of the registry/lineage snapshots
+/// written together, so readers can tell a torn pair from a consistent one.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct SnapshotPairCommitV1 {
+    schema_version: u32,
+    pair_seq: u64,
+    registry_sha256: String,
+    lineage_sha256: String,
+}
+
+/// A cross-file consistency violation found by [`RunArtifactBundle::validate_integrity`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityError {
+    /// A bundle file could not be read or parsed.
+    Unreadable { path: String, reason: String },
+    /// A `materializations.ndjson` record names a node that is not in `graph.json`.
+    UnknownMaterializationNode { asset_key: String, node_id: NodeId },
+    /// A lineage edge fingerprint was never registered (in `registry.json` or any
+    /// `registry_updates.ndjson` record).
+    UnregisteredLineageFingerprint {
+        fingerprint: String,
+        node_id: NodeId,
+    },
+    /// A node input has neither a producing node in `graph.json` nor a registered source.
+    UnproducedInput { node_key: String, asset_key: String },
+}
+
+impl core::fmt::Display for IntegrityError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Unreadable { path, reason } => write!(f, "unreadable {path}: {reason}"),
+            Self::UnknownMaterializationNode { asset_key, node_id } => write!(
+                f,
+                "materialization of {asset_key} references unknown node {node_id}"
+            ),
+            Self::UnregisteredLineageFingerprint {
+                fingerprint,
+                node_id,
+            } => write!(
+                f,
+                "lineage edge via node {node_id} references unregistered fingerprint {fingerprint}"
+            ),
+            Self::UnproducedInput {
+                node_key,
+                asset_key,
+            } => write!(
+                f,
+                "node {node_key} input {asset_key} has no producing node or registered source"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IntegrityError {}
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 struct ManifestV1 {
     schema_version: u32,
     run_id: RunId,
+    // `HashAlgo` wire name; kept as a string so unknown algorithms fail with a clear error.
     hash_algo: String,
     entries: Vec<ManifestEntryV1>,
 }
@@ -34,9 +96,15 @@ struct ManifestV1 {
 struct ManifestEntryV1 {
     // Path relative to `runs/<run_id>/`.
     path: String,
-    sha256: String, // lowercase hex
+    // Lowercase hex digest under `ManifestV1::hash_algo` (key predates algorithm choice).
+    #[serde(rename = "sha256")]
+    digest: String,
     bytes: u64,
     required: bool,
+    // Last-modified time observed when hashed; lets incremental refresh skip unchanged files.
+    // Not covered by `validate_manifest()`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mtime_unix_nanos: Option<u64>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -45,6 +113,36 @@ struct RunFileV1 {
     run_id: RunId,
     created_unix_nanos: u64,
     swarmtorch_version: String,
+    #[serde(default, skip_serializing_if = "NdjsonCompression::is_none")]
+    ndjson_compression: NdjsonCompression,
+}
+
+/// Compression applied to NDJSON artifact files (recorded in `run.json`).
+///
+/// Compressed files carry an extra suffix (`spans.ndjson.gz`); the manifest hashes
+/// the compressed bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NdjsonCompression {
+    /// Plain NDJSON (default).
+    #[default]
+    None,
+    /// Gzip; each append writes one gzip member (multi-member stream).
+    Gzip,
+}
+
+impl NdjsonCompression {
+    /// File name suffix appended to `*.ndjson`.
+    pub fn file_suffix(self) -> &'static str {
+        match self {
+            Self::None => "",
+            Self::Gzip => ".gz",
+        }
+    }
+
+    fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
 }
 
 /// A writer/validator for a single run artifact bundle (`runs/<run_id>/...`).
@@ -52,21 +150,54 @@ struct RunFileV1 {
 pub struct RunArtifactBundle {
     run_dir: PathBuf,
     run_id: RunId,
+    compression: NdjsonCompression,
+    hash_algo: HashAlgo,
 }
 
 impl RunArtifactBundle {
     /// Open an existing bundle directory (`runs/<run_id>/...`) by reading `run.json`.
+    ///
+    /// The manifest hash algorithm is taken from an existing `manifest.json` when it
+    /// names a supported one, else SHA-256.
     pub fn open(run_dir: impl AsRef<Path>) -> io::Result<Self> {
         let run_dir = run_dir.as_ref().to_path_buf();
         let run_file: RunFileV1 = read_json(&run_dir.join("run.json"))?;
+        let hash_algo = read_json::<ManifestV1>(&run_dir.join("manifest.json"))
+            .ok()
+            .and_then(|manifest| HashAlgo::from_name(&manifest.hash_algo))
+            .unwrap_or_default();
         Ok(Self {
             run_dir,
             run_id: run_file.run_id,
+            compression: run_file.ndjson_compression,
+            hash_algo,
         })
     }
 
     /// Create a new bundle directory at `<base>/runs/<run_id>/` with baseline v1 files.
     pub fn create(base: impl AsRef<Path>, run_id: RunId) -> io::Result<Self> {
+        Self::create_with_compression(base, run_id, NdjsonCompression::None)
+    }
+
+    /// Like [`Self::create`] with `run_id = RunId::from_trace_root(trace)`, so spans
+    /// emitted under the run root trace satisfy `trace_id == run_id`.
+    ///
+    /// An all-zero trace is rejected like an all-zero `run_id`.
+    pub fn create_for_trace(base: impl AsRef<Path>, trace: &TraceId) -> io::Result<Self> {
+        Self::create(base, RunId::from_trace_root(trace))
+    }
+
+    /// Like [`Self::create`], but NDJSON files are gzip-compressed (`*.ndjson.gz`).
+    #[cfg(feature = "compression")]
+    pub fn create_compressed(base: impl AsRef<Path>, run_id: RunId) -> io::Result<Self> {
+        Self::create_with_compression(base, run_id, NdjsonCompression::Gzip)
+    }
+
+    fn create_with_compression(
+        base: impl AsRef<Path>,
+        run_id: RunId,
+        compression: NdjsonCompression,
+    ) -> io::Result<Self> {
         if !run_id.is_valid() {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
@@ -98,6 +229,7 @@ impl RunArtifactBundle {
             run_id,
             created_unix_nanos,
             swarmtorch_version: env!("CARGO_PKG_VERSION").to_string(),
+            ndjson_compression: compression,
         };
         write_json_pretty_atomic(&run_dir.join("run.json"), &run_file)?;
 
@@ -105,24 +237,27 @@ impl RunArtifactBundle {
             graph_id: Some(run_id.to_string()),
             ..GraphV1::default()
         };
-        write_json_pretty_atomic(&run_dir.join("graph.json"), &graph)?;
+        write_json_canonical_atomic(&run_dir.join("graph.json"), &graph)?;
 
         // DataOps baselines (ADR-0016).
         let registry = DatasetRegistryV1::default();
-        write_json_pretty_atomic(&run_dir.join("datasets").join("registry.json"), &registry)?;
+        write_json_canonical_atomic(&run_dir.join("datasets").join("registry.json"), &registry)?;
 
         let lineage = DatasetLineageV1::default();
-        write_json_pretty_atomic(&run_dir.join("datasets").join("lineage.json"), &lineage)?;
+        write_json_canonical_atomic(&run_dir.join("datasets").join("lineage.json"), &lineage)?;
+
+        let bundle = Self {
+            run_dir,
+            run_id,
+            compression,
+            hash_algo: HashAlgo::default(),
+        };
 
-        // NDJSON baselines (empty files are valid).
-        ensure_file(&run_dir.join("spans.ndjson"))?;
-        ensure_file(&run_dir.join("events.ndjson"))?;
-        ensure_file(&run_dir.join("metrics.ndjson"))?;
-        ensure_file(&run_dir.join("datasets").join("materializations.ndjson"))?;
-        ensure_file(&run_dir.join("datasets").join("registry_updates.ndjson"))?;
-        ensure_file(&run_dir.join("datasets").join("lineage_edges.ndjson"))?;
+        // NDJSON baselines (empty files are valid, compressed or not).
+        for rel in NDJSON_PATHS_V1 {
+            ensure_file(&bundle.ndjson_path(rel))?;
+        }
 
-        let bundle = Self { run_dir, run_id };
         // Emit an initial manifest so a bundle is valid immediately.
         bundle.finalize_manifest()?;
         Ok(bundle)
@@ -136,6 +271,37 @@ impl RunArtifactBundle {
         &self.run_dir
     }
 
+    /// NDJSON compression recorded in `run.json`.
+    pub fn compression(&self) -> NdjsonCompression {
+        self.compression
+    }
+
+    /// Hash manifest entries with `hash_algo` from the next finalize on.
+    ///
+    /// The manifest records the algorithm, and [`Self::validate_manifest`] verifies
+    /// with whatever the manifest names.
+    pub fn with_hash_algo(mut self, hash_algo: HashAlgo) -> Self {
+        self.hash_algo = hash_algo;
+        self
+    }
+
+    /// Digest used when (re)writing `manifest.json`.
+    pub fn hash_algo(&self) -> HashAlgo {
+        self.hash_algo
+    }
+
+    /// On-disk path of a logical NDJSON file (e.g. `"datasets/materializations.ndjson"`),
+    /// including the compression suffix.
+    pub fn ndjson_path(&self, rel: &str) -> PathBuf {
+        self.run_dir
+            .join(format!("{rel}{}", self.compression.file_suffix()))
+    }
+
+    /// Open a logical NDJSON file for line reading, decompressing transparently.
+    pub fn open_ndjson(&self, rel: &str) -> io::Result<Box<dyn BufRead>> {
+        open_ndjson(&self.ndjson_path(rel), self.compression)
+    }
+
     /// Write (replace) `graph.json` with a normalized graph.
     ///
     /// This validates node field bounds (M-09) and computes derived fields
@@ -161,83 +327,208 @@ impl RunArtifactBundle {
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
         validate_graph_v1(&normalized)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
-        write_json_pretty_atomic(&self.run_dir.join("graph.json"), &normalized)
+        write_json_canonical_atomic(&self.run_dir.join("graph.json"), &normalized)
     }
 
     pub fn append_span(&self, span: &SpanRecord) -> io::Result<()> {
         validate_span_record(span).map_err(record_validation_error_to_io)?;
-        append_ndjson(&self.run_dir.join("spans.ndjson"), span)
+        self.append_ndjson("spans.ndjson", span)
     }
 
     pub fn append_event(&self, event: &EventRecord) -> io::Result<()> {
         validate_event_record(event).map_err(record_validation_error_to_io)?;
-        append_ndjson(&self.run_dir.join("events.ndjson"), event)
+        self.append_ndjson("events.ndjson", event)
     }
 
     pub fn append_metric(&self, metric: &MetricRecord) -> io::Result<()> {
         validate_metric_record(metric).map_err(record_validation_error_to_io)?;
-        append_ndjson(&self.run_dir.join("metrics.ndjson"), metric)
+        self.append_ndjson("metrics.ndjson", metric)
     }
 
     pub fn append_materialization(
         &self,
         materialization: &MaterializationRecordV1,
     ) -> io::Result<()> {
-        append_ndjson(
-            &self
-                .run_dir
-                .join("datasets")
-                .join("materializations.ndjson"),
-            materialization,
-        )
+        self.append_ndjson("datasets/materializations.ndjson", materialization)
     }
 
     pub fn append_materialization_v2(
         &self,
         materialization: &MaterializationRecordV2,
     ) -> io::Result<()> {
-        append_ndjson(
-            &self
-                .run_dir
-                .join("datasets")
-                .join("materializations.ndjson"),
-            materialization,
-        )
+        self.append_ndjson("datasets/materializations.ndjson", materialization)
     }
 
     pub fn append_registry_update(&self, dataset: &DatasetEntryV1) -> io::Result<()> {
-        append_ndjson(
-            &self
-                .run_dir
-                .join("datasets")
-                .join("registry_updates.ndjson"),
-            dataset,
-        )
+        self.append_ndjson("datasets/registry_updates.ndjson", dataset)
     }
 
     pub fn append_lineage_edge_update(&self, edge: &LineageEdgeV1) -> io::Result<()> {
-        append_ndjson(
-            &self.run_dir.join("datasets").join("lineage_edges.ndjson"),
-            edge,
-        )
+        self.append_ndjson("datasets/lineage_edges.ndjson", edge)
+    }
+
+    fn append_ndjson<T: serde::Serialize>(&self, rel: &str, record: &T) -> io::Result<()> {
+        append_ndjson(&self.ndjson_path(rel), record, self.compression)
     }
 
     pub fn write_dataset_registry(&self, registry: &DatasetRegistryV1) -> io::Result<()> {
-        write_json_pretty_atomic(
+        write_json_canonical_atomic(
             &self.run_dir.join("datasets").join("registry.json"),
             registry,
         )
     }
 
     pub fn write_dataset_lineage(&self, lineage: &DatasetLineageV1) -> io::Result<()> {
-        write_json_pretty_atomic(&self.run_dir.join("datasets").join("lineage.json"), lineage)
+        write_json_canonical_atomic(&self.run_dir.join("datasets").join("lineage.json"), lineage)
+    }
+
+    /// Write `datasets/snapshot_pair_commit.json` for the current registry/lineage pair.
+    pub(crate) fn write_snapshot_pair_commit(&self, pair_seq: u64) -> io::Result<()> {
+        let datasets_dir = self.run_dir.join("datasets");
+        let marker = SnapshotPairCommitV1 {
+            schema_version: SNAPSHOT_PAIR_SCHEMA_V1,
+            pair_seq,
+            registry_sha256: hex_lower(&sha256_file(&datasets_dir.join("registry.json"))?),
+            lineage_sha256: hex_lower(&sha256_file(&datasets_dir.join("lineage.json"))?),
+        };
+        write_json_pretty_atomic(&datasets_dir.join("snapshot_pair_commit.json"), &marker)
+    }
+
+    /// Latest registry state: `registry.json` with `registry_updates.ndjson` replayed
+    /// on top (last entry per `asset_key` wins), since streaming snapshots may lag.
+    fn replayed_registry(&self) -> io::Result<BTreeMap<String, DatasetEntryV1>> {
+        let snapshot: DatasetRegistryV1 = read_json(&self.run_dir.join("datasets/registry.json"))?;
+        let updates = self.read_ndjson_records("datasets/registry_updates.ndjson")?;
+        Ok(replay_registry(snapshot.datasets, updates))
+    }
+
+    /// Merge other runs of the same logical pipeline into this bundle.
+    ///
+    /// - NDJSON files are concatenated, skipping records already present (identity is the
+    ///   record's full JSON value)
+    /// - `datasets/registry.json` becomes the union by `asset_key` (sorted) of each bundle's
+    ///   registry with its `registry_updates.ndjson` replayed; the same key with a different
+    ///   `fingerprint_v0` fails with `InvalidData` before anything is written, as does an
+    ///   appended registry update that would replay to a different fingerprint
+    /// - `datasets/lineage.json` becomes the union of edges
+    /// - `datasets/snapshot_pair_commit.json` is rewritten for the merged pair
+    ///
+    /// `graph.json` is left as-is. The manifest is rewritten with a full rehash.
+    pub fn merge_from(&self, others: &[RunArtifactBundle]) -> io::Result<()> {
+        let datasets_dir = self.run_dir.join("datasets");
+        let registry_path = datasets_dir.join("registry.json");
+        let lineage_path = datasets_dir.join("lineage.json");
+
+        let mut registry: DatasetRegistryV1 = read_json(&registry_path)?;
+        let mut lineage: DatasetLineageV1 = read_json(&lineage_path)?;
+        let mut by_key = self.replayed_registry()?;
+        for other in others {
+            for (asset_key, entry) in other.replayed_registry()? {
+                match by_key.get(&asset_key) {
+                    Some(existing) if existing.fingerprint_v0 != entry.fingerprint_v0 => {
+                        return Err(registry_conflict(
+                            &asset_key,
+                            &existing.fingerprint_v0,
+                            &entry.fingerprint_v0,
+                        ));
+                    }
+                    Some(_) => {}
+                    None => {
+                        by_key.insert(asset_key, entry);
+                    }
+                }
+            }
+            let other_lineage: DatasetLineageV1 =
+                read_json(&other.run_dir.join("datasets").join("lineage.json"))?;
+            for edge in other_lineage.edges {
+                if !lineage.edges.contains(&edge) {
+                    lineage.edges.push(edge);
+                }
+            }
+        }
+
+        // Read everything before appending so a corrupt input leaves this bundle untouched.
+        let mut pending = Vec::new();
+        for rel in NDJSON_PATHS_V1 {
+            let mut seen: BTreeSet<String> = self
+                .read_ndjson_values(rel)?
+                .iter()
+                .map(serde_json::Value::to_string)
+                .collect();
+            for other in others {
+                for record in other.read_ndjson_values(rel)? {
+                    if seen.insert(record.to_string()) {
+                        pending.push((rel, record));
+                    }
+                }
+            }
+        }
+
+        // Replaying the merged updates log must land on the merged registry: the last
+        // appended update for each key decides it.
+        let mut last_appended: BTreeMap<String, DatasetEntryV1> = BTreeMap::new();
+        for (rel, record) in &pending {
+            if *rel == "datasets/registry_updates.ndjson" {
+                let entry: DatasetEntryV1 = serde_json::from_value(record.clone())
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                last_appended.insert(entry.asset_key.clone(), entry);
+            }
+        }
+        for (asset_key, entry) in &last_appended {
+            let merged = &by_key[asset_key];
+            if merged.fingerprint_v0 != entry.fingerprint_v0 {
+                return Err(registry_conflict(
+                    asset_key,
+                    &merged.fingerprint_v0,
+                    &entry.fingerprint_v0,
+                ));
+            }
+        }
+        registry.datasets = by_key.into_values().collect();
+
+        for (rel, record) in pending {
+            self.append_ndjson(rel, &record)?;
+        }
+
+        write_json_canonical_atomic(&registry_path, &registry)?;
+        write_json_canonical_atomic(&lineage_path, &lineage)?;
+        let pair_seq =
+            read_json::<SnapshotPairCommitV1>(&datasets_dir.join("snapshot_pair_commit.json"))
+                .map_or(1, |marker| marker.pair_seq.saturating_add(1));
+        self.write_snapshot_pair_commit(pair_seq)?;
+        self.finalize_manifest_full()
+    }
+
+    fn read_ndjson_records<T: serde::de::DeserializeOwned>(&self, rel: &str) -> io::Result<Vec<T>> {
+        self.read_ndjson_values(rel)?
+            .into_iter()
+            .map(|value| {
+                serde_json::from_value(value)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })
+            .collect()
+    }
+
+    fn read_ndjson_values(&self, rel: &str) -> io::Result<Vec<serde_json::Value>> {
+        let mut values = Vec::new();
+        for line in self.open_ndjson(rel)?.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            values.push(
+                serde_json::from_str(&line)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            );
+        }
+        Ok(values)
     }
 
     /// Optional durability hook: fsync all required v1 files (best-effort).
     ///
     /// This is intentionally not called by default for performance reasons.
     pub fn sync_required_v1(&self) -> io::Result<()> {
-        for rel in required_paths_v1() {
+        for rel in self.required_paths() {
             let path = self.run_dir.join(rel);
             let f = File::open(&path)?;
             // Best-effort: ignore sync errors on platforms/filesystems that don't support it.
@@ -248,12 +539,54 @@ impl RunArtifactBundle {
 
     /// (Re)compute and write `manifest.json` for all current files in the bundle.
     ///
+    /// Incremental: files whose size and mtime match the previous manifest keep their
+    /// recorded hash (see [`Self::finalize_manifest_incremental`]). Use
+    /// [`Self::finalize_manifest_full`] to rehash every file.
+    ///
     /// Note: `manifest.json` is excluded from itself (non-self-referential).
     pub fn finalize_manifest(&self) -> io::Result<()> {
+        self.finalize_manifest_incremental().map(|_| ())
+    }
+
+    /// Rewrite `manifest.json`, rehashing only files that are new or whose size/mtime
+    /// changed since the previous manifest. Returns the rehashed paths (sorted).
+    ///
+    /// Falls back to a full rehash when no readable manifest for this run exists.
+    /// A same-size rewrite within the filesystem's mtime granularity is not detected;
+    /// use [`Self::finalize_manifest_full`] when that matters.
+    pub fn finalize_manifest_incremental(&self) -> io::Result<Vec<String>> {
+        let previous = read_json::<ManifestV1>(&self.run_dir.join("manifest.json"))
+            .ok()
+            .filter(|manifest| {
+                manifest.schema_version == SCHEMA_VERSION_V1
+                    && manifest.run_id == self.run_id
+                    && manifest.hash_algo == self.hash_algo.as_str()
+            })
+            .map(|manifest| {
+                manifest
+                    .entries
+                    .into_iter()
+                    .map(|entry| (entry.path.clone(), entry))
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .unwrap_or_default();
+        self.write_manifest(&previous)
+    }
+
+    /// Rewrite `manifest.json`, rehashing every file in the bundle.
+    pub fn finalize_manifest_full(&self) -> io::Result<()> {
+        self.write_manifest(&BTreeMap::new()).map(|_| ())
+    }
+
+    fn write_manifest(
+        &self,
+        previous: &BTreeMap<String, ManifestEntryV1>,
+    ) -> io::Result<Vec<String>> {
         let canonical_root = self.run_dir.canonicalize()?;
 
         // Ensure baseline v1 required files exist before hashing.
-        for p in required_paths_v1() {
+        let required_paths = self.required_paths();
+        for p in &required_paths {
             let full = self.run_dir.join(p);
             if !full.exists() {
                 return Err(io::Error::new(
@@ -267,32 +600,265 @@ impl RunArtifactBundle {
         collect_files_recursive(&self.run_dir, &mut files)?;
 
         let mut entries = Vec::new();
+        let mut rehashed = Vec::new();
         for file_path in files {
-            if file_path.file_name().and_then(|s| s.to_str()) == Some("manifest.json") {
+            if matches!(
+                file_path.file_name().and_then(|s| s.to_str()),
+                Some("manifest.json" | "manifest.sig")
+            ) {
                 continue;
             }
             let rel = rel_path_string(&file_path, &self.run_dir)?;
             validate_manifest_path(&rel)?;
             ensure_path_within_bundle(&file_path, &canonical_root, &rel)?;
-            let bytes = fs::metadata(&file_path)?.len();
-            let digest = sha256_file(&file_path)?;
+            let meta = fs::metadata(&file_path)?;
+            let bytes = meta.len();
+            let mtime_unix_nanos = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_nanos().min(u64::MAX as u128) as u64);
+            let reusable = previous.get(&rel).filter(|prev| {
+                prev.bytes == bytes
+                    && mtime_unix_nanos.is_some()
+                    && prev.mtime_unix_nanos == mtime_unix_nanos
+            });
+            let digest = match reusable {
+                Some(prev) => prev.digest.clone(),
+                None => {
+                    rehashed.push(rel.clone());
+                    hex_lower(&hash_file(&file_path, self.hash_algo)?)
+                }
+            };
             entries.push(ManifestEntryV1 {
-                required: is_required_path_v1(&rel),
+                required: required_paths.contains(&rel),
                 path: rel,
-                sha256: hex_lower(&digest),
+                digest,
                 bytes,
+                mtime_unix_nanos,
             });
         }
         entries.sort_by(|a, b| a.path.cmp(&b.path));
+        rehashed.sort();
 
         let manifest = ManifestV1 {
             schema_version: SCHEMA_VERSION_V1,
             run_id: self.run_id,
-            hash_algo: "sha256".to_string(),
+            hash_algo: self.hash_algo.to_string(),
             entries,
         };
 
-        write_json_pretty_atomic(&self.run_dir.join("manifest.json"), &manifest)
+        write_json_pretty_atomic(&self.run_dir.join("manifest.json"), &manifest)?;
+        Ok(rehashed)
+    }
+
+    /// [`Self::finalize_manifest_full`], then write `manifest.sig`: a detached Ed25519
+    /// signature (64 raw bytes) over the exact `manifest.json` bytes.
+    ///
+    /// `manifest.sig` is excluded from the manifest itself.
+    pub fn finalize_manifest_signed(&self, auth: &MessageAuth) -> io::Result<()> {
+        self.finalize_manifest_full()?;
+        let manifest = fs::read(self.run_dir.join("manifest.json"))?;
+        let signature = auth.sign_detached(MANIFEST_SIGNATURE_DOMAIN, &manifest);
+        atomic_write(&self.run_dir.join("manifest.sig"), signature.as_bytes())
+    }
+
+    /// Verify `manifest.sig` against `public_key`, then [`Self::validate_manifest`].
+    ///
+    /// A missing/malformed signature or a wrong key fails with `InvalidData`, as does any
+    /// file that no longer matches its manifest hash.
+    pub fn validate_manifest_signed(&self, public_key: &[u8; 32]) -> io::Result<()> {
+        let manifest = fs::read(self.run_dir.join("manifest.json"))?;
+        let signature: [u8; 64] = fs::read(self.run_dir.join("manifest.sig"))?
+            .try_into()
+            .map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "manifest.sig must be 64 bytes")
+            })?;
+        MessageAuth::verify_detached(
+            public_key,
+            MANIFEST_SIGNATURE_DOMAIN,
+            &manifest,
+            &Signature::from_bytes(signature),
+        )
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("manifest signature: {e}"),
+            )
+        })?;
+        self.validate_manifest()
+    }
+
+    /// Read `manifest.json` and return `path -> digest` (lowercase hex) for every entry.
+    ///
+    /// This does not re-hash files; call `validate_manifest()` first when the hashes
+    /// must reflect current on-disk bytes.
+    pub fn manifest_entry_hashes(&self) -> io::Result<BTreeMap<String, String>> {
+        let manifest: ManifestV1 = read_json(&self.run_dir.join("manifest.json"))?;
+        Ok(manifest
+            .entries
+            .into_iter()
+            .map(|entry| (entry.path, entry.digest))
+            .collect())
+    }
+
+    /// Check cross-file references that byte hashes cannot catch, collecting every violation:
+    ///
+    /// - each `datasets/materializations.ndjson` `node_id` is a node in `graph.json`
+    /// - each lineage edge fingerprint (`datasets/lineage.json` plus
+    ///   `datasets/lineage_edges.ndjson`) was registered at some point: it appears in
+    ///   `datasets/registry.json` or anywhere in `datasets/registry_updates.ndjson`
+    /// - each node input asset is produced by a graph node or registered with a source
+    ///   in the replayed registry
+    ///
+    /// Snapshots are combined with their NDJSON logs, so bundles written with
+    /// `SnapshotProfile::Streaming` (whose snapshots lag) validate the same as strict ones.
+    /// Unreadable files are reported as [`IntegrityError::Unreadable`] and their checks skipped.
+    pub fn validate_integrity(&self) -> Result<(), Vec<IntegrityError>> {
+        let mut errors = Vec::new();
+        let mut unreadable = |path: &str, reason: String| {
+            errors.push(IntegrityError::Unreadable {
+                path: path.to_string(),
+                reason,
+            })
+        };
+
+        let graph = read_json::<GraphV1>(&self.run_dir.join("graph.json"))
+            .map_err(|e| unreadable("graph.json", e.to_string()))
+            .ok();
+        let registry =
+            read_json::<DatasetRegistryV1>(&self.run_dir.join("datasets").join("registry.json"))
+                .map_err(|e| unreadable("datasets/registry.json", e.to_string()))
+                .ok();
+        let registry_updates = self
+            .read_ndjson_records::<DatasetEntryV1>("datasets/registry_updates.ndjson")
+            .map_err(|e| unreadable("datasets/registry_updates.ndjson", e.to_string()))
+            .ok();
+        let lineage =
+            read_json::<DatasetLineageV1>(&self.run_dir.join("datasets").join("lineage.json"))
+                .map_err(|e| unreadable("datasets/lineage.json", e.to_string()))
+                .ok();
+        let lineage_updates = self
+            .read_ndjson_records::<LineageEdgeV1>("datasets/lineage_edges.ndjson")
+            .map_err(|e| unreadable("datasets/lineage_edges.ndjson", e.to_string()))
+            .ok();
+        // Every fingerprint the registry ever held, and its latest state.
+        let (registered_fingerprints, registry) = match (registry, registry_updates) {
+            (Some(registry), Some(updates)) => {
+                let fingerprints: BTreeSet<String> = registry
+                    .datasets
+                    .iter()
+                    .chain(&updates)
+                    .map(|entry| entry.fingerprint_v0.clone())
+                    .collect();
+                let replayed = replay_registry(registry.datasets, updates);
+                (Some(fingerprints), Some(replayed))
+            }
+            _ => (None, None),
+        };
+        let lineage_edges = match (lineage, lineage_updates) {
+            (Some(lineage), Some(updates)) => {
+                let mut seen = BTreeSet::new();
+                let edges: Vec<LineageEdgeV1> = lineage
+                    .edges
+                    .into_iter()
+                    .chain(updates)
+                    .filter(|edge| {
+                        seen.insert((
+                            edge.input_fingerprint_v0.clone(),
+                            edge.output_fingerprint_v0.clone(),
+                            *edge.node_id.as_bytes(),
+                        ))
+                    })
+                    .collect();
+                Some(edges)
+            }
+            _ => None,
+        };
+        let materializations = self
+            .read_materializations()
+            .map_err(|e| unreadable("datasets/materializations.ndjson", e.to_string()))
+            .ok();
+
+        if let (Some(graph), Some(materializations)) = (&graph, &materializations) {
+            let node_ids: BTreeSet<[u8; 16]> = graph
+                .nodes
+                .iter()
+                .map(|node| {
+                    node.node_id
+                        .unwrap_or_else(|| node_id_from_key(&node.node_key))
+                })
+                .map(|node_id| *node_id.as_bytes())
+                .collect();
+            for record in materializations {
+                if !node_ids.contains(record.node_id.as_bytes()) {
+                    errors.push(IntegrityError::UnknownMaterializationNode {
+                        asset_key: record.asset_key.clone(),
+                        node_id: record.node_id,
+                    });
+                }
+            }
+        }
+
+        if let (Some(fingerprints), Some(edges)) = (&registered_fingerprints, &lineage_edges) {
+            for edge in edges {
+                for fingerprint in [&edge.input_fingerprint_v0, &edge.output_fingerprint_v0] {
+                    if !fingerprints.contains(fingerprint) {
+                        errors.push(IntegrityError::UnregisteredLineageFingerprint {
+                            fingerprint: fingerprint.clone(),
+                            node_id: edge.node_id,
+                        });
+                    }
+                }
+            }
+        }
+
+        if let (Some(graph), Some(registry)) = (&graph, &registry) {
+            let produced: BTreeSet<&str> = graph
+                .nodes
+                .iter()
+                .flat_map(|node| node.outputs.iter().map(|output| output.asset_key.as_str()))
+                .chain(
+                    registry
+                        .values()
+                        .filter(|entry| entry.source.is_some())
+                        .map(|entry| entry.asset_key.as_str()),
+                )
+                .collect();
+            for node in &graph.nodes {
+                for input in &node.inputs {
+                    if !produced.contains(input.asset_key.as_str()) {
+                        errors.push(IntegrityError::UnproducedInput {
+                            node_key: node.node_key.clone(),
+                            asset_key: input.asset_key.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn read_materializations(&self) -> io::Result<Vec<MaterializationRecordV2>> {
+        let mut records = Vec::new();
+        for line in self
+            .open_ndjson("datasets/materializations.ndjson")?
+            .lines()
+        {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: MaterializationRecordCompat = serde_json::from_str(&line)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            records.push(record.into_v2());
+        }
+        Ok(records)
     }
 
     /// Validate `manifest.json` against current on-disk bytes.
@@ -313,13 +879,17 @@ impl RunArtifactBundle {
                 "manifest run_id mismatch",
             ));
         }
-        if manifest.hash_algo != "sha256" {
-            return Err(io::Error::new(
+        let hash_algo = HashAlgo::from_name(&manifest.hash_algo).ok_or_else(|| {
+            io::Error::new(
                 io::ErrorKind::InvalidData,
-                "unsupported hash algorithm",
-            ));
-        }
+                format!(
+                    "unsupported manifest hash_algo {:?} (unknown or not enabled in this build)",
+                    manifest.hash_algo
+                ),
+            )
+        })?;
 
+        let required_paths = self.required_paths();
         let mut seen_paths: BTreeSet<String> = BTreeSet::new();
         let mut seen_required_paths: BTreeSet<String> = BTreeSet::new();
 
@@ -333,7 +903,7 @@ impl RunArtifactBundle {
                 ));
             }
 
-            let required_by_schema = is_required_path_v1(&entry.path);
+            let required_by_schema = required_paths.contains(&entry.path);
             if required_by_schema && !entry.required {
                 return Err(io::Error::new(
                     io::ErrorKind::InvalidData,
@@ -365,18 +935,17 @@ impl RunArtifactBundle {
                     ),
                 ));
             }
-            let digest = sha256_file(&path)?;
-            let actual = hex_lower(&digest);
-            if actual != entry.sha256 {
+            let actual = hex_lower(&hash_file(&path, hash_algo)?);
+            if !ct_eq(&actual, &entry.digest) {
                 return Err(io::Error::new(
                     io::ErrorKind::InvalidData,
-                    format!("sha256 mismatch for {}", entry.path),
+                    format!("{hash_algo} mismatch for {}", entry.path),
                 ));
             }
         }
 
-        for required in required_paths_v1() {
-            if !seen_required_paths.contains(*required) {
+        for required in &required_paths {
+            if !seen_required_paths.contains(required) {
                 return Err(io::Error::new(
                     io::ErrorKind::InvalidData,
                     format!("required manifest entry missing: {required}"),
@@ -386,25 +955,51 @@ impl RunArtifactBundle {
 
         Ok(())
     }
+
+    /// Required v1 paths, with NDJSON names carrying the bundle's compression suffix.
+    fn required_paths(&self) -> Vec<String> {
+        let suffix = self.compression.file_suffix();
+        JSON_PATHS_V1
+            .iter()
+            .map(|p| p.to_string())
+            .chain(NDJSON_PATHS_V1.iter().map(|p| format!("{p}{suffix}")))
+            .collect()
+    }
 }
 
-fn required_paths_v1() -> &'static [&'static str] {
-    &[
-        "run.json",
-        "graph.json",
-        "spans.ndjson",
-        "events.ndjson",
-        "metrics.ndjson",
-        "datasets/registry.json",
-        "datasets/lineage.json",
-        "datasets/materializations.ndjson",
-        "datasets/registry_updates.ndjson",
-        "datasets/lineage_edges.ndjson",
-    ]
+const JSON_PATHS_V1: [&str; 4] = [
+    "run.json",
+    "graph.json",
+    "datasets/registry.json",
+    "datasets/lineage.json",
+];
+
+const NDJSON_PATHS_V1: [&str; 6] = [
+    "spans.ndjson",
+    "events.ndjson",
+    "metrics.ndjson",
+    "datasets/materializations.ndjson",
+    "datasets/registry_updates.ndjson",
+    "datasets/lineage_edges.ndjson",
+];
+
+/// Apply registry `updates` over a `snapshot` in order; the last entry per `asset_key` wins.
+fn replay_registry(
+    snapshot: Vec<DatasetEntryV1>,
+    updates: Vec<DatasetEntryV1>,
+) -> BTreeMap<String, DatasetEntryV1> {
+    let mut by_key = BTreeMap::new();
+    for entry in snapshot.into_iter().chain(updates) {
+        by_key.insert(entry.asset_key.clone(), entry);
+    }
+    by_key
 }
 
-fn is_required_path_v1(p: &str) -> bool {
-    required_paths_v1().contains(&p)
+fn registry_conflict(asset_key: &str, existing: &str, incoming: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("registry conflict for {asset_key}: {existing} vs {incoming}"),
+    )
 }
 
 fn validate_manifest_path(path: &str) -> io::Result<()> {
@@ -1,8 +1,10 @@
 use std::fs::{self, File, OpenOptions};
-use std::io::{self, Read, Write};
+use std::io::{self, BufRead, BufReader, Read, Write};
 use std::path::{Path, PathBuf};
 
-use sha2::{Digest, Sha256};
+use swarm_torch_core::dataops::HashAlgo;
+
+use super::bundle::NdjsonCompression;
 
 pub(crate) fn ensure_file(path: &Path) -> io::Result<()> {
     if path.exists() {
@@ -23,12 +25,56 @@ pub(crate) fn write_json_pretty_atomic<T: serde::Serialize>(
     atomic_write(path, &json)
 }
 
+/// Like [`write_json_pretty_atomic`], but in canonical form (see [`to_canonical_json`]).
+///
+/// Used for the hashed dataset/graph artifacts whose bytes must not depend on struct
+/// field order or map insertion order.
+pub(crate) fn write_json_canonical_atomic<T: serde::Serialize>(
+    path: &Path,
+    value: &T,
+) -> io::Result<()> {
+    atomic_write(path, &to_canonical_json(value)?)
+}
+
+/// Pretty JSON with object keys sorted (bytewise) at every level.
+///
+/// Numbers keep `serde_json`'s shortest round-trip text: the value is serialized, re-parsed
+/// and re-emitted, so an `f32` field prints as `0.1` rather than its widened `f64` digits.
+pub(crate) fn to_canonical_json<T: serde::Serialize>(value: &T) -> io::Result<Vec<u8>> {
+    let compact = serde_json::to_vec(value).map_err(io::Error::other)?;
+    let parsed: serde_json::Value = serde_json::from_slice(&compact).map_err(io::Error::other)?;
+    serde_json::to_vec_pretty(&canonicalize_json(parsed)).map_err(io::Error::other)
+}
+
+fn canonicalize_json(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, canonicalize_json(value)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(canonicalize_json).collect())
+        }
+        other => other,
+    }
+}
+
 pub(crate) fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> io::Result<T> {
     let file = File::open(path)?;
     serde_json::from_reader(file).map_err(io::Error::other)
 }
 
-pub(crate) fn append_ndjson<T: serde::Serialize>(path: &Path, record: &T) -> io::Result<()> {
+pub(crate) fn append_ndjson<T: serde::Serialize>(
+    path: &Path,
+    record: &T,
+    compression: NdjsonCompression,
+) -> io::Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
@@ -36,11 +82,62 @@ pub(crate) fn append_ndjson<T: serde::Serialize>(path: &Path, record: &T) -> io:
     let line = serde_json::to_string(record).map_err(io::Error::other)?;
     let mut buf = line.into_bytes();
     buf.push(b'\n');
-    file.write_all(&buf)?;
+    match compression {
+        NdjsonCompression::None => file.write_all(&buf)?,
+        NdjsonCompression::Gzip => file.write_all(&gzip_member(&buf)?)?,
+    }
     file.flush()?;
     Ok(())
 }
 
+/// Open an NDJSON file for line reading; gzip files may hold any number of members.
+pub(crate) fn open_ndjson(
+    path: &Path,
+    compression: NdjsonCompression,
+) -> io::Result<Box<dyn BufRead>> {
+    let file = File::open(path)?;
+    match compression {
+        NdjsonCompression::None => Ok(Box::new(BufReader::new(file))),
+        NdjsonCompression::Gzip => gzip_reader(file),
+    }
+}
+
+#[cfg(feature = "compression")]
+fn gzip_member(bytes: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(bytes)?;
+    encoder.finish()
+}
+
+#[cfg(not(feature = "compression"))]
+fn gzip_member(_bytes: &[u8]) -> io::Result<Vec<u8>> {
+    Err(compression_unsupported())
+}
+
+#[cfg(feature = "compression")]
+fn gzip_reader(file: File) -> io::Result<Box<dyn BufRead>> {
+    // An empty baseline file is a valid (empty) stream.
+    if file.metadata()?.len() == 0 {
+        return Ok(Box::new(io::empty()));
+    }
+    Ok(Box::new(BufReader::new(flate2::read::MultiGzDecoder::new(
+        file,
+    ))))
+}
+
+#[cfg(not(feature = "compression"))]
+fn gzip_reader(_file: File) -> io::Result<Box<dyn BufRead>> {
+    Err(compression_unsupported())
+}
+
+#[cfg(not(feature = "compression"))]
+fn compression_unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "gzip NDJSON requires the `compression` feature",
+    )
+}
+
 pub(crate) fn collect_files_recursive(dir: &Path, out: &mut Vec<PathBuf>) -> io::Result<()> {
     for entry in fs::read_dir(dir)? {
         let entry = entry?;
@@ -79,8 +176,12 @@ pub(crate) fn rel_path_string(path: &Path, base: &Path) -> io::Result<String> {
 }
 
 pub(crate) fn sha256_file(path: &Path) -> io::Result<[u8; 32]> {
+    hash_file(path, HashAlgo::Sha256)
+}
+
+pub(crate) fn hash_file(path: &Path, algo: HashAlgo) -> io::Result<[u8; 32]> {
     let mut file = File::open(path)?;
-    let mut hasher = Sha256::new();
+    let mut hasher = algo.hasher();
     let mut buf = [0u8; 8192];
     loop {
         let n = file.read(&mut buf)?;
@@ -89,10 +190,7 @@ pub(crate) fn sha256_file(path: &Path) -> io::Result<[u8; 32]> {
         }
         hasher.update(&buf[..n]);
     }
-    let digest = hasher.finalize();
-    let mut out = [0u8; 32];
-    out.copy_from_slice(&digest[..]);
-    Ok(out)
+    Ok(hasher.finalize())
 }
 
 pub(crate) fn atomic_write(path: &Path, bytes: &[u8]) -> io::Result<()> {
@@ -3,19 +3,25 @@ use std::io;
 use std::sync::Arc;
 
 use sha2::{Digest, Sha256};
+use swarm_torch_core::crypto::ct_eq;
 use swarm_torch_core::dataops::{
-    cache_hit_from_decision, cache_key_v0, dataset_fingerprint_v0, derived_source_fingerprint_v0,
-    no_schema_hash_v0, predict_output_fingerprints, recipe_hash_v0, sanitize_source_descriptor_v0,
-    schema_hash_v0, source_fingerprint_v0, CacheDecisionV0, DatasetEntryV1, DatasetLineageV1,
-    DatasetRegistryV1, LineageEdgeV1, MaterializationRecordV2, MaterializationStatusV0,
-    OutputSpecCore, PredictedOutput, SchemaDescriptorV0, SourceDescriptorV0, TransformAuditV0,
-    TrustClass, UnsafeReasonV0, DATAOPS_SCHEMA_V1, MATERIALIZATION_SCHEMA_V2,
+    cache_hit_from_decision, cache_key_v0, dataset_fingerprint_v0_with_algo,
+    derived_source_fingerprint_v0_with_algo, no_schema_hash_v0_with_algo,
+    predict_output_fingerprints_with_algo, recipe_hash_v0_with_algo, sanitize_source_descriptor_v0,
+    schema_hash_v0_with_algo, source_fingerprint_v0_with_algo, CacheDecisionV0, DatasetEntryV1,
+    DatasetLineageV1, DatasetRegistryV1, HashAlgo, LineageEdgeV1, MaterializationRecordV2,
+    MaterializationStatusV0, OutputSpecCore, PredictedOutput, QualityInputs, SchemaDescriptorV0,
+    SourceDescriptorV0, TransformAuditV0, TrustClass, UnsafeReasonV0, DATAOPS_SCHEMA_V1,
+    MATERIALIZATION_SCHEMA_V2,
 };
 use swarm_torch_core::execution::AssetInstanceV1;
-use swarm_torch_core::run_graph::{node_def_hash_v1, node_id_from_key, ExecutionTrust, NodeV1};
+use swarm_torch_core::run_graph::{
+    node_def_hash_v1, node_id_from_key, ExecutionTrust, GraphV1, HashVersion, NodeId, NodeV1,
+};
 
-use super::io::{hex_lower, sha256_file, write_json_pretty_atomic};
+use super::io::hex_lower;
 use super::{RunArtifactSink, SnapshotProfile};
+use crate::sandboxed_runner::SandboxedOpRunner;
 
 /// Error type for `DataOpsSession::predict()`.
 ///
@@ -31,6 +37,19 @@ pub enum PredictError {
     OutputContract(String),
 }
 
+/// Dry-run prediction for one graph node (see [`DataOpsSession::plan`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodePlan {
+    pub node_key: String,
+    pub node_id: NodeId,
+    /// `cache_key_v0` over the predicted upstream fingerprints.
+    pub cache_key: String,
+    /// Predicted output fingerprints, in `node.outputs[]` order.
+    pub outputs: Vec<PredictedOutput>,
+    /// `Hit` iff the node declares outputs and every prediction matches the registry.
+    pub cache_decision: CacheDecisionV0,
+}
+
 /// Output specification for `materialize_node_outputs`.
 #[derive(Debug, Clone)]
 pub struct OutputSpec {
@@ -40,14 +59,46 @@ pub struct OutputSpec {
     pub bytes: Option<u64>,
 }
 
-const SNAPSHOT_PAIR_SCHEMA_V1: u32 = 1;
+impl OutputSpec {
+    /// One spec per declared `node.outputs[]`, taking `schema`/`rows`/`bytes` from the
+    /// runner output with the same `asset_key` (all `None` if the runner didn't report it).
+    pub fn from_runner_outputs(node: &NodeV1, runner_outputs: &[AssetInstanceV1]) -> Vec<Self> {
+        node.outputs
+            .iter()
+            .map(|output| {
+                let reported = runner_outputs
+                    .iter()
+                    .find(|instance| instance.asset_key == output.asset_key);
+                OutputSpec {
+                    asset_key: output.asset_key.clone(),
+                    schema: reported.and_then(|instance| instance.schema.clone()),
+                    rows: reported.and_then(|instance| instance.rows),
+                    bytes: reported.and_then(|instance| instance.bytes),
+                }
+            })
+            .collect()
+    }
+}
+
+/// One source for [`DataOpsSession::register_sources`] (same fields as
+/// [`DataOpsSession::register_source`]).
+#[derive(Debug, Clone)]
+pub struct SourceRegistration<'a> {
+    pub asset_key: &'a str,
+    pub trust: TrustClass,
+    pub source: SourceDescriptorV0,
+    pub schema: Option<SchemaDescriptorV0>,
+    pub ingest_node: &'a NodeV1,
+}
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-struct SnapshotPairCommitV1 {
-    schema_version: u32,
-    pair_seq: u64,
-    registry_sha256: String,
-    lineage_sha256: String,
+/// Options that differ between the `materialize_*` entry points.
+#[derive(Debug, Default)]
+struct MaterializeMode<'a> {
+    quality: Option<&'a QualityInputs>,
+    /// Cache-key profile; `None` derives it from `node.execution_trust`.
+    execution_profile: Option<&'a str>,
+    /// Don't re-append registry entries identical to the current ones.
+    skip_unchanged_entries: bool,
 }
 
 /// DataOps session: manages registry/lineage with trust propagation and crash-safe persistence.
@@ -77,6 +128,11 @@ pub struct DataOpsSession {
     dataops_write_count: u64,
     /// Transform audits to attach to the next materialization record(s).
     pending_transform_audits: Vec<TransformAuditV0>,
+    /// Digest for fingerprints of entries this session derives.
+    hash_algo: HashAlgo,
+    /// Nodes whose latest run was recorded under a conforming sandbox (ADR-0018),
+    /// consumed by their next materialization.
+    conforming_sandbox_runs: BTreeSet<String>,
 }
 
 impl DataOpsSession {
@@ -97,9 +153,25 @@ impl DataOpsSession {
             next_snapshot_pair_seq: 1,
             dataops_write_count: 0,
             pending_transform_audits: Vec::new(),
+            hash_algo: HashAlgo::default(),
+            conforming_sandbox_runs: BTreeSet::new(),
         }
     }
 
+    /// Derive fingerprints with `hash_algo` instead of SHA-256.
+    ///
+    /// Each entry records its algorithm; inputs registered under another algorithm
+    /// still resolve (upstream fingerprints are opaque bytes to the recipe hash).
+    pub fn with_hash_algo(mut self, hash_algo: HashAlgo) -> Self {
+        self.hash_algo = hash_algo;
+        self
+    }
+
+    /// Digest used for fingerprints this session derives.
+    pub fn hash_algo(&self) -> HashAlgo {
+        self.hash_algo
+    }
+
     /// Record an applied update transform for the next materialization emission.
     ///
     /// These audits are attached to the next `materialize_node_outputs` call and then cleared.
@@ -107,6 +179,23 @@ impl DataOpsSession {
         self.pending_transform_audits.push(audit.clone());
     }
 
+    /// Record that `node` just ran under `runner`, for its next materialization.
+    ///
+    /// `SandboxedExtension` outputs are only trusted when the run was recorded here by
+    /// a runner reporting [`SandboxedOpRunner::conforms_to_adr0018`]; otherwise they are
+    /// flagged `UnsafeExtension` like any other extension output.
+    pub fn record_sandboxed_run<R: SandboxedOpRunner>(&mut self, node: &NodeV1, runner: &R) {
+        let node_id = node
+            .node_id
+            .unwrap_or_else(|| node_id_from_key(&node.node_key))
+            .to_string();
+        if runner.conforms_to_adr0018() {
+            self.conforming_sandbox_runs.insert(node_id);
+        } else {
+            self.conforming_sandbox_runs.remove(&node_id);
+        }
+    }
+
     /// Look up fingerprint (64-char hex) for an asset_key.
     pub fn fingerprint(&self, asset_key: &str) -> Option<&str> {
         self.registry
@@ -130,43 +219,89 @@ impl DataOpsSession {
         schema: Option<SchemaDescriptorV0>,
         ingest_node: &NodeV1,
     ) -> io::Result<()> {
-        let source = sanitize_source_descriptor_v0(&source)
+        let entry = Self::source_entry(
+            self.hash_algo,
+            asset_key,
+            trust,
+            &source,
+            schema,
+            ingest_node,
+        )?;
+        self.registry.insert(asset_key.to_string(), entry.clone());
+        self.sink.append_registry_update(&entry)?;
+        self.record_dataops_mutation()
+    }
+
+    /// Register several source datasets, flushing snapshots at most once.
+    ///
+    /// Every descriptor is sanitized and fingerprinted before anything is written, so
+    /// an invalid entry (e.g. an oversized URI) fails the whole batch with no registry
+    /// change. Later entries win on duplicate `asset_key`s, as with repeated
+    /// [`Self::register_source`] calls.
+    pub fn register_sources(&mut self, sources: &[SourceRegistration<'_>]) -> io::Result<()> {
+        let entries = sources
+            .iter()
+            .map(|s| {
+                Self::source_entry(
+                    self.hash_algo,
+                    s.asset_key,
+                    s.trust,
+                    &s.source,
+                    s.schema.clone(),
+                    s.ingest_node,
+                )
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        for entry in &entries {
+            self.registry.insert(entry.asset_key.clone(), entry.clone());
+            self.sink.append_registry_update(entry)?;
+        }
+        self.record_dataops_mutations(entries.len() as u64)
+    }
+
+    /// Sanitize `source` and derive the registry entry for a source dataset.
+    fn source_entry(
+        hash_algo: HashAlgo,
+        asset_key: &str,
+        trust: TrustClass,
+        source: &SourceDescriptorV0,
+        schema: Option<SchemaDescriptorV0>,
+        ingest_node: &NodeV1,
+    ) -> io::Result<DatasetEntryV1> {
+        let source = sanitize_source_descriptor_v0(source)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
-        let source_fp = source_fingerprint_v0(&source)
+        let source_fp = source_fingerprint_v0_with_algo(&source, hash_algo)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
         let schema_fp = schema
             .as_ref()
-            .map(schema_hash_v0)
+            .map(|s| schema_hash_v0_with_algo(s, hash_algo))
             .transpose()
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?
             .unwrap_or(
-                no_schema_hash_v0()
+                no_schema_hash_v0_with_algo(hash_algo)
                     .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?,
             );
 
         // recipe_hash for source = hash(ingest_node_def, [])
-        let recipe = recipe_hash_v0(ingest_node, &[])
+        let recipe = recipe_hash_v0_with_algo(ingest_node, &[], HashVersion::default(), hash_algo)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
 
-        let dataset_fp = dataset_fingerprint_v0(source_fp, schema_fp, recipe)
+        let dataset_fp = dataset_fingerprint_v0_with_algo(source_fp, schema_fp, recipe, hash_algo)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
 
-        let entry = DatasetEntryV1 {
+        Ok(DatasetEntryV1 {
             asset_key: asset_key.to_string(),
             fingerprint_v0: hex_lower(&dataset_fp),
             source_fingerprint_v0: hex_lower(&source_fp),
             schema_hash_v0: hex_lower(&schema_fp),
             recipe_hash_v0: hex_lower(&recipe),
+            hash_algo,
             trust,
             source: Some(source),
             schema,
             license_flags: Vec::new(),
             pii_tags: Vec::new(),
-        };
-
-        self.registry.insert(asset_key.to_string(), entry.clone());
-        self.sink.append_registry_update(&entry)?;
-        self.record_dataops_mutation()
+        })
     }
 
     /// Materialize node outputs: derives fingerprints, propagates trust, emits records, flushes.
@@ -182,7 +317,9 @@ impl DataOpsSession {
     ///
     /// Safety taxonomy:
     /// - `unsafe_reasons` includes `UntrustedInput` when any input trust is untrusted.
-    /// - `unsafe_reasons` includes `UnsafeExtension` when `execution_trust != Core`.
+    /// - `unsafe_reasons` includes `UnsafeExtension` when `execution_trust != Core`, except
+    ///   for `SandboxedExtension` nodes whose run was recorded under a conforming sandbox
+    ///   (see [`Self::record_sandboxed_run`]).
     /// - `unsafe_surface` is derived from reasons (`!unsafe_reasons.is_empty()`).
     pub fn materialize_node_outputs(
         &mut self,
@@ -192,6 +329,106 @@ impl DataOpsSession {
         cache_decision: impl Into<CacheDecisionV0>,
         duration_ms: u64,
     ) -> io::Result<()> {
+        self.materialize_node_outputs_with_quality(
+            node,
+            outputs,
+            ts_unix_nanos,
+            cache_decision,
+            duration_ms,
+            None,
+        )
+    }
+
+    /// Like [`Self::materialize_node_outputs`], attaching a `QualitySummaryV0` computed
+    /// from `quality` to every emitted `MaterializationRecordV2`.
+    ///
+    /// Flags (`schema_changed`, `null_rate_exceeded`) use `DEFAULT_NULL_RATE_THRESHOLD`.
+    pub fn materialize_node_outputs_with_quality(
+        &mut self,
+        node: &NodeV1,
+        outputs: &[OutputSpec],
+        ts_unix_nanos: u64,
+        cache_decision: impl Into<CacheDecisionV0>,
+        duration_ms: u64,
+        quality: Option<&QualityInputs>,
+    ) -> io::Result<()> {
+        self.materialize_with_mode(
+            node,
+            outputs,
+            ts_unix_nanos,
+            cache_decision.into(),
+            duration_ms,
+            MaterializeMode {
+                quality,
+                ..MaterializeMode::default()
+            },
+        )
+    }
+
+    /// Materialize `node` unless the registry already holds its predicted outputs.
+    ///
+    /// On a cache hit (every output predicted from the current inputs matches its
+    /// registry fingerprint) this emits `Hit` records with `duration_ms = 0` and
+    /// leaves identical registry entries untouched; otherwise it materializes as a
+    /// `Miss`. `execution_profile` feeds `cache_key_v0` (see [`Self::plan`]).
+    /// Returns the recorded decision.
+    pub fn materialize_or_skip(
+        &mut self,
+        node: &NodeV1,
+        outputs: &[OutputSpec],
+        ts_unix_nanos: u64,
+        execution_profile: &str,
+        duration_ms: u64,
+    ) -> io::Result<CacheDecisionV0> {
+        let specs: Vec<OutputSpecCore> = outputs
+            .iter()
+            .map(|output| OutputSpecCore {
+                asset_key: output.asset_key.clone(),
+                schema: output.schema.clone(),
+            })
+            .collect();
+        // Prediction errors fall through to materialization, which reports them.
+        let hit = self.predict(node, &specs).is_ok_and(|predicted| {
+            !predicted.is_empty()
+                && predicted
+                    .iter()
+                    .all(|output| self.is_cache_hit(&output.asset_key, &output.fingerprint_v0))
+        });
+        let (cache_decision, duration_ms) = if hit {
+            (CacheDecisionV0::Hit, 0)
+        } else {
+            (CacheDecisionV0::Miss, duration_ms)
+        };
+        self.materialize_with_mode(
+            node,
+            outputs,
+            ts_unix_nanos,
+            cache_decision,
+            duration_ms,
+            MaterializeMode {
+                quality: None,
+                execution_profile: Some(execution_profile),
+                skip_unchanged_entries: true,
+            },
+        )?;
+        Ok(cache_decision)
+    }
+
+    fn materialize_with_mode(
+        &mut self,
+        node: &NodeV1,
+        outputs: &[OutputSpec],
+        ts_unix_nanos: u64,
+        cache_decision: CacheDecisionV0,
+        duration_ms: u64,
+        mode: MaterializeMode<'_>,
+    ) -> io::Result<()> {
+        let MaterializeMode {
+            quality,
+            execution_profile,
+            skip_unchanged_entries,
+        } = mode;
+
         // ── PRE-VALIDATION ──────────────────────────────────────────────
 
         // 1. Reject duplicate output keys, and capture provided output set.
@@ -284,15 +521,22 @@ impl DataOpsSession {
         // ── DERIVE + EMIT ───────────────────────────────────────────────
 
         // 4. Compute recipe_hash_v0(node, upstream_fps)
-        let recipe = recipe_hash_v0(node, &upstream_fps)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        let recipe =
+            recipe_hash_v0_with_algo(node, &upstream_fps, HashVersion::default(), self.hash_algo)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
 
         // 5. Derive unsafe reasons and output trust classification.
         let mut unsafe_reasons = Vec::new();
         if any_untrusted_input {
             unsafe_reasons.push(UnsafeReasonV0::UntrustedInput);
         }
-        if !matches!(node.execution_trust, ExecutionTrust::Core) {
+        let sandbox_node_id = node
+            .node_id
+            .unwrap_or_else(|| node_id_from_key(&node.node_key))
+            .to_string();
+        let conforming_sandbox = node.execution_trust == ExecutionTrust::SandboxedExtension
+            && self.conforming_sandbox_runs.contains(&sandbox_node_id);
+        if node.execution_trust != ExecutionTrust::Core && !conforming_sandbox {
             unsafe_reasons.push(UnsafeReasonV0::UnsafeExtension);
         }
         let applied_transforms = self.pending_transform_audits.clone();
@@ -307,12 +551,11 @@ impl DataOpsSession {
         } else {
             TrustClass::Trusted
         };
-        let cache_decision = cache_decision.into();
-        let execution_profile = match node.execution_trust {
+        let execution_profile = execution_profile.unwrap_or(match node.execution_trust {
             ExecutionTrust::Core => "core",
             ExecutionTrust::SandboxedExtension => "sandboxed_extension",
             ExecutionTrust::UnsafeExtension => "unsafe_extension",
-        };
+        });
         let cache_key = cache_key_v0(node, &upstream_fps, execution_profile)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
 
@@ -331,6 +574,10 @@ impl DataOpsSession {
             .collect();
         let input_fingerprints_v0: Vec<String> =
             input_snapshots.iter().map(|(_, fp)| fp.clone()).collect();
+        let quality = quality
+            .map(QualityInputs::summarize)
+            .transpose()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
         let mut staged_entries: Vec<DatasetEntryV1> = Vec::with_capacity(outputs.len());
         let mut staged_new_edges: Vec<((String, String, String), LineageEdgeV1)> = Vec::new();
         let mut staged_materializations: Vec<MaterializationRecordV2> =
@@ -341,16 +588,18 @@ impl DataOpsSession {
         // 6. For each output: compute fingerprint, staged entry/lineage/materialization.
         for output in outputs {
             let schema_fp = match output.schema.as_ref() {
-                Some(s) => schema_hash_v0(s)
+                Some(s) => schema_hash_v0_with_algo(s, self.hash_algo)
                     .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?,
-                None => no_schema_hash_v0()
+                None => no_schema_hash_v0_with_algo(self.hash_algo)
                     .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?,
             };
 
-            let source_fp = derived_source_fingerprint_v0(&output.asset_key)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
-            let dataset_fp = dataset_fingerprint_v0(source_fp, schema_fp, recipe)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+            let source_fp =
+                derived_source_fingerprint_v0_with_algo(&output.asset_key, self.hash_algo)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+            let dataset_fp =
+                dataset_fingerprint_v0_with_algo(source_fp, schema_fp, recipe, self.hash_algo)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
             let fp_hex = hex_lower(&dataset_fp);
 
             let entry = DatasetEntryV1 {
@@ -359,6 +608,7 @@ impl DataOpsSession {
                 source_fingerprint_v0: hex_lower(&source_fp),
                 schema_hash_v0: hex_lower(&schema_fp),
                 recipe_hash_v0: hex_lower(&recipe),
+                hash_algo: self.hash_algo,
                 trust: output_trust,
                 source: None,
                 schema: output.schema.clone(),
@@ -407,7 +657,7 @@ impl DataOpsSession {
                 applied_transforms: applied_transforms.clone(),
                 status: MaterializationStatusV0::Ok,
                 error_code: None,
-                quality: None,
+                quality: quality.clone(),
             };
             staged_materializations.push(mat);
             next_record_seq = next_record_seq.saturating_add(1);
@@ -415,6 +665,9 @@ impl DataOpsSession {
 
         // 9. Persist staged writes before mutating in-memory state.
         for entry in &staged_entries {
+            if skip_unchanged_entries && self.registry.get(&entry.asset_key) == Some(entry) {
+                continue;
+            }
             self.sink.append_registry_update(entry)?;
         }
         for (_, edge) in &staged_new_edges {
@@ -433,6 +686,7 @@ impl DataOpsSession {
         }
         self.next_record_seq = next_record_seq;
         self.pending_transform_audits.clear();
+        self.conforming_sandbox_runs.remove(&node_id_str);
 
         // 11. Snapshot compaction (strict or streaming cadence).
         self.record_dataops_mutation()
@@ -457,14 +711,23 @@ impl DataOpsSession {
     }
 
     fn record_dataops_mutation(&mut self) -> io::Result<()> {
-        self.dataops_write_count = self.dataops_write_count.saturating_add(1);
+        self.record_dataops_mutations(1)
+    }
+
+    /// Count `n` mutations; flushes once if the batch crosses a snapshot boundary.
+    fn record_dataops_mutations(&mut self, n: u64) -> io::Result<()> {
+        if n == 0 {
+            return Ok(());
+        }
+        let before = self.dataops_write_count;
+        self.dataops_write_count = before.saturating_add(n);
         match self.snapshot_profile {
             SnapshotProfile::Strict => self.flush_snapshots(),
             SnapshotProfile::Streaming {
                 snapshot_every_n_writes,
             } => {
                 let period = snapshot_every_n_writes.max(1);
-                if self.dataops_write_count % period == 0 {
+                if self.dataops_write_count / period != before / period {
                     self.flush_snapshots()?;
                 }
                 Ok(())
@@ -488,17 +751,9 @@ impl DataOpsSession {
         };
         self.sink.write_dataset_lineage(&lineage)?;
 
-        let run_dir = self.sink.bundle().run_dir();
-        let datasets_dir = run_dir.join("datasets");
-        let registry_sha256 = hex_lower(&sha256_file(&datasets_dir.join("registry.json"))?);
-        let lineage_sha256 = hex_lower(&sha256_file(&datasets_dir.join("lineage.json"))?);
-        let marker = SnapshotPairCommitV1 {
-            schema_version: SNAPSHOT_PAIR_SCHEMA_V1,
-            pair_seq: self.next_snapshot_pair_seq,
-            registry_sha256,
-            lineage_sha256,
-        };
-        write_json_pretty_atomic(&datasets_dir.join("snapshot_pair_commit.json"), &marker)?;
+        self.sink
+            .bundle()
+            .write_snapshot_pair_commit(self.next_snapshot_pair_seq)?;
         self.next_snapshot_pair_seq = self.next_snapshot_pair_seq.saturating_add(1);
 
         // Keep manifest freshness aligned when snapshot pair markers change.
@@ -537,6 +792,9 @@ impl DataOpsSession {
             asset_key: entry.asset_key.clone(),
             fingerprint_v0: entry.fingerprint_v0.clone(),
             uri: entry.source.as_ref().map(|source| source.uri.clone()),
+            rows: None,
+            bytes: None,
+            schema: None,
         })
     }
 
@@ -734,20 +992,125 @@ impl DataOpsSession {
             upstream_fps.push(fp_bytes);
         }
 
-        let predicted = predict_output_fingerprints(node, outputs, &upstream_fps)
-            .map_err(|e| PredictError::InvalidFingerprint(e.to_string()))?;
+        let predicted =
+            predict_output_fingerprints_with_algo(node, outputs, &upstream_fps, self.hash_algo)
+                .map_err(|e| PredictError::InvalidFingerprint(e.to_string()))?;
         Ok(predicted)
     }
 
+    /// Dry-run `graph`: predict every node's outputs and cache decision without writing.
+    ///
+    /// Nodes are visited in scheduler order (`topological_sort_nodes`), additionally
+    /// deferring a node until every graph node producing one of its inputs is planned.
+    /// Inputs produced in the graph use the producer's predicted fingerprint; other
+    /// inputs resolve from the registry and fail closed when missing. Outputs are
+    /// predicted with the schema currently registered for that asset, if any.
+    pub fn plan(&self, graph: &GraphV1, execution_profile: &str) -> io::Result<Vec<NodePlan>> {
+        let ordered = crate::scheduler::topological_sort_nodes(graph)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+        let produced_in_graph: BTreeSet<&str> = ordered
+            .iter()
+            .flat_map(|node| node.outputs.iter().map(|o| o.asset_key.as_str()))
+            .collect();
+
+        let mut predicted: BTreeMap<String, [u8; 32]> = BTreeMap::new();
+        let mut pending: Vec<&NodeV1> = ordered.iter().collect();
+        let mut plans = Vec::with_capacity(ordered.len());
+        while !pending.is_empty() {
+            let ready = pending.iter().position(|node| {
+                node.inputs.iter().all(|input| {
+                    !produced_in_graph.contains(input.asset_key.as_str())
+                        || predicted.contains_key(&input.asset_key)
+                })
+            });
+            let Some(ready) = ready else {
+                let keys: Vec<&str> = pending.iter().map(|n| n.node_key.as_str()).collect();
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("graph assets form a cycle through nodes {}", keys.join(",")),
+                ));
+            };
+            let node = pending.remove(ready);
+            let plan = self.plan_node(node, &predicted, execution_profile)?;
+            for output in &plan.outputs {
+                let fp = hex_to_bytes(&output.fingerprint_v0).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid predicted fingerprint for {}", output.asset_key),
+                    )
+                })?;
+                predicted.insert(output.asset_key.clone(), fp);
+            }
+            plans.push(plan);
+        }
+        Ok(plans)
+    }
+
+    fn plan_node(
+        &self,
+        node: &NodeV1,
+        predicted: &BTreeMap<String, [u8; 32]>,
+        execution_profile: &str,
+    ) -> io::Result<NodePlan> {
+        let mut upstream_fps: Vec<[u8; 32]> = Vec::with_capacity(node.inputs.len());
+        for input in &node.inputs {
+            let fp = match predicted.get(&input.asset_key) {
+                Some(fp) => *fp,
+                None => self.fingerprint_bytes(&input.asset_key).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!(
+                            "missing input asset {} for node {}",
+                            input.asset_key, node.node_key,
+                        ),
+                    )
+                })?,
+            };
+            upstream_fps.push(fp);
+        }
+
+        let specs: Vec<OutputSpecCore> = node
+            .outputs
+            .iter()
+            .map(|output| OutputSpecCore {
+                asset_key: output.asset_key.clone(),
+                schema: self
+                    .registry
+                    .get(&output.asset_key)
+                    .and_then(|entry| entry.schema.clone()),
+            })
+            .collect();
+        let outputs =
+            predict_output_fingerprints_with_algo(node, &specs, &upstream_fps, self.hash_algo)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        let cache_key = cache_key_v0(node, &upstream_fps, execution_profile)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        let all_hit = !outputs.is_empty()
+            && outputs
+                .iter()
+                .all(|output| self.is_cache_hit(&output.asset_key, &output.fingerprint_v0));
+
+        Ok(NodePlan {
+            node_key: node.node_key.clone(),
+            node_id: node
+                .node_id
+                .unwrap_or_else(|| node_id_from_key(&node.node_key)),
+            cache_key,
+            outputs,
+            cache_decision: all_hit.into(),
+        })
+    }
+
     /// Asset-key scoped cache hit check.
     ///
     /// Returns `true` iff `asset_key` exists in the registry **and** its
     /// current fingerprint matches `predicted_fp`. This prevents false positives
-    /// from fingerprint collisions across different asset keys.
+    /// from fingerprint collisions across different asset keys. The fingerprint
+    /// comparison is constant-time ([`ct_eq`]).
     pub fn is_cache_hit(&self, asset_key: &str, predicted_fp: &str) -> bool {
         self.registry
             .get(asset_key)
-            .map(|e| e.fingerprint_v0 == predicted_fp)
+            .map(|e| ct_eq(&e.fingerprint_v0, predicted_fp))
             .unwrap_or(false)
     }
 
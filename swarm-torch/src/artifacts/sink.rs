@@ -6,8 +6,8 @@ use swarm_torch_core::dataops::{
     MaterializationRecordV2,
 };
 use swarm_torch_core::observe::{
-    validate_event_record, validate_metric_record, validate_span_record, EventRecord, MetricRecord,
-    SpanRecord,
+    validate_event_record, validate_metric_record, validate_span_record, AttrLimits, AttrMap,
+    EventRecord, MetricRecord, SpanRecord,
 };
 use swarm_torch_core::run_graph::GraphV1;
 
@@ -81,6 +81,7 @@ struct SinkState {
 pub struct RunArtifactSink {
     bundle: RunArtifactBundle,
     profile: ArtifactWriteProfile,
+    attr_limits: Option<AttrLimits>,
     lock: Mutex<SinkState>,
 }
 
@@ -88,16 +89,34 @@ impl swarm_torch_core::observe::RunEventEmitter for RunArtifactSink {
     type Error = io::Error;
 
     fn emit_span(&self, span: &SpanRecord) -> Result<(), Self::Error> {
+        if let Some(attrs) = self.bounded_attrs(&span.attrs)? {
+            return self.emit_span(&SpanRecord {
+                attrs,
+                ..span.clone()
+            });
+        }
         validate_span_record(span).map_err(record_validation_error_to_io)?;
         self.append_span(span)
     }
 
     fn emit_event(&self, event: &EventRecord) -> Result<(), Self::Error> {
+        if let Some(attrs) = self.bounded_attrs(&event.attrs)? {
+            return self.emit_event(&EventRecord {
+                attrs,
+                ..event.clone()
+            });
+        }
         validate_event_record(event).map_err(record_validation_error_to_io)?;
         self.append_event(event)
     }
 
     fn emit_metric(&self, metric: &MetricRecord) -> Result<(), Self::Error> {
+        if let Some(attrs) = self.bounded_attrs(&metric.attrs)? {
+            return self.emit_metric(&MetricRecord {
+                attrs,
+                ..metric.clone()
+            });
+        }
         validate_metric_record(metric).map_err(record_validation_error_to_io)?;
         self.append_metric(metric)
     }
@@ -108,14 +127,58 @@ impl RunArtifactSink {
         Self::with_profile(bundle, ArtifactWriteProfile::default())
     }
 
+    /// Sink that keeps `manifest.json` valid after every write, so live readers
+    /// (report tools, dashboards tailing a run) never need `finalize()`.
+    ///
+    /// Uses `ManifestRefreshPolicy::Always`; each refresh is incremental and only
+    /// rehashes files touched since the previous manifest. For very large NDJSON
+    /// streams prefer `ManifestRefreshPolicy::IntervalN` to batch refreshes.
+    pub fn new_auto_manifest(bundle: RunArtifactBundle) -> Self {
+        Self::with_profile(
+            bundle,
+            ArtifactWriteProfile {
+                snapshot_profile: SnapshotProfile::Strict,
+                manifest_policy: ManifestRefreshPolicy::Always,
+            },
+        )
+    }
+
     pub fn with_profile(bundle: RunArtifactBundle, profile: ArtifactWriteProfile) -> Self {
         Self {
             bundle,
             profile,
+            attr_limits: None,
             lock: Mutex::new(SinkState::default()),
         }
     }
 
+    /// Enforce a per-record attribute budget on emitted spans/events/metrics.
+    ///
+    /// Without limits, records are still bounded by the fixed L-09 validation.
+    pub fn with_attr_limits(mut self, limits: AttrLimits) -> Self {
+        self.attr_limits = Some(limits);
+        self
+    }
+
+    pub fn attr_limits(&self) -> Option<AttrLimits> {
+        self.attr_limits
+    }
+
+    /// Apply `attr_limits`; `Some` carries the truncated map when attrs were dropped.
+    fn bounded_attrs(&self, attrs: &AttrMap) -> io::Result<Option<AttrMap>> {
+        let Some(limits) = self.attr_limits else {
+            return Ok(None);
+        };
+        if limits.is_within(attrs) {
+            return Ok(None);
+        }
+        let mut attrs = attrs.clone();
+        limits
+            .apply(&mut attrs)
+            .map_err(record_validation_error_to_io)?;
+        Ok(Some(attrs))
+    }
+
     pub fn bundle(&self) -> &RunArtifactBundle {
         &self.bundle
     }
@@ -214,6 +277,12 @@ impl RunArtifactSink {
         self.bundle.finalize_manifest()
     }
 
+    /// Rehash every bundle file (see `RunArtifactBundle::finalize_manifest_full`).
+    pub fn finalize_manifest_full(&self) -> io::Result<()> {
+        let _g = self.guard()?;
+        self.bundle.finalize_manifest_full()
+    }
+
     pub fn validate_manifest(&self) -> io::Result<()> {
         let _g = self.guard()?;
         self.bundle.validate_manifest()
@@ -6,14 +6,15 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use swarm_torch_core::dataops::{
-    cache_key_v0, CacheDecisionV0, DatasetEntryV1, DatasetLineageV1, DatasetRegistryV1,
-    LineageEdgeV1, MaterializationRecordCompat, MaterializationRecordV1, MaterializationStatusV0,
-    OutputSpecCore, SourceDescriptorV0, TransformAuditV0, TrustClass, UnsafeReasonV0,
-    MATERIALIZATION_SCHEMA_V2, MAX_ETAG_OR_VERSION_LEN, MAX_SOURCE_URI_LEN,
+    cache_key_v0, CacheDecisionV0, DatasetEntryV1, DatasetLineageV1, DatasetRegistryV1, HashAlgo,
+    LineageEdgeV1, MaterializationRecordCompat, MaterializationRecordV1, MaterializationRecordV2,
+    MaterializationStatusV0, OutputSpecCore, QualityInputs, SchemaDescriptorV0, SourceDescriptorV0,
+    TransformAuditV0, TrustClass, UnsafeReasonV0, MATERIALIZATION_SCHEMA_V2,
+    MAX_ETAG_OR_VERSION_LEN, MAX_SOURCE_URI_LEN, QUALITY_FLAG_SCHEMA_CHANGED,
 };
 use swarm_torch_core::observe::{
     AttrMap, AttrValue, EventRecord, MetricRecord, RunEventEmitter, RunId, SpanId, SpanRecord,
-    TraceId, MAX_METRIC_UNIT_LEN, MAX_RECORD_ATTRS, MAX_RECORD_NAME_LEN,
+    TraceId, MAX_METRIC_UNIT_LEN, MAX_RECORD_ATTRS, MAX_RECORD_NAME_LEN, TRUNCATED_ATTR_KEY,
 };
 use swarm_torch_core::run_graph::{
     node_def_hash_v1, node_id_from_key, AssetRefV1, CanonParams, CanonValue, DeviceAffinity,
@@ -77,6 +78,126 @@ fn bundle_manifest_roundtrip() {
     let _ = fs::remove_dir_all(&base);
 }
 
+#[test]
+fn incremental_manifest_rehashes_only_changed_files() {
+    let base = temp_dir("manifest_incremental");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+
+    let run_id = RunId::from_bytes([13u8; 16]);
+    let bundle = RunArtifactBundle::create(&base, run_id).unwrap();
+    assert_eq!(
+        bundle.finalize_manifest_incremental().unwrap(),
+        Vec::<String>::new(),
+        "nothing changed since create()"
+    );
+
+    let span = SpanRecord {
+        schema_version: 1,
+        trace_id: TraceId::from_bytes([1u8; 16]),
+        span_id: SpanId::from_bytes([2u8; 8]),
+        parent_span_id: None,
+        name: "span/a".to_string(),
+        start_unix_nanos: 1000,
+        end_unix_nanos: Some(2000),
+        attrs: AttrMap::new(),
+    };
+    bundle.append_span(&span).unwrap();
+    assert!(bundle.validate_manifest().is_err(), "manifest is stale");
+
+    assert_eq!(
+        bundle.finalize_manifest_incremental().unwrap(),
+        vec!["spans.ndjson".to_string()]
+    );
+    bundle.validate_manifest().unwrap();
+
+    // Full mode rehashes everything and produces the same hashes.
+    let incremental = bundle.manifest_entry_hashes().unwrap();
+    bundle.finalize_manifest_full().unwrap();
+    assert_eq!(bundle.manifest_entry_hashes().unwrap(), incremental);
+    bundle.validate_manifest().unwrap();
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[cfg(feature = "blake3")]
+#[test]
+fn blake3_manifest_finalizes_and_validates() {
+    let base = temp_dir("manifest_blake3");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+
+    let run_id = RunId::from_bytes([119u8; 16]);
+    let bundle = RunArtifactBundle::create(&base, run_id)
+        .unwrap()
+        .with_hash_algo(HashAlgo::Blake3);
+    let sha256_hashes = bundle.manifest_entry_hashes().unwrap();
+
+    // Switching algorithms rehashes every file, even in incremental mode.
+    let rehashed = bundle.finalize_manifest_incremental().unwrap();
+    assert_eq!(rehashed.len(), sha256_hashes.len());
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&fs::read(bundle.run_dir().join("manifest.json")).unwrap()).unwrap();
+    assert_eq!(manifest["hash_algo"], "blake3");
+    let blake3_hashes = bundle.manifest_entry_hashes().unwrap();
+    assert_ne!(blake3_hashes["run.json"], sha256_hashes["run.json"]);
+    bundle.validate_manifest().unwrap();
+
+    // Reopening picks the algorithm up from the manifest.
+    let reopened = RunArtifactBundle::open(bundle.run_dir()).unwrap();
+    assert_eq!(reopened.hash_algo(), HashAlgo::Blake3);
+    assert!(reopened.finalize_manifest_incremental().unwrap().is_empty());
+
+    fs::write(bundle.run_dir().join("artifacts").join("extra.bin"), b"x").unwrap();
+    bundle.finalize_manifest().unwrap();
+    fs::write(bundle.run_dir().join("artifacts").join("extra.bin"), b"y").unwrap();
+    let err = bundle
+        .validate_manifest()
+        .expect_err("tampered file should fail blake3 validation");
+    assert!(err.to_string().contains("blake3 mismatch"), "{err}");
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn validate_manifest_rejects_unknown_hash_algo() {
+    let base = temp_dir("manifest_unknown_hash_algo");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+
+    let run_id = RunId::from_bytes([120u8; 16]);
+    let bundle = RunArtifactBundle::create(&base, run_id).unwrap();
+
+    let manifest_path = bundle.run_dir().join("manifest.json");
+    let mut manifest: serde_json::Value =
+        serde_json::from_slice(&fs::read(&manifest_path).unwrap()).unwrap();
+    manifest["hash_algo"] = serde_json::Value::String("md5".to_string());
+    fs::write(
+        &manifest_path,
+        serde_json::to_vec_pretty(&manifest).unwrap(),
+    )
+    .unwrap();
+
+    let err = bundle
+        .validate_manifest()
+        .expect_err("unknown hash algorithm should fail");
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(
+        err.to_string()
+            .contains(r#"unsupported manifest hash_algo "md5""#),
+        "error should name the algorithm: {err}"
+    );
+    // Reopening falls back to SHA-256 rather than failing.
+    assert_eq!(
+        RunArtifactBundle::open(bundle.run_dir())
+            .unwrap()
+            .hash_algo(),
+        HashAlgo::Sha256
+    );
+
+    let _ = fs::remove_dir_all(&base);
+}
+
 #[test]
 fn validate_manifest_rejects_missing_required_entries() {
     let base = temp_dir("manifest_missing_required");
@@ -548,6 +669,117 @@ fn emit_event_rejects_too_many_attrs_at_write_time() {
     let _ = fs::remove_dir_all(&base);
 }
 
+#[test]
+fn emit_span_enforces_configured_attr_limits() {
+    use std::io::BufRead;
+    use swarm_torch_core::observe::AttrLimits;
+
+    let base = temp_dir("emit_span_attr_limits");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+
+    let bundle = RunArtifactBundle::create(&base, RunId::from_bytes([114u8; 16])).unwrap();
+    let span_with = |n: usize| {
+        let mut attrs = AttrMap::new();
+        for i in 0..n {
+            attrs.insert(format!("k{i:02}"), AttrValue::U64(i as u64));
+        }
+        SpanRecord {
+            schema_version: 1,
+            trace_id: TraceId::from_bytes([1u8; 16]),
+            span_id: SpanId::from_bytes([1u8; 8]),
+            parent_span_id: None,
+            name: "op".to_string(),
+            start_unix_nanos: 1,
+            end_unix_nanos: Some(2),
+            attrs,
+        }
+    };
+
+    let rejecting =
+        RunArtifactSink::new(bundle.clone()).with_attr_limits(AttrLimits::reject(4, 1024));
+    rejecting.emit_span(&span_with(4)).unwrap();
+    let err = rejecting
+        .emit_span(&span_with(5))
+        .expect_err("span over the attr budget should be rejected");
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert!(err.to_string().contains("exceeds budget"));
+    assert_eq!(count_lines(&bundle, "spans.ndjson"), 1);
+
+    let truncating =
+        RunArtifactSink::new(bundle.clone()).with_attr_limits(AttrLimits::truncate(4, 1024));
+    truncating.emit_span(&span_with(10)).unwrap();
+    let last = bundle
+        .open_ndjson("spans.ndjson")
+        .unwrap()
+        .lines()
+        .last()
+        .unwrap()
+        .unwrap();
+    let written: SpanRecord = serde_json::from_str(&last).unwrap();
+    assert_eq!(written.attrs.len(), 4);
+    assert!(written.attrs.contains_key("k00"));
+    assert_eq!(
+        written
+            .attrs
+            .get(TRUNCATED_ATTR_KEY)
+            .and_then(AttrValue::as_u64),
+        Some(7)
+    );
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn redacting_emitter_scrubs_secret_attrs_before_disk() {
+    use std::io::BufRead;
+    use swarm_torch_core::observe::{AttrRedactor, RedactingEmitter, REDACTED_ATTR_VALUE};
+
+    let base = temp_dir("redacting_emitter");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+
+    let bundle = RunArtifactBundle::create(&base, RunId::from_bytes([115u8; 16])).unwrap();
+    let emitter = RedactingEmitter::new(
+        RunArtifactSink::new(bundle.clone()),
+        AttrRedactor::default(),
+    );
+
+    let mut attrs = AttrMap::new();
+    attrs.insert(
+        "auth_token".to_string(),
+        AttrValue::Str("s3cr3t".to_string()),
+    );
+    attrs.insert("node_key".to_string(), AttrValue::Str("train".to_string()));
+    emitter
+        .emit_event(&EventRecord {
+            schema_version: 1,
+            ts_unix_nanos: 1,
+            trace_id: TraceId::from_bytes([1u8; 16]),
+            span_id: None,
+            name: "login".to_string(),
+            attrs,
+        })
+        .unwrap();
+
+    let line = bundle
+        .open_ndjson("events.ndjson")
+        .unwrap()
+        .lines()
+        .last()
+        .unwrap()
+        .unwrap();
+    assert!(!line.contains("s3cr3t"));
+    let written: EventRecord = serde_json::from_str(&line).unwrap();
+    assert_eq!(
+        written.attrs["auth_token"].as_str(),
+        Some(REDACTED_ATTR_VALUE)
+    );
+    assert_eq!(written.attrs["node_key"].as_str(), Some("train"));
+
+    let _ = fs::remove_dir_all(&base);
+}
+
 #[test]
 fn emit_metric_accepts_valid_record_at_write_time() {
     let base = temp_dir("emit_metric_accepts_valid");
@@ -1801,6 +2033,185 @@ fn source_descriptor_rejects_oversized_uri() {
     let _ = fs::remove_dir_all(&base);
 }
 
+fn s3_source(uri: String) -> SourceDescriptorV0 {
+    SourceDescriptorV0 {
+        uri,
+        content_type: "application/parquet".to_string(),
+        auth_mode: swarm_torch_core::dataops::AuthModeMarker::None,
+        etag_or_version: None,
+    }
+}
+
+#[test]
+fn register_sources_rejects_batch_before_any_write() {
+    let base = temp_dir("register_sources_rejects_batch");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let run_id = RunId::from_bytes([116u8; 16]);
+    let bundle = RunArtifactBundle::create(&base, run_id).unwrap();
+    let sink = Arc::new(RunArtifactSink::new(bundle));
+    let mut session = DataOpsSession::new(Arc::clone(&sink));
+    let ingest = make_source_node("ingest/s3");
+
+    let batch = [
+        SourceRegistration {
+            asset_key: "dataset://ns/ok",
+            trust: TrustClass::Trusted,
+            source: s3_source("s3://bucket/ok".to_string()),
+            schema: None,
+            ingest_node: &ingest,
+        },
+        SourceRegistration {
+            asset_key: "dataset://ns/huge",
+            trust: TrustClass::Trusted,
+            source: s3_source(format!(
+                "s3://bucket/{}",
+                "a".repeat(MAX_SOURCE_URI_LEN + 1)
+            )),
+            schema: None,
+            ingest_node: &ingest,
+        },
+    ];
+    let err = session
+        .register_sources(&batch)
+        .expect_err("oversized uri should fail the batch");
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    assert!(session.fingerprint("dataset://ns/ok").is_none());
+    let datasets_dir = sink.bundle().run_dir().join("datasets");
+    let updates = fs::read_to_string(datasets_dir.join("registry_updates.ndjson")).unwrap();
+    assert!(updates.trim().is_empty());
+    assert!(!datasets_dir.join("snapshot_pair_commit.json").exists());
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn register_sources_registers_all_and_flushes_once() {
+    let base = temp_dir("register_sources_flushes_once");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let run_id = RunId::from_bytes([117u8; 16]);
+    let bundle = RunArtifactBundle::create(&base, run_id).unwrap();
+    let sink = Arc::new(RunArtifactSink::new(bundle));
+    let mut session = DataOpsSession::new(Arc::clone(&sink));
+    let ingest = make_source_node("ingest/s3");
+
+    let keys = ["dataset://ns/a", "dataset://ns/b", "dataset://ns/c"];
+    let batch: Vec<SourceRegistration<'_>> = keys
+        .iter()
+        .map(|key| SourceRegistration {
+            asset_key: key,
+            trust: TrustClass::Trusted,
+            source: s3_source(format!("s3://user:secret@bucket/{key}")),
+            schema: None,
+            ingest_node: &ingest,
+        })
+        .collect();
+    session.register_sources(&batch).unwrap();
+
+    for key in keys {
+        assert!(session.fingerprint(key).is_some(), "{key} not registered");
+    }
+    let datasets_dir = sink.bundle().run_dir().join("datasets");
+    let updates = fs::read_to_string(datasets_dir.join("registry_updates.ndjson")).unwrap();
+    assert_eq!(updates.lines().filter(|l| !l.trim().is_empty()).count(), 3);
+    assert!(!updates.contains("secret"), "credentials must be redacted");
+
+    // Strict profile: one snapshot pair for the whole batch.
+    let marker: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(datasets_dir.join("snapshot_pair_commit.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(marker["pair_seq"], 1);
+    let registry: DatasetRegistryV1 =
+        serde_json::from_str(&fs::read_to_string(datasets_dir.join("registry.json")).unwrap())
+            .unwrap();
+    assert_eq!(registry.datasets.len(), 3);
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[cfg(feature = "blake3")]
+#[test]
+fn blake3_session_records_hash_algo_in_registry() {
+    let base = temp_dir("blake3_session_registry");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let run_id = RunId::from_bytes([118u8; 16]);
+    let bundle = RunArtifactBundle::create(&base, run_id).unwrap();
+    let sink = Arc::new(RunArtifactSink::new(bundle));
+    let mut session = DataOpsSession::new(Arc::clone(&sink)).with_hash_algo(HashAlgo::Blake3);
+    let ingest = make_source_node("ingest/s3");
+    session
+        .register_source(
+            "dataset://ns/raw",
+            TrustClass::Trusted,
+            s3_source("s3://bucket/raw".to_string()),
+            None,
+            &ingest,
+        )
+        .unwrap();
+
+    let node = make_transform_node(
+        "transform/clean",
+        &["dataset://ns/raw"],
+        &["dataset://ns/clean"],
+        ExecutionTrust::Core,
+    );
+    let outputs = [OutputSpec {
+        asset_key: "dataset://ns/clean".to_string(),
+        schema: None,
+        rows: Some(1),
+        bytes: Some(1),
+    }];
+    let specs = [OutputSpecCore {
+        asset_key: "dataset://ns/clean".to_string(),
+        schema: None,
+    }];
+    let predicted = session.predict(&node, &specs).unwrap();
+    session
+        .materialize_node_outputs(&node, &outputs, 1000, CacheDecisionV0::Miss, 1)
+        .unwrap();
+    assert_eq!(
+        session.fingerprint("dataset://ns/clean"),
+        Some(predicted[0].fingerprint_v0.as_str())
+    );
+
+    let registry = session.registry_snapshot();
+    assert_eq!(registry.datasets.len(), 2);
+    assert!(registry
+        .datasets
+        .iter()
+        .all(|entry| entry.hash_algo == HashAlgo::Blake3));
+
+    let mut sha_session = DataOpsSession::new(Arc::clone(&sink));
+    sha_session
+        .register_source(
+            "dataset://ns/raw",
+            TrustClass::Trusted,
+            s3_source("s3://bucket/raw".to_string()),
+            None,
+            &ingest,
+        )
+        .unwrap();
+    assert_ne!(
+        sha_session.fingerprint("dataset://ns/raw"),
+        session.fingerprint("dataset://ns/raw")
+    );
+
+    let updates = fs::read_to_string(
+        sink.bundle()
+            .run_dir()
+            .join("datasets")
+            .join("registry_updates.ndjson"),
+    )
+    .unwrap();
+    assert!(updates.contains(r#""hash_algo":"blake3""#));
+
+    let _ = fs::remove_dir_all(&base);
+}
+
 #[test]
 fn source_descriptor_rejects_oversized_etag_or_version() {
     let base = temp_dir("source_descriptor_oversized_etag");
@@ -2631,8 +3042,8 @@ fn materialization_v2_includes_input_provenance() {
 }
 
 #[test]
-fn cache_hit_is_derived_from_cache_decision() {
-    let base = temp_dir("cache_hit_derived_from_decision");
+fn materialization_v2_record_seq_increments_across_calls() {
+    let base = temp_dir("mat_v2_record_seq");
     let _ = fs::remove_dir_all(&base);
     fs::create_dir_all(&base).unwrap();
     let run_id = RunId::from_bytes([91u8; 16]);
@@ -2657,31 +3068,44 @@ fn cache_hit_is_derived_from_cache_decision() {
         )
         .unwrap();
 
-    let node = make_transform_node(
-        "transform/cache",
+    let clean = make_transform_node(
+        "transform/clean",
         &["dataset://ns/raw"],
-        &["dataset://ns/out_a", "dataset://ns/out_b"],
+        &["dataset://ns/clean", "dataset://ns/rejects"],
         ExecutionTrust::Core,
     );
+    let output = |asset_key: &str| OutputSpec {
+        asset_key: asset_key.to_string(),
+        schema: None,
+        rows: None,
+        bytes: None,
+    };
     session
         .materialize_node_outputs(
-            &node,
-            &[
-                OutputSpec {
-                    asset_key: "dataset://ns/out_a".to_string(),
-                    schema: None,
-                    rows: Some(1),
-                    bytes: Some(1),
-                },
-                OutputSpec {
-                    asset_key: "dataset://ns/out_b".to_string(),
-                    schema: None,
-                    rows: Some(1),
-                    bytes: Some(1),
-                },
-            ],
+            &clean,
+            &[output("dataset://ns/clean"), output("dataset://ns/rejects")],
             1000,
-            true,
+            CacheDecisionV0::Miss,
+            5,
+        )
+        .unwrap();
+    let clean_fp = session
+        .fingerprint("dataset://ns/clean")
+        .unwrap()
+        .to_string();
+
+    let features = make_transform_node(
+        "transform/features",
+        &["dataset://ns/clean"],
+        &["dataset://ns/features"],
+        ExecutionTrust::Core,
+    );
+    session
+        .materialize_node_outputs(
+            &features,
+            &[output("dataset://ns/features")],
+            2000,
+            CacheDecisionV0::Bypass,
             5,
         )
         .unwrap();
@@ -2692,26 +3116,310 @@ fn cache_hit_is_derived_from_cache_decision() {
         .join("datasets")
         .join("materializations.ndjson");
     let content = fs::read_to_string(&mat_path).unwrap();
-    let rows: Vec<_> = content
+    let rows: Vec<MaterializationRecordV2> = content
         .lines()
         .filter(|line| !line.trim().is_empty())
-        .map(|line| {
-            serde_json::from_str::<MaterializationRecordCompat>(line)
-                .unwrap()
-                .into_v2()
-        })
+        .map(|line| serde_json::from_str::<MaterializationRecordCompat>(line).unwrap())
+        .map(MaterializationRecordCompat::into_v2)
         .collect();
 
-    assert_eq!(rows.len(), 2);
-    for row in rows {
-        assert_eq!(row.cache_decision, CacheDecisionV0::Hit);
-        assert_eq!(row.cache_hit, Some(true));
-        assert!(
-            row.cache_key_v0.as_ref().is_some_and(|v| v.len() == 64),
-            "cache_key_v0 should be a 64-char hex digest"
+    let transform_rows: Vec<&MaterializationRecordV2> = rows
+        .iter()
+        .filter(|row| row.op_type == "transform")
+        .collect();
+    assert_eq!(transform_rows.len(), 3);
+    for pair in rows.windows(2) {
+        assert_eq!(
+            pair[1].record_seq,
+            pair[0].record_seq + 1,
+            "record_seq must increment by one per row across calls"
         );
     }
 
+    let features_row = transform_rows[2];
+    assert_eq!(features_row.asset_key, "dataset://ns/features");
+    assert_eq!(
+        features_row.input_asset_keys,
+        vec!["dataset://ns/clean".to_string()]
+    );
+    assert_eq!(features_row.input_fingerprints_v0, vec![clean_fp]);
+    assert_eq!(features_row.cache_decision, CacheDecisionV0::Bypass);
+    assert_eq!(features_row.cache_hit, None);
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn materialization_v2_attaches_quality_summary() {
+    let base = temp_dir("mat_v2_quality");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let run_id = RunId::from_bytes([92u8; 16]);
+    let bundle = RunArtifactBundle::create(&base, run_id).unwrap();
+    let sink = Arc::new(RunArtifactSink::new(bundle));
+    let mut session = DataOpsSession::new(Arc::clone(&sink));
+
+    let source = SourceDescriptorV0 {
+        uri: "s3://bucket/data".to_string(),
+        content_type: "application/parquet".to_string(),
+        auth_mode: swarm_torch_core::dataops::AuthModeMarker::None,
+        etag_or_version: None,
+    };
+    let ingest = make_source_node("ingest/v1");
+    session
+        .register_source(
+            "dataset://ns/raw",
+            TrustClass::Trusted,
+            source,
+            None,
+            &ingest,
+        )
+        .unwrap();
+
+    let schema = |canonical: &str| SchemaDescriptorV0 {
+        format: "arrow-json".to_string(),
+        canonical: canonical.to_string(),
+    };
+    let node = make_transform_node(
+        "transform/clean",
+        &["dataset://ns/raw"],
+        &["dataset://ns/clean"],
+        ExecutionTrust::Core,
+    );
+    let quality = QualityInputs {
+        input_rows: 50,
+        output_rows: 40,
+        null_count: 0,
+        schema_before: Some(schema("{\"x\":\"i64\"}")),
+        schema_after: Some(schema("{\"x\":\"f64\"}")),
+    };
+    session
+        .materialize_node_outputs_with_quality(
+            &node,
+            &[OutputSpec {
+                asset_key: "dataset://ns/clean".to_string(),
+                schema: quality.schema_after.clone(),
+                rows: Some(40),
+                bytes: None,
+            }],
+            1000,
+            CacheDecisionV0::Miss,
+            5,
+            Some(&quality),
+        )
+        .unwrap();
+
+    let mat_path = sink
+        .bundle()
+        .run_dir()
+        .join("datasets")
+        .join("materializations.ndjson");
+    let content = fs::read_to_string(&mat_path).unwrap();
+    let row = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str::<MaterializationRecordCompat>(line)
+                .unwrap()
+                .into_v2()
+        })
+        .find(|row| row.asset_key == "dataset://ns/clean")
+        .expect("transform materialization row");
+    let summary = row.quality.expect("quality summary attached");
+    assert_eq!(summary.row_count_delta, Some(-10));
+    assert_eq!(summary.null_rate, Some(0.0));
+    assert_eq!(summary.schema_changed, Some(true));
+    assert_eq!(
+        summary.quality_flags,
+        vec![QUALITY_FLAG_SCHEMA_CHANGED.to_string()]
+    );
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn cache_hit_is_derived_from_cache_decision() {
+    let base = temp_dir("cache_hit_derived_from_decision");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let run_id = RunId::from_bytes([91u8; 16]);
+    let bundle = RunArtifactBundle::create(&base, run_id).unwrap();
+    let sink = Arc::new(RunArtifactSink::new(bundle));
+    let mut session = DataOpsSession::new(Arc::clone(&sink));
+
+    let source = SourceDescriptorV0 {
+        uri: "s3://bucket/data".to_string(),
+        content_type: "application/parquet".to_string(),
+        auth_mode: swarm_torch_core::dataops::AuthModeMarker::None,
+        etag_or_version: None,
+    };
+    let ingest = make_source_node("ingest/v1");
+    session
+        .register_source(
+            "dataset://ns/raw",
+            TrustClass::Trusted,
+            source,
+            None,
+            &ingest,
+        )
+        .unwrap();
+
+    let node = make_transform_node(
+        "transform/cache",
+        &["dataset://ns/raw"],
+        &["dataset://ns/out_a", "dataset://ns/out_b"],
+        ExecutionTrust::Core,
+    );
+    session
+        .materialize_node_outputs(
+            &node,
+            &[
+                OutputSpec {
+                    asset_key: "dataset://ns/out_a".to_string(),
+                    schema: None,
+                    rows: Some(1),
+                    bytes: Some(1),
+                },
+                OutputSpec {
+                    asset_key: "dataset://ns/out_b".to_string(),
+                    schema: None,
+                    rows: Some(1),
+                    bytes: Some(1),
+                },
+            ],
+            1000,
+            true,
+            5,
+        )
+        .unwrap();
+
+    let mat_path = sink
+        .bundle()
+        .run_dir()
+        .join("datasets")
+        .join("materializations.ndjson");
+    let content = fs::read_to_string(&mat_path).unwrap();
+    let rows: Vec<_> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str::<MaterializationRecordCompat>(line)
+                .unwrap()
+                .into_v2()
+        })
+        .collect();
+
+    assert_eq!(rows.len(), 2);
+    for row in rows {
+        assert_eq!(row.cache_decision, CacheDecisionV0::Hit);
+        assert_eq!(row.cache_hit, Some(true));
+        assert!(
+            row.cache_key_v0.as_ref().is_some_and(|v| v.len() == 64),
+            "cache_key_v0 should be a 64-char hex digest"
+        );
+    }
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn materialize_or_skip_records_hit_for_unchanged_node() {
+    let base = temp_dir("materialize_or_skip_hit");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let bundle = RunArtifactBundle::create(&base, RunId::from_bytes([92u8; 16])).unwrap();
+    let sink = Arc::new(RunArtifactSink::new(bundle));
+    let mut session = DataOpsSession::new(Arc::clone(&sink));
+
+    let source = SourceDescriptorV0 {
+        uri: "s3://bucket/data".to_string(),
+        content_type: "application/parquet".to_string(),
+        auth_mode: swarm_torch_core::dataops::AuthModeMarker::None,
+        etag_or_version: None,
+    };
+    session
+        .register_source(
+            "dataset://ns/raw",
+            TrustClass::Trusted,
+            source,
+            None,
+            &make_source_node("ingest/v1"),
+        )
+        .unwrap();
+
+    let node = make_transform_node(
+        "transform/skip",
+        &["dataset://ns/raw"],
+        &["dataset://ns/out"],
+        ExecutionTrust::Core,
+    );
+    let outputs = [OutputSpec {
+        asset_key: "dataset://ns/out".to_string(),
+        schema: None,
+        rows: Some(3),
+        bytes: Some(30),
+    }];
+
+    let first = session
+        .materialize_or_skip(&node, &outputs, 1000, "core", 7)
+        .unwrap();
+    assert_eq!(first, CacheDecisionV0::Miss);
+    let registry_lines = count_lines(sink.bundle(), "datasets/registry_updates.ndjson");
+
+    let second = session
+        .materialize_or_skip(&node, &outputs, 2000, "core", 7)
+        .unwrap();
+    assert_eq!(second, CacheDecisionV0::Hit);
+    assert_eq!(
+        count_lines(sink.bundle(), "datasets/registry_updates.ndjson"),
+        registry_lines,
+        "an unchanged node must not re-append its registry entry"
+    );
+
+    let content = fs::read_to_string(
+        sink.bundle()
+            .run_dir()
+            .join("datasets")
+            .join("materializations.ndjson"),
+    )
+    .unwrap();
+    let rows: Vec<_> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str::<MaterializationRecordCompat>(line)
+                .unwrap()
+                .into_v2()
+        })
+        .collect();
+    let out_rows: Vec<_> = rows
+        .iter()
+        .filter(|row| row.asset_key == "dataset://ns/out")
+        .collect();
+    assert_eq!(out_rows.len(), 2);
+    assert_eq!(out_rows[0].cache_decision, CacheDecisionV0::Miss);
+    assert_eq!(out_rows[0].duration_ms, Some(7));
+    assert_eq!(out_rows[1].cache_decision, CacheDecisionV0::Hit);
+    assert_eq!(out_rows[1].duration_ms, Some(0));
+    assert_eq!(out_rows[0].fingerprint_v0, out_rows[1].fingerprint_v0);
+    assert_eq!(out_rows[0].cache_key_v0, out_rows[1].cache_key_v0);
+
+    // A different execution profile yields a different cache key.
+    session
+        .materialize_or_skip(&node, &outputs, 3000, "sandboxed_extension", 7)
+        .unwrap();
+    let last = fs::read_to_string(
+        sink.bundle()
+            .run_dir()
+            .join("datasets")
+            .join("materializations.ndjson"),
+    )
+    .unwrap();
+    let last = serde_json::from_str::<MaterializationRecordCompat>(last.lines().last().unwrap())
+        .unwrap()
+        .into_v2();
+    assert_ne!(last.cache_key_v0, out_rows[1].cache_key_v0);
+
     let _ = fs::remove_dir_all(&base);
 }
 
@@ -3122,6 +3830,55 @@ fn strict_profile_updates_manifest_on_each_write() {
     let _ = fs::remove_dir_all(&base);
 }
 
+#[test]
+fn auto_manifest_sink_validates_without_finalize() {
+    let base = temp_dir("manifest_auto");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let run_id = RunId::from_bytes([106u8; 16]);
+    let bundle = RunArtifactBundle::create(&base, run_id).unwrap();
+    let sink = RunArtifactSink::new_auto_manifest(bundle);
+    assert_eq!(
+        sink.profile().manifest_policy,
+        ManifestRefreshPolicy::Always
+    );
+
+    for i in 0..3u64 {
+        sink.append_event(&EventRecord {
+            schema_version: 1,
+            trace_id: TraceId::from_bytes([1u8; 16]),
+            span_id: None,
+            name: format!("event/{i}"),
+            ts_unix_nanos: 1000 + i,
+            attrs: AttrMap::new(),
+        })
+        .unwrap();
+        sink.append_metric(&MetricRecord {
+            schema_version: 1,
+            trace_id: TraceId::from_bytes([1u8; 16]),
+            span_id: None,
+            name: "loss".to_string(),
+            ts_unix_nanos: 1000 + i,
+            value: 0.5,
+            unit: None,
+            attrs: AttrMap::new(),
+        })
+        .unwrap();
+        assert!(
+            sink.validate_manifest().is_ok(),
+            "manifest must be fresh after every append"
+        );
+    }
+
+    // A fresh reader (e.g. a dashboard) validates without any finalize call.
+    RunArtifactBundle::open(sink.bundle().run_dir())
+        .unwrap()
+        .validate_manifest()
+        .unwrap();
+
+    let _ = fs::remove_dir_all(&base);
+}
+
 #[test]
 fn manifest_interval_policy_refreshes_after_n_writes() {
     let base = temp_dir("manifest_interval_policy");
@@ -3298,3 +4055,483 @@ fn materialization_schema_stable_after_artifacts_module_split() {
 
     let _ = fs::remove_dir_all(&base);
 }
+
+#[cfg(feature = "compression")]
+#[test]
+fn compressed_bundle_roundtrips_ndjson_and_manifest() {
+    let base = temp_dir("compressed_bundle");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let run_id = RunId::from_bytes([107u8; 16]);
+    let bundle = RunArtifactBundle::create_compressed(&base, run_id).unwrap();
+    assert_eq!(bundle.compression(), NdjsonCompression::Gzip);
+    assert!(bundle.run_dir().join("events.ndjson.gz").exists());
+    assert!(!bundle.run_dir().join("events.ndjson").exists());
+
+    for i in 0..3u64 {
+        bundle
+            .append_event(&EventRecord {
+                schema_version: 1,
+                trace_id: TraceId::from_bytes([1u8; 16]),
+                span_id: None,
+                name: format!("event/{i}"),
+                ts_unix_nanos: 1000 + i,
+                attrs: AttrMap::new(),
+            })
+            .unwrap();
+    }
+    bundle.finalize_manifest().unwrap();
+
+    let raw = fs::read(bundle.run_dir().join("events.ndjson.gz")).unwrap();
+    assert_eq!(
+        &raw[..2],
+        &[0x1f, 0x8b],
+        "events must be gzip-encoded on disk"
+    );
+
+    // Compression is recorded in run.json and restored by open().
+    let reopened = RunArtifactBundle::open(bundle.run_dir()).unwrap();
+    assert_eq!(reopened.compression(), NdjsonCompression::Gzip);
+    reopened.validate_manifest().unwrap();
+    let manifest = fs::read_to_string(reopened.run_dir().join("manifest.json")).unwrap();
+    assert!(manifest.contains("\"events.ndjson.gz\""));
+
+    let report = crate::report::load_report(reopened.run_dir()).unwrap();
+    let names: Vec<&str> = report.events.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, ["event/0", "event/1", "event/2"]);
+    assert!(report.spans.is_empty());
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn bundle_for_trace_root_uses_trace_bytes_as_run_id() {
+    let base = temp_dir("bundle_for_trace_root");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+
+    let trace = TraceId::derive(b"root");
+    let bundle = RunArtifactBundle::create_for_trace(&base, &trace).unwrap();
+    assert_eq!(bundle.run_id().as_bytes(), trace.as_bytes());
+    assert!(bundle.run_dir().ends_with(trace.to_string()));
+
+    let err = RunArtifactBundle::create_for_trace(&base, &TraceId::from_bytes([0u8; 16]))
+        .expect_err("all-zero trace must be rejected");
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+fn integrity_session(prefix: &str) -> (PathBuf, Arc<RunArtifactSink>, DataOpsSession) {
+    integrity_session_with_profile(prefix, SnapshotProfile::Strict)
+}
+
+fn integrity_source(etag: &str) -> SourceDescriptorV0 {
+    SourceDescriptorV0 {
+        uri: "s3://bucket/raw.parquet".to_string(),
+        content_type: "application/parquet".to_string(),
+        auth_mode: swarm_torch_core::dataops::AuthModeMarker::None,
+        etag_or_version: Some(etag.to_string()),
+    }
+}
+
+fn integrity_session_with_profile(
+    prefix: &str,
+    profile: SnapshotProfile,
+) -> (PathBuf, Arc<RunArtifactSink>, DataOpsSession) {
+    let base = temp_dir(prefix);
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let bundle = RunArtifactBundle::create(&base, RunId::from_bytes([107u8; 16])).unwrap();
+    let sink = Arc::new(RunArtifactSink::new(bundle));
+    let mut session = DataOpsSession::with_profile(Arc::clone(&sink), profile);
+
+    let ingest = make_source_node("ingest/raw");
+    let transform = make_transform_node(
+        "transform/clean",
+        &["dataset://ns/raw"],
+        &["dataset://ns/clean"],
+        ExecutionTrust::Core,
+    );
+    let graph = GraphV1 {
+        nodes: vec![ingest.clone(), transform.clone()],
+        ..Default::default()
+    };
+    sink.bundle().write_graph(&graph).unwrap();
+
+    session
+        .register_source(
+            "dataset://ns/raw",
+            TrustClass::Trusted,
+            integrity_source("v1"),
+            None,
+            &ingest,
+        )
+        .unwrap();
+    session
+        .materialize_node_outputs(
+            &transform,
+            &[OutputSpec {
+                asset_key: "dataset://ns/clean".to_string(),
+                schema: None,
+                rows: Some(10),
+                bytes: Some(100),
+            }],
+            1_000,
+            false,
+            5,
+        )
+        .unwrap();
+    (base, sink, session)
+}
+
+#[test]
+fn validate_integrity_passes_for_clean_pipeline() {
+    let (base, sink, mut session) = integrity_session("integrity_clean");
+    session.finalize().unwrap();
+    assert_eq!(sink.bundle().validate_integrity(), Ok(()));
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn validate_integrity_passes_while_streaming_snapshots_lag() {
+    let (base, sink, _session) =
+        integrity_session_with_profile("integrity_streaming", SnapshotProfile::streaming(100));
+    // No snapshot flush yet: registry.json and lineage.json are still empty.
+    let registry: DatasetRegistryV1 =
+        read_json(&sink.bundle().run_dir().join("datasets/registry.json")).unwrap();
+    assert!(registry.datasets.is_empty());
+    assert_eq!(sink.bundle().validate_integrity(), Ok(()));
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn validate_integrity_accepts_lineage_to_superseded_fingerprints() {
+    let (base, sink, mut session) = integrity_session("integrity_superseded");
+    // A new source version replaces the registry entry; the earlier edge keeps the old
+    // fingerprint, which only the registry update history still records.
+    session
+        .register_source(
+            "dataset://ns/raw",
+            TrustClass::Trusted,
+            integrity_source("v2"),
+            None,
+            &make_source_node("ingest/raw"),
+        )
+        .unwrap();
+    session.finalize().unwrap();
+    assert_eq!(sink.bundle().validate_integrity(), Ok(()));
+
+    // A fingerprint that was never registered is still reported.
+    sink.bundle()
+        .append_lineage_edge_update(&LineageEdgeV1 {
+            input_fingerprint_v0: "ee".repeat(32),
+            ..read_json::<DatasetLineageV1>(&sink.bundle().run_dir().join("datasets/lineage.json"))
+                .unwrap()
+                .edges[0]
+                .clone()
+        })
+        .unwrap();
+    let errors = sink.bundle().validate_integrity().unwrap_err();
+    assert!(matches!(
+        &errors[..],
+        [IntegrityError::UnregisteredLineageFingerprint { fingerprint, .. }] if *fingerprint == "ee".repeat(32)
+    ));
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn validate_integrity_reports_unknown_materialization_node() {
+    let (base, sink, mut session) = integrity_session("integrity_unknown_node");
+    let rogue = make_transform_node(
+        "transform/rogue",
+        &["dataset://ns/clean"],
+        &["dataset://ns/rogue"],
+        ExecutionTrust::Core,
+    );
+    session
+        .materialize_node_outputs(
+            &rogue,
+            &[OutputSpec {
+                asset_key: "dataset://ns/rogue".to_string(),
+                schema: None,
+                rows: None,
+                bytes: None,
+            }],
+            2_000,
+            false,
+            1,
+        )
+        .unwrap();
+    session.finalize().unwrap();
+
+    let errors = sink.bundle().validate_integrity().unwrap_err();
+    assert_eq!(
+        errors,
+        vec![IntegrityError::UnknownMaterializationNode {
+            asset_key: "dataset://ns/rogue".to_string(),
+            node_id: node_id_from_key("transform/rogue"),
+        }]
+    );
+    assert!(errors[0].to_string().contains("unknown node"));
+    let _ = fs::remove_dir_all(&base);
+}
+
+fn merge_span(byte: u8) -> SpanRecord {
+    SpanRecord {
+        schema_version: 1,
+        trace_id: TraceId::from_bytes([byte; 16]),
+        span_id: SpanId::from_bytes([byte; 8]),
+        parent_span_id: None,
+        name: format!("coordinator/{byte}"),
+        start_unix_nanos: 1,
+        end_unix_nanos: Some(2),
+        attrs: AttrMap::new(),
+    }
+}
+
+fn merge_entry(asset_key: &str, fingerprint: &str) -> DatasetEntryV1 {
+    DatasetEntryV1 {
+        asset_key: asset_key.to_string(),
+        fingerprint_v0: fingerprint.to_string(),
+        source_fingerprint_v0: "00".repeat(32),
+        schema_hash_v0: "00".repeat(32),
+        recipe_hash_v0: "00".repeat(32),
+        hash_algo: HashAlgo::Sha256,
+        trust: TrustClass::Trusted,
+        source: None,
+        schema: None,
+        license_flags: Vec::new(),
+        pii_tags: Vec::new(),
+    }
+}
+
+fn count_lines(bundle: &RunArtifactBundle, rel: &str) -> usize {
+    use std::io::BufRead;
+    bundle.open_ndjson(rel).unwrap().lines().count()
+}
+
+#[test]
+fn merge_from_unions_spans_and_registries() {
+    let base = temp_dir("merge_from_union");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let primary = RunArtifactBundle::create(&base, RunId::from_bytes([110u8; 16])).unwrap();
+    let secondary = RunArtifactBundle::create(&base, RunId::from_bytes([111u8; 16])).unwrap();
+
+    primary.append_span(&merge_span(1)).unwrap();
+    primary.append_span(&merge_span(2)).unwrap();
+    secondary.append_span(&merge_span(2)).unwrap();
+    secondary.append_span(&merge_span(3)).unwrap();
+    primary
+        .write_dataset_registry(&DatasetRegistryV1 {
+            datasets: vec![merge_entry("dataset://ns/a", &"aa".repeat(32))],
+            ..Default::default()
+        })
+        .unwrap();
+    secondary
+        .write_dataset_registry(&DatasetRegistryV1 {
+            datasets: vec![
+                merge_entry("dataset://ns/b", &"bb".repeat(32)),
+                merge_entry("dataset://ns/a", &"aa".repeat(32)),
+            ],
+            ..Default::default()
+        })
+        .unwrap();
+
+    primary
+        .merge_from(std::slice::from_ref(&secondary))
+        .unwrap();
+    assert_eq!(count_lines(&primary, "spans.ndjson"), 3);
+    let registry: DatasetRegistryV1 =
+        read_json(&primary.run_dir().join("datasets/registry.json")).unwrap();
+    let keys: Vec<&str> = registry
+        .datasets
+        .iter()
+        .map(|entry| entry.asset_key.as_str())
+        .collect();
+    assert_eq!(keys, ["dataset://ns/a", "dataset://ns/b"]);
+    primary.validate_manifest().unwrap();
+
+    // Re-merging is idempotent.
+    primary.merge_from(&[secondary]).unwrap();
+    assert_eq!(count_lines(&primary, "spans.ndjson"), 3);
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn merge_from_rejects_registry_fingerprint_conflict() {
+    let base = temp_dir("merge_from_conflict");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let primary = RunArtifactBundle::create(&base, RunId::from_bytes([112u8; 16])).unwrap();
+    let secondary = RunArtifactBundle::create(&base, RunId::from_bytes([113u8; 16])).unwrap();
+
+    primary
+        .write_dataset_registry(&DatasetRegistryV1 {
+            datasets: vec![merge_entry("dataset://ns/a", &"aa".repeat(32))],
+            ..Default::default()
+        })
+        .unwrap();
+    secondary
+        .write_dataset_registry(&DatasetRegistryV1 {
+            datasets: vec![merge_entry("dataset://ns/a", &"ab".repeat(32))],
+            ..Default::default()
+        })
+        .unwrap();
+    secondary.append_span(&merge_span(9)).unwrap();
+
+    let err = primary.merge_from(&[secondary]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("dataset://ns/a"), "{err}");
+    assert_eq!(count_lines(&primary, "spans.ndjson"), 0);
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+fn register_merge_source(session: &mut DataOpsSession, asset_key: &str, etag: &str) {
+    let source = SourceDescriptorV0 {
+        uri: format!("s3://bucket/{asset_key}"),
+        content_type: "application/parquet".to_string(),
+        auth_mode: swarm_torch_core::dataops::AuthModeMarker::None,
+        etag_or_version: Some(etag.to_string()),
+    };
+    let node = make_source_node(&format!("ingest/{asset_key}"));
+    session
+        .register_source(asset_key, TrustClass::Trusted, source, None, &node)
+        .unwrap();
+}
+
+#[test]
+fn merge_from_session_bundles_rewrites_snapshot_pair_commit() {
+    let base = temp_dir("merge_from_session");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let primary = RunArtifactBundle::create(&base, RunId::from_bytes([114u8; 16])).unwrap();
+    let secondary = RunArtifactBundle::create(&base, RunId::from_bytes([115u8; 16])).unwrap();
+
+    let mut session = DataOpsSession::new(Arc::new(RunArtifactSink::new(primary.clone())));
+    register_merge_source(&mut session, "dataset://ns/raw", "v1");
+    session.finalize().unwrap();
+    // Streaming with a long period: the secondary's entries exist only in its updates log.
+    let mut session = DataOpsSession::with_profile(
+        Arc::new(RunArtifactSink::new(secondary.clone())),
+        SnapshotProfile::streaming(100),
+    );
+    register_merge_source(&mut session, "dataset://ns/raw", "v1");
+    register_merge_source(&mut session, "dataset://ns/other", "v1");
+
+    primary.merge_from(&[secondary]).unwrap();
+
+    let registry: DatasetRegistryV1 =
+        read_json(&primary.run_dir().join("datasets/registry.json")).unwrap();
+    let keys: Vec<&str> = registry
+        .datasets
+        .iter()
+        .map(|entry| entry.asset_key.as_str())
+        .collect();
+    assert_eq!(keys, ["dataset://ns/other", "dataset://ns/raw"]);
+    primary.validate_manifest().unwrap();
+    let (report, warnings) = crate::report::load_report_with_warnings(primary.run_dir()).unwrap();
+    assert!(
+        !warnings
+            .iter()
+            .any(|w| matches!(w, crate::report::LoadWarning::SnapshotPairMismatch { .. })),
+        "{warnings:?}"
+    );
+    assert_eq!(report.registry.datasets.len(), 2);
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn merge_from_rejects_conflict_only_in_registry_updates() {
+    let base = temp_dir("merge_from_updates_conflict");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let primary = RunArtifactBundle::create(&base, RunId::from_bytes([116u8; 16])).unwrap();
+    let secondary = RunArtifactBundle::create(&base, RunId::from_bytes([117u8; 16])).unwrap();
+
+    let mut session = DataOpsSession::new(Arc::new(RunArtifactSink::new(primary.clone())));
+    register_merge_source(&mut session, "dataset://ns/raw", "v1");
+    session.finalize().unwrap();
+    let mut session = DataOpsSession::with_profile(
+        Arc::new(RunArtifactSink::new(secondary.clone())),
+        SnapshotProfile::streaming(100),
+    );
+    register_merge_source(&mut session, "dataset://ns/raw", "v2");
+
+    let err = primary.merge_from(&[secondary]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("dataset://ns/raw"), "{err}");
+    assert_eq!(count_lines(&primary, "datasets/registry_updates.ndjson"), 1);
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+#[test]
+fn canonical_json_is_byte_identical_and_key_sorted() {
+    #[derive(serde::Serialize)]
+    struct Unordered {
+        zeta: f32,
+        alpha: Vec<u64>,
+        mid: std::collections::HashMap<String, f64>,
+    }
+
+    let unordered = |pairs: &[(&str, f64)]| Unordered {
+        zeta: 0.1,
+        alpha: vec![3, 1, 2],
+        mid: pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+    };
+    // Same content, different map insertion order.
+    let forward = unordered(&[("b", 0.5), ("a", 0.0), ("c", 1.0)]);
+    let reverse = unordered(&[("c", 1.0), ("a", 0.0), ("b", 0.5)]);
+
+    let first = to_canonical_json(&forward).unwrap();
+    assert_eq!(first, to_canonical_json(&forward).unwrap());
+    assert_eq!(first, to_canonical_json(&reverse).unwrap());
+
+    let text = String::from_utf8(first).unwrap();
+    let position = |needle: &str| text.find(needle).unwrap();
+    assert!(position("\"alpha\"") < position("\"mid\""));
+    assert!(position("\"mid\"") < position("\"zeta\""));
+    assert!(position("\"a\"") < position("\"b\""));
+    // f32 keeps its short form; array order is data and is preserved.
+    assert!(text.contains("\"zeta\": 0.1\n"), "{text}");
+    assert!(text.contains("3,\n    1,\n    2"), "{text}");
+}
+
+#[test]
+fn signed_manifest_detects_tampering_and_wrong_key() {
+    use swarm_torch_core::crypto::{KeyPair, MessageAuth};
+
+    let base = temp_dir("signed_manifest");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let bundle = RunArtifactBundle::create(&base, RunId::from_bytes([114u8; 16])).unwrap();
+    bundle.append_span(&merge_span(1)).unwrap();
+
+    let signer = KeyPair::from_seed([21u8; 32]).unwrap();
+    let public_key = *signer.public_key();
+    bundle
+        .finalize_manifest_signed(&MessageAuth::new(signer))
+        .unwrap();
+    bundle.validate_manifest_signed(&public_key).unwrap();
+    // Re-signing must not hash the previous signature into the manifest.
+    bundle
+        .finalize_manifest_signed(&MessageAuth::new(KeyPair::from_seed([21u8; 32]).unwrap()))
+        .unwrap();
+    bundle.validate_manifest_signed(&public_key).unwrap();
+
+    let other = *KeyPair::from_seed([22u8; 32]).unwrap().public_key();
+    let err = bundle.validate_manifest_signed(&other).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("signature"), "{err}");
+
+    bundle.append_span(&merge_span(2)).unwrap();
+    let err = bundle.validate_manifest_signed(&public_key).unwrap_err();
+    assert!(err.to_string().contains("mismatch"), "{err}");
+
+    let _ = fs::remove_dir_all(&base);
+}
@@ -0,0 +1,138 @@
+//! Analytics export of run artifacts (Parquet).
+//!
+//! The export is a flat, fixed-schema projection of `datasets/materializations.ndjson`
+//! intended for ad-hoc querying (e.g. DuckDB). It is not part of the artifact bundle and
+//! is not covered by `manifest.json`.
+
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use parquet::basic::Type as PhysicalType;
+use parquet::data_type::{BoolType, ByteArray, ByteArrayType, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+use swarm_torch_core::dataops::MaterializationRecordV2;
+
+use crate::artifacts::RunArtifactBundle;
+use crate::report::{cache_decision_label, read_materializations};
+
+/// Parquet schema for [`export_materializations_parquet`], in column order.
+///
+/// `u64` counters are stored as unsigned `INT64`; absent values are `NULL`.
+pub const MATERIALIZATIONS_PARQUET_SCHEMA: &str = "
+message materialization_v2 {
+    REQUIRED BYTE_ARRAY asset_key (STRING);
+    REQUIRED BYTE_ARRAY fingerprint (STRING);
+    REQUIRED BYTE_ARRAY node_id (STRING);
+    REQUIRED BYTE_ARRAY op_type (STRING);
+    OPTIONAL INT64 rows (INTEGER(64,false));
+    OPTIONAL INT64 bytes (INTEGER(64,false));
+    OPTIONAL INT64 duration_ms (INTEGER(64,false));
+    REQUIRED BYTE_ARRAY cache_decision (STRING);
+    REQUIRED BOOLEAN unsafe_surface;
+}
+";
+
+/// Export a run's materializations (V1/V2 via compat) to a Parquet file at `out_path`.
+///
+/// Rows are ordered like the report timeline (`ts_unix_nanos`, then `record_seq`).
+/// `node_id` is lowercase hex; `cache_decision` is `hit|miss|bypass|unknown`.
+/// Returns the number of rows written.
+pub fn export_materializations_parquet(
+    run_dir: impl AsRef<Path>,
+    out_path: impl AsRef<Path>,
+) -> io::Result<usize> {
+    let bundle = RunArtifactBundle::open(run_dir)?;
+    let records = read_materializations(&bundle)?;
+    write_materializations_parquet(&records, File::create(out_path)?).map_err(io::Error::other)?;
+    Ok(records.len())
+}
+
+fn write_materializations_parquet(
+    records: &[MaterializationRecordV2],
+    file: File,
+) -> parquet::errors::Result<()> {
+    let schema = Arc::new(parse_message_type(MATERIALIZATIONS_PARQUET_SCHEMA)?);
+    let props = Arc::new(WriterProperties::builder().build());
+    let mut writer = SerializedFileWriter::new(file, schema, props)?;
+
+    if !records.is_empty() {
+        let columns = writer.schema_descr().columns().to_vec();
+        let mut row_group = writer.next_row_group()?;
+        for descr in columns {
+            let Some(mut column) = row_group.next_column()? else {
+                break;
+            };
+            match (descr.name(), descr.physical_type()) {
+                (name, PhysicalType::BYTE_ARRAY) => {
+                    let values: Vec<ByteArray> = records
+                        .iter()
+                        .map(|m| ByteArray::from(string_column(m, name).into_bytes()))
+                        .collect();
+                    column
+                        .typed::<ByteArrayType>()
+                        .write_batch(&values, None, None)?;
+                }
+                (name, PhysicalType::INT64) => {
+                    let (values, def_levels) = optional_u64_column(records, name);
+                    column
+                        .typed::<Int64Type>()
+                        .write_batch(&values, Some(&def_levels), None)?;
+                }
+                (_, PhysicalType::BOOLEAN) => {
+                    let values: Vec<bool> = records.iter().map(|m| m.unsafe_surface).collect();
+                    column
+                        .typed::<BoolType>()
+                        .write_batch(&values, None, None)?;
+                }
+                (name, ty) => {
+                    return Err(parquet::errors::ParquetError::General(format!(
+                        "unexpected column {name} ({ty})"
+                    )));
+                }
+            }
+            column.close()?;
+        }
+        row_group.close()?;
+    }
+
+    writer.close()?;
+    Ok(())
+}
+
+fn string_column(m: &MaterializationRecordV2, name: &str) -> String {
+    match name {
+        "asset_key" => m.asset_key.clone(),
+        "fingerprint" => m.fingerprint_v0.clone(),
+        "node_id" => m.node_id.to_string(),
+        "op_type" => m.op_type.clone(),
+        "cache_decision" => cache_decision_label(m.cache_decision).to_string(),
+        _ => unreachable!("column {name} is not in MATERIALIZATIONS_PARQUET_SCHEMA"),
+    }
+}
+
+/// Non-null values plus definition levels (`1` = present, `0` = `NULL`).
+fn optional_u64_column(records: &[MaterializationRecordV2], name: &str) -> (Vec<i64>, Vec<i16>) {
+    let mut values = Vec::new();
+    let mut def_levels = Vec::with_capacity(records.len());
+    for m in records {
+        let value = match name {
+            "rows" => m.rows,
+            "bytes" => m.bytes,
+            "duration_ms" => m.duration_ms,
+            _ => unreachable!("column {name} is not in MATERIALIZATIONS_PARQUET_SCHEMA"),
+        };
+        match value {
+            // Unsigned INT64: the bit pattern is preserved.
+            Some(v) => {
+                values.push(v as i64);
+                def_levels.push(1);
+            }
+            None => def_levels.push(0),
+        }
+    }
+    (values, def_levels)
+}
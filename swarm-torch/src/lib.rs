@@ -66,15 +66,26 @@ pub use swarm_torch_runtime as runtime;
 pub use swarm_torch_core::{
     aggregation::{self, RobustAggregation, RobustAggregator},
     algorithms::Topology,
-    traits::{GradientUpdate, PeerId, SwarmModel},
+    consensus::{GossipConfig, RoundId, RoundPhase, RoundState},
+    convergence::ConvergenceMonitor,
+    crypto::GradientValidator,
+    traits::{GradientUpdate, PeerId, SwarmModel, UpdateEncoding},
     Error, Result,
 };
 
 pub use swarm_torch_net::{
-    protocol::{MessageEnvelope, MessageType},
+    protocol::{AuthenticatedEnvelopeVerifier, MessageEnvelope, MessageType},
     traits::{SwarmTransport, TransportCapabilities},
 };
 
+use std::future::{poll_fn, Future};
+use std::pin::pin;
+use std::task::Poll;
+use std::time::Duration;
+
+use swarm_torch_core::crypto::MessageAuth;
+use swarm_torch_runtime::SwarmRuntime;
+
 /// Artifact bundle writing/validation (std-only).
 #[cfg(feature = "std")]
 pub mod artifacts;
@@ -87,10 +98,30 @@ pub mod report;
 #[cfg(feature = "std")]
 pub mod native_runner;
 
+/// Process-isolated OpRunner for sandboxed extensions (std-only).
+#[cfg(feature = "std")]
+pub mod sandboxed_runner;
+
 /// Sequential graph scheduler (std-only).
 #[cfg(feature = "std")]
 pub mod scheduler;
 
+/// Schema evolution/compatibility checks (std-only).
+#[cfg(feature = "std")]
+pub mod schema_compat;
+
+/// OpenLineage export of run lineage (std-only).
+#[cfg(feature = "std")]
+pub mod openlineage;
+
+/// Parquet export of run artifacts for analytics (`parquet` feature).
+#[cfg(feature = "parquet")]
+pub mod export;
+
+/// OTLP/HTTP exporter implementing `RunEventEmitter` (`otlp` feature).
+#[cfg(feature = "otlp")]
+pub mod otlp;
+
 /// Prelude module for convenient imports
 ///
 /// ```rust,ignore
@@ -115,6 +146,10 @@ pub struct SwarmConfig {
     pub max_rounds: u64,
     /// Convergence threshold for early stopping
     pub convergence_threshold: f32,
+    /// Fraction of expected participants needed to close a round, in `(0, 1]`
+    pub quorum_ratio: f32,
+    /// Seconds a round collects updates before it times out
+    pub round_timeout_secs: u64,
 }
 
 /// SwarmConfig validation error (M-13).
@@ -126,6 +161,8 @@ pub enum SwarmConfigError {
     ConvergenceThresholdNotFinite,
     /// `convergence_threshold` must be non-negative.
     ConvergenceThresholdNegative,
+    /// `quorum_ratio` must be in `(0, 1]`.
+    QuorumRatioOutOfRange,
 }
 
 impl std::fmt::Display for SwarmConfigError {
@@ -138,12 +175,84 @@ impl std::fmt::Display for SwarmConfigError {
             Self::ConvergenceThresholdNegative => {
                 write!(f, "convergence_threshold must be non-negative")
             }
+            Self::QuorumRatioOutOfRange => write!(f, "quorum_ratio must be in (0, 1]"),
         }
     }
 }
 
 impl std::error::Error for SwarmConfigError {}
 
+/// Unified error across the SwarmTorch stack, so `?` works across crate boundaries.
+#[derive(Debug)]
+pub enum SwarmError {
+    /// Core operation (aggregation, validation, serialization) failed.
+    Core(Error),
+    /// Network operation failed.
+    Net(swarm_torch_net::Error),
+    /// Invalid cluster configuration.
+    Config(SwarmConfigError),
+    /// Filesystem or stream I/O failed.
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for SwarmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Core(e) => write!(f, "core error: {e}"),
+            Self::Net(e) => write!(f, "network error: {e}"),
+            Self::Config(e) => write!(f, "invalid config: {e}"),
+            Self::Io(e) => write!(f, "io error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SwarmError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Core(e) => Some(e),
+            Self::Net(e) => Some(e),
+            Self::Config(e) => Some(e),
+            Self::Io(e) => Some(e),
+        }
+    }
+}
+
+impl From<Error> for SwarmError {
+    fn from(error: Error) -> Self {
+        Self::Core(error)
+    }
+}
+
+impl From<swarm_torch_net::Error> for SwarmError {
+    fn from(error: swarm_torch_net::Error) -> Self {
+        Self::Net(error)
+    }
+}
+
+impl From<SwarmConfigError> for SwarmError {
+    fn from(error: SwarmConfigError) -> Self {
+        Self::Config(error)
+    }
+}
+
+impl From<std::io::Error> for SwarmError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<swarm_torch_core::replay::ReplayError> for SwarmError {
+    fn from(error: swarm_torch_core::replay::ReplayError) -> Self {
+        Self::Net(error.into())
+    }
+}
+
+impl From<swarm_torch_net::protocol::VerifyError> for SwarmError {
+    fn from(error: swarm_torch_net::protocol::VerifyError) -> Self {
+        Self::Net(error.into())
+    }
+}
+
 impl SwarmConfig {
     /// Validate configuration.
     pub fn validate(&self) -> std::result::Result<(), SwarmConfigError> {
@@ -156,8 +265,19 @@ impl SwarmConfig {
         if self.convergence_threshold < 0.0 {
             return Err(SwarmConfigError::ConvergenceThresholdNegative);
         }
+        if !(self.quorum_ratio > 0.0 && self.quorum_ratio <= 1.0) {
+            return Err(SwarmConfigError::QuorumRatioOutOfRange);
+        }
         Ok(())
     }
+
+    /// Early-stopping monitor keyed to `convergence_threshold`.
+    ///
+    /// Feed it each round's aggregated parameters and stop the `max_rounds` loop once
+    /// it reports convergence.
+    pub fn convergence_monitor(&self, patience: usize) -> ConvergenceMonitor {
+        ConvergenceMonitor::new(self.convergence_threshold, patience)
+    }
 }
 
 impl Default for SwarmConfig {
@@ -167,6 +287,8 @@ impl Default for SwarmConfig {
             aggregation: RobustAggregation::default(),
             max_rounds: 100,
             convergence_threshold: 0.01,
+            quorum_ratio: GossipConfig::default().quorum_ratio,
+            round_timeout_secs: 60,
         }
     }
 }
@@ -207,6 +329,18 @@ impl SwarmConfigBuilder {
         self
     }
 
+    /// Set the fraction of expected participants needed to close a round
+    pub fn quorum_ratio(mut self, ratio: f32) -> Self {
+        self.config.quorum_ratio = ratio;
+        self
+    }
+
+    /// Set how long a round collects updates before timing out
+    pub fn round_timeout_secs(mut self, secs: u64) -> Self {
+        self.config.round_timeout_secs = secs;
+        self
+    }
+
     /// Build the configuration
     pub fn build(self) -> SwarmConfig {
         self.config
@@ -250,6 +384,152 @@ impl SwarmCluster {
     pub fn local_peer(&self) -> &PeerId {
         &self.local_peer
     }
+
+    /// Run one aggregation round over `transport`.
+    ///
+    /// Broadcasts `local_update` in a `GradientUpdate` envelope signed with `auth`
+    /// (whose peer id must be [`Self::local_peer`]), then collects peers' updates for
+    /// the same `round_id` into a [`RoundState`] until quorum
+    /// (`ceil(expected_participants * quorum_ratio)`, counting the local update)
+    /// and aggregates them with `aggregator`. Every received envelope must pass
+    /// `verifier` (signature, timestamp window and replay state) before it is
+    /// decoded; keep the same verifier across rounds so replayed updates from an
+    /// earlier round are rejected. Messages that fail verification or decoding,
+    /// belong to another round, whose signer does not hash to the transport peer,
+    /// are delta-encoded (no per-peer baseline is kept here), or fail gradient
+    /// validation are dropped.
+    ///
+    /// The round deadline is measured on `runtime.now()`, and each `recv` is raced
+    /// against `runtime.sleep`, so the round fails with `InsufficientUpdates` once
+    /// `round_timeout_secs` elapses on the runtime clock, even if no message
+    /// arrives. Envelope timestamps stay Unix seconds, as the wire format requires.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn run_round<T: SwarmTransport, R: SwarmRuntime, A: RobustAggregator>(
+        &self,
+        transport: &T,
+        runtime: &R,
+        auth: &MessageAuth,
+        verifier: &mut AuthenticatedEnvelopeVerifier,
+        aggregator: &A,
+        local_update: GradientUpdate,
+        expected_participants: usize,
+    ) -> std::result::Result<Vec<f32>, SwarmError> {
+        if auth.key_pair().peer_id() != self.local_peer {
+            return Err(Error::VerificationFailed.into());
+        }
+        let validator = GradientValidator::default();
+        validator
+            .validate(&local_update.gradients)
+            .map_err(|reason| Error::InvalidGradient { index: 0, reason })?;
+        let round_id = local_update.round_id;
+        let dim = local_update.gradients.len();
+
+        let payload = postcard::to_allocvec(&local_update).map_err(Error::from)?;
+        let envelope = MessageEnvelope::signed(
+            auth,
+            MessageType::GradientUpdate,
+            payload,
+            local_update.sequence,
+            unix_now_secs(),
+        );
+        let bytes = envelope.serialize().map_err(swarm_torch_net::Error::from)?;
+        transport.broadcast(&bytes).await?;
+
+        let gossip = GossipConfig {
+            quorum_ratio: self.config.quorum_ratio,
+            ..GossipConfig::default()
+        };
+        // `RoundState` only compares against its deadline, so runtime milliseconds work.
+        let deadline_ms = runtime
+            .now()
+            .saturating_add(self.config.round_timeout_secs.saturating_mul(1000));
+        let mut round = RoundState::from_config(
+            &gossip,
+            RoundId(round_id),
+            expected_participants,
+            deadline_ms,
+        );
+        round.on_update(self.local_peer);
+        let mut updates = vec![local_update];
+
+        while round.phase() == RoundPhase::Collecting {
+            let remaining = Duration::from_millis(deadline_ms.saturating_sub(runtime.now()));
+            let received = race_sleep(runtime, remaining, transport.recv()).await;
+            let timed_out = round.on_deadline(runtime.now()) == RoundPhase::TimedOut;
+            let Some(received) = received.filter(|_| !timed_out) else {
+                return Err(Error::InsufficientUpdates {
+                    got: round.received_count(),
+                    need: round.quorum,
+                }
+                .into());
+            };
+            let (from, bytes) = received?;
+            let Some(update) = decode_peer_update(verifier, &from, &bytes) else {
+                continue;
+            };
+            if update.round_id != round_id
+                || update.gradients.len() != dim
+                || validator.validate(&update.gradients).is_err()
+            {
+                continue;
+            }
+            if round.on_update(from) {
+                updates.push(update);
+            }
+        }
+
+        let aggregated = aggregator.aggregate(&updates)?;
+        // Aggregation always follows quorum here, so these transitions cannot fail.
+        let _ = round.mark_aggregated();
+        let _ = round.mark_complete();
+        Ok(aggregated)
+    }
+}
+
+/// Verify and decode a full `GradientUpdate` envelope from `from`, or `None` if it
+/// is malformed, unauthenticated, replayed, of another type, delta-encoded, or
+/// signed by a different sender.
+fn decode_peer_update(
+    verifier: &mut AuthenticatedEnvelopeVerifier,
+    from: &PeerId,
+    bytes: &[u8],
+) -> Option<GradientUpdate> {
+    let envelope = MessageEnvelope::deserialize_bounded(bytes, Default::default()).ok()?;
+    let envelope = verifier
+        .verify_and_unwrap_with_time(envelope, unix_now_secs())
+        .ok()?;
+    if envelope.message_type != MessageType::GradientUpdate
+        || envelope.sender_peer_id().ok()? != *from
+    {
+        return None;
+    }
+    let update = GradientUpdate::from_postcard_bytes(&envelope.payload).ok()?;
+    (&update.sender == from.as_bytes() && update.encoding == UpdateEncoding::Full).then_some(update)
+}
+
+/// Await `future`, or `None` if `runtime.sleep(limit)` completes first.
+async fn race_sleep<R: SwarmRuntime, F: Future>(
+    runtime: &R,
+    limit: Duration,
+    future: F,
+) -> Option<F::Output> {
+    let mut future = pin!(future);
+    let mut sleep = pin!(runtime.sleep(limit));
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+        sleep.as_mut().poll(cx).map(|()| None)
+    })
+    .await
+}
+
+/// Wall-clock Unix seconds for envelope timestamps and replay windows.
+fn unix_now_secs() -> u32 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs().min(u64::from(u32::MAX)) as u32)
+        .unwrap_or(0)
 }
 
 #[cfg(test)]
@@ -268,6 +548,28 @@ mod tests {
         assert!((config.convergence_threshold - 0.001).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn convergence_monitor_stops_before_max_rounds() {
+        let config = SwarmCluster::builder()
+            .max_rounds(100)
+            .convergence_threshold(0.01)
+            .build();
+        let mut monitor = config.convergence_monitor(2);
+        assert_eq!(monitor.threshold(), config.convergence_threshold);
+
+        // Parameters halve their distance to 1.0 every round.
+        let mut param = 0.0f32;
+        let mut stopped_at = None;
+        for round in 0..config.max_rounds {
+            param += (1.0 - param) * 0.5;
+            if monitor.record(&[param]) {
+                stopped_at = Some(round);
+                break;
+            }
+        }
+        assert_eq!(stopped_at, Some(7));
+    }
+
     #[test]
     fn test_peer_id() {
         let bytes = [1u8; 32];
@@ -275,6 +577,42 @@ mod tests {
         assert_eq!(peer.as_bytes(), &bytes);
     }
 
+    #[test]
+    fn swarm_error_unifies_sub_crate_errors() {
+        fn aggregate_empty() -> std::result::Result<Vec<f32>, SwarmError> {
+            Ok(aggregation::FedAvg.aggregate(&[])?)
+        }
+        assert!(matches!(
+            aggregate_empty(),
+            Err(SwarmError::Core(Error::InsufficientUpdates {
+                got: 0,
+                need: 1
+            }))
+        ));
+
+        let replay = swarm_torch_core::replay::ReplayError::Expired {
+            ts: 1,
+            now: 100,
+            window: 10,
+        };
+        let err = SwarmError::from(replay);
+        assert!(matches!(
+            err,
+            SwarmError::Net(swarm_torch_net::Error::Replay(_))
+        ));
+        assert!(std::error::Error::source(&err).is_some());
+
+        let err: SwarmError = SwarmCluster::builder()
+            .max_rounds(0)
+            .try_build()
+            .unwrap_err()
+            .into();
+        assert_eq!(
+            err.to_string(),
+            "invalid config: max_rounds must be non-zero"
+        );
+    }
+
     // ── M-13: SwarmConfig validation tests ──
 
     #[test]
@@ -305,6 +643,224 @@ mod tests {
         );
     }
 
+    #[test]
+    fn try_build_rejects_out_of_range_quorum_ratio() {
+        for ratio in [0.0, 1.5, f32::NAN] {
+            let result = SwarmCluster::builder().quorum_ratio(ratio).try_build();
+            assert_eq!(result.unwrap_err(), SwarmConfigError::QuorumRatioOutOfRange);
+        }
+    }
+
+    /// Test runtime backed by tokio timers.
+    struct TestRuntime;
+
+    impl SwarmRuntime for TestRuntime {
+        fn now(&self) -> u64 {
+            static EPOCH: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+            EPOCH
+                .get_or_init(std::time::Instant::now)
+                .elapsed()
+                .as_millis() as u64
+        }
+
+        async fn sleep(&self, duration: Duration) {
+            tokio::time::sleep(duration).await;
+        }
+
+        fn spawn<F>(&self, future: F)
+        where
+            F: Future<Output = ()> + Send + 'static,
+        {
+            tokio::spawn(future);
+        }
+    }
+
+    fn auth(seed: u8) -> MessageAuth {
+        MessageAuth::new(swarm_torch_core::crypto::KeyPair::from_seed([seed; 32]).unwrap())
+    }
+
+    fn peer(seed: u8) -> PeerId {
+        auth(seed).key_pair().peer_id()
+    }
+
+    fn update_from(
+        sender: u8,
+        sequence: u64,
+        round_id: u64,
+        gradients: Vec<f32>,
+    ) -> GradientUpdate {
+        GradientUpdate {
+            sender: *peer(sender).as_bytes(),
+            sequence,
+            gradients,
+            round_id,
+            encoding: UpdateEncoding::Full,
+        }
+    }
+
+    fn signed_update(sender: u8, update: &GradientUpdate) -> MessageEnvelope {
+        MessageEnvelope::signed(
+            &auth(sender),
+            MessageType::GradientUpdate,
+            postcard::to_allocvec(update).unwrap(),
+            update.sequence,
+            unix_now_secs(),
+        )
+    }
+
+    fn envelope_from(sender: u8, sequence: u64, round_id: u64, gradients: Vec<f32>) -> Vec<u8> {
+        signed_update(sender, &update_from(sender, sequence, round_id, gradients))
+            .serialize()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn run_round_aggregates_local_and_peer_updates_at_quorum() {
+        let cluster = SwarmCluster::new(SwarmConfig::default(), peer(1));
+        let transport = swarm_torch_net::MockTransport::new();
+        // Noise the round must drop: wrong round, spoofed sender, bad shape, garbage,
+        // out-of-bounds gradient.
+        transport.push_incoming(peer(2), envelope_from(2, 1, 6, vec![0.0, 0.0]));
+        transport.push_incoming(peer(3), envelope_from(2, 2, 7, vec![0.0, 0.0]));
+        transport.push_incoming(peer(2), envelope_from(2, 3, 7, vec![0.0]));
+        transport.push_incoming(peer(2), vec![0xff; 4]);
+        transport.push_incoming(peer(3), envelope_from(3, 1, 7, vec![500.0, 0.0]));
+        // The two simulated peers.
+        transport.push_incoming(peer(2), envelope_from(2, 4, 7, vec![3.0, -1.0]));
+        transport.push_incoming(peer(3), envelope_from(3, 2, 7, vec![9.0, 2.0]));
+
+        let local = GradientUpdate {
+            sender: *peer(1).as_bytes(),
+            sequence: 1,
+            gradients: vec![1.0, 0.0],
+            round_id: 7,
+            encoding: UpdateEncoding::Full,
+        };
+        let aggregated = cluster
+            .run_round(
+                &transport,
+                &TestRuntime,
+                &auth(1),
+                &mut AuthenticatedEnvelopeVerifier::new(),
+                &aggregation::TrimmedMean::new(0.34),
+                local,
+                3,
+            )
+            .await
+            .unwrap();
+
+        // Trimming one value per side of three leaves the coordinate-wise median.
+        assert_eq!(aggregated, vec![3.0, 0.0]);
+    }
+
+    #[tokio::test]
+    async fn run_round_times_out_without_quorum() {
+        let config = SwarmConfig {
+            round_timeout_secs: 1,
+            ..SwarmConfig::default()
+        };
+        let cluster = SwarmCluster::new(config, peer(1));
+        // An empty transport never yields a message; the round must not hang.
+        let transport = swarm_torch_net::MockTransport::new();
+        let local = GradientUpdate {
+            sender: *peer(1).as_bytes(),
+            sequence: 1,
+            gradients: vec![1.0, 0.0],
+            round_id: 7,
+            encoding: UpdateEncoding::Full,
+        };
+        let err = cluster
+            .run_round(
+                &transport,
+                &TestRuntime,
+                &auth(1),
+                &mut AuthenticatedEnvelopeVerifier::new(),
+                &aggregation::TrimmedMean::new(0.34),
+                local,
+                3,
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            SwarmError::Core(Error::InsufficientUpdates { got: 1, need: 3 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn run_round_drops_unsigned_forged_and_replayed_updates() {
+        let config = SwarmConfig {
+            round_timeout_secs: 1,
+            ..SwarmConfig::default()
+        };
+        let cluster = SwarmCluster::new(config, peer(1));
+        let transport = swarm_torch_net::MockTransport::new();
+        let mut verifier = AuthenticatedEnvelopeVerifier::new();
+
+        // Unsigned envelope claiming peer 2.
+        let mut unsigned = signed_update(2, &update_from(2, 1, 7, vec![5.0, 5.0]));
+        unsigned.signature = None;
+        transport.push_incoming(peer(2), unsigned.serialize().unwrap());
+        // Peer 4 signs an update but stamps peer 2's key as the sender.
+        let mut forged = signed_update(4, &update_from(2, 2, 7, vec![5.0, 5.0]));
+        forged.sender = *auth(2).key_pair().public_key();
+        transport.push_incoming(peer(2), forged.serialize().unwrap());
+        // A genuine update from peer 3 the verifier already accepted in an earlier round.
+        let replayed = envelope_from(3, 1, 7, vec![5.0, 5.0]);
+        let envelope = MessageEnvelope::deserialize(&replayed).unwrap();
+        verifier
+            .verify_and_unwrap_with_time(envelope, unix_now_secs())
+            .unwrap();
+        transport.push_incoming(peer(3), replayed);
+
+        let local = update_from(1, 1, 7, vec![1.0, 0.0]);
+        let err = cluster
+            .run_round(
+                &transport,
+                &TestRuntime,
+                &auth(1),
+                &mut verifier,
+                &aggregation::TrimmedMean::new(0.0),
+                local,
+                2,
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            SwarmError::Core(Error::InsufficientUpdates { got: 1, need: 2 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn run_round_rejects_auth_for_another_peer() {
+        let cluster = SwarmCluster::new(SwarmConfig::default(), peer(1));
+        let transport = swarm_torch_net::MockTransport::new();
+        let local = GradientUpdate {
+            sender: *peer(1).as_bytes(),
+            sequence: 1,
+            gradients: vec![1.0],
+            round_id: 7,
+            encoding: UpdateEncoding::Full,
+        };
+        let err = cluster
+            .run_round(
+                &transport,
+                &TestRuntime,
+                &auth(2),
+                &mut AuthenticatedEnvelopeVerifier::new(),
+                &aggregation::TrimmedMean::new(0.0),
+                local,
+                1,
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, SwarmError::Core(Error::VerificationFailed)));
+    }
+
     #[test]
     fn try_build_accepts_valid_config() {
         let result = SwarmCluster::builder()
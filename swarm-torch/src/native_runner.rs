@@ -1,13 +1,19 @@
 //! Minimal native OpRunner (alpha.6, std-only).
 //!
-//! Implements three metadata-only ops:
+//! Implements five metadata-only ops:
 //! - `passthrough`: forwards inputs unchanged
 //! - `filter_rows`: filters rows (metadata-only; rows/bytes = None)
 //! - `union`: forwards input metadata for union-style stages; materialization computes outputs
+//! - `join`: ≥2 inputs into one declared output; keys from `params.on`, kind from `params.how`
+//! - `aggregate`: ≥1 input into one declared output; keys from `params.group_by`
+//!
+//! `join`/`aggregate` validate their param contract and record it as span attributes
+//! (`swarmtorch.join.*`, `swarmtorch.aggregate.*`).
 //!
 //! All ops emit a deterministic span:
 //! - `trace_id = run_id` (16 bytes → TraceId)
-//! - `span_id = sha256(node_id_bytes || ts_nanos_be)[0..8]`
+//! - `span_id = SpanId::from_parts(node_id, ts_nanos)`
+//!   (`sha256(node_id || ts_nanos_be)[0..8]`)
 //!
 //! **ADR-0018:** The runner boundary is separate from the scheduler.
 //! Policy enforcement must happen BEFORE calling `run()`.
@@ -15,11 +21,11 @@
 use std::collections::BTreeMap;
 use std::io;
 
-use sha2::{Digest, Sha256};
-
 use swarm_torch_core::execution::{AssetInstanceV1, OpRunner};
-use swarm_torch_core::observe::{AttrMap, RunEventEmitter, RunId, SpanId, SpanRecord, TraceId};
-use swarm_torch_core::run_graph::NodeV1;
+use swarm_torch_core::observe::{
+    AttrMap, AttrValue, RunEventEmitter, RunId, SpanId, SpanRecord, TraceId,
+};
+use swarm_torch_core::run_graph::{CanonValue, NodeV1};
 
 /// Execution context for the native runner.
 ///
@@ -30,23 +36,14 @@ pub struct ExecutionContext {
     pub clock_nanos: fn() -> u64,
 }
 
-/// Deterministic span ID: `sha256(node_id_bytes || ts_nanos_be)[0..8]`.
-fn deterministic_span_id(node_id_bytes: &[u8; 16], ts_nanos: u64) -> SpanId {
-    let mut hasher = Sha256::new();
-    hasher.update(node_id_bytes);
-    hasher.update(ts_nanos.to_be_bytes());
-    let hash = hasher.finalize();
-    let mut bytes = [0u8; 8];
-    bytes.copy_from_slice(&hash[..8]);
-    SpanId::from_bytes(bytes)
-}
-
 /// Minimal native OpRunner (metadata-only).
 ///
-/// Supports three op_types:
+/// Supports five op_types:
 /// - `"passthrough"` — returns inputs as-is
 /// - `"filter_rows"` — returns inputs with metadata indicating filter applied
 /// - `"union"` — returns input metadata unchanged (output derivation happens at materialization)
+/// - `"join"` — validates `on`/`how` params and input/output counts; forwards input metadata
+/// - `"aggregate"` — validates `group_by` param and input/output counts; forwards input metadata
 pub struct NativeOpRunner;
 
 impl NativeOpRunner {
@@ -62,20 +59,14 @@ impl NativeOpRunner {
     ) -> io::Result<Vec<AssetInstanceV1>> {
         let start_nanos = (ctx.clock_nanos)();
 
-        // Resolve node_id
-        let node_id = node
-            .node_id
-            .unwrap_or_else(|| swarm_torch_core::run_graph::node_id_from_key(&node.node_key));
-        let node_id_bytes = node_id.as_bytes();
-
-        let span_id = deterministic_span_id(node_id_bytes, start_nanos);
-        let trace_id = TraceId::from_bytes(*ctx.run_id.as_bytes());
-
         // Dispatch by op_type
+        let mut op_attrs = AttrMap::new();
         let outputs = match node.op_type.as_str() {
             "passthrough" => Self::op_passthrough(inputs),
             "filter_rows" => Self::op_filter_rows(inputs, node),
             "union" => Self::op_union(inputs, node),
+            "join" => Self::op_join(inputs, node, &mut op_attrs)?,
+            "aggregate" => Self::op_aggregate(inputs, node, &mut op_attrs)?,
             other => {
                 return Err(io::Error::new(
                     io::ErrorKind::InvalidInput,
@@ -86,35 +77,15 @@ impl NativeOpRunner {
 
         let end_nanos = (ctx.clock_nanos)();
 
-        // Emit span
-        let mut attrs: AttrMap = BTreeMap::new();
-        attrs.insert(
-            "swarmtorch.op_type".to_string(),
-            swarm_torch_core::observe::AttrValue::Str(node.op_type.clone()),
-        );
-        attrs.insert(
-            "swarmtorch.node_key".to_string(),
-            swarm_torch_core::observe::AttrValue::Str(node.node_key.clone()),
-        );
-        attrs.insert(
-            "swarmtorch.input_count".to_string(),
-            swarm_torch_core::observe::AttrValue::I64(inputs.len() as i64),
+        let mut span = op_span(
+            ctx,
+            node,
+            start_nanos,
+            end_nanos,
+            inputs.len(),
+            outputs.len(),
         );
-        attrs.insert(
-            "swarmtorch.output_count".to_string(),
-            swarm_torch_core::observe::AttrValue::I64(outputs.len() as i64),
-        );
-
-        let span = SpanRecord {
-            schema_version: 1,
-            trace_id,
-            span_id,
-            parent_span_id: None,
-            name: format!("op/{}", node.op_type),
-            start_unix_nanos: start_nanos,
-            end_unix_nanos: Some(end_nanos),
-            attrs,
-        };
+        span.attrs.extend(op_attrs);
         emitter.emit_span(&span)?;
 
         Ok(outputs)
@@ -142,6 +113,204 @@ impl NativeOpRunner {
         // materialize_node_outputs to derive canonical output fingerprints.
         inputs.to_vec()
     }
+
+    /// Join: metadata-only; validates the join contract and records keys on the span.
+    ///
+    /// Params: `on` (string or non-empty array of strings, required), `how`
+    /// (`inner`/`left`/`right`/`outer`, default `inner`).
+    fn op_join(
+        inputs: &[AssetInstanceV1],
+        node: &NodeV1,
+        attrs: &mut AttrMap,
+    ) -> io::Result<Vec<AssetInstanceV1>> {
+        require_arity(node, inputs, 2)?;
+        let on = string_list_param(node, "on")?.ok_or_else(|| {
+            contract_error(node, "requires param `on` (string or array of strings)")
+        })?;
+        if on.is_empty() {
+            return Err(contract_error(
+                node,
+                "param `on` must name at least one key",
+            ));
+        }
+        let how = match node.params.get("how") {
+            None => "inner",
+            Some(CanonValue::Str(how))
+                if matches!(how.as_str(), "inner" | "left" | "right" | "outer") =>
+            {
+                how.as_str()
+            }
+            Some(other) => {
+                return Err(contract_error(
+                    node,
+                    &format!("param `how` must be inner/left/right/outer, got {other:?}"),
+                ))
+            }
+        };
+        attrs.insert(
+            "swarmtorch.join.on".to_string(),
+            AttrValue::Str(on.join(",")),
+        );
+        attrs.insert(
+            "swarmtorch.join.how".to_string(),
+            AttrValue::Str(how.to_string()),
+        );
+        Ok(inputs.to_vec())
+    }
+
+    /// Aggregate: metadata-only; validates the group-by contract and records it on the span.
+    ///
+    /// Params: `group_by` (string or array of strings, required; an empty array is a
+    /// global aggregate).
+    fn op_aggregate(
+        inputs: &[AssetInstanceV1],
+        node: &NodeV1,
+        attrs: &mut AttrMap,
+    ) -> io::Result<Vec<AssetInstanceV1>> {
+        require_arity(node, inputs, 1)?;
+        let group_by = string_list_param(node, "group_by")?.ok_or_else(|| {
+            contract_error(
+                node,
+                "requires param `group_by` (string or array of strings)",
+            )
+        })?;
+        attrs.insert(
+            "swarmtorch.aggregate.group_by".to_string(),
+            AttrValue::Str(group_by.join(",")),
+        );
+        Ok(inputs.to_vec())
+    }
+}
+
+fn contract_error(node: &NodeV1, detail: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("{} node {}: {}", node.op_type, node.node_key, detail),
+    )
+}
+
+/// At least `min_inputs` inputs and exactly one declared output.
+fn require_arity(node: &NodeV1, inputs: &[AssetInstanceV1], min_inputs: usize) -> io::Result<()> {
+    if inputs.len() < min_inputs {
+        return Err(contract_error(
+            node,
+            &format!(
+                "requires at least {min_inputs} inputs, got {}",
+                inputs.len()
+            ),
+        ));
+    }
+    if node.outputs.len() != 1 {
+        return Err(contract_error(
+            node,
+            &format!(
+                "produces exactly one output, {} declared",
+                node.outputs.len()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// `params[key]` as a string list (a bare string is one key); `None` if absent.
+fn string_list_param(node: &NodeV1, key: &str) -> io::Result<Option<Vec<String>>> {
+    match node.params.get(key) {
+        None => Ok(None),
+        Some(CanonValue::Str(value)) => Ok(Some(vec![value.clone()])),
+        Some(CanonValue::Array(values)) => values
+            .iter()
+            .map(|value| match value {
+                CanonValue::Str(value) => Ok(value.clone()),
+                other => Err(contract_error(
+                    node,
+                    &format!("param `{key}` entries must be strings, got {other:?}"),
+                )),
+            })
+            .collect::<io::Result<Vec<_>>>()
+            .map(Some),
+        Some(other) => Err(contract_error(
+            node,
+            &format!("param `{key}` must be a string or array of strings, got {other:?}"),
+        )),
+    }
+}
+
+/// Deterministic `op/<op_type>` span shared by the native and sandboxed runners.
+pub(crate) fn op_span(
+    ctx: &ExecutionContext,
+    node: &NodeV1,
+    start_nanos: u64,
+    end_nanos: u64,
+    input_count: usize,
+    output_count: usize,
+) -> SpanRecord {
+    let node_id = node
+        .node_id
+        .unwrap_or_else(|| swarm_torch_core::run_graph::node_id_from_key(&node.node_key));
+    let span_id = SpanId::from_parts(&node_id, start_nanos);
+    let trace_id = TraceId::from_bytes(*ctx.run_id.as_bytes());
+
+    let mut attrs: AttrMap = BTreeMap::new();
+    attrs.insert(
+        "swarmtorch.op_type".to_string(),
+        AttrValue::Str(node.op_type.clone()),
+    );
+    attrs.insert(
+        "swarmtorch.node_key".to_string(),
+        AttrValue::Str(node.node_key.clone()),
+    );
+    attrs.insert(
+        "swarmtorch.input_count".to_string(),
+        AttrValue::I64(input_count as i64),
+    );
+    attrs.insert(
+        "swarmtorch.output_count".to_string(),
+        AttrValue::I64(output_count as i64),
+    );
+
+    SpanRecord {
+        schema_version: 1,
+        trace_id,
+        span_id,
+        parent_span_id: None,
+        name: format!("op/{}", node.op_type),
+        start_unix_nanos: start_nanos,
+        end_unix_nanos: Some(end_nanos),
+        attrs,
+    }
+}
+
+/// Context for trait-level `OpRunner::run` calls, which carry no `ExecutionContext`.
+pub(crate) fn fallback_context() -> ExecutionContext {
+    // Without ExecutionContext, we can't derive a deterministic trace_id or span_id;
+    // callers should prefer run_with_context().
+    //
+    // H-03: derive a per-invocation RunId from the wall-clock so that
+    // telemetry from different trait-level calls does not silently collide
+    // under a single all-zero sentinel.  The id is NOT cryptographic —
+    // it is a best-effort uniqueness measure for the logging layer.
+    let clock_nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64;
+    let hash = {
+        use sha2::{Digest, Sha256};
+        let mut h = Sha256::new();
+        h.update(b"swarmtorch.trait_fallback_run_id");
+        h.update(clock_nanos.to_le_bytes());
+        h.finalize()
+    };
+    let mut run_id_bytes = [0u8; 16];
+    run_id_bytes.copy_from_slice(&hash[..16]);
+    ExecutionContext {
+        run_id: RunId::from_bytes(run_id_bytes),
+        clock_nanos: || {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos() as u64
+        },
+    }
 }
 
 impl OpRunner for NativeOpRunner {
@@ -153,36 +322,7 @@ impl OpRunner for NativeOpRunner {
         inputs: &[AssetInstanceV1],
         emitter: &E,
     ) -> Result<Vec<AssetInstanceV1>, Self::Error> {
-        // Without ExecutionContext, we can't derive trace_id or deterministic span_id.
-        // This is the trait-level fallback; callers should prefer run_with_context().
-        //
-        // H-03: derive a per-invocation RunId from the wall-clock so that
-        // telemetry from different trait-level calls does not silently collide
-        // under a single all-zero sentinel.  The id is NOT cryptographic —
-        // it is a best-effort uniqueness measure for the logging layer.
-        let clock_nanos = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_nanos() as u64;
-        let hash = {
-            use sha2::{Digest, Sha256};
-            let mut h = Sha256::new();
-            h.update(b"swarmtorch.trait_fallback_run_id");
-            h.update(clock_nanos.to_le_bytes());
-            h.finalize()
-        };
-        let mut run_id_bytes = [0u8; 16];
-        run_id_bytes.copy_from_slice(&hash[..16]);
-        let ctx = ExecutionContext {
-            run_id: RunId::from_bytes(run_id_bytes),
-            clock_nanos: || {
-                std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_nanos() as u64
-            },
-        };
-        self.run_with_context(&ctx, node, inputs, emitter)
+        self.run_with_context(&fallback_context(), node, inputs, emitter)
     }
 }
 
@@ -190,7 +330,7 @@ impl OpRunner for NativeOpRunner {
 mod tests {
     use super::*;
     use swarm_torch_core::observe::{EventRecord, MetricRecord};
-    use swarm_torch_core::run_graph::{AssetRefV1, CanonParams, ExecutionTrust, NodeV1, OpKind};
+    use swarm_torch_core::run_graph::{AssetRefV1, CanonParams, ExecutionTrust, OpKind};
 
     /// Test emitter that captures spans.
     struct TestEmitter {
@@ -262,6 +402,9 @@ mod tests {
             asset_key: "dataset://ns/raw".to_string(),
             fingerprint_v0: "a".repeat(64),
             uri: Some("s3://bucket/raw".to_string()),
+            rows: None,
+            bytes: None,
+            schema: None,
         }]
     }
 
@@ -334,22 +477,130 @@ mod tests {
         assert_eq!(spans[0].name, "op/union");
     }
 
+    fn join_node(on: Option<CanonValue>) -> NodeV1 {
+        let mut node = test_node("join");
+        node.inputs.push(AssetRefV1 {
+            asset_key: "dataset://ns/dim".to_string(),
+            fingerprint: None,
+        });
+        node.outputs.push(AssetRefV1 {
+            asset_key: "dataset://ns/joined".to_string(),
+            fingerprint: None,
+        });
+        if let Some(on) = on {
+            node.params.insert("on".to_string(), on);
+        }
+        node
+    }
+
+    fn join_inputs() -> Vec<AssetInstanceV1> {
+        let mut inputs = test_inputs();
+        inputs.push(AssetInstanceV1 {
+            asset_key: "dataset://ns/dim".to_string(),
+            fingerprint_v0: "b".repeat(64),
+            uri: None,
+            rows: None,
+            bytes: None,
+            schema: None,
+        });
+        inputs
+    }
+
+    #[test]
+    fn join_two_inputs_records_keys_on_span() {
+        let ctx = test_ctx();
+        let emitter = TestEmitter::new();
+        let node = join_node(Some(CanonValue::Array(vec![
+            CanonValue::Str("id".to_string()),
+            CanonValue::Str("day".to_string()),
+        ])));
+        let inputs = join_inputs();
+
+        let outputs = NativeOpRunner
+            .run_with_context(&ctx, &node, &inputs, &emitter)
+            .unwrap();
+        assert_eq!(outputs, inputs);
+
+        let spans = emitter.spans.read().unwrap();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].name, "op/join");
+        assert_eq!(
+            spans[0].attrs.get("swarmtorch.join.on"),
+            Some(&AttrValue::Str("id,day".to_string()))
+        );
+        assert_eq!(
+            spans[0].attrs.get("swarmtorch.join.how"),
+            Some(&AttrValue::Str("inner".to_string()))
+        );
+    }
+
+    #[test]
+    fn join_rejects_single_input_and_missing_keys() {
+        let ctx = test_ctx();
+        let emitter = TestEmitter::new();
+        let node = join_node(Some(CanonValue::Str("id".to_string())));
+
+        let err = NativeOpRunner
+            .run_with_context(&ctx, &node, &test_inputs(), &emitter)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(
+            err.to_string().contains("at least 2 inputs, got 1"),
+            "{err}"
+        );
+
+        let err = NativeOpRunner
+            .run_with_context(&ctx, &join_node(None), &join_inputs(), &emitter)
+            .unwrap_err();
+        assert!(err.to_string().contains("requires param `on`"), "{err}");
+
+        let mut bad_how = join_node(Some(CanonValue::Str("id".to_string())));
+        bad_how
+            .params
+            .insert("how".to_string(), CanonValue::Str("cross".to_string()));
+        assert!(NativeOpRunner
+            .run_with_context(&ctx, &bad_how, &join_inputs(), &emitter)
+            .is_err());
+        assert!(emitter.spans.read().unwrap().is_empty());
+    }
+
     #[test]
-    fn deterministic_span_id_is_stable() {
-        // Same (node_id, ts) → same span_id
-        let node_a = [42u8; 16];
-        let node_b = [43u8; 16];
-        let id1 = deterministic_span_id(&node_a, 1_000_000_000);
-        let id2 = deterministic_span_id(&node_a, 1_000_000_000);
-        assert_eq!(id1, id2);
-
-        // Different ts → different span_id
-        let id3 = deterministic_span_id(&node_a, 2_000_000_000);
-        assert_ne!(id1, id3);
-
-        // Different node_id → different span_id
-        let id4 = deterministic_span_id(&node_b, 1_000_000_000);
-        assert_ne!(id1, id4);
+    fn aggregate_requires_group_by_and_single_output() {
+        let ctx = test_ctx();
+        let emitter = TestEmitter::new();
+        let mut node = test_node("aggregate");
+        node.outputs.push(AssetRefV1 {
+            asset_key: "dataset://ns/daily".to_string(),
+            fingerprint: None,
+        });
+
+        let err = NativeOpRunner
+            .run_with_context(&ctx, &node, &test_inputs(), &emitter)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("requires param `group_by`"),
+            "{err}"
+        );
+
+        node.params
+            .insert("group_by".to_string(), CanonValue::Str("day".to_string()));
+        NativeOpRunner
+            .run_with_context(&ctx, &node, &test_inputs(), &emitter)
+            .unwrap();
+        {
+            let spans = emitter.spans.read().unwrap();
+            assert_eq!(spans[0].name, "op/aggregate");
+            assert_eq!(
+                spans[0].attrs.get("swarmtorch.aggregate.group_by"),
+                Some(&AttrValue::Str("day".to_string()))
+            );
+        }
+
+        node.outputs.clear();
+        let err = NativeOpRunner
+            .run_with_context(&ctx, &node, &test_inputs(), &emitter)
+            .unwrap_err();
+        assert!(err.to_string().contains("exactly one output"), "{err}");
     }
 
     #[test]
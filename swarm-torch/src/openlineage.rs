@@ -0,0 +1,319 @@
+//! OpenLineage export of run lineage.
+//!
+//! [`lineage_to_openlineage`] projects a run's `graph.json`, dataset registry, and
+//! lineage edges onto OpenLineage `RunEvent`s (one `COMPLETE` event per graph node),
+//! so catalogs that ingest OpenLineage can consume offline bundles without a custom
+//! connector:
+//! - node → job (`namespace` = [`OPENLINEAGE_JOB_NAMESPACE`], `name` = `node_key`)
+//! - asset key `scheme://namespace/name` → dataset (`namespace` = `scheme://namespace`)
+//! - registry entry → `swarmtorch_dataset` facet (fingerprint, trust)
+//! - lineage edges of the node → `swarmtorch_lineage` run facet
+//!
+//! Output is deterministic: events follow `graph.nodes` order and the event time is
+//! supplied by the caller.
+
+use serde_json::{json, Value};
+use swarm_torch_core::dataops::{DatasetLineageV1, DatasetRegistryV1, TrustClass};
+use swarm_torch_core::observe::RunId;
+use swarm_torch_core::run_graph::{node_id_from_key, AssetRefV1, GraphV1};
+
+/// `producer` URI stamped on events and custom facets.
+pub const OPENLINEAGE_PRODUCER: &str = "https://github.com/swarmic/SwarmTorch";
+/// OpenLineage spec version the events conform to.
+pub const OPENLINEAGE_SCHEMA_URL: &str =
+    "https://openlineage.io/spec/2-0-2/OpenLineage.json#/definitions/RunEvent";
+/// Job namespace for graph nodes.
+pub const OPENLINEAGE_JOB_NAMESPACE: &str = "swarmtorch";
+
+/// Map a run's lineage to a JSON array of OpenLineage `RunEvent`s, one per node.
+///
+/// Every event shares `run.runId` (`run_id` formatted as a UUID) and `eventTime`
+/// (`event_time_unix_nanos` as RFC 3339 UTC). Inputs/outputs follow each node's
+/// declared `inputs`/`outputs`; datasets without a registry entry are emitted
+/// without the fingerprint facet.
+pub fn lineage_to_openlineage(
+    graph: &GraphV1,
+    registry: &DatasetRegistryV1,
+    lineage: &DatasetLineageV1,
+    run_id: RunId,
+    event_time_unix_nanos: u64,
+) -> Value {
+    let index = registry.index();
+    let run_uuid = uuid_string(run_id.as_bytes());
+    let event_time = rfc3339_utc(event_time_unix_nanos);
+
+    let dataset = |asset: &AssetRefV1| {
+        let (namespace, name) = split_asset_key(&asset.asset_key);
+        let mut value = json!({ "namespace": namespace, "name": name });
+        if let Some(entry) = index.get(asset.asset_key.as_str()) {
+            value["facets"] = json!({
+                "swarmtorch_dataset": custom_facet(json!({
+                    "assetKey": entry.asset_key,
+                    "fingerprintV0": entry.fingerprint_v0,
+                    "trust": match entry.trust {
+                        TrustClass::Trusted => "trusted",
+                        TrustClass::Untrusted => "untrusted",
+                    },
+                })),
+            });
+        }
+        value
+    };
+
+    let events = graph
+        .nodes
+        .iter()
+        .map(|node| {
+            let node_id = node
+                .node_id
+                .unwrap_or_else(|| node_id_from_key(&node.node_key));
+            let edges: Vec<Value> = lineage
+                .edges
+                .iter()
+                .filter(|edge| edge.node_id == node_id)
+                .map(|edge| {
+                    json!({
+                        "inputFingerprintV0": edge.input_fingerprint_v0,
+                        "outputFingerprintV0": edge.output_fingerprint_v0,
+                    })
+                })
+                .collect();
+            json!({
+                "eventType": "COMPLETE",
+                "eventTime": event_time,
+                "producer": OPENLINEAGE_PRODUCER,
+                "schemaURL": OPENLINEAGE_SCHEMA_URL,
+                "run": {
+                    "runId": run_uuid,
+                    "facets": {
+                        "swarmtorch_lineage": custom_facet(json!({ "edges": edges })),
+                    },
+                },
+                "job": {
+                    "namespace": OPENLINEAGE_JOB_NAMESPACE,
+                    "name": node.node_key,
+                    "facets": {
+                        "swarmtorch_node": custom_facet(json!({
+                            "nodeId": node_id.to_string(),
+                            "opKind": node.op_kind,
+                            "opType": node.op_type,
+                            "executionTrust": node.execution_trust,
+                            "nodeDefHash": node.node_def_hash,
+                        })),
+                    },
+                },
+                "inputs": node.inputs.iter().map(dataset).collect::<Vec<_>>(),
+                "outputs": node.outputs.iter().map(dataset).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    Value::Array(events)
+}
+
+/// Add the `_producer`/`_schemaURL` fields OpenLineage requires on custom facets.
+fn custom_facet(mut fields: Value) -> Value {
+    fields["_producer"] = json!(OPENLINEAGE_PRODUCER);
+    fields["_schemaURL"] = json!(OPENLINEAGE_SCHEMA_URL);
+    fields
+}
+
+/// `scheme://namespace/name` → (`scheme://namespace`, `name`); keys without that
+/// shape land in the job namespace under their full key.
+fn split_asset_key(asset_key: &str) -> (String, String) {
+    asset_key
+        .split_once("://")
+        .and_then(|(scheme, rest)| {
+            let (namespace, name) = rest.split_once('/')?;
+            Some((format!("{scheme}://{namespace}"), name.to_string()))
+        })
+        .unwrap_or_else(|| (OPENLINEAGE_JOB_NAMESPACE.to_string(), asset_key.to_string()))
+}
+
+fn uuid_string(bytes: &[u8; 16]) -> String {
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// RFC 3339 UTC timestamp with nanosecond precision.
+fn rfc3339_utc(unix_nanos: u64) -> String {
+    let secs = unix_nanos / 1_000_000_000;
+    let nanos = unix_nanos % 1_000_000_000;
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // Civil-from-days (proleptic Gregorian), days since 1970-01-01.
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{nanos:09}Z",
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swarm_torch_core::dataops::{DatasetEntryV1, HashAlgo, LineageEdgeV1, DATAOPS_SCHEMA_V1};
+    use swarm_torch_core::run_graph::{CanonParams, ExecutionTrust, NodeV1, OpKind};
+
+    fn asset(key: &str) -> AssetRefV1 {
+        AssetRefV1 {
+            asset_key: key.to_string(),
+            fingerprint: None,
+        }
+    }
+
+    fn node(key: &str, op_kind: OpKind, inputs: &[&str], outputs: &[&str]) -> NodeV1 {
+        NodeV1 {
+            node_key: key.to_string(),
+            node_id: None,
+            op_kind,
+            op_type: "op".to_string(),
+            inputs: inputs.iter().map(|k| asset(k)).collect(),
+            outputs: outputs.iter().map(|k| asset(k)).collect(),
+            params: CanonParams::new(),
+            code_ref: None,
+            unsafe_surface: false,
+            execution_trust: ExecutionTrust::Core,
+            node_def_hash: None,
+            execution_hint: None,
+            cache_policy: None,
+            materialization_policy: None,
+            resources: None,
+            op_hash: None,
+        }
+    }
+
+    fn entry(key: &str, fingerprint: char) -> DatasetEntryV1 {
+        DatasetEntryV1 {
+            asset_key: key.to_string(),
+            fingerprint_v0: fingerprint.to_string().repeat(64),
+            source_fingerprint_v0: "0".repeat(64),
+            schema_hash_v0: "0".repeat(64),
+            recipe_hash_v0: "0".repeat(64),
+            hash_algo: HashAlgo::Sha256,
+            trust: TrustClass::Trusted,
+            source: None,
+            schema: None,
+            license_flags: Vec::new(),
+            pii_tags: Vec::new(),
+        }
+    }
+
+    fn names(datasets: &Value) -> Vec<String> {
+        datasets
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|d| {
+                format!(
+                    "{}/{}",
+                    d["namespace"].as_str().unwrap(),
+                    d["name"].as_str().unwrap()
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn one_job_per_node_with_graph_asset_flow() {
+        let graph = GraphV1 {
+            nodes: vec![
+                node("ingest/raw", OpKind::Data, &[], &["dataset://ns/raw"]),
+                node(
+                    "prep/clean",
+                    OpKind::Data,
+                    &["dataset://ns/raw"],
+                    &["dataset://ns/clean"],
+                ),
+                node(
+                    "train/model",
+                    OpKind::Train,
+                    &["dataset://ns/clean"],
+                    &["model://ns/classifier"],
+                ),
+            ],
+            ..GraphV1::default()
+        };
+        let registry = DatasetRegistryV1 {
+            schema_version: DATAOPS_SCHEMA_V1,
+            datasets: vec![
+                entry("dataset://ns/raw", 'a'),
+                entry("dataset://ns/clean", 'b'),
+            ],
+        };
+        let lineage = DatasetLineageV1 {
+            schema_version: DATAOPS_SCHEMA_V1,
+            edges: vec![LineageEdgeV1 {
+                input_fingerprint_v0: "a".repeat(64),
+                output_fingerprint_v0: "b".repeat(64),
+                node_id: node_id_from_key("prep/clean"),
+                op_kind: OpKind::Data,
+            }],
+        };
+
+        let run_id = RunId::from_bytes([0x11; 16]);
+        let events = lineage_to_openlineage(&graph, &registry, &lineage, run_id, 1_500_000_000);
+        let events = events.as_array().unwrap();
+        assert_eq!(events.len(), 3);
+
+        let jobs: Vec<&str> = events
+            .iter()
+            .map(|e| e["job"]["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(jobs, ["ingest/raw", "prep/clean", "train/model"]);
+
+        assert!(names(&events[0]["inputs"]).is_empty());
+        assert_eq!(names(&events[0]["outputs"]), ["dataset://ns/raw"]);
+        assert_eq!(names(&events[1]["inputs"]), ["dataset://ns/raw"]);
+        assert_eq!(names(&events[1]["outputs"]), ["dataset://ns/clean"]);
+        assert_eq!(names(&events[2]["inputs"]), ["dataset://ns/clean"]);
+        assert_eq!(names(&events[2]["outputs"]), ["model://ns/classifier"]);
+
+        let clean = &events[1]["outputs"][0];
+        assert_eq!(
+            clean["facets"]["swarmtorch_dataset"]["fingerprintV0"],
+            "b".repeat(64)
+        );
+        assert!(events[2]["outputs"][0].get("facets").is_none());
+
+        let edges = &events[1]["run"]["facets"]["swarmtorch_lineage"]["edges"];
+        assert_eq!(edges.as_array().unwrap().len(), 1);
+        assert!(events[0]["run"]["facets"]["swarmtorch_lineage"]["edges"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(
+            events[0]["run"]["runId"],
+            "11111111-1111-1111-1111-111111111111"
+        );
+        assert_eq!(events[0]["eventTime"], "1970-01-01T00:00:01.500000000Z");
+        assert_eq!(events[0]["eventType"], "COMPLETE");
+    }
+
+    #[test]
+    fn rfc3339_formats_leap_day() {
+        // 2024-02-29T12:34:56Z
+        assert_eq!(
+            rfc3339_utc(1_709_210_096 * 1_000_000_000 + 7),
+            "2024-02-29T12:34:56.000000007Z"
+        );
+    }
+}
@@ -0,0 +1,637 @@
+//! OTLP/HTTP exporter for run events (`otlp` feature).
+//!
+//! [`OtlpEmitter`] implements [`RunEventEmitter`] and ships records to an OpenTelemetry
+//! collector using the OTLP/HTTP JSON encoding (`application/json`):
+//! - spans → `POST {endpoint}/v1/traces`
+//! - events → `POST {endpoint}/v1/logs` (one log record per event, linked to its span)
+//! - metrics → `POST {endpoint}/v1/metrics` (gauge data points)
+//!
+//! `emit_*` only enqueues; a background thread batches and exports. Only plain `http://`
+//! endpoints are supported (run a local collector/sidecar for TLS).
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use swarm_torch_core::observe::{
+    AttrMap, AttrValue, EventRecord, MetricRecord, RunEventEmitter, SpanRecord,
+};
+
+const SCOPE_NAME: &str = "swarm-torch";
+
+/// Exporter configuration.
+#[derive(Debug, Clone)]
+pub struct OtlpConfig {
+    /// Collector base URL, e.g. `http://127.0.0.1:4318`.
+    pub endpoint: String,
+    /// `service.name` resource attribute.
+    pub service_name: String,
+    /// Export as soon as this many records are buffered.
+    pub max_batch: usize,
+    /// Export buffered records at least this often.
+    pub flush_interval: Duration,
+    /// Connect/read/write timeout for one export request.
+    pub timeout: Duration,
+}
+
+impl OtlpConfig {
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            service_name: SCOPE_NAME.to_string(),
+            max_batch: 512,
+            flush_interval: Duration::from_secs(1),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Errors from [`OtlpEmitter`].
+#[derive(Debug)]
+pub enum OtlpError {
+    /// Endpoint is not an `http://host[:port][/path]` URL.
+    InvalidEndpoint(String),
+    /// Transport failure talking to the collector.
+    Io(io::Error),
+    /// Collector answered with a non-2xx status.
+    HttpStatus { path: String, status: u16 },
+    /// The emitter was shut down.
+    ShutDown,
+}
+
+impl fmt::Display for OtlpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidEndpoint(endpoint) => write!(f, "invalid OTLP endpoint: {endpoint}"),
+            Self::Io(e) => write!(f, "OTLP export failed: {e}"),
+            Self::HttpStatus { path, status } => {
+                write!(f, "OTLP export to {path} returned HTTP {status}")
+            }
+            Self::ShutDown => write!(f, "OTLP emitter is shut down"),
+        }
+    }
+}
+
+impl std::error::Error for OtlpError {}
+
+impl From<io::Error> for OtlpError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+enum Message {
+    Span(SpanRecord),
+    Event(EventRecord),
+    Metric(MetricRecord),
+    Flush(Sender<Result<(), OtlpError>>),
+}
+
+#[derive(Default)]
+struct Batch {
+    spans: Vec<SpanRecord>,
+    events: Vec<EventRecord>,
+    metrics: Vec<MetricRecord>,
+}
+
+impl Batch {
+    fn len(&self) -> usize {
+        self.spans.len() + self.events.len() + self.metrics.len()
+    }
+}
+
+/// Non-blocking OTLP/HTTP emitter with a background batching thread.
+///
+/// Failed background exports drop the batch and bump [`OtlpEmitter::export_failures`];
+/// [`OtlpEmitter::flush`] reports the error to the caller instead.
+pub struct OtlpEmitter {
+    sender: Mutex<Option<Sender<Message>>>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+    export_failures: Arc<AtomicU64>,
+}
+
+impl OtlpEmitter {
+    /// Validate the endpoint and start the background exporter.
+    pub fn new(config: OtlpConfig) -> Result<Self, OtlpError> {
+        let exporter = HttpExporter::new(&config)?;
+        let (sender, receiver) = mpsc::channel();
+        let export_failures = Arc::new(AtomicU64::new(0));
+        let failures = export_failures.clone();
+        let worker = std::thread::Builder::new()
+            .name("swarm-torch-otlp".to_string())
+            .spawn(move || run_worker(receiver, exporter, config, failures))?;
+        Ok(Self {
+            sender: Mutex::new(Some(sender)),
+            worker: Mutex::new(Some(worker)),
+            export_failures,
+        })
+    }
+
+    /// Export everything enqueued so far and wait for the result.
+    pub fn flush(&self) -> Result<(), OtlpError> {
+        let (reply, result) = mpsc::channel();
+        self.send(Message::Flush(reply))?;
+        result.recv().map_err(|_| OtlpError::ShutDown)?
+    }
+
+    /// Flush, then stop the background thread. Later `emit_*` calls fail with `ShutDown`.
+    pub fn shutdown(&self) -> Result<(), OtlpError> {
+        let flushed = self.flush();
+        drop(lock(&self.sender).take());
+        if let Some(worker) = lock(&self.worker).take() {
+            let _ = worker.join();
+        }
+        flushed
+    }
+
+    /// Number of background exports that failed (their records were dropped).
+    pub fn export_failures(&self) -> u64 {
+        self.export_failures.load(Ordering::Relaxed)
+    }
+
+    fn send(&self, message: Message) -> Result<(), OtlpError> {
+        lock(&self.sender)
+            .as_ref()
+            .ok_or(OtlpError::ShutDown)?
+            .send(message)
+            .map_err(|_| OtlpError::ShutDown)
+    }
+}
+
+impl Drop for OtlpEmitter {
+    fn drop(&mut self) {
+        let _ = self.shutdown();
+    }
+}
+
+impl RunEventEmitter for OtlpEmitter {
+    type Error = OtlpError;
+
+    fn emit_span(&self, span: &SpanRecord) -> Result<(), OtlpError> {
+        self.send(Message::Span(span.clone()))
+    }
+
+    fn emit_event(&self, event: &EventRecord) -> Result<(), OtlpError> {
+        self.send(Message::Event(event.clone()))
+    }
+
+    fn emit_metric(&self, metric: &MetricRecord) -> Result<(), OtlpError> {
+        self.send(Message::Metric(metric.clone()))
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn run_worker(
+    receiver: mpsc::Receiver<Message>,
+    exporter: HttpExporter,
+    config: OtlpConfig,
+    failures: Arc<AtomicU64>,
+) {
+    let mut batch = Batch::default();
+    let mut deadline = Instant::now() + config.flush_interval;
+    let export_in_background = |batch: &mut Batch| {
+        if exporter.export(batch).is_err() {
+            failures.fetch_add(1, Ordering::Relaxed);
+        }
+    };
+    loop {
+        let wait = deadline.saturating_duration_since(Instant::now());
+        match receiver.recv_timeout(wait) {
+            Ok(Message::Span(span)) => batch.spans.push(span),
+            Ok(Message::Event(event)) => batch.events.push(event),
+            Ok(Message::Metric(metric)) => batch.metrics.push(metric),
+            Ok(Message::Flush(reply)) => {
+                let _ = reply.send(exporter.export(&mut batch));
+                deadline = Instant::now() + config.flush_interval;
+                continue;
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => {
+                export_in_background(&mut batch);
+                return;
+            }
+        }
+        if batch.len() >= config.max_batch.max(1) || Instant::now() >= deadline {
+            if batch.len() > 0 {
+                export_in_background(&mut batch);
+            }
+            deadline = Instant::now() + config.flush_interval;
+        }
+    }
+}
+
+/// Minimal HTTP/1.1 client for `http://` collector endpoints.
+struct HttpExporter {
+    authority: String,
+    path_prefix: String,
+    resource: Value,
+    timeout: Duration,
+}
+
+impl HttpExporter {
+    fn new(config: &OtlpConfig) -> Result<Self, OtlpError> {
+        let invalid = || OtlpError::InvalidEndpoint(config.endpoint.clone());
+        let rest = config
+            .endpoint
+            .strip_prefix("http://")
+            .ok_or_else(invalid)?;
+        let (authority, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], rest[i..].trim_end_matches('/')),
+            None => (rest, ""),
+        };
+        if authority.is_empty() {
+            return Err(invalid());
+        }
+        let authority = if authority.contains(':') {
+            authority.to_string()
+        } else {
+            format!("{authority}:4318")
+        };
+        Ok(Self {
+            authority,
+            path_prefix: path.to_string(),
+            resource: json!({
+                "attributes": [string_attr("service.name", &config.service_name)],
+            }),
+            timeout: config.timeout,
+        })
+    }
+
+    /// Export and clear `batch` (records are dropped on failure).
+    fn export(&self, batch: &mut Batch) -> Result<(), OtlpError> {
+        let batch = std::mem::take(batch);
+        if !batch.spans.is_empty() {
+            self.post("/v1/traces", &traces_request(&self.resource, &batch.spans))?;
+        }
+        if !batch.events.is_empty() {
+            self.post("/v1/logs", &logs_request(&self.resource, &batch.events))?;
+        }
+        if !batch.metrics.is_empty() {
+            self.post(
+                "/v1/metrics",
+                &metrics_request(&self.resource, &batch.metrics),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn post(&self, signal_path: &str, body: &Value) -> Result<(), OtlpError> {
+        let path = format!("{}{signal_path}", self.path_prefix);
+        let body = serde_json::to_vec(body).map_err(io::Error::other)?;
+
+        let addr = self
+            .authority
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| OtlpError::InvalidEndpoint(self.authority.clone()))?;
+        let mut stream = TcpStream::connect_timeout(&addr, self.timeout)?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+
+        let head = format!(
+            "POST {path} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.authority,
+            body.len()
+        );
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(&body)?;
+        stream.flush()?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response)?;
+        let status = parse_status(&response)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed HTTP response"))?;
+        if (200..300).contains(&status) {
+            Ok(())
+        } else {
+            Err(OtlpError::HttpStatus { path, status })
+        }
+    }
+}
+
+/// `HTTP/1.1 200 OK` → `200`.
+fn parse_status(response: &[u8]) -> Option<u16> {
+    let line_end = response.windows(2).position(|w| w == b"\r\n")?;
+    let line = std::str::from_utf8(&response[..line_end]).ok()?;
+    let mut parts = line.split(' ');
+    parts.next().filter(|v| v.starts_with("HTTP/"))?;
+    parts.next()?.parse().ok()
+}
+
+fn scope() -> Value {
+    json!({ "name": SCOPE_NAME, "version": env!("CARGO_PKG_VERSION") })
+}
+
+fn traces_request(resource: &Value, spans: &[SpanRecord]) -> Value {
+    json!({
+        "resourceSpans": [{
+            "resource": resource,
+            "scopeSpans": [{
+                "scope": scope(),
+                "spans": spans.iter().map(span_to_otlp).collect::<Vec<_>>(),
+            }],
+        }],
+    })
+}
+
+fn logs_request(resource: &Value, events: &[EventRecord]) -> Value {
+    json!({
+        "resourceLogs": [{
+            "resource": resource,
+            "scopeLogs": [{
+                "scope": scope(),
+                "logRecords": events.iter().map(event_to_otlp).collect::<Vec<_>>(),
+            }],
+        }],
+    })
+}
+
+fn metrics_request(resource: &Value, metrics: &[MetricRecord]) -> Value {
+    json!({
+        "resourceMetrics": [{
+            "resource": resource,
+            "scopeMetrics": [{
+                "scope": scope(),
+                "metrics": metrics.iter().map(metric_to_otlp).collect::<Vec<_>>(),
+            }],
+        }],
+    })
+}
+
+/// OTLP JSON `Span`. Ids are lowercase hex of the raw bytes; an open span
+/// (`end_unix_nanos = None`) is exported with a zero duration.
+pub(crate) fn span_to_otlp(span: &SpanRecord) -> Value {
+    let mut out = json!({
+        "traceId": span.trace_id.to_string(),
+        "spanId": span.span_id.to_string(),
+        "name": span.name,
+        "kind": 1,
+        "startTimeUnixNano": span.start_unix_nanos.to_string(),
+        "endTimeUnixNano": span.end_unix_nanos.unwrap_or(span.start_unix_nanos).to_string(),
+        "attributes": attributes(&span.attrs),
+    });
+    if let Some(parent) = span.parent_span_id {
+        out["parentSpanId"] = Value::String(parent.to_string());
+    }
+    out
+}
+
+/// OTLP JSON `LogRecord`; the event name is the body and `event.name`.
+pub(crate) fn event_to_otlp(event: &EventRecord) -> Value {
+    let mut attrs = attributes(&event.attrs);
+    attrs.push(string_attr("event.name", &event.name));
+    let mut out = json!({
+        "timeUnixNano": event.ts_unix_nanos.to_string(),
+        "traceId": event.trace_id.to_string(),
+        "body": { "stringValue": event.name },
+        "attributes": attrs,
+    });
+    if let Some(span_id) = event.span_id {
+        out["spanId"] = Value::String(span_id.to_string());
+    }
+    out
+}
+
+/// OTLP JSON gauge `Metric` with one data point.
+pub(crate) fn metric_to_otlp(metric: &MetricRecord) -> Value {
+    json!({
+        "name": metric.name,
+        "unit": metric.unit.as_deref().unwrap_or(""),
+        "gauge": {
+            "dataPoints": [{
+                "timeUnixNano": metric.ts_unix_nanos.to_string(),
+                "asDouble": metric.value,
+                "attributes": attributes(&metric.attrs),
+            }],
+        },
+    })
+}
+
+fn attributes(attrs: &AttrMap) -> Vec<Value> {
+    attrs
+        .iter()
+        .map(|(key, value)| json!({ "key": key, "value": any_value(value) }))
+        .collect()
+}
+
+fn string_attr(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+/// OTLP `AnyValue`; 64-bit integers are JSON strings per the OTLP JSON mapping.
+fn any_value(value: &AttrValue) -> Value {
+    match value {
+        AttrValue::Str(v) => json!({ "stringValue": v }),
+        AttrValue::Bool(v) => json!({ "boolValue": v }),
+        AttrValue::I64(v) => json!({ "intValue": v.to_string() }),
+        AttrValue::U64(v) => match i64::try_from(*v) {
+            Ok(v) => json!({ "intValue": v.to_string() }),
+            // OTLP ints are signed; keep the exact value as a string.
+            Err(_) => json!({ "stringValue": v.to_string() }),
+        },
+        AttrValue::F64(v) => json!({ "doubleValue": v }),
+        AttrValue::Bytes(v) => json!({ "bytesValue": base64_encode(v) }),
+    }
+}
+
+/// Standard padded base64, as required for OTLP JSON `bytesValue`.
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufRead;
+    use std::net::TcpListener;
+    use swarm_torch_core::observe::{SpanId, TraceId};
+
+    fn span() -> SpanRecord {
+        let mut attrs = AttrMap::new();
+        attrs.insert("node_key".to_string(), AttrValue::Str("train".to_string()));
+        attrs.insert("rows".to_string(), AttrValue::U64(42));
+        attrs.insert("cached".to_string(), AttrValue::Bool(false));
+        SpanRecord {
+            schema_version: 1,
+            trace_id: TraceId::from_bytes([0xab; 16]),
+            span_id: SpanId::from_bytes([0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]),
+            parent_span_id: Some(SpanId::from_bytes([0xff; 8])),
+            name: "node/train".to_string(),
+            start_unix_nanos: 1_000,
+            end_unix_nanos: Some(3_000),
+            attrs,
+        }
+    }
+
+    #[test]
+    fn span_maps_ids_times_and_attributes() {
+        let otlp = span_to_otlp(&span());
+        assert_eq!(otlp["traceId"], "abababababababababababababababab");
+        assert_eq!(otlp["spanId"], "0102030405060708");
+        assert_eq!(otlp["parentSpanId"], "ffffffffffffffff");
+        assert_eq!(otlp["startTimeUnixNano"], "1000");
+        assert_eq!(otlp["endTimeUnixNano"], "3000");
+        assert_eq!(
+            otlp["attributes"],
+            json!([
+                { "key": "cached", "value": { "boolValue": false } },
+                { "key": "node_key", "value": { "stringValue": "train" } },
+                { "key": "rows", "value": { "intValue": "42" } },
+            ])
+        );
+
+        let root = SpanRecord {
+            parent_span_id: None,
+            end_unix_nanos: None,
+            ..span()
+        };
+        let otlp = span_to_otlp(&root);
+        assert!(otlp.get("parentSpanId").is_none());
+        assert_eq!(otlp["endTimeUnixNano"], "1000");
+        assert_eq!(
+            any_value(&AttrValue::U64(u64::MAX)),
+            json!({ "stringValue": u64::MAX.to_string() })
+        );
+    }
+
+    /// Accept `n` requests, answer 200, and return `(path, body)` for each.
+    fn mock_collector(n: usize) -> (String, std::thread::JoinHandle<Vec<(String, Value)>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for stream in listener.incoming().take(n) {
+                let mut stream = stream.unwrap();
+                let mut reader = io::BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let path = request_line.split(' ').nth(1).unwrap().to_string();
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header == "\r\n" {
+                        break;
+                    }
+                    if let Some(v) = header.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = v.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0u8; content_length];
+                reader.read_exact(&mut body).unwrap();
+                stream
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n{}")
+                    .unwrap();
+                requests.push((path, serde_json::from_slice(&body).unwrap()));
+            }
+            requests
+        });
+        (endpoint, handle)
+    }
+
+    #[test]
+    fn emitter_batches_and_posts_each_signal() {
+        let (endpoint, collector) = mock_collector(3);
+        let mut config = OtlpConfig::new(endpoint);
+        config.flush_interval = Duration::from_secs(60);
+        let emitter = OtlpEmitter::new(config).unwrap();
+
+        let span = span();
+        emitter.emit_span(&span).unwrap();
+        emitter
+            .emit_event(&EventRecord {
+                schema_version: 1,
+                ts_unix_nanos: 2_000,
+                trace_id: span.trace_id,
+                span_id: Some(span.span_id),
+                name: "checkpoint".to_string(),
+                attrs: AttrMap::new(),
+            })
+            .unwrap();
+        emitter
+            .emit_metric(&MetricRecord {
+                schema_version: 1,
+                ts_unix_nanos: 2_500,
+                trace_id: span.trace_id,
+                span_id: Some(span.span_id),
+                name: "loss".to_string(),
+                value: 0.25,
+                unit: None,
+                attrs: AttrMap::new(),
+            })
+            .unwrap();
+        emitter.shutdown().unwrap();
+        assert!(matches!(emitter.emit_span(&span), Err(OtlpError::ShutDown)));
+
+        let requests = collector.join().unwrap();
+        let paths: Vec<&str> = requests.iter().map(|(p, _)| p.as_str()).collect();
+        assert_eq!(paths, ["/v1/traces", "/v1/logs", "/v1/metrics"]);
+
+        let traces = &requests[0].1["resourceSpans"][0];
+        assert_eq!(
+            traces["resource"]["attributes"][0]["value"]["stringValue"],
+            SCOPE_NAME
+        );
+        assert_eq!(
+            traces["scopeSpans"][0]["spans"][0]["spanId"],
+            "0102030405060708"
+        );
+        let log = &requests[1].1["resourceLogs"][0]["scopeLogs"][0]["logRecords"][0];
+        assert_eq!(log["spanId"], "0102030405060708");
+        assert_eq!(log["body"]["stringValue"], "checkpoint");
+        let point = &requests[2].1["resourceMetrics"][0]["scopeMetrics"][0]["metrics"][0]["gauge"]
+            ["dataPoints"][0];
+        assert_eq!(point["asDouble"], 0.25);
+        assert_eq!(emitter.export_failures(), 0);
+    }
+
+    #[test]
+    fn bytes_attrs_map_to_base64_bytes_value() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(
+            any_value(&AttrValue::Bytes(b"foob".to_vec())),
+            json!({ "bytesValue": "Zm9vYg==" })
+        );
+    }
+
+    #[test]
+    fn rejects_non_http_endpoints() {
+        for endpoint in ["https://collector:4318", "collector:4318", "http://"] {
+            assert!(matches!(
+                OtlpEmitter::new(OtlpConfig::new(endpoint)),
+                Err(OtlpError::InvalidEndpoint(_))
+            ));
+        }
+    }
+}
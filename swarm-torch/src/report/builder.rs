@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::artifacts::RunArtifactBundle;
+
+use super::load::load_report;
+use super::render::{render_head, render_section, REPORT_HTML_TAIL};
+
+/// Top-level sections of the HTML report, in render order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ReportSection {
+    /// Unsafe-surface summary banner.
+    Summary,
+    /// Run graph SVG.
+    Graph,
+    /// Events/metrics/spans/materializations timeline.
+    Timeline,
+    /// Dataset registry table.
+    Registry,
+    /// Lineage edge table.
+    Lineage,
+}
+
+/// Files that determine the loaded registry/lineage (snapshot pair + NDJSON replay).
+const DATAOPS_STATE_INPUTS: [&str; 5] = [
+    "datasets/registry.json",
+    "datasets/lineage.json",
+    "datasets/snapshot_pair_commit.json",
+    "datasets/registry_updates.ndjson",
+    "datasets/lineage_edges.ndjson",
+];
+
+impl ReportSection {
+    /// All sections in render order.
+    pub const ALL: &'static [ReportSection] = &[
+        ReportSection::Summary,
+        ReportSection::Graph,
+        ReportSection::Timeline,
+        ReportSection::Registry,
+        ReportSection::Lineage,
+    ];
+
+    /// Bundle-relative paths whose bytes fully determine this section's output.
+    pub fn inputs(self) -> Vec<&'static str> {
+        let mut inputs = DATAOPS_STATE_INPUTS.to_vec();
+        match self {
+            Self::Summary => {
+                inputs.extend(["graph.json", "datasets/materializations.ndjson"]);
+            }
+            Self::Graph => inputs.push("graph.json"),
+            Self::Timeline => inputs.extend([
+                "graph.json",
+                "spans.ndjson",
+                "events.ndjson",
+                "metrics.ndjson",
+                "datasets/materializations.ndjson",
+            ]),
+            Self::Registry | Self::Lineage => {}
+        }
+        inputs
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedSection {
+    key: String,
+    html: String,
+}
+
+/// Incremental report generator for live dashboards.
+///
+/// Each rendered section is cached under a key derived from the manifest `sha256`
+/// of its input files (`ReportSection::inputs`). `build()` re-renders only the
+/// sections whose inputs changed since the previous build and splices cached
+/// bytes for the rest.
+///
+/// The bundle is still loaded (and its manifest validated) on every build, so
+/// mid-run callers need a fresh manifest, e.g. `ManifestRefreshPolicy::Always`.
+#[derive(Debug)]
+pub struct ReportBuilder {
+    run_dir: PathBuf,
+    cache: BTreeMap<ReportSection, CachedSection>,
+    last_rendered: Vec<ReportSection>,
+}
+
+impl ReportBuilder {
+    /// Create a builder for a run bundle directory (`runs/<run_id>/`).
+    pub fn new(run_dir: impl AsRef<Path>) -> Self {
+        Self {
+            run_dir: run_dir.as_ref().to_path_buf(),
+            cache: BTreeMap::new(),
+            last_rendered: Vec::new(),
+        }
+    }
+
+    /// Build the full HTML report, re-rendering only stale sections.
+    ///
+    /// Output is byte-identical to `generate_report_html` for the same bundle state.
+    pub fn build(&mut self) -> io::Result<String> {
+        // Read hashes before loading: if the manifest moves in between, the cache key
+        // is older than the rendered bytes and the next build simply re-renders.
+        let bundle = RunArtifactBundle::open(&self.run_dir)?;
+        let hashes = bundle.manifest_entry_hashes()?;
+        let ndjson_suffix = bundle.compression().file_suffix();
+        let report = load_report(&self.run_dir)?;
+
+        self.last_rendered.clear();
+        let mut html = render_head(&report);
+        for section in ReportSection::ALL {
+            let key = section_cache_key(*section, &hashes, ndjson_suffix);
+            let fresh = matches!(self.cache.get(section), Some(cached) if cached.key == key);
+            if !fresh {
+                let rendered = render_section(&report, *section);
+                self.cache.insert(
+                    *section,
+                    CachedSection {
+                        key,
+                        html: rendered,
+                    },
+                );
+                self.last_rendered.push(*section);
+            }
+            html.push_str(&self.cache[section].html);
+        }
+        html.push_str(REPORT_HTML_TAIL);
+        Ok(html)
+    }
+
+    /// Build and write the report to `out_path`.
+    pub fn build_to_file(&mut self, out_path: impl AsRef<Path>) -> io::Result<()> {
+        let html = self.build()?;
+        std::fs::write(out_path, html)
+    }
+
+    /// Cached HTML for a section from the most recent build.
+    pub fn section_html(&self, section: ReportSection) -> Option<&str> {
+        self.cache.get(&section).map(|cached| cached.html.as_str())
+    }
+
+    /// Sections that were (re-)rendered by the most recent build.
+    pub fn last_rendered(&self) -> &[ReportSection] {
+        &self.last_rendered
+    }
+
+    /// Drop all cached sections so the next build renders everything.
+    pub fn invalidate(&mut self) {
+        self.cache.clear();
+    }
+}
+
+fn section_cache_key(
+    section: ReportSection,
+    hashes: &BTreeMap<String, String>,
+    ndjson_suffix: &str,
+) -> String {
+    let mut key = String::new();
+    for path in section.inputs() {
+        let path = if path.ends_with(".ndjson") {
+            format!("{path}{ndjson_suffix}")
+        } else {
+            path.to_string()
+        };
+        key.push_str(&path);
+        key.push('=');
+        key.push_str(hashes.get(&path).map(String::as_str).unwrap_or("-"));
+        key.push(';');
+    }
+    key
+}
@@ -1,19 +1,22 @@
 use std::collections::BTreeMap;
 use std::fs;
 use std::io::{self, BufRead};
+use std::marker::PhantomData;
 use std::path::Path;
 
 use sha2::{Digest, Sha256};
+use swarm_torch_core::crypto::ct_eq;
 use swarm_torch_core::dataops::{
-    validate_source_descriptor_bounds, DatasetEntryV1, DatasetLineageV1, DatasetRegistryV1,
-    LineageEdgeV1, MaterializationRecordCompat, MaterializationRecordV2,
+    is_recognized_content_type, validate_source_descriptor_bounds, DatasetEntryV1,
+    DatasetLineageV1, DatasetRegistryV1, LineageEdgeV1, MaterializationRecordCompat,
+    MaterializationRecordV2,
 };
 use swarm_torch_core::observe::{EventRecord, MetricRecord, SpanRecord};
 use swarm_torch_core::run_graph::GraphV1;
 
 use crate::artifacts::RunArtifactBundle;
 
-use super::model::Report;
+use super::model::{Report, ReportSummary, SummaryAccumulator};
 
 pub fn load_report(run_dir: impl AsRef<Path>) -> io::Result<Report> {
     let (report, _) = load_report_with_warnings(run_dir)?;
@@ -22,8 +25,18 @@ pub fn load_report(run_dir: impl AsRef<Path>) -> io::Result<Report> {
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum LoadWarning {
-    SourceDescriptorBoundsExceeded { asset_key: String, message: String },
-    SnapshotPairMismatch { message: String },
+    SourceDescriptorBoundsExceeded {
+        asset_key: String,
+        message: String,
+    },
+    SnapshotPairMismatch {
+        message: String,
+    },
+    /// Source `content_type` is not in `KNOWN_CONTENT_TYPES` (after alias mapping).
+    UnrecognizedContentType {
+        asset_key: String,
+        content_type: String,
+    },
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -46,15 +59,78 @@ pub fn load_report_with_warnings(
     // Enforce tamper-evidence by default.
     bundle.validate_manifest()?;
 
+    let (graph, registry, lineage) = load_graph_and_datasets(&bundle, &mut warnings)?;
+
+    let spans: Vec<SpanRecord> = read_ndjson(&bundle, "spans.ndjson")?;
+    let events: Vec<EventRecord> = read_ndjson(&bundle, "events.ndjson")?;
+    let metrics: Vec<MetricRecord> = read_ndjson(&bundle, "metrics.ndjson")?;
+    let materializations = read_materializations(&bundle)?;
+
+    Ok((
+        Report {
+            run_dir,
+            graph,
+            registry,
+            lineage,
+            materializations,
+            spans,
+            events,
+            metrics,
+        },
+        warnings,
+    ))
+}
+
+/// Summarize a run without materializing its span/event/metric/materialization
+/// records: each NDJSON file is decoded one line at a time and folded into a
+/// [`ReportSummary`], so memory stays bounded by the graph and dataset snapshots.
+///
+/// Produces the same summary as `ReportSummary::from_report(&load_report(run_dir)?)`.
+pub fn load_report_streaming(run_dir: impl AsRef<Path>) -> io::Result<ReportSummary> {
+    let run_dir = run_dir.as_ref().to_path_buf();
+    let bundle = RunArtifactBundle::open(&run_dir)?;
+    let mut warnings = Vec::new();
+
+    bundle.validate_manifest()?;
+
+    let (graph, registry, _lineage) = load_graph_and_datasets(&bundle, &mut warnings)?;
+
+    let mut acc = SummaryAccumulator::new(&graph, &registry);
+    for span in NdjsonReader::<SpanRecord>::open(&bundle, "spans.ndjson")? {
+        acc.observe_span(&span?);
+    }
+    for event in NdjsonReader::<EventRecord>::open(&bundle, "events.ndjson")? {
+        acc.observe_event(&event?);
+    }
+    for metric in NdjsonReader::<MetricRecord>::open(&bundle, "metrics.ndjson")? {
+        acc.observe_metric(&metric?);
+    }
+    for record in NdjsonReader::<MaterializationRecordCompat>::open(
+        &bundle,
+        "datasets/materializations.ndjson",
+    )? {
+        acc.observe_materialization(&record?.into_v2());
+    }
+    Ok(acc.finish(run_dir))
+}
+
+/// Load `graph.json` and the dataset registry/lineage (snapshot + NDJSON replay),
+/// pushing descriptor-bounds and snapshot-pair warnings.
+fn load_graph_and_datasets(
+    bundle: &RunArtifactBundle,
+    warnings: &mut Vec<LoadWarning>,
+) -> io::Result<(GraphV1, DatasetRegistryV1, DatasetLineageV1)> {
+    let run_dir = bundle.run_dir();
+
     let mut graph: GraphV1 = read_json(run_dir.join("graph.json"))?;
     graph = graph
         .normalize()
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
 
     let registry_updates: Vec<DatasetEntryV1> =
-        read_ndjson_if_exists(run_dir.join("datasets").join("registry_updates.ndjson"))?;
+        read_ndjson_if_exists(bundle, "datasets/registry_updates.ndjson")?;
     let lineage_updates: Vec<LineageEdgeV1> =
-        read_ndjson_if_exists(run_dir.join("datasets").join("lineage_edges.ndjson"))?;
+        read_ndjson_if_exists(bundle, "datasets/lineage_edges.ndjson")?;
     let datasets_dir = run_dir.join("datasets");
 
     let pair_mismatch = snapshot_pair_mismatch_reason(&datasets_dir)?;
@@ -80,14 +156,25 @@ pub fn load_report_with_warnings(
                     message: e.to_string(),
                 });
             }
+            if !is_recognized_content_type(&source.content_type) {
+                warnings.push(LoadWarning::UnrecognizedContentType {
+                    asset_key: entry.asset_key.clone(),
+                    content_type: source.content_type.clone(),
+                });
+            }
         }
     }
 
-    let spans: Vec<SpanRecord> = read_ndjson(run_dir.join("spans.ndjson"))?;
-    let events: Vec<EventRecord> = read_ndjson(run_dir.join("events.ndjson"))?;
-    let metrics: Vec<MetricRecord> = read_ndjson(run_dir.join("metrics.ndjson"))?;
+    Ok((graph, registry, lineage))
+}
+
+/// Read `datasets/materializations.ndjson` (V1/V2 via compat) as V2 records ordered by
+/// `(ts_unix_nanos, record_seq)`; V1 records get their 1-based line position as `record_seq`.
+pub(crate) fn read_materializations(
+    bundle: &RunArtifactBundle,
+) -> io::Result<Vec<MaterializationRecordV2>> {
     let materializations_raw: Vec<MaterializationRecordCompat> =
-        read_ndjson(run_dir.join("datasets").join("materializations.ndjson"))?;
+        read_ndjson(bundle, "datasets/materializations.ndjson")?;
     let mut materializations: Vec<MaterializationRecordV2> = materializations_raw
         .into_iter()
         .enumerate()
@@ -100,20 +187,7 @@ pub fn load_report_with_warnings(
         })
         .collect();
     materializations.sort_by_key(|m| (m.ts_unix_nanos, m.record_seq));
-
-    Ok((
-        Report {
-            run_dir,
-            graph,
-            registry,
-            lineage,
-            materializations,
-            spans,
-            events,
-            metrics,
-        },
-        warnings,
-    ))
+    Ok(materializations)
 }
 
 fn snapshot_pair_mismatch_reason(datasets_dir: &Path) -> io::Result<Option<String>> {
@@ -135,7 +209,9 @@ fn snapshot_pair_mismatch_reason(datasets_dir: &Path) -> io::Result<Option<Strin
     let actual_registry = hash_file_sha256_hex(&registry_path)?;
     let actual_lineage = hash_file_sha256_hex(&lineage_path)?;
 
-    if marker.registry_sha256 != actual_registry || marker.lineage_sha256 != actual_lineage {
+    if !ct_eq(&marker.registry_sha256, &actual_registry)
+        || !ct_eq(&marker.lineage_sha256, &actual_lineage)
+    {
         return Ok(Some(
             "snapshot pair hash mismatch; replaying from NDJSON updates".to_string(),
         ));
@@ -164,34 +240,72 @@ fn read_json<T: serde::de::DeserializeOwned>(path: impl AsRef<Path>) -> io::Resu
     serde_json::from_slice(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
 
-fn read_ndjson<T: serde::de::DeserializeOwned>(path: impl AsRef<Path>) -> io::Result<Vec<T>> {
-    let f = std::fs::File::open(path)?;
-    let reader = io::BufReader::new(f);
-    let mut out = Vec::new();
-    for (i, line) in reader.lines().enumerate() {
-        let line = line?;
-        if line.trim().is_empty() {
-            continue;
+/// Streaming NDJSON decoder: yields one record per non-blank line without
+/// buffering the whole file.
+///
+/// Decode failures are `InvalidData` errors naming the 1-based line number.
+pub struct NdjsonReader<T, R = Box<dyn BufRead>> {
+    lines: io::Lines<R>,
+    line_no: usize,
+    _record: PhantomData<fn() -> T>,
+}
+
+impl<T, R: BufRead> NdjsonReader<T, R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            line_no: 0,
+            _record: PhantomData,
+        }
+    }
+}
+
+impl<T> NdjsonReader<T> {
+    /// Open a logical NDJSON file through the bundle (handles compressed bundles).
+    pub fn open(bundle: &RunArtifactBundle, rel: &str) -> io::Result<Self> {
+        Ok(Self::new(bundle.open_ndjson(rel)?))
+    }
+}
+
+impl<T: serde::de::DeserializeOwned, R: BufRead> Iterator for NdjsonReader<T, R> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            self.line_no += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Some(serde_json::from_str::<T>(&line).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid ndjson at line {}: {}", self.line_no, e),
+                )
+            }));
         }
-        let v = serde_json::from_str::<T>(&line).map_err(|e| {
-            io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("invalid ndjson at line {}: {}", i + 1, e),
-            )
-        })?;
-        out.push(v);
     }
-    Ok(out)
+}
+
+/// Read a logical NDJSON file through the bundle (handles compressed bundles).
+fn read_ndjson<T: serde::de::DeserializeOwned>(
+    bundle: &RunArtifactBundle,
+    rel: &str,
+) -> io::Result<Vec<T>> {
+    NdjsonReader::open(bundle, rel)?.collect()
 }
 
 fn read_ndjson_if_exists<T: serde::de::DeserializeOwned>(
-    path: impl AsRef<Path>,
+    bundle: &RunArtifactBundle,
+    rel: &str,
 ) -> io::Result<Vec<T>> {
-    let path = path.as_ref();
-    if !path.exists() {
+    if !bundle.ndjson_path(rel).exists() {
         return Ok(Vec::new());
     }
-    read_ndjson(path)
+    read_ndjson(bundle, rel)
 }
 
 fn apply_registry_updates(
@@ -5,13 +5,28 @@
 //! - validates `manifest.json`
 //! - generates a self-contained `report.html` without requiring a server/DB/UI framework
 
+mod builder;
 mod load;
 mod model;
 mod render;
 
-pub use load::{load_report, load_report_with_warnings, LoadWarning};
-pub use model::{is_node_unsafe, Report};
-pub use render::{generate_report, generate_report_html};
+pub use builder::{ReportBuilder, ReportSection};
+pub use load::{
+    load_report, load_report_streaming, load_report_with_warnings, LoadWarning, NdjsonReader,
+};
+pub use model::{
+    is_node_unsafe, is_node_unsafe_with_policy, MissingInputPolicy, Report, ReportSummary,
+    SpanDuration, SUMMARY_SLOWEST_SPANS,
+};
+pub use render::{
+    generate_report, generate_report_html, generate_report_html_with_options,
+    generate_report_with_options, ReportOptions,
+};
+
+#[cfg(feature = "parquet")]
+pub(crate) use load::read_materializations;
+#[cfg(feature = "parquet")]
+pub(crate) use model::cache_decision_label;
 
 #[cfg(test)]
 mod tests;
@@ -1,8 +1,10 @@
-use std::collections::BTreeMap;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
 use std::path::{Path, PathBuf};
 
 use swarm_torch_core::dataops::{
-    DatasetLineageV1, DatasetRegistryV1, MaterializationRecordV2, TrustClass, UnsafeReasonV0,
+    CacheDecisionV0, DatasetEntryV1, DatasetLineageV1, DatasetRegistryV1, MaterializationRecordV2,
+    QualitySummaryV0, TrustClass, UnsafeReasonV0,
 };
 use swarm_torch_core::observe::{EventRecord, MetricRecord, SpanRecord};
 use swarm_torch_core::run_graph::{ExecutionTrust, GraphV1, NodeV1};
@@ -21,10 +23,170 @@ pub struct Report {
     pub metrics: Vec<MetricRecord>,
 }
 
+/// Number of slowest spans kept in [`ReportSummary::slowest_spans`].
+pub const SUMMARY_SLOWEST_SPANS: usize = 10;
+
+/// Span name and duration, as listed in [`ReportSummary::slowest_spans`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+pub struct SpanDuration {
+    pub duration_nanos: u64,
+    pub name: String,
+}
+
+/// Aggregate view of a run that can be computed in one pass over its records.
+///
+/// Built either from a loaded [`Report`] ([`ReportSummary::from_report`]) or
+/// incrementally from the artifact files (`load_report_streaming`); both paths
+/// fold records through the same accumulator and yield identical summaries.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ReportSummary {
+    #[serde(serialize_with = "serialize_path")]
+    pub run_dir: PathBuf,
+    pub node_count: usize,
+    /// Node keys derived unsafe by [`is_node_unsafe`], in graph order.
+    pub unsafe_nodes: Vec<String>,
+    pub dataset_count: usize,
+    pub span_count: u64,
+    pub event_count: u64,
+    pub metric_count: u64,
+    pub materialization_count: u64,
+    pub unsafe_materialization_count: u64,
+    /// Earliest/latest timeline timestamp (span start, event/metric/materialization ts).
+    pub first_ts_unix_nanos: Option<u64>,
+    pub last_ts_unix_nanos: Option<u64>,
+    /// Up to [`SUMMARY_SLOWEST_SPANS`] completed spans, longest first
+    /// (ties broken by name, descending).
+    pub slowest_spans: Vec<SpanDuration>,
+}
+
+impl ReportSummary {
+    pub fn from_report(report: &Report) -> Self {
+        let mut acc = SummaryAccumulator::new(&report.graph, &report.registry);
+        report.spans.iter().for_each(|s| acc.observe_span(s));
+        report.events.iter().for_each(|e| acc.observe_event(e));
+        report.metrics.iter().for_each(|m| acc.observe_metric(m));
+        report
+            .materializations
+            .iter()
+            .for_each(|m| acc.observe_materialization(m));
+        acc.finish(report.run_dir.clone())
+    }
+}
+
+/// One-pass fold behind [`ReportSummary`]; memory is bounded by the graph plus
+/// a [`SUMMARY_SLOWEST_SPANS`]-sized heap.
+pub(crate) struct SummaryAccumulator {
+    summary: ReportSummary,
+    slowest: BinaryHeap<Reverse<SpanDuration>>,
+}
+
+impl SummaryAccumulator {
+    pub(crate) fn new(graph: &GraphV1, registry: &DatasetRegistryV1) -> Self {
+        let registry_index = registry.index();
+        let unsafe_nodes = graph
+            .nodes
+            .iter()
+            .filter(|n| {
+                is_node_unsafe_with_index(n, &registry_index, MissingInputPolicy::FailClosed)
+            })
+            .map(|n| n.node_key.clone())
+            .collect();
+        Self {
+            summary: ReportSummary {
+                run_dir: PathBuf::new(),
+                node_count: graph.nodes.len(),
+                unsafe_nodes,
+                dataset_count: registry.datasets.len(),
+                span_count: 0,
+                event_count: 0,
+                metric_count: 0,
+                materialization_count: 0,
+                unsafe_materialization_count: 0,
+                first_ts_unix_nanos: None,
+                last_ts_unix_nanos: None,
+                slowest_spans: Vec::new(),
+            },
+            slowest: BinaryHeap::with_capacity(SUMMARY_SLOWEST_SPANS + 1),
+        }
+    }
+
+    fn observe_ts(&mut self, ts: u64) {
+        let s = &mut self.summary;
+        s.first_ts_unix_nanos = Some(s.first_ts_unix_nanos.map_or(ts, |t| t.min(ts)));
+        s.last_ts_unix_nanos = Some(s.last_ts_unix_nanos.map_or(ts, |t| t.max(ts)));
+    }
+
+    pub(crate) fn observe_span(&mut self, span: &SpanRecord) {
+        self.summary.span_count += 1;
+        self.observe_ts(span.start_unix_nanos);
+        let Some(duration_nanos) = span
+            .end_unix_nanos
+            .and_then(|end| end.checked_sub(span.start_unix_nanos))
+        else {
+            return;
+        };
+        self.slowest.push(Reverse(SpanDuration {
+            duration_nanos,
+            name: span.name.clone(),
+        }));
+        if self.slowest.len() > SUMMARY_SLOWEST_SPANS {
+            self.slowest.pop();
+        }
+    }
+
+    pub(crate) fn observe_event(&mut self, event: &EventRecord) {
+        self.summary.event_count += 1;
+        self.observe_ts(event.ts_unix_nanos);
+    }
+
+    pub(crate) fn observe_metric(&mut self, metric: &MetricRecord) {
+        self.summary.metric_count += 1;
+        self.observe_ts(metric.ts_unix_nanos);
+    }
+
+    pub(crate) fn observe_materialization(&mut self, record: &MaterializationRecordV2) {
+        self.summary.materialization_count += 1;
+        if record.unsafe_surface {
+            self.summary.unsafe_materialization_count += 1;
+        }
+        self.observe_ts(record.ts_unix_nanos);
+    }
+
+    pub(crate) fn finish(mut self, run_dir: PathBuf) -> ReportSummary {
+        self.summary.run_dir = run_dir;
+        // `into_sorted_vec` is ascending in `Reverse`, i.e. longest first.
+        self.summary.slowest_spans = self
+            .slowest
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(span)| span)
+            .collect();
+        self.summary
+    }
+}
+
 fn serialize_path<S: serde::Serializer>(path: &Path, s: S) -> Result<S::Ok, S::Error> {
     s.serialize_str(&path.display().to_string())
 }
 
+/// How unsafe-node derivation treats inputs that are missing from the registry.
+///
+/// `FailOpen` and `Warn` are for exploratory runs whose sources are not registered
+/// yet. They weaken the report's safety signal: a node reading an unregistered
+/// (and so unvetted) asset is no longer flagged unsafe, so never use them for
+/// runs whose report gates deployment or review.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MissingInputPolicy {
+    /// Missing input ⇒ node is unsafe.
+    #[default]
+    FailClosed,
+    /// Missing inputs are ignored.
+    FailOpen,
+    /// Missing inputs do not make the node unsafe, but the node is listed in
+    /// the summary and annotated in the graph.
+    Warn,
+}
+
 /// Derive whether a node should be marked unsafe.
 ///
 /// A node is unsafe if:
@@ -32,37 +194,51 @@ fn serialize_path<S: serde::Serializer>(path: &Path, s: S) -> Result<S::Ok, S::E
 /// - any input asset_key is `Untrusted` in the registry, OR
 /// - any input asset_key is **missing** from the registry (fail closed).
 pub fn is_node_unsafe(node: &NodeV1, registry: &DatasetRegistryV1) -> bool {
-    let trust_index = build_registry_trust_index(registry);
-    is_node_unsafe_with_index(node, &trust_index)
+    is_node_unsafe_with_policy(node, registry, MissingInputPolicy::FailClosed)
 }
 
-pub(crate) fn build_registry_trust_index(
+/// [`is_node_unsafe`] with an explicit [`MissingInputPolicy`].
+pub fn is_node_unsafe_with_policy(
+    node: &NodeV1,
     registry: &DatasetRegistryV1,
-) -> BTreeMap<String, TrustClass> {
-    registry
-        .datasets
-        .iter()
-        .map(|entry| (entry.asset_key.clone(), entry.trust))
-        .collect()
+    policy: MissingInputPolicy,
+) -> bool {
+    is_node_unsafe_with_index(node, &registry.index(), policy)
 }
 
+/// [`is_node_unsafe_with_policy`] against a prebuilt [`DatasetRegistryV1::index`],
+/// so rendering many nodes costs one index build instead of a registry scan per input.
 pub(crate) fn is_node_unsafe_with_index(
     node: &NodeV1,
-    trust_index: &BTreeMap<String, TrustClass>,
+    registry_index: &BTreeMap<&str, &DatasetEntryV1>,
+    policy: MissingInputPolicy,
 ) -> bool {
     if node.execution_trust != ExecutionTrust::Core {
         return true;
     }
     for input in &node.inputs {
-        match trust_index.get(&input.asset_key).copied() {
+        match registry_index
+            .get(input.asset_key.as_str())
+            .map(|entry| entry.trust)
+        {
             Some(TrustClass::Untrusted) => return true,
-            None => return true, // missing input -> fail closed
+            None if policy == MissingInputPolicy::FailClosed => return true,
             _ => {}
         }
     }
     false
 }
 
+/// Whether any input of `node` is absent from the registry.
+pub(crate) fn has_missing_input(
+    node: &NodeV1,
+    registry_index: &BTreeMap<&str, &DatasetEntryV1>,
+) -> bool {
+    node.inputs
+        .iter()
+        .any(|input| !registry_index.contains_key(input.asset_key.as_str()))
+}
+
 pub(crate) fn unsafe_reason_label(reason: UnsafeReasonV0) -> &'static str {
     match reason {
         UnsafeReasonV0::UntrustedInput => "untrusted_input",
@@ -82,6 +258,38 @@ pub(crate) fn format_unsafe_reasons(reasons: &[UnsafeReasonV0]) -> String {
         .join(",")
 }
 
+pub(crate) fn cache_decision_label(decision: CacheDecisionV0) -> &'static str {
+    match decision {
+        CacheDecisionV0::Hit => "hit",
+        CacheDecisionV0::Miss => "miss",
+        CacheDecisionV0::Bypass => "bypass",
+        CacheDecisionV0::Unknown => "unknown",
+    }
+}
+
+pub(crate) fn format_quality(quality: Option<&QualitySummaryV0>) -> String {
+    let Some(quality) = quality else {
+        return "none".to_string();
+    };
+    let mut parts = Vec::new();
+    if let Some(null_rate) = quality.null_rate {
+        parts.push(format!("null_rate:{null_rate:.4}"));
+    }
+    if let Some(delta) = quality.row_count_delta {
+        parts.push(format!("row_count_delta:{delta}"));
+    }
+    if let Some(changed) = quality.schema_changed {
+        parts.push(format!("schema_changed:{changed}"));
+    }
+    if !quality.quality_flags.is_empty() {
+        parts.push(format!("flags:{}", quality.quality_flags.join("|")));
+    }
+    if parts.is_empty() {
+        return "none".to_string();
+    }
+    parts.join(",")
+}
+
 pub(crate) fn format_transform_names(
     transforms: &[swarm_torch_core::dataops::TransformAuditV0],
 ) -> String {
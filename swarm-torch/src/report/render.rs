@@ -1,20 +1,59 @@
 use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::fs;
 
 use swarm_torch_core::run_graph::{GraphV1, NodeId};
 
+use super::builder::ReportSection;
 use super::load::load_report;
 use super::model::{
-    build_registry_trust_index, format_transform_names, format_unsafe_reasons,
-    is_node_unsafe_with_index, Report,
+    cache_decision_label, format_quality, format_transform_names, format_unsafe_reasons,
+    has_missing_input, is_node_unsafe_with_index, MissingInputPolicy, Report,
 };
 
+/// Render-time options for the HTML report.
+///
+/// Filters apply to the timeline table only; the loaded `Report` (and its JSON
+/// output) stays complete.
+#[derive(Debug, Clone, Default)]
+pub struct ReportOptions<'a> {
+    /// Drop timeline rows with `ts < since_unix_nanos`.
+    pub since_unix_nanos: Option<u64>,
+    /// Drop timeline rows with `ts >= until_unix_nanos`.
+    pub until_unix_nanos: Option<u64>,
+    /// Keep only these row kinds (`span`, `event`, `metric`, `materialization`).
+    pub kinds: Option<HashSet<&'a str>>,
+    /// Unsafe derivation for inputs missing from the registry (graph, timeline,
+    /// summary). See [`MissingInputPolicy`] before relaxing the default.
+    pub missing_input_policy: MissingInputPolicy,
+}
+
+impl ReportOptions<'_> {
+    fn includes(&self, ts: u64, kind: &str) -> bool {
+        self.since_unix_nanos.map_or(true, |since| ts >= since)
+            && self.until_unix_nanos.map_or(true, |until| ts < until)
+            && self
+                .kinds
+                .as_ref()
+                .map_or(true, |kinds| kinds.contains(kind))
+    }
+}
+
 pub fn generate_report_html(
     run_dir: impl AsRef<std::path::Path>,
     out_path: impl AsRef<std::path::Path>,
+) -> std::io::Result<()> {
+    generate_report_html_with_options(run_dir, out_path, &ReportOptions::default())
+}
+
+/// Like [`generate_report_html`], with timeline filters applied at render.
+pub fn generate_report_html_with_options(
+    run_dir: impl AsRef<std::path::Path>,
+    out_path: impl AsRef<std::path::Path>,
+    options: &ReportOptions<'_>,
 ) -> std::io::Result<()> {
     let report = load_report(run_dir)?;
-    let html = render_html(&report);
+    let html = render_html(&report, options);
     fs::write(out_path, html)
 }
 
@@ -25,9 +64,19 @@ pub fn generate_report(
     run_dir: impl AsRef<std::path::Path>,
     html_out: impl AsRef<std::path::Path>,
     json_out: Option<impl AsRef<std::path::Path>>,
+) -> std::io::Result<()> {
+    generate_report_with_options(run_dir, html_out, json_out, &ReportOptions::default())
+}
+
+/// Like [`generate_report`], with timeline filters applied to the HTML only.
+pub fn generate_report_with_options(
+    run_dir: impl AsRef<std::path::Path>,
+    html_out: impl AsRef<std::path::Path>,
+    json_out: Option<impl AsRef<std::path::Path>>,
+    options: &ReportOptions<'_>,
 ) -> std::io::Result<()> {
     let report = load_report(&run_dir)?;
-    let html = render_html(&report);
+    let html = render_html(&report, options);
     fs::write(&html_out, html)?;
 
     if let Some(json_path) = json_out {
@@ -56,7 +105,11 @@ fn node_index_map(graph: &GraphV1) -> std::collections::HashMap<NodeId, usize> {
     m
 }
 
-fn render_svg(graph: &GraphV1, registry: &swarm_torch_core::dataops::DatasetRegistryV1) -> String {
+fn render_svg(
+    graph: &GraphV1,
+    registry: &swarm_torch_core::dataops::DatasetRegistryV1,
+    policy: MissingInputPolicy,
+) -> String {
     let width = 900;
     let node_w = 820;
     let node_h = 56;
@@ -66,7 +119,7 @@ fn render_svg(graph: &GraphV1, registry: &swarm_torch_core::dataops::DatasetRegi
     let height = y0 + (graph.nodes.len().max(1) * y_step) + 30;
 
     let idx = node_index_map(graph);
-    let trust_index = build_registry_trust_index(registry);
+    let registry_index = registry.index();
 
     let mut svg = String::new();
     svg.push_str(&format!(
@@ -96,17 +149,24 @@ fn render_svg(graph: &GraphV1, registry: &swarm_torch_core::dataops::DatasetRegi
     for (i, n) in graph.nodes.iter().enumerate() {
         let x = x0;
         let y = y0 + i * y_step;
-        let derived_unsafe = is_node_unsafe_with_index(n, &trust_index);
+        let derived_unsafe = is_node_unsafe_with_index(n, &registry_index, policy);
+        let missing_warning =
+            policy == MissingInputPolicy::Warn && has_missing_input(n, &registry_index);
         let cls = if derived_unsafe { "s u" } else { "s" };
         svg.push_str(&format!(
             "<rect x=\"{x}\" y=\"{y}\" rx=\"10\" ry=\"10\" width=\"{node_w}\" height=\"{node_h}\" class=\"{cls}\"/>"
         ));
         let title = format!(
-            "{}  [{}:{}]{}",
+            "{}  [{}:{}]{}{}",
             n.node_key,
             format!("{:?}", n.op_kind).to_lowercase(),
             n.op_type,
-            if derived_unsafe { "  UNSAFE" } else { "" }
+            if derived_unsafe { "  UNSAFE" } else { "" },
+            if missing_warning {
+                "  MISSING_INPUTS"
+            } else {
+                ""
+            }
         );
         svg.push_str(&format!(
             "<text class=\"n\" x=\"{}\" y=\"{}\">{}</text>",
@@ -132,14 +192,17 @@ struct TimelineRow {
     detail: String,
 }
 
-pub(crate) fn render_timeline(report: &Report) -> String {
+pub(crate) fn render_timeline(report: &Report, options: &ReportOptions<'_>) -> String {
     let mut rows: Vec<TimelineRow> = Vec::new();
-    let trust_index = build_registry_trust_index(&report.registry);
+    let registry_index = report.registry.index();
     let mut node_unsafe_by_id: std::collections::HashMap<NodeId, bool> =
         std::collections::HashMap::new();
     for node in &report.graph.nodes {
         if let Some(node_id) = node.node_id {
-            node_unsafe_by_id.insert(node_id, is_node_unsafe_with_index(node, &trust_index));
+            node_unsafe_by_id.insert(
+                node_id,
+                is_node_unsafe_with_index(node, &registry_index, options.missing_input_policy),
+            );
         }
     }
 
@@ -181,16 +244,19 @@ pub(crate) fn render_timeline(report: &Report) -> String {
             kind: "materialization",
             name: m.asset_key.clone(),
             detail: format!(
-                "rows={} bytes={} cache_hit={} unsafe={} unsafe_reasons={} transforms={} node_id={} node_def_hash={}",
+                "op_type={} rows={} bytes={} cache_decision={} cache_hit={} quality={} unsafe={} unsafe_reasons={} transforms={} node_id={} node_def_hash={}",
+                m.op_type,
                 m.rows
                     .map(|v| v.to_string())
                     .unwrap_or_else(|| "?".to_string()),
                 m.bytes
                     .map(|v| v.to_string())
                     .unwrap_or_else(|| "?".to_string()),
+                cache_decision_label(m.cache_decision),
                 m.cache_hit
                     .map(|v| v.to_string())
                     .unwrap_or_else(|| "?".to_string()),
+                format_quality(m.quality.as_ref()),
                 derived_unsafe,
                 unsafe_reasons,
                 transforms,
@@ -200,6 +266,7 @@ pub(crate) fn render_timeline(report: &Report) -> String {
         });
     }
 
+    rows.retain(|r| options.includes(r.ts, r.kind));
     rows.sort_by(|a, b| match a.ts.cmp(&b.ts) {
         Ordering::Equal => a.kind.cmp(b.kind),
         o => o,
@@ -220,13 +287,77 @@ pub(crate) fn render_timeline(report: &Report) -> String {
     out
 }
 
-pub(crate) fn render_html(report: &Report) -> String {
-    let trust_index = build_registry_trust_index(&report.registry);
+pub(crate) fn render_html(report: &Report, options: &ReportOptions<'_>) -> String {
+    let mut html = render_head(report);
+    for section in ReportSection::ALL {
+        html.push_str(&render_section_with_options(report, *section, options));
+    }
+    html.push_str(REPORT_HTML_TAIL);
+    html
+}
+
+pub(crate) const REPORT_HTML_TAIL: &str = "</body></html>";
+
+pub(crate) fn render_head(report: &Report) -> String {
+    let mut html = String::new();
+    html.push_str("<!doctype html><html><head><meta charset=\"utf-8\"/>");
+    html.push_str("<meta name=\"viewport\" content=\"width=device-width,initial-scale=1\"/>");
+    html.push_str("<title>SwarmTorch Run Report</title>");
+    html.push_str("<style>body{font:15px ui-sans-serif,system-ui,-apple-system,Segoe UI,Roboto,Helvetica,Arial,sans-serif;max-width:1100px;margin:24px auto;padding:0 16px;color:#111}h1,h2{margin:18px 0 10px}code,.mono{font-family:ui-monospace,SFMono-Regular,Menlo,Monaco,monospace;font-size:13px}table{border-collapse:collapse;width:100%;margin:8px 0 16px}th,td{border:1px solid #ddd;padding:8px;vertical-align:top}th{background:#fafafa;text-align:left}section{margin:18px 0 22px}.warn{border:2px solid #b00020;padding:10px;border-radius:10px;background:#fff5f5}.ok{border:2px solid #2e7d32;padding:10px;border-radius:10px;background:#f5fff7}</style>");
+    html.push_str("</head><body>");
+    html.push_str("<h1>SwarmTorch Run Report</h1>");
+
+    html.push_str(&format!(
+        "<p><strong>Run dir:</strong> <code>{}</code></p>",
+        escape_html(&report.run_dir.display().to_string())
+    ));
+    html
+}
+
+/// Render a single report section. Output depends only on the section's inputs
+/// (see `ReportSection::inputs`), which is what makes per-section caching sound.
+pub(crate) fn render_section(report: &Report, section: ReportSection) -> String {
+    render_section_with_options(report, section, &ReportOptions::default())
+}
+
+fn render_section_with_options(
+    report: &Report,
+    section: ReportSection,
+    options: &ReportOptions<'_>,
+) -> String {
+    match section {
+        ReportSection::Summary => render_summary(report, options.missing_input_policy),
+        ReportSection::Graph => {
+            let mut html = String::from("<section><h2>Run Graph</h2>");
+            html.push_str(&render_svg(
+                &report.graph,
+                &report.registry,
+                options.missing_input_policy,
+            ));
+            html.push_str("</section>");
+            html
+        }
+        ReportSection::Timeline => {
+            let mut html = String::from("<section><h2>Timeline</h2>");
+            html.push_str(&render_timeline(report, options));
+            html.push_str("</section>");
+            html
+        }
+        ReportSection::Registry => render_registry(report),
+        ReportSection::Lineage => render_lineage(report),
+    }
+}
+
+fn render_summary(report: &Report, policy: MissingInputPolicy) -> String {
+    let registry_index = report.registry.index();
     // Derive unsafe nodes using is_node_unsafe (registry-aware)
     let mut unsafe_nodes = Vec::new();
+    let mut missing_input_nodes = Vec::new();
     for n in &report.graph.nodes {
-        if is_node_unsafe_with_index(n, &trust_index) {
+        if is_node_unsafe_with_index(n, &registry_index, policy) {
             unsafe_nodes.push(n.node_key.clone());
+        } else if policy == MissingInputPolicy::Warn && has_missing_input(n, &registry_index) {
+            missing_input_nodes.push(n.node_key.clone());
         }
     }
 
@@ -248,18 +379,6 @@ pub(crate) fn render_html(report: &Report) -> String {
     }
 
     let mut html = String::new();
-    html.push_str("<!doctype html><html><head><meta charset=\"utf-8\"/>");
-    html.push_str("<meta name=\"viewport\" content=\"width=device-width,initial-scale=1\"/>");
-    html.push_str("<title>SwarmTorch Run Report</title>");
-    html.push_str("<style>body{font:15px ui-sans-serif,system-ui,-apple-system,Segoe UI,Roboto,Helvetica,Arial,sans-serif;max-width:1100px;margin:24px auto;padding:0 16px;color:#111}h1,h2{margin:18px 0 10px}code,.mono{font-family:ui-monospace,SFMono-Regular,Menlo,Monaco,monospace;font-size:13px}table{border-collapse:collapse;width:100%;margin:8px 0 16px}th,td{border:1px solid #ddd;padding:8px;vertical-align:top}th{background:#fafafa;text-align:left}section{margin:18px 0 22px}.warn{border:2px solid #b00020;padding:10px;border-radius:10px;background:#fff5f5}.ok{border:2px solid #2e7d32;padding:10px;border-radius:10px;background:#f5fff7}</style>");
-    html.push_str("</head><body>");
-    html.push_str("<h1>SwarmTorch Run Report</h1>");
-
-    html.push_str(&format!(
-        "<p><strong>Run dir:</strong> <code>{}</code></p>",
-        escape_html(&report.run_dir.display().to_string())
-    ));
-
     if unsafe_nodes.is_empty() && unsafe_datasets.is_empty() && unsafe_materializations.is_empty() {
         html.push_str("<div class=\"ok\"><strong>Unsafe surfaces:</strong> none detected in the current artifacts.</div>");
     } else {
@@ -282,15 +401,29 @@ pub(crate) fn render_html(report: &Report) -> String {
         }
         html.push_str("</ul></div>");
     }
+    if !missing_input_nodes.is_empty() {
+        html.push_str("<div class=\"warn\"><strong>Unregistered inputs (not marked unsafe; missing_input_policy=warn):</strong><ul>");
+        for n in missing_input_nodes {
+            html.push_str(&format!("<li>node: <code>{}</code></li>", escape_html(&n)));
+        }
+        html.push_str("</ul></div>");
+    }
+    let orphans = report.registry.orphans(&report.graph);
+    if !orphans.is_empty() {
+        html.push_str("<div class=\"warn\"><strong>Orphan datasets (no node reads or writes them; consider removing from the registry):</strong><ul>");
+        for asset_key in orphans {
+            html.push_str(&format!(
+                "<li>dataset: <code>{}</code></li>",
+                escape_html(&asset_key)
+            ));
+        }
+        html.push_str("</ul></div>");
+    }
+    html
+}
 
-    html.push_str("<section><h2>Run Graph</h2>");
-    html.push_str(&render_svg(&report.graph, &report.registry));
-    html.push_str("</section>");
-
-    html.push_str("<section><h2>Timeline</h2>");
-    html.push_str(&render_timeline(report));
-    html.push_str("</section>");
-
+fn render_registry(report: &Report) -> String {
+    let mut html = String::new();
     html.push_str("<section><h2>Dataset Registry</h2>");
     html.push_str("<table><thead><tr><th>asset_key</th><th>fingerprint_v0</th><th>trust</th><th>source</th></tr></thead><tbody>");
     for d in &report.registry.datasets {
@@ -308,7 +441,11 @@ pub(crate) fn render_html(report: &Report) -> String {
         ));
     }
     html.push_str("</tbody></table></section>");
+    html
+}
 
+fn render_lineage(report: &Report) -> String {
+    let mut html = String::new();
     html.push_str("<section><h2>Lineage</h2>");
     html.push_str("<table><thead><tr><th>input_fingerprint</th><th>output_fingerprint</th><th>node_id</th><th>op_kind</th></tr></thead><tbody>");
     for e in &report.lineage.edges {
@@ -321,7 +458,5 @@ pub(crate) fn render_html(report: &Report) -> String {
         ));
     }
     html.push_str("</tbody></table></section>");
-
-    html.push_str("</body></html>");
     html
 }
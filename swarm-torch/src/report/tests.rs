@@ -8,11 +8,11 @@ use std::fs;
 use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 use swarm_torch_core::dataops::{
-    DatasetEntryV1, DatasetLineageV1, DatasetRegistryV1, MaterializationRecordV1,
+    DatasetEntryV1, DatasetLineageV1, DatasetRegistryV1, HashAlgo, MaterializationRecordV1,
     MaterializationRecordV2, MaterializationStatusV0, SourceDescriptorV0, TransformAuditV0,
     TrustClass, UnsafeReasonV0, MATERIALIZATION_SCHEMA_V2, MAX_SOURCE_URI_LEN,
 };
-use swarm_torch_core::observe::{RunId, TraceId};
+use swarm_torch_core::observe::{AttrMap, EventRecord, MetricRecord, RunId, TraceId};
 use swarm_torch_core::run_graph::{
     AssetRefV1, CanonParams, ExecutionTrust, GraphV1, NodeV1, OpKind,
 };
@@ -62,6 +62,7 @@ fn make_entry(asset_key: &str, trust: TrustClass) -> DatasetEntryV1 {
         source_fingerprint_v0: "b".repeat(64),
         schema_hash_v0: "c".repeat(64),
         recipe_hash_v0: "d".repeat(64),
+        hash_algo: HashAlgo::Sha256,
         trust,
         source: None,
         schema: None,
@@ -183,6 +184,57 @@ fn report_node_safe_with_core_trust_and_trusted_inputs() {
     );
 }
 
+#[test]
+fn large_registry_renders_with_indexed_unsafe_derivation() {
+    // 2k nodes x 4 inputs against 8k datasets: a per-input registry scan would be
+    // ~64M comparisons per pass; the index keeps each pass O((n + m) log m).
+    const NODES: usize = 2_000;
+    let key = |i: usize| format!("dataset://ns/d{i}");
+    let datasets: Vec<DatasetEntryV1> = (0..NODES * 4)
+        .map(|i| {
+            let trust = if i % 1_000 == 999 {
+                TrustClass::Untrusted
+            } else {
+                TrustClass::Trusted
+            };
+            make_entry(&key(i), trust)
+        })
+        .collect();
+    let nodes: Vec<NodeV1> = (0..NODES)
+        .map(|n| {
+            let inputs: Vec<String> = (0..4).map(|k| key(n * 4 + k)).collect();
+            let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+            make_node(&format!("n/{n}"), ExecutionTrust::Core, &inputs)
+        })
+        .collect();
+    let report = Report {
+        run_dir: PathBuf::from("/tmp/test"),
+        graph: GraphV1 {
+            schema_version: 1,
+            graph_id: None,
+            nodes,
+            edges: vec![],
+        },
+        registry: DatasetRegistryV1 {
+            schema_version: 1,
+            datasets,
+        },
+        lineage: DatasetLineageV1 {
+            schema_version: 1,
+            edges: vec![],
+        },
+        materializations: vec![],
+        spans: vec![],
+        events: vec![],
+        metrics: vec![],
+    };
+
+    // One untrusted dataset per 1k, each feeding a distinct node.
+    let html = render_html(&report, &ReportOptions::default());
+    assert_eq!(html.matches("<li>node:").count(), NODES * 4 / 1_000);
+    assert!(html.contains("<li>node: <code>n/249</code></li>"));
+}
+
 #[test]
 fn timeline_materialization_includes_node_id_and_node_def_hash() {
     let node_id = TraceId::from_bytes([1u8; 16]);
@@ -240,7 +292,7 @@ fn timeline_materialization_includes_node_id_and_node_def_hash() {
         events: vec![],
         metrics: vec![],
     };
-    let timeline_html = render_timeline(&report);
+    let timeline_html = render_timeline(&report, &ReportOptions::default());
 
     assert!(
         timeline_html.contains("node_id="),
@@ -318,7 +370,7 @@ fn timeline_materialization_includes_unsafe_reasons_when_present() {
         metrics: vec![],
     };
 
-    let timeline_html = render_timeline(&report);
+    let timeline_html = render_timeline(&report, &ReportOptions::default());
     assert!(
         timeline_html.contains("unsafe_reasons=untrusted_input,unsafe_extension"),
         "timeline should render serialized unsafe reason labels: {timeline_html}"
@@ -376,7 +428,7 @@ fn report_warning_lists_unsafe_materialization_reasons() {
         metrics: vec![],
     };
 
-    let html = render_html(&report);
+    let html = render_html(&report, &ReportOptions::default());
     assert!(
         html.contains("unsafe materialization"),
         "report warning banner should list unsafe materializations: {html}"
@@ -467,6 +519,61 @@ fn report_loads_materialization_v1_and_v2() {
     );
 }
 
+#[test]
+fn report_timeline_renders_mixed_v1_v2_fixture() {
+    let base = temp_dir("mixed_v1_v2_fixture");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let run_id = RunId::from_bytes([78u8; 16]);
+    let bundle = RunArtifactBundle::create(&base, run_id).unwrap();
+    fs::write(
+        bundle
+            .run_dir()
+            .join("datasets")
+            .join("materializations.ndjson"),
+        include_str!("fixtures/materializations_v1_v2.ndjson"),
+    )
+    .unwrap();
+    bundle.finalize_manifest().unwrap();
+
+    let report = load_report(bundle.run_dir()).unwrap();
+    assert_eq!(report.materializations.len(), 2);
+
+    let legacy = &report.materializations[0];
+    assert_eq!(legacy.asset_key, "dataset://ns/legacy");
+    assert_eq!(legacy.op_type, "unknown");
+    assert!(legacy.unsafe_surface, "v1 rows must normalize as unsafe");
+    assert_eq!(
+        legacy.unsafe_reasons,
+        vec![UnsafeReasonV0::MissingProvenance]
+    );
+
+    let current = &report.materializations[1];
+    assert_eq!(current.asset_key, "dataset://ns/current");
+    assert_eq!(current.record_seq, 7);
+    assert!(current.unsafe_reasons.is_empty());
+
+    let timeline_html = render_timeline(&report, &ReportOptions::default());
+    assert!(timeline_html.contains("dataset://ns/legacy"));
+    assert!(timeline_html.contains("dataset://ns/current"));
+    assert!(
+        timeline_html.contains("op_type=unknown rows=10 bytes=100 cache_decision=miss"),
+        "v1 row should render normalized fields: {timeline_html}"
+    );
+    assert!(
+        timeline_html.contains("unsafe_reasons=missing_provenance"),
+        "v1 row should surface missing provenance: {timeline_html}"
+    );
+    assert!(
+        timeline_html.contains("op_type=transform rows=8 bytes=80 cache_decision=bypass"),
+        "v2 row should render op_type and cache_decision: {timeline_html}"
+    );
+    assert!(
+        timeline_html.contains("quality=null_rate:0.2500,row_count_delta:-2,schema_changed:false"),
+        "v2 row should render quality summary: {timeline_html}"
+    );
+}
+
 #[test]
 fn report_loads_record_with_and_without_applied_transforms() {
     let base = temp_dir("compat_applied_transforms");
@@ -664,6 +771,7 @@ fn load_report_with_warnings_emits_descriptor_bounds_violations() {
         source_fingerprint_v0: "b".repeat(64),
         schema_hash_v0: "c".repeat(64),
         recipe_hash_v0: "d".repeat(64),
+        hash_algo: HashAlgo::Sha256,
         trust: TrustClass::Trusted,
         source: Some(SourceDescriptorV0 {
             uri: "x".repeat(MAX_SOURCE_URI_LEN + 1),
@@ -675,10 +783,18 @@ fn load_report_with_warnings_emits_descriptor_bounds_violations() {
         license_flags: vec![],
         pii_tags: vec![],
     };
+    let mut custom_entry = oversized_entry.clone();
+    custom_entry.asset_key = "dataset://ns/custom".to_string();
+    custom_entry.source = Some(SourceDescriptorV0 {
+        uri: "s3://bucket/custom".to_string(),
+        content_type: "application/x-custom".to_string(),
+        auth_mode: swarm_torch_core::dataops::AuthModeMarker::None,
+        etag_or_version: None,
+    });
     bundle
         .write_dataset_registry(&DatasetRegistryV1 {
             schema_version: 1,
-            datasets: vec![oversized_entry],
+            datasets: vec![oversized_entry, custom_entry],
         })
         .unwrap();
     bundle.finalize_manifest().unwrap();
@@ -692,6 +808,14 @@ fn load_report_with_warnings_emits_descriptor_bounds_violations() {
         )),
         "expected descriptor bounds warning, got: {warnings:?}"
     );
+    let unrecognized: Vec<&str> = warnings
+        .iter()
+        .filter_map(|warning| match warning {
+            LoadWarning::UnrecognizedContentType { asset_key, .. } => Some(asset_key.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(unrecognized, ["dataset://ns/custom"]);
 }
 
 #[test]
@@ -720,6 +844,7 @@ fn load_report_with_warnings_prefers_replay_on_snapshot_pair_mismatch() {
                 source_fingerprint_v0: "2".repeat(64),
                 schema_hash_v0: "3".repeat(64),
                 recipe_hash_v0: "4".repeat(64),
+                hash_algo: HashAlgo::Sha256,
                 trust: TrustClass::Trusted,
                 source: None,
                 schema: None,
@@ -737,6 +862,7 @@ fn load_report_with_warnings_prefers_replay_on_snapshot_pair_mismatch() {
             source_fingerprint_v0: "b".repeat(64),
             schema_hash_v0: "c".repeat(64),
             recipe_hash_v0: "d".repeat(64),
+            hash_algo: HashAlgo::Sha256,
             trust: TrustClass::Trusted,
             source: None,
             schema: None,
@@ -787,3 +913,423 @@ fn load_report_with_warnings_prefers_replay_on_snapshot_pair_mismatch() {
         "inconsistent snapshot should be ignored in replay-preferred mode"
     );
 }
+
+#[test]
+fn report_builder_reuses_sections_whose_inputs_are_unchanged() {
+    use swarm_torch_core::observe::{AttrMap, SpanId, SpanRecord};
+
+    let base = temp_dir("report_builder_incremental");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let run_id = RunId::from_bytes([92u8; 16]);
+    let bundle = RunArtifactBundle::create(&base, run_id).unwrap();
+    let profile = ArtifactWriteProfile {
+        snapshot_profile: SnapshotProfile::Strict,
+        manifest_policy: ManifestRefreshPolicy::Always,
+    };
+    let sink = std::sync::Arc::new(RunArtifactSink::with_profile(bundle, profile));
+    let mut session = DataOpsSession::new(std::sync::Arc::clone(&sink));
+
+    let mut ingest_node = make_node("ingest/raw", ExecutionTrust::Core, &[]);
+    ingest_node.outputs = vec![AssetRefV1 {
+        asset_key: "dataset://ns/raw".to_string(),
+        fingerprint: None,
+    }];
+    let source = SourceDescriptorV0 {
+        uri: "s3://bucket/raw.parquet".to_string(),
+        content_type: "application/parquet".to_string(),
+        auth_mode: swarm_torch_core::dataops::AuthModeMarker::None,
+        etag_or_version: Some("v1".to_string()),
+    };
+    session
+        .register_source(
+            "dataset://ns/raw",
+            TrustClass::Trusted,
+            source,
+            None,
+            &ingest_node,
+        )
+        .unwrap();
+
+    let span = |name: &str, n: u8| SpanRecord {
+        schema_version: 1,
+        trace_id: TraceId::from_bytes([n; 16]),
+        span_id: SpanId::from_bytes([n; 8]),
+        parent_span_id: None,
+        name: name.to_string(),
+        start_unix_nanos: u64::from(n) * 1_000,
+        end_unix_nanos: Some(u64::from(n) * 1_000 + 500),
+        attrs: AttrMap::new(),
+    };
+    sink.append_span(&span("train/epoch_1", 1)).unwrap();
+
+    let run_dir = sink.bundle().run_dir().to_path_buf();
+    let mut builder = ReportBuilder::new(&run_dir);
+    let first = builder.build().unwrap();
+    assert_eq!(builder.last_rendered(), ReportSection::ALL);
+    let registry_before = builder
+        .section_html(ReportSection::Registry)
+        .unwrap()
+        .to_string();
+    let timeline_before = builder
+        .section_html(ReportSection::Timeline)
+        .unwrap()
+        .to_string();
+    assert!(registry_before.contains("dataset://ns/raw"));
+
+    // Only spans.ndjson changes (manifest refreshed by the Always policy).
+    sink.append_span(&span("train/epoch_2", 2)).unwrap();
+    let second = builder.build().unwrap();
+
+    assert_eq!(builder.last_rendered(), &[ReportSection::Timeline]);
+    assert_eq!(
+        builder
+            .section_html(ReportSection::Registry)
+            .unwrap()
+            .as_bytes(),
+        registry_before.as_bytes(),
+        "registry section should be reused byte-for-byte"
+    );
+    let timeline_after = builder.section_html(ReportSection::Timeline).unwrap();
+    assert_ne!(timeline_after, timeline_before);
+    assert!(timeline_after.contains("train/epoch_2"));
+
+    assert_ne!(first, second);
+    assert_eq!(
+        second,
+        render_html(&load_report(&run_dir).unwrap(), &ReportOptions::default()),
+        "incremental build must match a full render"
+    );
+
+    // No changes at all: nothing re-renders.
+    let third = builder.build().unwrap();
+    assert!(builder.last_rendered().is_empty());
+    assert_eq!(third, second);
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn materializations_export_to_parquet() {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::RowAccessor;
+
+    let base = temp_dir("parquet_export");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let run_id = RunId::from_bytes([79u8; 16]);
+    let bundle = RunArtifactBundle::create(&base, run_id).unwrap();
+    fs::write(
+        bundle
+            .run_dir()
+            .join("datasets")
+            .join("materializations.ndjson"),
+        include_str!("fixtures/materializations_v1_v2.ndjson"),
+    )
+    .unwrap();
+
+    let out = base.join("materializations.parquet");
+    let written = crate::export::export_materializations_parquet(bundle.run_dir(), &out).unwrap();
+    assert_eq!(written, 2);
+
+    let reader = SerializedFileReader::new(fs::File::open(&out).unwrap()).unwrap();
+    assert_eq!(reader.metadata().file_metadata().num_rows(), 2);
+    let rows: Vec<_> = reader
+        .get_row_iter(None)
+        .unwrap()
+        .map(|row| row.unwrap())
+        .collect();
+
+    assert_eq!(rows[0].get_string(0).unwrap(), "dataset://ns/legacy");
+    assert_eq!(
+        rows[0].get_string(2).unwrap(),
+        "02020202020202020202020202020202"
+    );
+    assert_eq!(rows[0].get_string(3).unwrap(), "unknown");
+    assert_eq!(rows[0].get_ulong(4).unwrap(), 10);
+    assert_eq!(rows[0].get_string(7).unwrap(), "miss");
+    assert!(rows[0].get_bool(8).unwrap(), "v1 rows normalize as unsafe");
+
+    assert_eq!(rows[1].get_string(0).unwrap(), "dataset://ns/current");
+    assert_eq!(rows[1].get_ulong(5).unwrap(), 80);
+    assert_eq!(rows[1].get_ulong(6).unwrap(), 4);
+    assert_eq!(rows[1].get_string(7).unwrap(), "bypass");
+    assert!(!rows[1].get_bool(8).unwrap());
+
+    let _ = fs::remove_dir_all(&base);
+}
+
+fn timeline_filter_report() -> Report {
+    let trace_id = TraceId::from_bytes([5u8; 16]);
+    let event = |name: &str, ts: u64| EventRecord {
+        schema_version: 1,
+        trace_id,
+        span_id: None,
+        name: name.to_string(),
+        ts_unix_nanos: ts,
+        attrs: AttrMap::new(),
+    };
+    let metric = |name: &str, ts: u64| MetricRecord {
+        schema_version: 1,
+        trace_id,
+        span_id: None,
+        name: name.to_string(),
+        ts_unix_nanos: ts,
+        value: 1.0,
+        unit: None,
+        attrs: AttrMap::new(),
+    };
+    Report {
+        run_dir: PathBuf::from("/tmp/test"),
+        graph: GraphV1 {
+            schema_version: 1,
+            graph_id: None,
+            nodes: vec![],
+            edges: vec![],
+        },
+        registry: DatasetRegistryV1 {
+            schema_version: 1,
+            datasets: vec![],
+        },
+        lineage: DatasetLineageV1 {
+            schema_version: 1,
+            edges: vec![],
+        },
+        materializations: vec![],
+        spans: vec![],
+        events: vec![event("event/early", 100), event("event/late", 300)],
+        metrics: vec![metric("metric/early", 150), metric("metric/late", 250)],
+    }
+}
+
+#[test]
+fn report_options_time_window_drops_rows_outside_window() {
+    let report = timeline_filter_report();
+    let options = ReportOptions {
+        since_unix_nanos: Some(200),
+        until_unix_nanos: Some(300),
+        ..ReportOptions::default()
+    };
+    let html = render_html(&report, &options);
+    assert!(html.contains("metric/late"), "{html}");
+    assert!(!html.contains("event/early"));
+    assert!(!html.contains("metric/early"));
+    assert!(
+        !html.contains("event/late"),
+        "until_unix_nanos is exclusive"
+    );
+
+    // Unfiltered output keeps every row; the Report itself is never modified.
+    let full = render_html(&report, &ReportOptions::default());
+    assert!(full.contains("event/early") && full.contains("event/late"));
+    assert_eq!(report.events.len(), 2);
+}
+
+#[test]
+fn report_options_kind_filter_keeps_only_requested_kinds() {
+    let report = timeline_filter_report();
+    let options = ReportOptions {
+        kinds: Some(["metric"].into_iter().collect()),
+        ..ReportOptions::default()
+    };
+    let html = render_html(&report, &options);
+    assert!(html.contains("metric/early") && html.contains("metric/late"));
+    assert!(!html.contains("<td>event</td>"), "{html}");
+    assert!(!html.contains("event/early") && !html.contains("event/late"));
+}
+
+#[test]
+fn missing_input_policy_controls_unsafe_derivation_for_unregistered_inputs() {
+    let mut report = timeline_filter_report();
+    report.graph.nodes = vec![make_node(
+        "explore/join",
+        ExecutionTrust::Core,
+        &["dataset://ns/unregistered"],
+    )];
+    let render = |report: &Report, policy| {
+        render_html(
+            report,
+            &ReportOptions {
+                missing_input_policy: policy,
+                ..ReportOptions::default()
+            },
+        )
+    };
+
+    assert!(is_node_unsafe_with_policy(
+        &report.graph.nodes[0],
+        &report.registry,
+        MissingInputPolicy::FailClosed
+    ));
+    let closed = render(&report, MissingInputPolicy::FailClosed);
+    assert!(
+        closed.contains("explore/join  [data:test]  UNSAFE"),
+        "{closed}"
+    );
+    assert!(closed.contains("<li>node: <code>explore/join</code></li>"));
+    assert_eq!(closed, render_html(&report, &ReportOptions::default()));
+
+    assert!(!is_node_unsafe_with_policy(
+        &report.graph.nodes[0],
+        &report.registry,
+        MissingInputPolicy::FailOpen
+    ));
+    let open = render(&report, MissingInputPolicy::FailOpen);
+    assert!(!open.contains("UNSAFE"), "{open}");
+    assert!(!open.contains("MISSING_INPUTS"));
+    assert!(open.contains("none detected"));
+
+    let warn = render(&report, MissingInputPolicy::Warn);
+    assert!(!warn.contains("UNSAFE"), "{warn}");
+    assert!(warn.contains("explore/join  [data:test]  MISSING_INPUTS"));
+    assert!(warn.contains("missing_input_policy=warn"));
+
+    // Untrusted inputs stay unsafe regardless of policy.
+    report.registry.datasets = vec![make_entry(
+        "dataset://ns/unregistered",
+        TrustClass::Untrusted,
+    )];
+    assert!(render(&report, MissingInputPolicy::FailOpen).contains("  UNSAFE"));
+}
+
+#[test]
+fn report_summary_lists_orphan_datasets_as_cleanup_hint() {
+    let mut report = timeline_filter_report();
+    report.graph.nodes = vec![make_node(
+        "prep/clean",
+        ExecutionTrust::Core,
+        &["dataset://ns/source"],
+    )];
+    report.registry.datasets = vec![
+        make_entry("dataset://ns/source", TrustClass::Trusted),
+        make_entry("dataset://ns/stale", TrustClass::Trusted),
+    ];
+
+    let html = render_html(&report, &ReportOptions::default());
+    assert!(html.contains("Orphan datasets"), "{html}");
+    assert!(html.contains("<li>dataset: <code>dataset://ns/stale</code></li>"));
+    assert!(!html.contains("<li>dataset: <code>dataset://ns/source</code></li>"));
+
+    report.registry.datasets.pop();
+    let html = render_html(&report, &ReportOptions::default());
+    assert!(!html.contains("Orphan datasets"));
+}
+
+#[test]
+fn streaming_summary_matches_in_memory_summary_on_large_ndjson() {
+    use std::io::{BufWriter, Write};
+    use swarm_torch_core::observe::{SpanId, SpanRecord};
+
+    const SPANS: u64 = 20_000;
+    const EVENTS: u64 = 5_000;
+
+    let base = temp_dir("streaming_summary_large");
+    let _ = fs::remove_dir_all(&base);
+    fs::create_dir_all(&base).unwrap();
+    let run_id = RunId::from_bytes([93u8; 16]);
+    let bundle = RunArtifactBundle::create(&base, run_id).unwrap();
+    bundle
+        .write_graph(&GraphV1 {
+            schema_version: 1,
+            graph_id: None,
+            nodes: vec![make_node(
+                "train/step",
+                ExecutionTrust::Core,
+                &["dataset://ns/missing"],
+            )],
+            edges: vec![],
+        })
+        .unwrap();
+
+    // Write the bulk NDJSON directly; per-record appends would dominate the test.
+    let trace_id = TraceId::from_bytes([93u8; 16]);
+    let append = |rel: &str| {
+        BufWriter::new(
+            fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(bundle.ndjson_path(rel))
+                .unwrap(),
+        )
+    };
+    let mut spans = append("spans.ndjson");
+    for n in 0..SPANS {
+        let span = SpanRecord {
+            schema_version: 1,
+            trace_id,
+            span_id: SpanId::from_bytes((n + 1).to_be_bytes()),
+            parent_span_id: None,
+            name: format!("step/{n}"),
+            start_unix_nanos: 1_000 + n,
+            end_unix_nanos: (n % 7 != 0).then_some(1_000 + n + (n * 7_919) % 100_000),
+            attrs: AttrMap::new(),
+        };
+        serde_json::to_writer(&mut spans, &span).unwrap();
+        spans.write_all(b"\n").unwrap();
+    }
+    spans.flush().unwrap();
+    let mut events = append("events.ndjson");
+    for n in 0..EVENTS {
+        let event = EventRecord {
+            schema_version: 1,
+            trace_id,
+            span_id: None,
+            name: "tick".to_string(),
+            ts_unix_nanos: 500 + n * 10,
+            attrs: AttrMap::new(),
+        };
+        serde_json::to_writer(&mut events, &event).unwrap();
+        events.write_all(b"\n\n").unwrap();
+    }
+    events.flush().unwrap();
+    drop((spans, events));
+
+    for n in 0u8..3 {
+        bundle
+            .append_materialization(&MaterializationRecordV1 {
+                schema_version: 1,
+                ts_unix_nanos: 200_000 + u64::from(n),
+                asset_key: format!("dataset://ns/out{n}"),
+                fingerprint_v0: "a".repeat(64),
+                node_id: TraceId::from_bytes([7u8; 16]),
+                node_def_hash: "b".repeat(64),
+                rows: Some(10),
+                bytes: Some(100),
+                cache_hit: Some(false),
+                duration_ms: Some(5),
+                quality_flags: None,
+                unsafe_surface: false,
+            })
+            .unwrap();
+    }
+    bundle.finalize_manifest().unwrap();
+
+    let streamed = load_report_streaming(bundle.run_dir()).unwrap();
+    let in_memory = ReportSummary::from_report(&load_report(bundle.run_dir()).unwrap());
+    assert_eq!(streamed, in_memory);
+
+    assert_eq!(streamed.span_count, SPANS);
+    assert_eq!(streamed.event_count, EVENTS);
+    assert_eq!(streamed.metric_count, 0);
+    assert_eq!(streamed.materialization_count, 3);
+    // Legacy V1 rows normalize to unsafe (missing provenance).
+    assert_eq!(streamed.unsafe_materialization_count, 3);
+    assert_eq!(streamed.unsafe_nodes, vec!["train/step".to_string()]);
+    assert_eq!(streamed.first_ts_unix_nanos, Some(500));
+    assert_eq!(streamed.last_ts_unix_nanos, Some(200_002));
+    assert_eq!(streamed.slowest_spans.len(), SUMMARY_SLOWEST_SPANS);
+    assert!(streamed
+        .slowest_spans
+        .windows(2)
+        .all(|w| w[0].duration_nanos >= w[1].duration_nanos));
+}
+
+#[test]
+fn ndjson_reader_reports_line_number_of_invalid_record() {
+    let input = "{\"a\":1}\n\nnot json\n";
+    let mut reader = NdjsonReader::<serde_json::Value, _>::new(input.as_bytes());
+    assert!(reader.next().unwrap().is_ok());
+    let err = reader.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("line 3"), "{err}");
+    assert!(reader.next().is_none());
+}
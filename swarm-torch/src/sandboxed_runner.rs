@@ -0,0 +1,617 @@
+//! Sandboxed extension OpRunner (std-only).
+//!
+//! Runs `ExecutionTrust::SandboxedExtension` ops outside the host process. The first
+//! isolation backend is a child OS process per invocation:
+//! - the environment is cleared except for an explicit allowlist
+//! - inputs are written to stdin as a JSON array of `AssetInstanceV1`
+//! - node identity is passed as `SWARMTORCH_NODE_KEY` / `SWARMTORCH_OP_TYPE`
+//! - stdout must be a JSON array of `AssetInstanceV1` (size-capped)
+//! - non-zero exit, death by signal, or timeout is an error; the child is killed
+//!
+//! Spans match `NativeOpRunner` (same deterministic ids) with an added
+//! `swarmtorch.sandbox` attribute.
+//!
+//! **Scope:** the process backend only clears the environment and bounds wall-clock
+//! time and stdout. The child keeps the host user's network, filesystem, and memory
+//! access, so it does not meet ADR-0018's sandbox requirements (network denied,
+//! filesystem allowlist, memory limit) and
+//! [`SandboxedOpRunner::conforms_to_adr0018`] is `false`: `DataOpsSession` keeps
+//! flagging its outputs `UnsafeExtension`. A backend that enforces those limits (e.g.
+//! WASM) reports conformance, and its runs are recorded with
+//! `DataOpsSession::record_sandboxed_run`.
+
+use std::collections::BTreeMap;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use swarm_torch_core::execution::{AssetInstanceV1, OpRunner};
+use swarm_torch_core::observe::{AttrValue, RunEventEmitter};
+use swarm_torch_core::run_graph::NodeV1;
+
+use crate::native_runner::{fallback_context, op_span, ExecutionContext};
+
+/// Default wall-clock limit for one sandboxed op.
+pub const DEFAULT_SANDBOX_TIMEOUT: Duration = Duration::from_secs(30);
+/// Default cap on a sandboxed op's stdout.
+pub const DEFAULT_SANDBOX_MAX_OUTPUT_BYTES: usize = 1 << 20;
+/// Stderr bytes kept for error messages.
+const STDERR_TAIL_BYTES: usize = 4096;
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Runner boundary for ops executed in an isolated context.
+pub trait SandboxedOpRunner: Send + Sync {
+    /// Isolation backend name recorded on spans (e.g. `"process"`).
+    fn isolation(&self) -> &'static str;
+
+    /// Whether the backend enforces ADR-0018 isolation: no network, a filesystem
+    /// allowlist, and a memory limit. Only then may outputs be trusted.
+    fn conforms_to_adr0018(&self) -> bool {
+        false
+    }
+
+    /// Run `node` in isolation, emitting the same span as `NativeOpRunner`.
+    fn run_with_context<E: RunEventEmitter<Error = io::Error>>(
+        &self,
+        ctx: &ExecutionContext,
+        node: &NodeV1,
+        inputs: &[AssetInstanceV1],
+        emitter: &E,
+    ) -> io::Result<Vec<AssetInstanceV1>>;
+}
+
+/// Program invoked for one `op_type`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandboxCommand {
+    /// Executable spawned for each invocation (resolved via `PATH` if relative).
+    pub program: PathBuf,
+    /// Arguments passed to `program`, in order.
+    pub args: Vec<String>,
+}
+
+/// Process-isolated runner: one child process per op invocation.
+#[derive(Debug, Clone)]
+pub struct ProcessSandboxRunner {
+    ops: BTreeMap<String, SandboxCommand>,
+    allowed_env: Vec<String>,
+    timeout: Duration,
+    max_output_bytes: usize,
+}
+
+impl Default for ProcessSandboxRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessSandboxRunner {
+    /// Runner with no registered ops and an empty environment allowlist.
+    pub fn new() -> Self {
+        Self {
+            ops: BTreeMap::new(),
+            allowed_env: Vec::new(),
+            timeout: DEFAULT_SANDBOX_TIMEOUT,
+            max_output_bytes: DEFAULT_SANDBOX_MAX_OUTPUT_BYTES,
+        }
+    }
+
+    /// Route `op_type` to `program args...`.
+    pub fn with_op(
+        mut self,
+        op_type: impl Into<String>,
+        program: impl Into<PathBuf>,
+        args: &[&str],
+    ) -> Self {
+        self.ops.insert(
+            op_type.into(),
+            SandboxCommand {
+                program: program.into(),
+                args: args.iter().map(|a| a.to_string()).collect(),
+            },
+        );
+        self
+    }
+
+    /// Pass the host's value of `name` through to children (if set).
+    pub fn allow_env(mut self, name: impl Into<String>) -> Self {
+        self.allowed_env.push(name.into());
+        self
+    }
+
+    /// Override [`DEFAULT_SANDBOX_TIMEOUT`].
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Override [`DEFAULT_SANDBOX_MAX_OUTPUT_BYTES`].
+    pub fn with_max_output_bytes(mut self, max_output_bytes: usize) -> Self {
+        self.max_output_bytes = max_output_bytes;
+        self
+    }
+
+    fn invoke(
+        &self,
+        command: &SandboxCommand,
+        node: &NodeV1,
+        inputs: &[AssetInstanceV1],
+    ) -> io::Result<Vec<AssetInstanceV1>> {
+        let payload = serde_json::to_vec(inputs)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        let mut cmd = Command::new(&command.program);
+        cmd.args(&command.args)
+            .env_clear()
+            .env("SWARMTORCH_NODE_KEY", &node.node_key)
+            .env("SWARMTORCH_OP_TYPE", &node.op_type)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        for name in &self.allowed_env {
+            if let Some(value) = std::env::var_os(name) {
+                cmd.env(name, value);
+            }
+        }
+        let mut child = cmd.spawn()?;
+
+        // Pipes are serviced on threads so a chatty child cannot deadlock us.
+        let mut stdin = child.stdin.take();
+        let writer = thread::spawn(move || {
+            if let Some(stdin) = stdin.as_mut() {
+                // A child that exits without reading its input is judged by its status.
+                let _ = stdin.write_all(&payload);
+            }
+        });
+        let stdout = read_capped(child.stdout.take(), self.max_output_bytes + 1);
+        let stderr = read_capped(child.stderr.take(), STDERR_TAIL_BYTES);
+
+        let started = Instant::now();
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if started.elapsed() >= self.timeout {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!(
+                        "sandboxed op {} timed out after {:?}",
+                        node.op_type, self.timeout
+                    ),
+                ));
+            }
+            thread::sleep(POLL_INTERVAL);
+        };
+        let _ = writer.join();
+        let stdout = join_reader(stdout)?;
+        let stderr = join_reader(stderr)?;
+
+        if !status.success() {
+            return Err(io::Error::other(format!(
+                "sandboxed op {} failed ({}): {}",
+                node.op_type,
+                status,
+                String::from_utf8_lossy(&stderr).trim()
+            )));
+        }
+        if stdout.len() > self.max_output_bytes {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "sandboxed op {} output exceeds {} bytes",
+                    node.op_type, self.max_output_bytes
+                ),
+            ));
+        }
+        serde_json::from_slice(&stdout).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("sandboxed op {} returned invalid output: {e}", node.op_type),
+            )
+        })
+    }
+}
+
+impl SandboxedOpRunner for ProcessSandboxRunner {
+    fn isolation(&self) -> &'static str {
+        "process"
+    }
+
+    fn run_with_context<E: RunEventEmitter<Error = io::Error>>(
+        &self,
+        ctx: &ExecutionContext,
+        node: &NodeV1,
+        inputs: &[AssetInstanceV1],
+        emitter: &E,
+    ) -> io::Result<Vec<AssetInstanceV1>> {
+        let start_nanos = (ctx.clock_nanos)();
+        let command = self.ops.get(&node.op_type).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unsupported op_type: {}", node.op_type),
+            )
+        })?;
+        let outputs = self.invoke(command, node, inputs)?;
+        let end_nanos = (ctx.clock_nanos)();
+
+        let mut span = op_span(
+            ctx,
+            node,
+            start_nanos,
+            end_nanos,
+            inputs.len(),
+            outputs.len(),
+        );
+        span.attrs.insert(
+            "swarmtorch.sandbox".to_string(),
+            AttrValue::Str(self.isolation().to_string()),
+        );
+        emitter.emit_span(&span)?;
+
+        Ok(outputs)
+    }
+}
+
+impl OpRunner for ProcessSandboxRunner {
+    type Error = io::Error;
+
+    fn run<E: RunEventEmitter<Error = Self::Error>>(
+        &self,
+        node: &NodeV1,
+        inputs: &[AssetInstanceV1],
+        emitter: &E,
+    ) -> Result<Vec<AssetInstanceV1>, Self::Error> {
+        SandboxedOpRunner::run_with_context(self, &fallback_context(), node, inputs, emitter)
+    }
+}
+
+fn read_capped<R: Read + Send + 'static>(
+    pipe: Option<R>,
+    limit: usize,
+) -> thread::JoinHandle<io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            (&mut pipe).take(limit as u64).read_to_end(&mut buf)?;
+            // Keep draining so an oversized writer is not blocked on a full pipe.
+            io::copy(&mut pipe, &mut io::sink())?;
+        }
+        Ok(buf)
+    })
+}
+
+fn join_reader(handle: thread::JoinHandle<io::Result<Vec<u8>>>) -> io::Result<Vec<u8>> {
+    handle
+        .join()
+        .map_err(|_| io::Error::other("sandbox pipe reader panicked"))?
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    use swarm_torch_core::dataops::{CacheDecisionV0, SourceDescriptorV0, TrustClass};
+    use swarm_torch_core::observe::RunId;
+    use swarm_torch_core::run_graph::{AssetRefV1, CanonParams, ExecutionTrust, OpKind};
+
+    use crate::artifacts::{DataOpsSession, OutputSpec, RunArtifactBundle, RunArtifactSink};
+
+    fn test_ctx() -> ExecutionContext {
+        static CLOCK: AtomicU64 = AtomicU64::new(5_000_000_000);
+        ExecutionContext {
+            run_id: RunId::from_bytes([7u8; 16]),
+            clock_nanos: || CLOCK.fetch_add(1_000_000, Ordering::SeqCst),
+        }
+    }
+
+    fn runner() -> ProcessSandboxRunner {
+        ProcessSandboxRunner::new()
+            .with_op("echo", "/bin/cat", &[])
+            .with_op("crash", "/bin/sh", &["-c", "echo boom >&2; kill -SEGV $$"])
+            .with_op(
+                "leak_env",
+                "/bin/sh",
+                &["-c", "test -z \"$HOME\" || exit 9; cat"],
+            )
+            .with_op("hang", "/bin/sh", &["-c", "sleep 5"])
+            .with_op(
+                "stats",
+                "/bin/sh",
+                &[
+                    "-c",
+                    "cat >/dev/null; printf '%s' '[{\"asset_key\":\"dataset://ns/stats\",\"fingerprint_v0\":\"\",\"rows\":42,\"bytes\":4096}]'",
+                ],
+            )
+            .with_timeout(Duration::from_secs(10))
+    }
+
+    fn sandboxed_node(key: &str, op_type: &str, output: &str) -> NodeV1 {
+        NodeV1 {
+            node_key: key.to_string(),
+            node_id: None,
+            op_kind: OpKind::Data,
+            op_type: op_type.to_string(),
+            inputs: vec![AssetRefV1 {
+                asset_key: "dataset://ns/raw".to_string(),
+                fingerprint: None,
+            }],
+            outputs: vec![AssetRefV1 {
+                asset_key: output.to_string(),
+                fingerprint: None,
+            }],
+            params: CanonParams::new(),
+            code_ref: Some("ext@0.1.0".to_string()),
+            unsafe_surface: false,
+            execution_trust: ExecutionTrust::SandboxedExtension,
+            node_def_hash: None,
+            execution_hint: None,
+            cache_policy: None,
+            materialization_policy: None,
+            resources: None,
+            op_hash: None,
+        }
+    }
+
+    fn create_session(prefix: &str) -> (PathBuf, DataOpsSession) {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let base = std::env::temp_dir().join(format!(
+            "swarmtorch_sandbox_{prefix}_{}_{nanos}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&base).unwrap();
+        let bundle = RunArtifactBundle::create(&base, RunId::from_bytes([7u8; 16])).unwrap();
+        let mut session = DataOpsSession::new(Arc::new(RunArtifactSink::new(bundle)));
+        let mut ingest = sandboxed_node("ingest/raw", "ingest", "dataset://ns/raw");
+        ingest.inputs.clear();
+        ingest.execution_trust = ExecutionTrust::Core;
+        let source = SourceDescriptorV0 {
+            uri: "s3://bucket/raw.parquet".to_string(),
+            content_type: "application/parquet".to_string(),
+            auth_mode: swarm_torch_core::dataops::AuthModeMarker::None,
+            etag_or_version: Some("v1".to_string()),
+        };
+        session
+            .register_source(
+                "dataset://ns/raw",
+                TrustClass::Trusted,
+                source,
+                None,
+                &ingest,
+            )
+            .unwrap();
+        (base, session)
+    }
+
+    fn spans_named(session: &DataOpsSession, name: &str) -> usize {
+        use std::io::BufRead;
+        let reader = session.sink().bundle().open_ndjson("spans.ndjson").unwrap();
+        reader
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(&line.unwrap()).unwrap())
+            .filter(|span| span["name"] == name)
+            .count()
+    }
+
+    #[test]
+    fn sandboxed_echo_returns_inputs_and_emits_span() {
+        let (base, mut session) = create_session("echo");
+        let inputs = vec![session.resolve_asset_instance("dataset://ns/raw").unwrap()];
+        let node = sandboxed_node("ext/echo", "echo", "dataset://ns/echoed");
+
+        let outputs = runner()
+            .run_with_context(&test_ctx(), &node, &inputs, session.sink().as_ref())
+            .unwrap();
+        assert_eq!(outputs, inputs);
+        assert_eq!(spans_named(&session, "op/echo"), 1);
+
+        session
+            .materialize_node_outputs(
+                &node,
+                &[OutputSpec {
+                    asset_key: "dataset://ns/echoed".to_string(),
+                    schema: None,
+                    rows: None,
+                    bytes: None,
+                }],
+                1,
+                CacheDecisionV0::Miss,
+                0,
+            )
+            .unwrap();
+        let registry = session.registry_snapshot();
+        let echoed = registry
+            .datasets
+            .iter()
+            .find(|entry| entry.asset_key == "dataset://ns/echoed")
+            .unwrap();
+        assert_eq!(echoed.trust, TrustClass::Untrusted);
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    /// Test backend claiming ADR-0018 conformance, delegating execution.
+    struct ConformingRunner(ProcessSandboxRunner);
+
+    impl SandboxedOpRunner for ConformingRunner {
+        fn isolation(&self) -> &'static str {
+            "conforming"
+        }
+
+        fn conforms_to_adr0018(&self) -> bool {
+            true
+        }
+
+        fn run_with_context<E: RunEventEmitter<Error = io::Error>>(
+            &self,
+            ctx: &ExecutionContext,
+            node: &NodeV1,
+            inputs: &[AssetInstanceV1],
+            emitter: &E,
+        ) -> io::Result<Vec<AssetInstanceV1>> {
+            self.0.run_with_context(ctx, node, inputs, emitter)
+        }
+    }
+
+    #[test]
+    fn sandboxed_outputs_trusted_only_after_conforming_run() {
+        let (base, mut session) = create_session("conforming");
+        let inputs = vec![session.resolve_asset_instance("dataset://ns/raw").unwrap()];
+        let node = sandboxed_node("ext/echo", "echo", "dataset://ns/echoed");
+        let spec = [OutputSpec {
+            asset_key: "dataset://ns/echoed".to_string(),
+            schema: None,
+            rows: None,
+            bytes: None,
+        }];
+        let trust = |session: &DataOpsSession| {
+            session
+                .registry_snapshot()
+                .datasets
+                .iter()
+                .find(|entry| entry.asset_key == "dataset://ns/echoed")
+                .unwrap()
+                .trust
+        };
+
+        // The process backend is not a conforming sandbox.
+        let process = runner();
+        assert!(!process.conforms_to_adr0018());
+        process
+            .run_with_context(&test_ctx(), &node, &inputs, session.sink().as_ref())
+            .unwrap();
+        session.record_sandboxed_run(&node, &process);
+        session
+            .materialize_node_outputs(&node, &spec, 1, CacheDecisionV0::Miss, 0)
+            .unwrap();
+        assert_eq!(trust(&session), TrustClass::Untrusted);
+
+        let conforming = ConformingRunner(runner());
+        conforming
+            .run_with_context(&test_ctx(), &node, &inputs, session.sink().as_ref())
+            .unwrap();
+        session.record_sandboxed_run(&node, &conforming);
+        session
+            .materialize_node_outputs(&node, &spec, 2, CacheDecisionV0::Miss, 0)
+            .unwrap();
+        assert_eq!(trust(&session), TrustClass::Trusted);
+
+        // Provenance is consumed: a later unrecorded run is untrusted again.
+        session
+            .materialize_node_outputs(&node, &spec, 3, CacheDecisionV0::Miss, 0)
+            .unwrap();
+        assert_eq!(trust(&session), TrustClass::Untrusted);
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn runner_reported_stats_flow_into_materialization_record() {
+        let (base, mut session) = create_session("stats");
+        let inputs = vec![session.resolve_asset_instance("dataset://ns/raw").unwrap()];
+        let node = sandboxed_node("ext/stats", "stats", "dataset://ns/stats");
+
+        let outputs = runner()
+            .run_with_context(&test_ctx(), &node, &inputs, session.sink().as_ref())
+            .unwrap();
+        assert_eq!((outputs[0].rows, outputs[0].bytes), (Some(42), Some(4096)));
+        assert_eq!(outputs[0].schema, None);
+
+        let specs = OutputSpec::from_runner_outputs(&node, &outputs);
+        session
+            .materialize_node_outputs(&node, &specs, 1, CacheDecisionV0::Miss, 0)
+            .unwrap();
+
+        use std::io::BufRead;
+        let record = session
+            .sink()
+            .bundle()
+            .open_ndjson("datasets/materializations.ndjson")
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(&line.unwrap()).unwrap())
+            .find(|record| record["asset_key"] == "dataset://ns/stats")
+            .unwrap();
+        assert_eq!(record["rows"], 42);
+        assert_eq!(record["bytes"], 4096);
+
+        // Instances without stats (older runners) still deserialize and serialize compactly.
+        let legacy: AssetInstanceV1 =
+            serde_json::from_str(r#"{"asset_key":"a","fingerprint_v0":"f"}"#).unwrap();
+        assert_eq!((legacy.rows, legacy.bytes), (None, None));
+        assert_eq!(
+            serde_json::to_string(&legacy).unwrap(),
+            r#"{"asset_key":"a","fingerprint_v0":"f"}"#
+        );
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn crashing_op_errors_without_poisoning_session() {
+        let (base, mut session) = create_session("crash");
+        let inputs = vec![session.resolve_asset_instance("dataset://ns/raw").unwrap()];
+        let crash = sandboxed_node("ext/crash", "crash", "dataset://ns/crashed");
+
+        let err = runner()
+            .run_with_context(&test_ctx(), &crash, &inputs, session.sink().as_ref())
+            .unwrap_err();
+        assert!(err.to_string().contains("boom"), "{err}");
+        session
+            .record_node_error(&crash, 1, 0, err.to_string())
+            .unwrap();
+        assert_eq!(spans_named(&session, "op/crash"), 0);
+        assert!(session.fingerprint("dataset://ns/crashed").is_none());
+
+        // The same session keeps working for later nodes.
+        let echo = sandboxed_node("ext/echo", "echo", "dataset://ns/echoed");
+        let outputs = runner()
+            .run_with_context(&test_ctx(), &echo, &inputs, session.sink().as_ref())
+            .unwrap();
+        assert_eq!(outputs, inputs);
+        assert!(session.fingerprint("dataset://ns/raw").is_some());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn environment_is_cleared_and_timeouts_kill_child() {
+        let (base, session) = create_session("env_timeout");
+        let inputs = vec![session.resolve_asset_instance("dataset://ns/raw").unwrap()];
+        let emitter = session.sink().as_ref();
+
+        let leak = sandboxed_node("ext/leak", "leak_env", "dataset://ns/leak");
+        assert_eq!(
+            runner()
+                .run_with_context(&test_ctx(), &leak, &inputs, emitter)
+                .unwrap(),
+            inputs
+        );
+
+        let hang = sandboxed_node("ext/hang", "hang", "dataset://ns/hang");
+        let started = Instant::now();
+        let err = runner()
+            .with_timeout(Duration::from_millis(100))
+            .run_with_context(&test_ctx(), &hang, &inputs, emitter)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(4));
+
+        let unknown = sandboxed_node("ext/unknown", "nope", "dataset://ns/nope");
+        let err = runner()
+            .run_with_context(&test_ctx(), &unknown, &inputs, emitter)
+            .unwrap_err();
+        assert!(err.to_string().contains("unsupported op_type"));
+
+        let _ = fs::remove_dir_all(&base);
+    }
+}
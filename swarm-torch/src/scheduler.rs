@@ -280,31 +280,25 @@ pub fn execute_graph_sequential(
             runner.run_with_context(&context, &node, &inputs, session.sink().as_ref());
         let finished = (clock_nanos)();
         let duration_ms = finished.saturating_sub(started) / 1_000_000;
-        if let Err(error) = runner_result {
-            let error_code = format!("runner_error:{error}");
-            session.record_node_error(&node, finished, duration_ms, error_code.clone())?;
-            emit_scheduler_event(
-                session.sink().as_ref(),
-                trace_id,
-                finished,
-                "scheduler/node_failed",
-                &node.node_key,
-                Some(&error_code),
-            )?;
-            report.failed_nodes.push(node.node_key.clone());
-            continue;
-        }
+        let runner_outputs = match runner_result {
+            Ok(runner_outputs) => runner_outputs,
+            Err(error) => {
+                let error_code = format!("runner_error:{error}");
+                session.record_node_error(&node, finished, duration_ms, error_code.clone())?;
+                emit_scheduler_event(
+                    session.sink().as_ref(),
+                    trace_id,
+                    finished,
+                    "scheduler/node_failed",
+                    &node.node_key,
+                    Some(&error_code),
+                )?;
+                report.failed_nodes.push(node.node_key.clone());
+                continue;
+            }
+        };
 
-        let output_specs: Vec<OutputSpec> = node
-            .outputs
-            .iter()
-            .map(|output| OutputSpec {
-                asset_key: output.asset_key.clone(),
-                schema: None,
-                rows: None,
-                bytes: None,
-            })
-            .collect();
+        let output_specs = OutputSpec::from_runner_outputs(&node, &runner_outputs);
         let materialize = session.materialize_node_outputs(
             &node,
             &output_specs,
@@ -866,4 +860,136 @@ mod tests {
 
         let _ = fs::remove_dir_all(&base);
     }
+
+    fn two_step_graph() -> GraphV1 {
+        let n1 = make_node(
+            "node/one",
+            "passthrough",
+            &["dataset://ns/raw"],
+            &["dataset://ns/a"],
+            ExecutionTrust::Core,
+        );
+        let n2 = make_node(
+            "node/two",
+            "union",
+            &["dataset://ns/a"],
+            &["dataset://ns/b"],
+            ExecutionTrust::Core,
+        );
+        // No explicit edge: the planner orders by the produced/consumed assets.
+        GraphV1 {
+            schema_version: 1,
+            graph_id: Some("plan".to_string()),
+            nodes: vec![n2, n1],
+            edges: vec![],
+        }
+    }
+
+    #[test]
+    fn plan_predicts_cache_hits_for_materialized_outputs() {
+        let (base, mut session) = create_session("plan_cache_hits");
+        register_source(&mut session, "dataset://ns/raw");
+        let graph = two_step_graph();
+
+        let before = session.plan(&graph, "core").unwrap();
+        let keys: Vec<&str> = before.iter().map(|p| p.node_key.as_str()).collect();
+        assert_eq!(keys, ["node/one", "node/two"]);
+        assert!(before
+            .iter()
+            .all(|p| p.cache_decision == CacheDecisionV0::Miss));
+
+        let report = execute_graph_sequential(
+            &graph,
+            &mut session,
+            &NativeOpRunner,
+            &PermissivePolicy,
+            RunId::from_bytes([0x44; 16]),
+            test_clock,
+        )
+        .unwrap();
+        assert_eq!(report.executed_nodes.len(), 2);
+
+        let after = session.plan(&graph, "core").unwrap();
+        assert_eq!(after.len(), 2);
+        for (plan, planned_before) in after.iter().zip(&before) {
+            assert_eq!(
+                plan.cache_decision,
+                CacheDecisionV0::Hit,
+                "{}",
+                plan.node_key
+            );
+            assert_eq!(plan.outputs, planned_before.outputs);
+            assert_eq!(
+                session.fingerprint(&plan.outputs[0].asset_key),
+                Some(plan.outputs[0].fingerprint_v0.as_str())
+            );
+        }
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn plan_flips_downstream_to_miss_when_upstream_changes() {
+        let (base, mut session) = create_session("plan_upstream_change");
+        register_source(&mut session, "dataset://ns/raw");
+        let graph = two_step_graph();
+        execute_graph_sequential(
+            &graph,
+            &mut session,
+            &NativeOpRunner,
+            &PermissivePolicy,
+            RunId::from_bytes([0x55; 16]),
+            test_clock,
+        )
+        .unwrap();
+        let cached = session.plan(&graph, "core").unwrap();
+
+        // A new source version changes the raw fingerprint.
+        let ingest = make_node(
+            "ingest/raw",
+            "ingest",
+            &[],
+            &["dataset://ns/raw"],
+            ExecutionTrust::Core,
+        );
+        let source = SourceDescriptorV0 {
+            uri: "s3://bucket/raw.parquet".to_string(),
+            content_type: "application/parquet".to_string(),
+            auth_mode: swarm_torch_core::dataops::AuthModeMarker::None,
+            etag_or_version: Some("v2".to_string()),
+        };
+        session
+            .register_source(
+                "dataset://ns/raw",
+                TrustClass::Trusted,
+                source,
+                None,
+                &ingest,
+            )
+            .unwrap();
+
+        let replanned = session.plan(&graph, "core").unwrap();
+        for (plan, old) in replanned.iter().zip(&cached) {
+            assert_eq!(
+                plan.cache_decision,
+                CacheDecisionV0::Miss,
+                "{}",
+                plan.node_key
+            );
+            assert_ne!(plan.outputs, old.outputs);
+            assert_ne!(plan.cache_key, old.cache_key);
+        }
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn plan_fails_closed_on_missing_external_input() {
+        let (base, session) = create_session("plan_missing_input");
+        let err = session.plan(&two_step_graph(), "core").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(err.to_string().contains("dataset://ns/raw"));
+
+        let _ = fs::remove_dir_all(&base);
+    }
 }
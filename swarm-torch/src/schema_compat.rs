@@ -0,0 +1,285 @@
+//! Schema evolution checks for `SchemaDescriptorV0` (pre-run gate).
+//!
+//! Compatibility is judged from the reader's side: a `Compatible` change lets consumers
+//! built against `next` keep reading data produced under `prev`.
+//!
+//! Format-aware rules (`arrow-json`, `json-schema`):
+//! - added field → `Compatible` (unless `json-schema` lists it in `required`)
+//! - removed field → `Breaking`
+//! - retyped field → `Breaking`
+//! - `arrow-json` nullable → non-nullable → `Breaking` (the reverse is `Compatible`)
+//!
+//! Anything else that differs (unknown format, format change, unparsable `canonical`)
+//! is `Breaking`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::Value;
+use swarm_torch_core::dataops::SchemaDescriptorV0;
+
+/// Result of comparing two schema descriptors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaCompat {
+    /// Same schema (after normalization / JSON parsing).
+    Identical,
+    /// Additive, backward-compatible change.
+    Compatible,
+    /// Removed or retyped fields, or a change that cannot be analyzed.
+    Breaking,
+}
+
+/// Field shape extracted from a schema's `canonical` representation.
+#[derive(Debug, PartialEq)]
+struct FieldShape<'a> {
+    ty: Option<&'a Value>,
+    nullable: bool,
+}
+
+/// Parsed field set: fields by name plus the names a reader requires to be present.
+#[derive(Debug)]
+struct FieldSet<'a> {
+    fields: BTreeMap<&'a str, FieldShape<'a>>,
+    required: BTreeSet<&'a str>,
+}
+
+/// Classify the change from `prev` to `next`.
+pub fn schema_compat(prev: &SchemaDescriptorV0, next: &SchemaDescriptorV0) -> SchemaCompat {
+    let format = prev.format.trim().to_ascii_lowercase();
+    if format != next.format.trim().to_ascii_lowercase() {
+        return SchemaCompat::Breaking;
+    }
+    if prev.canonical.trim() == next.canonical.trim() {
+        return SchemaCompat::Identical;
+    }
+
+    let (Ok(prev_json), Ok(next_json)) = (
+        serde_json::from_str::<Value>(&prev.canonical),
+        serde_json::from_str::<Value>(&next.canonical),
+    ) else {
+        return SchemaCompat::Breaking;
+    };
+    if prev_json == next_json {
+        return SchemaCompat::Identical;
+    }
+
+    let parse = match format.as_str() {
+        "arrow-json" => arrow_json_fields,
+        "json-schema" => json_schema_fields,
+        _ => return SchemaCompat::Breaking,
+    };
+    match (parse(&prev_json), parse(&next_json)) {
+        (Some(prev_fields), Some(next_fields)) => compare_fields(&prev_fields, &next_fields),
+        _ => SchemaCompat::Breaking,
+    }
+}
+
+fn compare_fields(prev: &FieldSet<'_>, next: &FieldSet<'_>) -> SchemaCompat {
+    let mut changed = false;
+    for (name, prev_field) in &prev.fields {
+        let Some(next_field) = next.fields.get(name) else {
+            return SchemaCompat::Breaking;
+        };
+        if prev_field.ty != next_field.ty || (prev_field.nullable && !next_field.nullable) {
+            return SchemaCompat::Breaking;
+        }
+        changed |= prev_field != next_field;
+    }
+    for name in next.fields.keys() {
+        if !prev.fields.contains_key(name) {
+            if next.required.contains(name) {
+                return SchemaCompat::Breaking;
+            }
+            changed = true;
+        }
+    }
+    if next
+        .required
+        .iter()
+        .any(|name| !prev.required.contains(name))
+    {
+        return SchemaCompat::Breaking;
+    }
+
+    if changed || prev.required != next.required {
+        SchemaCompat::Compatible
+    } else {
+        // Only non-field metadata differs.
+        SchemaCompat::Identical
+    }
+}
+
+/// `{"fields":[{"name":"x","type":"i64","nullable":true}, ...]}`
+fn arrow_json_fields(schema: &Value) -> Option<FieldSet<'_>> {
+    let mut fields = BTreeMap::new();
+    for field in schema.get("fields")?.as_array()? {
+        let name = field.get("name")?.as_str()?;
+        let shape = FieldShape {
+            ty: field.get("type"),
+            nullable: field
+                .get("nullable")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        };
+        if fields.insert(name, shape).is_some() {
+            return None;
+        }
+    }
+    Some(FieldSet {
+        fields,
+        required: BTreeSet::new(),
+    })
+}
+
+/// `{"properties":{"x":{"type":"integer"}, ...},"required":["x"]}`
+fn json_schema_fields(schema: &Value) -> Option<FieldSet<'_>> {
+    let fields = schema
+        .get("properties")?
+        .as_object()?
+        .iter()
+        .map(|(name, property)| {
+            (
+                name.as_str(),
+                FieldShape {
+                    ty: Some(property),
+                    nullable: true,
+                },
+            )
+        })
+        .collect();
+    let required = match schema.get("required") {
+        Some(required) => required
+            .as_array()?
+            .iter()
+            .map(Value::as_str)
+            .collect::<Option<BTreeSet<_>>>()?,
+        None => BTreeSet::new(),
+    };
+    Some(FieldSet { fields, required })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arrow(canonical: &str) -> SchemaDescriptorV0 {
+        SchemaDescriptorV0 {
+            format: "arrow-json".to_string(),
+            canonical: canonical.to_string(),
+        }
+    }
+
+    fn json_schema(canonical: &str) -> SchemaDescriptorV0 {
+        SchemaDescriptorV0 {
+            format: "json-schema".to_string(),
+            canonical: canonical.to_string(),
+        }
+    }
+
+    const BASE: &str = r#"{"fields":[{"name":"id","type":"i64"},{"name":"value","type":"f64"}]}"#;
+
+    #[test]
+    fn identical_schemas_ignore_whitespace_and_key_order() {
+        let reordered =
+            r#"{ "fields": [ {"type":"i64","name":"id"}, {"name":"value","type":"f64"} ] }"#;
+        assert_eq!(
+            schema_compat(&arrow(BASE), &arrow(BASE)),
+            SchemaCompat::Identical
+        );
+        assert_eq!(
+            schema_compat(&arrow(BASE), &arrow(reordered)),
+            SchemaCompat::Identical
+        );
+    }
+
+    #[test]
+    fn added_field_is_compatible() {
+        let next = r#"{"fields":[{"name":"id","type":"i64"},{"name":"value","type":"f64"},{"name":"tag","type":"utf8","nullable":true}]}"#;
+        assert_eq!(
+            schema_compat(&arrow(BASE), &arrow(next)),
+            SchemaCompat::Compatible
+        );
+
+        let prev = r#"{"properties":{"id":{"type":"integer"}},"required":["id"]}"#;
+        let optional =
+            r#"{"properties":{"id":{"type":"integer"},"tag":{"type":"string"}},"required":["id"]}"#;
+        let required = r#"{"properties":{"id":{"type":"integer"},"tag":{"type":"string"}},"required":["id","tag"]}"#;
+        assert_eq!(
+            schema_compat(&json_schema(prev), &json_schema(optional)),
+            SchemaCompat::Compatible
+        );
+        assert_eq!(
+            schema_compat(&json_schema(prev), &json_schema(required)),
+            SchemaCompat::Breaking
+        );
+    }
+
+    #[test]
+    fn removed_field_is_breaking() {
+        let next = r#"{"fields":[{"name":"id","type":"i64"}]}"#;
+        assert_eq!(
+            schema_compat(&arrow(BASE), &arrow(next)),
+            SchemaCompat::Breaking
+        );
+
+        let prev = r#"{"properties":{"id":{"type":"integer"},"tag":{"type":"string"}}}"#;
+        let next = r#"{"properties":{"id":{"type":"integer"}}}"#;
+        assert_eq!(
+            schema_compat(&json_schema(prev), &json_schema(next)),
+            SchemaCompat::Breaking
+        );
+    }
+
+    #[test]
+    fn type_change_is_breaking() {
+        let next = r#"{"fields":[{"name":"id","type":"utf8"},{"name":"value","type":"f64"}]}"#;
+        assert_eq!(
+            schema_compat(&arrow(BASE), &arrow(next)),
+            SchemaCompat::Breaking
+        );
+
+        let prev = r#"{"properties":{"id":{"type":"integer"}}}"#;
+        let next = r#"{"properties":{"id":{"type":"string"}}}"#;
+        assert_eq!(
+            schema_compat(&json_schema(prev), &json_schema(next)),
+            SchemaCompat::Breaking
+        );
+    }
+
+    #[test]
+    fn nullability_tightening_is_breaking() {
+        let nullable = r#"{"fields":[{"name":"id","type":"i64","nullable":true}]}"#;
+        let strict = r#"{"fields":[{"name":"id","type":"i64","nullable":false}]}"#;
+        assert_eq!(
+            schema_compat(&arrow(nullable), &arrow(strict)),
+            SchemaCompat::Breaking
+        );
+        assert_eq!(
+            schema_compat(&arrow(strict), &arrow(nullable)),
+            SchemaCompat::Compatible
+        );
+    }
+
+    #[test]
+    fn unknown_format_or_format_change_falls_back_to_breaking() {
+        let thrift = |canonical: &str| SchemaDescriptorV0 {
+            format: "parquet-thrift".to_string(),
+            canonical: canonical.to_string(),
+        };
+        assert_eq!(
+            schema_compat(&thrift("a"), &thrift(" a ")),
+            SchemaCompat::Identical
+        );
+        assert_eq!(
+            schema_compat(&thrift("a"), &thrift("b")),
+            SchemaCompat::Breaking
+        );
+        assert_eq!(
+            schema_compat(&arrow(BASE), &json_schema(BASE)),
+            SchemaCompat::Breaking
+        );
+        assert_eq!(
+            schema_compat(&arrow(BASE), &arrow("not json")),
+            SchemaCompat::Breaking
+        );
+    }
+}